@@ -0,0 +1,81 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use num_bigint::BigInt;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+use weierstrass_to_montgomery::{
+    default_rng, lookup, mod_inverse, scalar_mul, scalar_mul_protected, Countermeasures, FieldElement,
+    MontgomeryCurve, MontgomeryMap, Point, TransformOptions,
+};
+
+/// The system allocator, counting the allocations and reallocations that go through it.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+// SAFETY: every call is passed on unchanged to the system allocator
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Runs `f` once and returns the number of allocations it made.
+fn allocations<T>(f: impl FnOnce() -> T) -> u64 {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+/// Benchmarks `f` under `name`, printing the allocations of one call first.
+fn bench<T>(c: &mut Criterion, name: &str, mut f: impl FnMut() -> T) {
+    println!("{}: {} allocations", name, allocations(&mut f));
+    c.bench_function(name, |bencher| bencher.iter(&mut f));
+}
+
+/// The hot paths on 256-bit inputs over 2^255 − 19: an inversion, the Montgomery ladder, the affine and
+/// Jacobian double-and-add on Wei25519 and a mapped point, each with its allocations per call.
+fn bench_allocations(c: &mut Criterion) {
+    let wei25519 = lookup("Wei25519").expect("registered");
+    let (a, b) = wei25519.coefficients();
+    let p = wei25519.p();
+    let base_point = wei25519.base_point();
+    let order = wei25519.order();
+    let scalar: BigInt = &order - 12345u32;
+    let curve25519 = lookup("Curve25519").expect("registered");
+    let (a_montgomery, b_montgomery) = curve25519.coefficients();
+    let montgomery = MontgomeryCurve::new(&a_montgomery, &b_montgomery, &p).expect("B ≠ 0");
+    let weierstrass = montgomery.to_weierstrass();
+    assert_eq!((&weierstrass.a, &weierstrass.b), (&a, &b));
+    let map = MontgomeryMap::new(&a, &b, &p, &TransformOptions::default()).expect("has a map");
+    let Point::Affine(x, y) = base_point.clone() else {
+        unreachable!("the base point is affine");
+    };
+    let value = FieldElement::new(&scalar, &p);
+
+    bench(c, "mod_inverse_256_bit", || mod_inverse(black_box(&scalar), &p));
+    bench(c, "field_element_mul_256_bit", || black_box(&value) * black_box(&value));
+    bench(c, "ladder_256_bit", || montgomery.ladder(black_box(&BigInt::from(9)), &scalar));
+    bench(c, "scalar_mul_affine_256_bit", || scalar_mul(&weierstrass, black_box(&base_point), &scalar));
+    let countermeasures = Countermeasures { scalar_blinding: false, randomize_coordinates: true };
+    let mut rng = default_rng();
+    bench(c, "scalar_mul_jacobian_256_bit", || {
+        scalar_mul_protected(&weierstrass, black_box(&base_point), &scalar, &order, countermeasures, &mut rng)
+    });
+    bench(c, "map_point_256_bit", || map.map_point(black_box(&x), &y));
+}
+
+criterion_group!(benches, bench_allocations);
+criterion_main!(benches);
@@ -0,0 +1,46 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::fmt::Write;
+use std::io::Cursor;
+use weierstrass_to_montgomery::{
+    default_rng, lookup, map_lines, random_point, Field, MontgomeryMap, SearchLimits, TransformOptions,
+};
+
+/// Points in the benchmarked file.
+const POINTS: usize = 10_000;
+
+/// Streams a file of random Wei25519 points through `map_lines`, formatting each image as the `batch`
+/// command does, with one thread and with all cores, and reports points per second.
+fn bench_batch(c: &mut Criterion) {
+    let (curve, _) = lookup("Wei25519").expect("registered").to_weierstrass().expect("converts");
+    let limits = SearchLimits::default();
+    let options = TransformOptions::default();
+    let map = MontgomeryMap::new(&curve.a, &curve.b, &curve.p, &options).expect("has a map");
+    let field = Field::new(&curve.p);
+    let mut rng = default_rng();
+    let mut file = String::new();
+    for _ in 0..POINTS {
+        let (x, y) = random_point(&field, &curve.a, &curve.b, &limits, &mut rng).expect("finds a point");
+        writeln!(file, "{} {}", x, y).unwrap();
+    }
+
+    let mut group = c.benchmark_group("batch_wei25519");
+    group.throughput(Throughput::Elements(POINTS as u64));
+    group.sample_size(10);
+    for threads in [1, 0] {
+        group.bench_function(format!("threads_{}", threads), |bencher| {
+            bencher.iter(|| {
+                let mut output = String::with_capacity(file.len());
+                map_lines(&map, Cursor::new(file.as_bytes()), 4096, threads, |_, image| {
+                    let (u, v) = image?;
+                    writeln!(output, "{} {}", u, v).unwrap();
+                    Ok(())
+                })
+                .expect("every point is on the curve")
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_batch);
+criterion_main!(benches);
@@ -0,0 +1,34 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use num_bigint::BigInt;
+use std::str::FromStr;
+use weierstrass_to_montgomery::field::window_modpow;
+
+/// Compares the sliding-window exponentiation with `BigInt::modpow` on a Legendre-symbol exponent.
+fn bench_legendre_exponent(c: &mut Criterion, name: &str, p: &str) {
+    let p = BigInt::from_str(p).unwrap();
+    let exponent: BigInt = (&p - 1u32) / 2u32;
+    let base = BigInt::from(9);
+
+    let mut group = c.benchmark_group(name);
+    group.bench_function("BigInt::modpow", |bencher| {
+        bencher.iter(|| black_box(&base).modpow(black_box(&exponent), black_box(&p)))
+    });
+    group.bench_function("window_modpow", |bencher| {
+        bencher.iter(|| window_modpow(black_box(&base), black_box(&exponent), black_box(&p)))
+    });
+    group.finish();
+}
+
+fn bench_modpow(c: &mut Criterion) {
+    bench_legendre_exponent(c, "modpow_30_bit", "1000000007");
+    bench_legendre_exponent(c, "modpow_128_bit", "340282366920938463463374607431768211297");
+    // 2^255 - 19
+    bench_legendre_exponent(
+        c,
+        "modpow_255_bit",
+        "57896044618658097711785492504343953926634992332820282019728792003956564819949",
+    );
+}
+
+criterion_group!(benches, bench_modpow);
+criterion_main!(benches);
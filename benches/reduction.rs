@@ -0,0 +1,83 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use num_bigint::{BigInt, RandBigInt};
+use num_integer::Integer;
+use std::str::FromStr;
+use weierstrass_to_montgomery::field::{Barrett, PseudoMersenne};
+use weierstrass_to_montgomery::solinas::NistPrime;
+
+/// Compares Barrett reduction with `mod_floor`, and with pseudo-Mersenne folding and the Solinas routines
+/// where p allows them, on products of two field elements.
+fn bench_reduction(c: &mut Criterion, name: &str, p: &str) {
+    let p = BigInt::from_str(p).unwrap();
+    let barrett = Barrett::new(&p);
+    let mut rng = rand::thread_rng();
+    let products: Vec<BigInt> = (0..256)
+        .map(|_| rng.gen_bigint_range(&BigInt::from(0), &p) * rng.gen_bigint_range(&BigInt::from(0), &p))
+        .collect();
+
+    let mut group = c.benchmark_group(name);
+    group.bench_function("mod_floor", |bencher| {
+        bencher.iter(|| {
+            for product in &products {
+                black_box(product.mod_floor(&p));
+            }
+        })
+    });
+    group.bench_function("Barrett::reduce", |bencher| {
+        bencher.iter(|| {
+            for product in &products {
+                black_box(barrett.reduce(product));
+            }
+        })
+    });
+    if let Some(pseudo_mersenne) = PseudoMersenne::detect(&p) {
+        group.bench_function("PseudoMersenne::reduce", |bencher| {
+            bencher.iter(|| {
+                for product in &products {
+                    black_box(pseudo_mersenne.reduce(product));
+                }
+            })
+        });
+    }
+    if let Some(prime) = NistPrime::detect(&p) {
+        group.bench_function("NistPrime::reduce", |bencher| {
+            bencher.iter(|| {
+                for product in &products {
+                    black_box(prime.reduce(product));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_reductions(c: &mut Criterion) {
+    bench_reduction(c, "reduce_128_bit", "340282366920938463463374607431768211297");
+    // 2^255 - 19
+    bench_reduction(
+        c,
+        "reduce_255_bit",
+        "57896044618658097711785492504343953926634992332820282019728792003956564819949",
+    );
+    // P-256
+    bench_reduction(
+        c,
+        "reduce_p256",
+        "115792089210356248762697446949407573530086143415290314195533631308867097853951",
+    );
+    // P-384
+    bench_reduction(
+        c,
+        "reduce_p384",
+        "39402006196394479212279040100143613805079739270465446667948293404245721771496870329047266088258938001861606973112319",
+    );
+    // P-521 = 2^521 - 1
+    bench_reduction(
+        c,
+        "reduce_521_bit",
+        "6864797660130609714981900799081393217269435300143305409394463459185543183397656052122559640661454554977296311391480858037121987999716643812574028291115057151",
+    );
+}
+
+criterion_group!(benches, bench_reductions);
+criterion_main!(benches);
@@ -0,0 +1,46 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use num_bigint::BigInt;
+use std::str::FromStr;
+use weierstrass_to_montgomery::numtheory::{mod_sqrt_with, SqrtAlgorithm};
+use weierstrass_to_montgomery::SearchLimits;
+
+/// Compares the square-root algorithms on the square of 7 modulo `p`, whose p − 1 has 2-adicity `two_adicity`.
+fn bench_sqrt(c: &mut Criterion, two_adicity: u32, p: &str) {
+    let p = BigInt::from_str(p).unwrap();
+    let value = BigInt::from(49);
+    let limits = SearchLimits::default();
+    let mut algorithms = vec![SqrtAlgorithm::TonelliShanks, SqrtAlgorithm::Cipolla];
+    if two_adicity == 1 {
+        algorithms.push(SqrtAlgorithm::ThreeModFour);
+    }
+
+    let mut group = c.benchmark_group(format!("sqrt_{}_bit_2_adicity_{}", p.bits(), two_adicity));
+    for algorithm in algorithms {
+        group.bench_function(format!("{:?}", algorithm), |bencher| {
+            bencher.iter(|| mod_sqrt_with(black_box(&value), black_box(&p), algorithm, &limits))
+        });
+    }
+    group.finish();
+}
+
+fn bench_sqrt_algorithms(c: &mut Criterion) {
+    // secp256k1 base field
+    bench_sqrt(c, 1, "115792089237316195423570985008687907853269984665640564039457584007908834671663");
+    // 2^255 - 19
+    bench_sqrt(c, 2, "57896044618658097711785492504343953926634992332820282019728792003956564819949");
+    // P-224
+    bench_sqrt(c, 96, "26959946667150639794667015087019630673557916260026308143510066298881");
+    // BN254 scalar field
+    bench_sqrt(c, 28, "21888242871839275222246405745257275088548364400416034343698204186575808495617");
+    // BLS12-381 scalar field
+    bench_sqrt(c, 32, "52435875175126190479447740508185965837690552500527637822603658699938581184513");
+    // Goldilocks, 2^64 - 2^32 + 1
+    bench_sqrt(c, 32, "18446744069414584321");
+    // BabyBear, 15 * 2^27 + 1
+    bench_sqrt(c, 27, "2013265921");
+    // 2^64 - 59
+    bench_sqrt(c, 2, "18446744073709551557");
+}
+
+criterion_group!(benches, bench_sqrt_algorithms);
+criterion_main!(benches);
@@ -0,0 +1,152 @@
+//! A dudect-style timing test of the code paths the `ct` feature is meant to make regular:
+//!
+//! ```text
+//! cargo run --release --example dudect --features ct -- [measurements per target]
+//! ```
+//!
+//! Each target is timed on two classes of secret inputs, one fixed value and fresh random values, in a
+//! random interleaving; Welch's t-test then compares the two timing distributions, as is and cropped at
+//! several percentiles to cut off interrupts and other noise (Reparaz, Balasch and Verbauwhede, "Dude, is
+//! my code constant time?", 2017). |t| above 4.5 is evidence that the time depends on the secret; below
+//! it, this many measurements found none. `Field::inverse_with(ExtendedGcd)` runs as a control that
+//! should leak, to show the test can tell. Exits with 1 if any other target leaks.
+//!
+//! The operations run on `BigInt`s, whose reductions end in value-dependent subtractions, so depending on
+//! the fixed value drawn the inversion and square root can be flagged too; the ladder runs a twentieth of
+//! the measurements, as each takes far longer.
+
+use num_bigint::{BigInt, RandBigInt};
+use rand::Rng;
+use std::hint::black_box;
+use std::time::Instant;
+use weierstrass_to_montgomery::ed25519::curve25519;
+use weierstrass_to_montgomery::{lookup, Field, InversionAlgorithm, SearchLimits};
+
+/// Measurements per target unless given on the command line.
+const DEFAULT_MEASUREMENTS: usize = 20_000;
+
+/// |t| from which a target counts as leaking, dudect's threshold.
+const LEAK_THRESHOLD: f64 = 4.5;
+
+/// Percentiles of all measurements at which the samples are also cropped, besides not at all.
+const CROP_PERCENTILES: [f64; 5] = [0.5, 0.75, 0.9, 0.95, 0.99];
+
+/// Running mean and variance of one class (Welford's method).
+#[derive(Default)]
+struct Moments {
+    count: f64,
+    mean: f64,
+    m2: f64,
+}
+
+impl Moments {
+    fn push(&mut self, sample: f64) {
+        self.count += 1.0;
+        let delta = sample - self.mean;
+        self.mean += delta / self.count;
+        self.m2 += delta * (sample - self.mean);
+    }
+
+    fn variance(&self) -> f64 {
+        self.m2 / (self.count - 1.0)
+    }
+}
+
+/// Welch's t-statistic between the fixed and the random class, over samples below `limit`.
+fn welch_t(samples: &[(bool, f64)], limit: f64) -> f64 {
+    let mut classes = [Moments::default(), Moments::default()];
+    for &(random, time) in samples.iter().filter(|&&(_, time)| time <= limit) {
+        classes[random as usize].push(time);
+    }
+    let [fixed, random] = classes;
+    if fixed.count < 2.0 || random.count < 2.0 {
+        return 0.0;
+    }
+    (fixed.mean - random.mean) / (fixed.variance() / fixed.count + random.variance() / random.count).sqrt()
+}
+
+/// Times `operation` on `fixed` and on values drawn with `draw`, returning the largest |t| over the crops.
+fn measure<T: Clone>(
+    measurements: usize,
+    fixed: T,
+    mut draw: impl FnMut() -> T,
+    mut operation: impl FnMut(&T),
+) -> f64 {
+    let mut rng = rand::thread_rng();
+    let classes: Vec<bool> = (0..measurements).map(|_| rng.gen()).collect();
+    // Both classes get their own copies, so that neither stays warmer in the cache
+    let inputs: Vec<T> = classes.iter().map(|&random| if random { draw() } else { fixed.clone() }).collect();
+    let mut samples = Vec::with_capacity(measurements);
+    for (&random, input) in classes.iter().zip(&inputs) {
+        let start = Instant::now();
+        operation(black_box(input));
+        samples.push((random, start.elapsed().as_nanos() as f64));
+    }
+    let mut times: Vec<f64> = samples.iter().map(|&(_, time)| time).collect();
+    times.sort_by(f64::total_cmp);
+    let limits = CROP_PERCENTILES
+        .iter()
+        .map(|&percentile| times[(percentile * (times.len() - 1) as f64) as usize]);
+    std::iter::once(f64::INFINITY)
+        .chain(limits)
+        .map(|limit| welch_t(&samples, limit).abs())
+        .fold(0.0, f64::max)
+}
+
+fn report(name: &str, t: f64, control: bool) -> bool {
+    let leaks = t > LEAK_THRESHOLD;
+    let verdict = match (leaks, control) {
+        (true, true) => "leaks, as expected of the control",
+        (false, true) => "no leak found in the control: too few measurements to trust the others",
+        (true, false) => "LEAKS",
+        (false, false) => "no evidence of a leak",
+    };
+    println!("{:<40} max |t| = {:>8.2}  {}", name, t, verdict);
+    leaks && !control
+}
+
+fn main() {
+    let measurements = std::env::args()
+        .nth(1)
+        .map(|count| count.parse().expect("the number of measurements is an integer"))
+        .unwrap_or(DEFAULT_MEASUREMENTS);
+    let mut rng = rand::thread_rng();
+    let (p256, _) = lookup("P-256").expect("registered").to_weierstrass().expect("converts");
+    let field = Field::new(&p256.p);
+    let element = |rng: &mut rand::rngs::ThreadRng| rng.gen_bigint_range(&BigInt::from(1), &p256.p);
+    let fixed = element(&mut rng);
+    println!("{} measurements per target, P-256 field and Curve25519\n", measurements);
+
+    let mut leaked = false;
+    let t = measure(measurements, fixed.clone(), || element(&mut rand::thread_rng()), |value| {
+        black_box(field.inverse_with(value, InversionAlgorithm::ExtendedGcd));
+    });
+    report("Field::inverse_with(ExtendedGcd)", t, true);
+
+    let t = measure(measurements, fixed.clone(), || element(&mut rand::thread_rng()), |value| {
+        black_box(field.inverse_with(value, InversionAlgorithm::Fermat));
+    });
+    leaked |= report("Field::inverse_with(Fermat)", t, false);
+
+    // Squares only, so that both classes take the same path past the residuosity check
+    let square = |value: BigInt| field.reduce(&(&value * &value));
+    let t = measure(measurements, square(fixed), || square(element(&mut rand::thread_rng())), |value| {
+        black_box(field.sqrt(value, &SearchLimits::default()).expect("p = 3 mod 4 needs no search"));
+    });
+    leaked |= report("Field::sqrt", t, false);
+
+    // 255-bit scalars with the top bit set, as X25519 clamping leaves them
+    let curve = curve25519();
+    let u = BigInt::from(9);
+    let scalar = |rng: &mut rand::rngs::ThreadRng| {
+        BigInt::from(rng.gen_biguint(254)) + (BigInt::from(1) << 254u32)
+    };
+    let t = measure(measurements / 20, scalar(&mut rng), || scalar(&mut rand::thread_rng()), |k| {
+        black_box(curve.ladder(&u, k));
+    });
+    leaked |= report("MontgomeryCurve::ladder", t, false);
+
+    if leaked {
+        std::process::exit(1);
+    }
+}
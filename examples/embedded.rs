@@ -0,0 +1,66 @@
+//! Provisioning logic for microcontroller firmware, built without `std`:
+//!
+//! ```text
+//! rustup target add thumbv7em-none-eabihf
+//! cargo build --example embedded --no-default-features --features postcard --target thumbv7em-none-eabihf
+//! ```
+//!
+//! The example is a library, since a firmware image also needs a panic handler, a global allocator
+//! (e.g. `embedded-alloc`) and an entry point from the board support crate; it links into such a
+//! firmware as is. The transform runs on the fixed-width `SmallField` backend, seeded from the
+//! device's hardware RNG, and replies fit in a stack buffer through `postcard::to_slice`. Its root search
+//! samples at random, which only suits small fields; maps for real curves are computed by a host, arrive
+//! in the `compact` encoding and are applied with `BigInt`s on the heap.
+
+#![no_std]
+
+extern crate alloc;
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::Zero;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+use weierstrass_to_montgomery::{
+    transform_to_montgomery_small_with_rng, MontgomeryMap, SearchLimits, SmallField, TransformError,
+};
+
+/// The largest prime below 2^16, small enough for the random root search to succeed quickly.
+pub type Fp = SmallField<65521>;
+
+/// y^2 = x^3 − 11x + 14, the Weierstrass form of v^2 = u^3 + 6u^2 + u.
+pub const A: Fp = Fp::from_i64(-11);
+pub const B: Fp = Fp::new(14);
+
+/// The device's reply to the provisioning host: its point on the Montgomery curve and the curve itself.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Provisioned {
+    pub u: Fp,
+    pub v: Fp,
+    pub a_montgomery: Fp,
+    pub b_montgomery: Fp,
+}
+
+/// Maps the point (x, y) to Montgomery form and writes the postcard-encoded `Provisioned` reply to
+/// `reply`, returning its length; at most 12 bytes.
+pub fn provision(seed: [u8; 32], x: u64, y: u64, reply: &mut [u8]) -> Result<usize, TransformError> {
+    let mut rng = ChaCha20Rng::from_seed(seed);
+    let limits = SearchLimits { max_attempts: 1 << 20, timeout: None };
+    let (u, v, a_montgomery, b_montgomery) =
+        transform_to_montgomery_small_with_rng(Fp::new(x), Fp::new(y), A, B, &limits, &mut rng)?;
+    let record = Provisioned { u, v, a_montgomery, b_montgomery };
+    let written = postcard::to_slice(&record, reply)
+        .map_err(|_| TransformError::InvalidParameters("the reply buffer is too small".into()))?;
+    Ok(written.len())
+}
+
+/// Applies a map received from the host (`MontgomeryMap::to_postcard`) to the point (x, y), after checking
+/// that it lies on the map's Weierstrass curve.
+pub fn apply_received_map(map: &[u8], x: &BigInt, y: &BigInt) -> Result<(BigInt, BigInt), TransformError> {
+    let map = MontgomeryMap::from_postcard(map)?;
+    if !(y * y - x * x * x - &map.a * x - &map.b).mod_floor(&map.p).is_zero() {
+        return Err(TransformError::PointNotOnCurve);
+    }
+    Ok(map.map_point(x, y))
+}
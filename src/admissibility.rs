@@ -0,0 +1,155 @@
+use crate::cubic::cubic_roots;
+use crate::error::TransformError;
+use crate::limits::SearchLimits;
+use crate::numtheory::legendre_symbol;
+use crate::weierstrass::WeierstrassCurve;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use std::fmt;
+
+/// How one root z0 of z^3 + az + b fares in the transform.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RootCheck {
+    pub z0: BigInt,
+    /// 3·z0^2 + a, which must be a non-zero square for the transform to use this root.
+    pub s_squared: BigInt,
+    pub is_square: bool,
+}
+
+/// Why a curve has no Montgomery form. The order of every Montgomery curve is divisible by 4, and which
+/// obstruction applies says what that rules out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Obstruction {
+    /// z^3 + az + b has no root in F_p, so the curve has no point of order 2 and its order is odd.
+    IrreducibleCubic,
+    /// The cubic has a single root z0 and 3·z0^2 + a is a non-residue: (z0, 0) is not twice any point, so
+    /// the group has no point of order 4 and its order is 2 mod 4.
+    NoPointOfOrderFour,
+    /// The cubic has three roots, so 4 divides the order, but 3·z0^2 + a is a non-residue for each.
+    NonSquareForEveryRoot,
+}
+
+impl Obstruction {
+    /// Stable machine-readable name of the obstruction.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Obstruction::IrreducibleCubic => "irreducible_cubic",
+            Obstruction::NoPointOfOrderFour => "no_point_of_order_four",
+            Obstruction::NonSquareForEveryRoot => "non_square_for_every_root",
+        }
+    }
+
+    /// The obstruction for a cubic with `roots` roots in F_p, none of them usable.
+    pub(crate) fn for_root_count(roots: usize) -> Obstruction {
+        match roots {
+            0 => Obstruction::IrreducibleCubic,
+            1 => Obstruction::NoPointOfOrderFour,
+            _ => Obstruction::NonSquareForEveryRoot,
+        }
+    }
+}
+
+impl fmt::Display for Obstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Obstruction::IrreducibleCubic => write!(
+                f,
+                "z^3 + az + b has no root in F_p, so it is irreducible and the curve has no point of \
+                 order 2: its group order is odd, while that of every Montgomery curve is divisible by 4. \
+                 This is a property of the curve, shared by secp256k1 and every other curve of prime order, \
+                 not a limitation of the transform"
+            ),
+            Obstruction::NoPointOfOrderFour => write!(
+                f,
+                "z^3 + az + b has a single root z0 and 3*z0^2 + a is not a square, so (z0, 0) is not twice \
+                 any point: the curve has no point of order 4 and its group order is 2 mod 4, while that of \
+                 every Montgomery curve is divisible by 4"
+            ),
+            Obstruction::NonSquareForEveryRoot => write!(
+                f,
+                "3*z0^2 + a is a non-residue for every root z0 of z^3 + az + b, so s = sqrt(3*z0^2 + a) does \
+                 not exist in F_p; the group order is divisible by 4, but no root gives a Montgomery form"
+            ),
+        }
+    }
+}
+
+/// Why a curve does or does not admit a Montgomery form.
+///
+/// A short Weierstrass curve is birationally equivalent to a Montgomery curve exactly when it has a
+/// rational point (z0, 0) of order 2 for which 3·z0^2 + a is a square in F_p.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdmissibilityReport {
+    pub curve: WeierstrassCurve,
+    /// Every root of the cubic in F_p, in increasing order.
+    pub roots: Vec<RootCheck>,
+}
+
+impl AdmissibilityReport {
+    /// Whether the curve has a rational point of order 2, i.e. the cubic has a root.
+    pub fn has_two_torsion(&self) -> bool {
+        !self.roots.is_empty()
+    }
+
+    /// The first root the transform can use, if any.
+    pub fn usable_root(&self) -> Option<&RootCheck> {
+        self.roots.iter().find(|root| root.is_square)
+    }
+
+    pub fn admits_montgomery_form(&self) -> bool {
+        self.usable_root().is_some()
+    }
+
+    /// What rules out a Montgomery form, if anything does.
+    pub fn obstruction(&self) -> Option<Obstruction> {
+        (!self.admits_montgomery_form()).then(|| Obstruction::for_root_count(self.roots.len()))
+    }
+}
+
+impl fmt::Display for AdmissibilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let curve = &self.curve;
+        writeln!(f, "y^2 = x^3 + {}x + {} over F_{}", curve.a, curve.b, curve.p)?;
+        for root in &self.roots {
+            let verdict = if root.is_square { "a square" } else { "not a square" };
+            writeln!(f, "root z0 = {}: 3*z0^2 + a = {} is {}", root.z0, root.s_squared, verdict)?;
+        }
+        match (self.usable_root(), self.obstruction()) {
+            (Some(root), _) => writeln!(f, "admits a Montgomery form, for example through z0 = {}", root.z0),
+            (None, Some(obstruction)) => writeln!(f, "no Montgomery form: {}", obstruction),
+            (None, None) => unreachable!("a curve without a usable root has an obstruction"),
+        }
+    }
+}
+
+/// Decides whether `curve` admits a Montgomery form and explains why, using `cubic_roots`.
+pub fn can_transform(curve: &WeierstrassCurve) -> Result<AdmissibilityReport, TransformError> {
+    can_transform_with_rng(curve, &SearchLimits::default(), &mut OsRng)
+}
+
+/// `can_transform`, with explicit search limits and randomness for splitting the cubic.
+pub fn can_transform_with_rng<R: RngCore + CryptoRng + ?Sized>(
+    curve: &WeierstrassCurve,
+    limits: &SearchLimits,
+    rng: &mut R,
+) -> Result<AdmissibilityReport, TransformError> {
+    let p = &curve.p;
+    let roots = cubic_roots(&curve.a, &curve.b, p, limits, rng)?
+        .into_iter()
+        .map(|z0| {
+            let s_squared = (BigInt::from(3) * &z0 * &z0 + &curve.a).mod_floor(p);
+            let is_square = legendre_symbol(&s_squared, p) == 1;
+            RootCheck {
+                z0,
+                s_squared,
+                is_square,
+            }
+        })
+        .collect();
+    Ok(AdmissibilityReport {
+        curve: curve.clone(),
+        roots,
+    })
+}
@@ -0,0 +1,204 @@
+use crate::curve::Point;
+use crate::error::TransformError;
+use crate::field::Field;
+use crate::limits::SearchLimits;
+use crate::log::debug;
+use crate::numtheory::{is_probable_prime, largest_prime_factor, multiplicative_order, MILLER_RABIN_ROUNDS};
+use crate::order::{count_points, trace_of_frobenius};
+use crate::scalar_mul::scalar_mul;
+use crate::weierstrass::WeierstrassCurve;
+use num_bigint::BigInt;
+use num_traits::Zero;
+use std::fmt;
+
+/// Largest embedding degree k counted as MOV/Frey-Rück vulnerable: a pairing then moves discrete
+/// logarithms in the subgroup into F_{p^k}^*, where index calculus solves them far faster.
+pub const MOV_DEGREE_BOUND: u64 = 20;
+
+/// Default cap on the embedding degree search of `analyze`.
+pub const DEFAULT_EMBEDDING_DEGREE_CAP: u64 = 10_000;
+
+/// Points whose order has fewer bits are flagged by `weaknesses`: Pollard's rho needs about 2^(bits/2)
+/// steps, and SafeCurves asks for more than 2^100.
+pub const MIN_SUBGROUP_BITS: u64 = 200;
+
+/// A red flag raised by `weaknesses` against a point and its curve.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Weakness {
+    /// The order of the point has fewer than `MIN_SUBGROUP_BITS` bits.
+    SmallSubgroup { order: BigInt },
+    /// The curve has exactly p points, so Smart's attack solves discrete logarithms in linear time.
+    Anomalous,
+    /// The order of the point is not prime, so a discrete logarithm splits into smaller ones
+    /// (Pohlig-Hellman).
+    CompositeOrder { order: BigInt },
+    /// The embedding degree is at most `MOV_DEGREE_BOUND`, so a pairing moves discrete logarithms into
+    /// a small extension field (MOV/Frey-Rück).
+    SmallEmbeddingDegree { degree: u64 },
+}
+
+impl Weakness {
+    /// Stable machine-readable name: `small_subgroup`, `anomalous`, `composite_order` or
+    /// `small_embedding_degree`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Weakness::SmallSubgroup { .. } => "small_subgroup",
+            Weakness::Anomalous => "anomalous",
+            Weakness::CompositeOrder { .. } => "composite_order",
+            Weakness::SmallEmbeddingDegree { .. } => "small_embedding_degree",
+        }
+    }
+}
+
+impl fmt::Display for Weakness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Weakness::SmallSubgroup { order } => write!(
+                f,
+                "the point has order {} of {} bits, fewer than {}: Pollard's rho finds discrete \
+                 logarithms in about 2^{} steps",
+                order,
+                order.bits(),
+                MIN_SUBGROUP_BITS,
+                order.bits() / 2
+            ),
+            Weakness::Anomalous => write!(f, "the curve is anomalous (#E = p): Smart's attack breaks it"),
+            Weakness::CompositeOrder { order } => write!(
+                f,
+                "the point has composite order {}: Pohlig-Hellman splits discrete logarithms along its \
+                 factors",
+                order
+            ),
+            Weakness::SmallEmbeddingDegree { degree } => write!(
+                f,
+                "the embedding degree is {}: the MOV/Frey-Rück attack moves discrete logarithms to F_p^{}",
+                degree, degree
+            ),
+        }
+    }
+}
+
+/// The red flags of a point of order `point_order` on a curve over F_p with `group_order` points, if
+/// known: a small or composite point order, and an anomalous curve. A point of order p also makes the
+/// curve anomalous, as p is then the only multiple of it in the Hasse interval.
+pub fn weaknesses(p: &BigInt, point_order: &BigInt, group_order: Option<&BigInt>) -> Vec<Weakness> {
+    let mut weaknesses = Vec::new();
+    if point_order.bits() < MIN_SUBGROUP_BITS {
+        weaknesses.push(Weakness::SmallSubgroup { order: point_order.clone() });
+    }
+    if point_order == p || group_order == Some(p) {
+        weaknesses.push(Weakness::Anomalous);
+    }
+    if point_order > &BigInt::from(1) && !is_probable_prime(point_order, MILLER_RABIN_ROUNDS) {
+        weaknesses.push(Weakness::CompositeOrder { order: point_order.clone() });
+    }
+    weaknesses
+}
+
+/// The group structure of a curve and the red flags it raises against known discrete-logarithm attacks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CurveAnalysis {
+    pub curve: WeierstrassCurve,
+    /// Number of points, including the point at infinity.
+    pub order: BigInt,
+    /// Largest prime factor r of the order, the subgroup a protocol would use.
+    pub subgroup_order: BigInt,
+    pub cofactor: BigInt,
+    /// Trace of Frobenius p + 1 − #E.
+    pub trace: BigInt,
+    /// The embedding degree, the smallest k with r | p^k − 1, if it is at most `embedding_degree_cap`.
+    pub embedding_degree: Option<u64>,
+    /// How far the embedding degree was searched.
+    pub embedding_degree_cap: u64,
+}
+
+impl CurveAnalysis {
+    /// Whether #E = p, so that Smart's attack lifts the curve to the p-adics and solves discrete
+    /// logarithms in linear time.
+    pub fn is_anomalous(&self) -> bool {
+        self.order == self.curve.p
+    }
+
+    /// Whether the embedding degree is at most `MOV_DEGREE_BOUND`.
+    pub fn is_mov_vulnerable(&self) -> bool {
+        self.embedding_degree.is_some_and(|degree| degree <= MOV_DEGREE_BOUND)
+    }
+
+    /// The red flags of the curve: `Weakness::Anomalous` and `Weakness::SmallEmbeddingDegree`.
+    pub fn weaknesses(&self) -> Vec<Weakness> {
+        let mut weaknesses = Vec::new();
+        if self.is_anomalous() {
+            weaknesses.push(Weakness::Anomalous);
+        }
+        if let (Some(degree), true) = (self.embedding_degree, self.is_mov_vulnerable()) {
+            weaknesses.push(Weakness::SmallEmbeddingDegree { degree });
+        }
+        weaknesses
+    }
+}
+
+/// The embedding degree of a subgroup of prime order r of a curve over F_p: the multiplicative order of
+/// p modulo r, if it is at most `cap`. It is undefined, and `None`, when r = p.
+pub fn embedding_degree(p: &BigInt, r: &BigInt, cap: u64) -> Option<u64> {
+    multiplicative_order(p, r, cap)
+}
+
+/// Checks `order` against a point of the curve: [order]P must be the point at infinity.
+fn check_order(curve: &WeierstrassCurve, order: &BigInt) -> Result<(), TransformError> {
+    let field = Field::new(&curve.p);
+    let mut x = BigInt::zero();
+    let point = loop {
+        if let Some(y) = field.sqrt(&curve.rhs(&x), &SearchLimits::default())? {
+            break Point::Affine(x, y);
+        }
+        x += 1u32;
+    };
+    if !scalar_mul(curve, &point, order).is_infinity() {
+        return Err(TransformError::InvalidParameters(format!(
+            "{} is not the order of the curve: multiplying {} by it does not give the point at infinity",
+            order, point
+        )));
+    }
+    Ok(())
+}
+
+/// Analyzes `curve`, counting its points when `order` is not given (which needs p ≤ 2^24), and searching
+/// the embedding degree up to `embedding_degree_cap` (raised to `MOV_DEGREE_BOUND` if lower).
+///
+/// A given order is checked against the Hasse bound and a point of the curve. Anomalous and
+/// MOV-vulnerable curves, which fail SafeCurves' "transfer" criterion, are listed by
+/// `CurveAnalysis::weaknesses`.
+pub fn analyze(
+    curve: &WeierstrassCurve,
+    order: Option<&BigInt>,
+    embedding_degree_cap: u64,
+) -> Result<CurveAnalysis, TransformError> {
+    let p = &curve.p;
+    let order = match order {
+        Some(order) => {
+            check_order(curve, order)?;
+            order.clone()
+        }
+        None => count_points(&curve.a, &curve.b, p)?,
+    };
+    let trace = trace_of_frobenius(&order, p)?;
+    let subgroup_order = largest_prime_factor(&order);
+    let embedding_degree_cap = embedding_degree_cap.max(MOV_DEGREE_BOUND);
+    let embedding_degree = embedding_degree(p, &subgroup_order, embedding_degree_cap);
+    let analysis = CurveAnalysis {
+        curve: curve.clone(),
+        cofactor: &order / &subgroup_order,
+        order,
+        subgroup_order,
+        trace,
+        embedding_degree,
+        embedding_degree_cap,
+    };
+    if analysis.is_mov_vulnerable() {
+        debug!(
+            degree = embedding_degree,
+            "small embedding degree: the MOV/Frey-Rück attack moves discrete logarithms to F_p^k"
+        );
+    }
+    Ok(analysis)
+}
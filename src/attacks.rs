@@ -0,0 +1,338 @@
+use crate::curve::{CurveGroup, Point};
+use crate::error::TransformError;
+use crate::fp2::Fp2;
+use crate::limits::SearchLimits;
+use crate::numtheory::{crt, largest_prime_factor, mod_sqrt};
+use crate::order::count_points;
+use crate::scalar_mul::{scalar_mul, scalar_mul_protected, Countermeasures};
+use crate::weierstrass::WeierstrassCurve;
+use num_bigint::{BigInt, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
+use std::fmt;
+
+/// Largest prime order of a leaking point; the attacker finds each residue by trying every candidate.
+pub const SMALL_ORDER_BOUND: u64 = 1 << 16;
+
+/// A congruence k ≡ residue (mod modulus) the attacker learns from the victim's answer for `point`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leak {
+    /// The curve `point` really lies on: the victim's own, a model of its quadratic twist, or an
+    /// invalid curve with another b.
+    pub curve: WeierstrassCurve,
+    pub point: Point,
+    /// The victim's answer [k]·point.
+    pub answer: Point,
+    /// The prime order of `point`.
+    pub modulus: BigInt,
+    /// k modulo `modulus`; only up to sign for the twist, where the victim reveals just an x-coordinate.
+    pub residue: BigInt,
+}
+
+/// A worked example of the small-subgroup, twist and invalid-curve attacks against a victim that
+/// multiplies received points by its secret key without validating them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttackDemo {
+    pub curve: WeierstrassCurve,
+    pub order: BigInt,
+    /// Largest prime factor r of the order; the secret lies in [1, r).
+    pub subgroup_order: BigInt,
+    pub secret: BigInt,
+    /// Points of small order on the curve itself, from the cofactor.
+    pub small_subgroup: Vec<Leak>,
+    /// Points of small order on the quadratic twist, accepted by an x-only ladder.
+    pub twist: Vec<Leak>,
+    /// Points of small order on curves y^2 = x^3 + ax + b' with b' ≠ b.
+    pub invalid_curve: Vec<Leak>,
+    /// The secret modulo the product of the small-subgroup and invalid-curve moduli, by CRT.
+    pub recovered: BigInt,
+    pub recovered_modulus: BigInt,
+    /// What the victim's scalar multiplication did against side channels, blinding with the order.
+    pub countermeasures: Countermeasures,
+}
+
+impl AttackDemo {
+    /// Whether the leaks pin down the secret completely.
+    pub fn recovers_secret(&self) -> bool {
+        self.recovered_modulus >= self.subgroup_order && self.recovered == self.secret
+    }
+}
+
+/// The distinct prime factors of `n` up to `SMALL_ORDER_BOUND`.
+fn small_prime_factors(n: &BigInt) -> Vec<BigInt> {
+    let mut rest = n.clone();
+    let mut factors = Vec::new();
+    for q in 2..=SMALL_ORDER_BOUND {
+        if rest.is_multiple_of(&BigInt::from(q)) {
+            factors.push(BigInt::from(q));
+            while rest.is_multiple_of(&BigInt::from(q)) {
+                rest /= q;
+            }
+        }
+    }
+    factors
+}
+
+/// A point of prime order q on `curve` of order `order`: for R with increasing x, [order / q^e]·R (with
+/// q^e the full power of q dividing the order) multiplied by q until the next multiple would vanish.
+fn point_of_order(
+    curve: &WeierstrassCurve,
+    order: &BigInt,
+    q: &BigInt,
+) -> Result<Option<Point>, TransformError> {
+    let mut cofactor = order.clone();
+    while cofactor.is_multiple_of(q) {
+        cofactor /= q;
+    }
+    let mut x = BigInt::zero();
+    while x < curve.p {
+        if let Some(y) = mod_sqrt(&curve.rhs(&x), &curve.p, &SearchLimits::default())? {
+            let mut point = scalar_mul(curve, &Point::Affine(x.clone(), y), &cofactor);
+            if !point.is_infinity() {
+                loop {
+                    let next = scalar_mul(curve, &point, q);
+                    if next.is_infinity() {
+                        return Ok(Some(point));
+                    }
+                    point = next;
+                }
+            }
+        }
+        x += 1u32;
+    }
+    Ok(None)
+}
+
+/// The attacker's side: the j in [0, q) with [j]·point = answer, or with the same x-coordinate when
+/// `x_only`, by trying each in turn.
+fn discrete_log(
+    curve: &WeierstrassCurve,
+    point: &Point,
+    answer: &Point,
+    q: &BigInt,
+    x_only: bool,
+) -> BigInt {
+    let same = |candidate: &Point| match (candidate, answer) {
+        (Point::Affine(x1, _), Point::Affine(x2, _)) if x_only => x1 == x2,
+        _ => candidate == answer,
+    };
+    let mut multiple = Point::Infinity;
+    let mut j = BigInt::zero();
+    while !same(&multiple) && &j < q {
+        multiple = curve.add(&multiple, point);
+        j += 1u32;
+    }
+    j
+}
+
+/// How the victim multiplies: its secret, the group order it blinds with, and its countermeasures.
+struct Victim<'a, R: ?Sized> {
+    secret: &'a BigInt,
+    order: &'a BigInt,
+    countermeasures: Countermeasures,
+    rng: &'a mut R,
+}
+
+/// Plays one exchange: the victim multiplies `point` by its secret with its own curve's formulas, which
+/// never look at b, and the attacker solves for the residue.
+fn leak<R: RngCore + CryptoRng + ?Sized>(
+    victim: &mut Victim<'_, R>,
+    victim_curve: &WeierstrassCurve,
+    curve: &WeierstrassCurve,
+    point: Point,
+    q: &BigInt,
+    x_only: bool,
+) -> Leak {
+    let Victim { secret, order, countermeasures, .. } = *victim;
+    let answer = scalar_mul_protected(victim_curve, &point, secret, order, countermeasures, victim.rng);
+    let residue = discrete_log(curve, &point, &answer, q, x_only);
+    Leak {
+        curve: curve.clone(),
+        point,
+        answer,
+        modulus: q.clone(),
+        residue,
+    }
+}
+
+/// Runs the demonstration on `curve` (p ≤ 2^24, since points are counted), with a random secret in
+/// [1, r) for the largest prime factor r of the order.
+///
+/// Small-subgroup leaks come from the cofactor, twist leaks from the order 2p + 2 − #E of the twist,
+/// and invalid curves are tried for b' = 0, 1, 2, ... until their fresh prime orders, together with
+/// the small-subgroup ones, exceed r.
+pub fn demonstrate_attacks<R: RngCore + CryptoRng + ?Sized>(
+    curve: &WeierstrassCurve,
+    rng: &mut R,
+) -> Result<AttackDemo, TransformError> {
+    demonstrate_attacks_with(curve, Countermeasures::default(), rng)
+}
+
+/// `demonstrate_attacks` against a victim using `countermeasures` in its scalar multiplications, with
+/// the group order #E for scalar blinding.
+///
+/// Randomized coordinates change nothing an attacker sees here. Blinding leaves the small-subgroup leaks
+/// intact, as their orders divide #E, but turns the twist and invalid-curve answers into random
+/// multiples, so the residues and the CRT result come out wrong.
+pub fn demonstrate_attacks_with<R: RngCore + CryptoRng + ?Sized>(
+    curve: &WeierstrassCurve,
+    countermeasures: Countermeasures,
+    rng: &mut R,
+) -> Result<AttackDemo, TransformError> {
+    let p = &curve.p;
+    let order = count_points(&curve.a, &curve.b, p)?;
+    let subgroup_order = largest_prime_factor(&order);
+    let secret = if subgroup_order > BigInt::one() {
+        rng.gen_bigint_range(&BigInt::one(), &subgroup_order)
+    } else {
+        BigInt::one()
+    };
+    let mut modulus = BigInt::one();
+    let mut victim = Victim { secret: &secret, order: &order, countermeasures, rng };
+
+    let mut small_subgroup = Vec::new();
+    for q in small_prime_factors(&(&order / &subgroup_order)) {
+        if q == subgroup_order {
+            continue;
+        }
+        if let Some(point) = point_of_order(curve, &order, &q)? {
+            small_subgroup.push(leak(&mut victim, curve, curve, point, &q, false));
+            modulus *= &q;
+        }
+    }
+
+    // The twist d·y^2 = x^3 + ax + b is isomorphic to y^2 = x^3 + a·d^2·x + b·d^3 by
+    // (x, y) -> (d·x, d^2·y), and an x-only ladder fed an x with x^3 + ax + b a non-square silently
+    // computes on it
+    let d = Fp2::find_non_residue(p, &SearchLimits::default())?;
+    let twist = WeierstrassCurve::new(&(&curve.a * &d * &d), &(&curve.b * d.pow(3)), p)?;
+    let twist_order = BigInt::from(2) * p + 2u32 - &order;
+    let mut twist_leaks = Vec::new();
+    for q in small_prime_factors(&twist_order) {
+        if let Some(point) = point_of_order(&twist, &twist_order, &q)? {
+            twist_leaks.push(leak(&mut victim, &twist, &twist, point, &q, true));
+        }
+    }
+
+    let mut invalid_curve = Vec::new();
+    let mut b = BigInt::zero();
+    while modulus < subgroup_order && &b < p {
+        let candidate = WeierstrassCurve::new(&curve.a, &b, p);
+        b += 1u32;
+        let Ok(candidate) = candidate else { continue };
+        if candidate.b == curve.b.mod_floor(p) {
+            continue;
+        }
+        let candidate_order = count_points(&candidate.a, &candidate.b, p)?;
+        for q in small_prime_factors(&candidate_order) {
+            if !modulus.gcd(&q).is_one() || modulus >= subgroup_order {
+                continue;
+            }
+            if let Some(point) = point_of_order(&candidate, &candidate_order, &q)? {
+                invalid_curve.push(leak(&mut victim, curve, &candidate, point, &q, false));
+                modulus *= &q;
+            }
+        }
+    }
+
+    let congruences: Vec<(BigInt, BigInt)> = small_subgroup
+        .iter()
+        .chain(&invalid_curve)
+        .map(|leak| (leak.residue.clone(), leak.modulus.clone()))
+        .collect();
+    let recovered = crt(&congruences).expect("the moduli are distinct primes");
+    Ok(AttackDemo {
+        curve: curve.clone(),
+        order,
+        subgroup_order,
+        secret,
+        small_subgroup,
+        twist: twist_leaks,
+        invalid_curve,
+        recovered,
+        recovered_modulus: modulus,
+        countermeasures,
+    })
+}
+
+impl fmt::Display for AttackDemo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let curve = &self.curve;
+        writeln!(
+            f,
+            "curve y^2 = x^3 + {}x + {} over F_{} with {} points",
+            curve.a, curve.b, curve.p, self.order
+        )?;
+        writeln!(
+            f,
+            "victim's secret k = {}, in [1, r) for the subgroup order r = {}",
+            self.secret, self.subgroup_order
+        )?;
+        let Countermeasures { scalar_blinding, randomize_coordinates } = self.countermeasures;
+        match (scalar_blinding, randomize_coordinates) {
+            (false, false) => {}
+            (true, false) => writeln!(f, "the victim blinds k with multiples of #E")?,
+            (false, true) => writeln!(f, "the victim randomizes its projective coordinates")?,
+            (true, true) => writeln!(
+                f,
+                "the victim blinds k with multiples of #E and randomizes its projective coordinates"
+            )?,
+        }
+        writeln!(f)?;
+        writeln!(f, "small-subgroup attack: the victim multiplies points of the cofactor subgroup")?;
+        for leak in &self.small_subgroup {
+            writeln!(
+                f,
+                "  P = {} of order {}: [k]P = {}, so k = {} (mod {})",
+                leak.point, leak.modulus, leak.answer, leak.residue, leak.modulus
+            )?;
+        }
+        if self.small_subgroup.is_empty() {
+            writeln!(f, "  nothing to learn: the cofactor has no prime factor other than r")?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "twist attack: an x-only ladder also accepts x with x^3 + ax + b a non-square, which lies"
+        )?;
+        writeln!(
+            f,
+            "on the quadratic twist, shown here as a point of an isomorphic model (the victim sees x / d)"
+        )?;
+        for leak in &self.twist {
+            writeln!(
+                f,
+                "  P = {} of order {} on y^2 = x^3 + {}x + {}: [k]P = {}, so k = +-{} (mod {})",
+                leak.point, leak.modulus, leak.curve.a, leak.curve.b, leak.answer, leak.residue, leak.modulus
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "invalid-curve attack: the addition formulas never use b, so points of other curves work too"
+        )?;
+        for leak in &self.invalid_curve {
+            writeln!(
+                f,
+                "  b' = {}: P = {} of order {}: [k]P = {}, so k = {} (mod {})",
+                leak.curve.b, leak.point, leak.modulus, leak.answer, leak.residue, leak.modulus
+            )?;
+        }
+        writeln!(f)?;
+        let verdict = if self.recovers_secret() {
+            "the secret"
+        } else if self.recovered_modulus >= self.subgroup_order {
+            "which is wrong: blinding by #E turns the answers on other curves into random multiples"
+        } else {
+            "part of the secret"
+        };
+        writeln!(
+            f,
+            "by CRT: k = {} (mod {}), {}\n\nchecking that received points lie on the curve and in the \
+             subgroup of order r stops all three attacks",
+            self.recovered, self.recovered_modulus, verdict
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+//! Streaming conversion of point files too large to hold in memory: the points are read, mapped and
+//! handed on a chunk at a time, so memory use depends on the chunk size and not on the input.
+
+use crate::error::TransformError;
+use crate::map::MontgomeryMap;
+use num_bigint::BigInt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// Points per chunk unless told otherwise: a few tens of MB for 256-bit coordinates.
+pub const DEFAULT_CHUNK: usize = 65_536;
+
+/// What `map_lines` went through.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BatchSummary {
+    /// Points read, mapped or not, counting the lines that were not a point.
+    pub points: u64,
+    /// Points that were not mapped: off the curve, or not a point at all.
+    pub failures: u64,
+    /// Lines that were not a point, among the failures.
+    pub malformed: u64,
+}
+
+fn invalid(message: &str) -> TransformError {
+    TransformError::InvalidParameters(message.to_string())
+}
+
+/// Parses `x y` or `x,y`, in decimal or 0x-prefixed hexadecimal, followed by the label when `labeled`:
+/// the rest of the line after the separator, as it is. Without `labeled` nothing may follow the point.
+fn parse_point(text: &str, labeled: bool) -> Result<((BigInt, BigInt), &str), TransformError> {
+    let is_separator = |c: char| c == ',' || c.is_whitespace();
+    let mut rest = text;
+    let mut coordinate = |name: &str| {
+        let start = rest.trim_start_matches(is_separator);
+        let (field, tail) = start.split_at(start.find(is_separator).unwrap_or(start.len()));
+        rest = tail;
+        if field.is_empty() {
+            return Err(invalid(&format!("missing {}", name)));
+        }
+        let parsed = match field.strip_prefix("0x").or_else(|| field.strip_prefix("0X")) {
+            Some(digits) => BigInt::parse_bytes(digits.as_bytes(), 16),
+            None => BigInt::from_str(field).ok(),
+        };
+        parsed.ok_or_else(|| invalid(&format!("{} is not an integer: {:?}", name, field)))
+    };
+    let point = (coordinate("x")?, coordinate("y")?);
+    let label = rest.trim_start_matches(is_separator);
+    if !labeled && !label.is_empty() {
+        return Err(invalid(&format!("unexpected {:?} after the point", label)));
+    }
+    Ok((point, label))
+}
+
+/// Reads points of the map's Weierstrass curve from `input`, one `x y` (or `x,y`) per line in decimal or
+/// 0x-prefixed hexadecimal, and passes each image to `sink` in input order with its 1-based line number;
+/// blank lines and lines starting with `#` are skipped.
+///
+/// At most `chunk` points are held at a time (0 means `DEFAULT_CHUNK`). With the `parallel` feature each
+/// chunk is mapped by `MontgomeryMap::map_points_parallel` over `threads` threads; otherwise `threads`
+/// is ignored. A point off the curve reaches `sink` as `Err(PointNotOnCurve)`, and a line that is not a
+/// point as `Err(InvalidParameters)`, and the rest go on; a read error (`TransformError::Io`) or an
+/// error from `sink` stops the batch.
+pub fn map_lines<R, F>(
+    map: &MontgomeryMap,
+    input: R,
+    chunk: usize,
+    threads: usize,
+    mut sink: F,
+) -> Result<BatchSummary, TransformError>
+where
+    R: BufRead,
+    F: FnMut(u64, Result<(BigInt, BigInt), TransformError>) -> Result<(), TransformError>,
+{
+    map_records(map, input, chunk, threads, false, |line, _, image| sink(line, image))
+}
+
+/// `map_lines` for lines with a label after the point, such as a key ID or an index: `x y label` or
+/// `x,y,label`. The label is the rest of the line after the separator following y, trimmed but otherwise
+/// unchanged, and reaches `sink` with the image of its point; it may be empty.
+pub fn map_labeled_lines<R, F>(
+    map: &MontgomeryMap,
+    input: R,
+    chunk: usize,
+    threads: usize,
+    sink: F,
+) -> Result<BatchSummary, TransformError>
+where
+    R: BufRead,
+    F: FnMut(u64, &str, Result<(BigInt, BigInt), TransformError>) -> Result<(), TransformError>,
+{
+    map_records(map, input, chunk, threads, true, sink)
+}
+
+fn map_records<R, F>(
+    map: &MontgomeryMap,
+    input: R,
+    chunk: usize,
+    threads: usize,
+    labeled: bool,
+    mut sink: F,
+) -> Result<BatchSummary, TransformError>
+where
+    R: BufRead,
+    F: FnMut(u64, &str, Result<(BigInt, BigInt), TransformError>) -> Result<(), TransformError>,
+{
+    let chunk = if chunk == 0 { DEFAULT_CHUNK } else { chunk };
+    let mut summary = BatchSummary::default();
+    // Each line of the chunk with the reason it is not a point, if it is not one
+    let mut lines: Vec<(u64, Option<TransformError>)> = Vec::with_capacity(chunk);
+    let mut labels = Vec::with_capacity(if labeled { chunk } else { 0 });
+    let mut points = Vec::with_capacity(chunk);
+    let mut flush = |lines: &mut Vec<(u64, Option<TransformError>)>,
+                     labels: &mut Vec<String>,
+                     points: &mut Vec<(BigInt, BigInt)>| {
+        #[cfg(feature = "parallel")]
+        let images = map.map_points_parallel(points, threads);
+        #[cfg(not(feature = "parallel"))]
+        let images = {
+            let _ = threads;
+            map.map_points(points)
+        };
+        let mut images = images.into_iter();
+        let mut labels = labels.drain(..);
+        for (line, malformed) in lines.drain(..) {
+            let image = match malformed {
+                Some(error) => {
+                    summary.malformed += 1;
+                    Err(error)
+                }
+                None => images.next().expect("one image per parsed point"),
+            };
+            summary.points += 1;
+            summary.failures += image.is_err() as u64;
+            sink(line, &labels.next().unwrap_or_default(), image)?;
+        }
+        points.clear();
+        Ok::<(), TransformError>(())
+    };
+    for (index, text) in input.lines().enumerate() {
+        let line = index as u64 + 1;
+        let text = text.map_err(|error| {
+            TransformError::Io(format!("line {}: cannot read: {}", line, error))
+        })?;
+        let text = text.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        let (malformed, label) = match parse_point(text, labeled) {
+            Ok((point, label)) => {
+                points.push(point);
+                (None, label)
+            }
+            Err(error) => (Some(error), ""),
+        };
+        lines.push((line, malformed));
+        if labeled {
+            labels.push(label.to_string());
+        }
+        if lines.len() == chunk {
+            flush(&mut lines, &mut labels, &mut points)?;
+        }
+    }
+    flush(&mut lines, &mut labels, &mut points)?;
+    Ok(summary)
+}
@@ -0,0 +1,110 @@
+use num_bigint::{BigInt, Sign};
+use num_traits::Zero;
+
+/// CBOR tag for an unsigned bignum (RFC 8949, section 3.4.3).
+const TAG_POSITIVE_BIGNUM: u64 = 2;
+/// CBOR tag for a negative bignum, encoding −1 − n.
+const TAG_NEGATIVE_BIGNUM: u64 = 3;
+
+/// The subset of CBOR data items needed to report results.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CborValue {
+    Unsigned(u64),
+    /// The negative integer −1 − n.
+    Negative(u64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Bool(bool),
+    Array(Vec<CborValue>),
+    /// A map with text keys; `encode` sorts the entries into canonical order.
+    Map(Vec<(String, CborValue)>),
+    Tag(u64, Box<CborValue>),
+}
+
+/// Writes an item head with the shortest argument encoding, as deterministic CBOR requires.
+fn write_head(out: &mut Vec<u8>, major: u8, argument: u64) {
+    let major = major << 5;
+    match argument {
+        0..=23 => out.push(major | argument as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, argument as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(argument as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend_from_slice(&(argument as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&argument.to_be_bytes());
+        }
+    }
+}
+
+impl CborValue {
+    /// Encodes a machine integer as an unsigned or negative integer item.
+    pub fn integer(value: i64) -> CborValue {
+        if value < 0 {
+            CborValue::Negative(!value as u64)
+        } else {
+            CborValue::Unsigned(value as u64)
+        }
+    }
+
+    /// Encodes an integer as a tagged bignum whose content is a big-endian byte string without leading zeros.
+    pub fn bignum(value: &BigInt) -> CborValue {
+        let (tag, magnitude) = match value.sign() {
+            Sign::Minus => (TAG_NEGATIVE_BIGNUM, -value - 1u32),
+            _ => (TAG_POSITIVE_BIGNUM, value.clone()),
+        };
+        let bytes = if magnitude.is_zero() { Vec::new() } else { magnitude.to_bytes_be().1 };
+        CborValue::Tag(tag, Box::new(CborValue::Bytes(bytes)))
+    }
+
+    /// Encodes the item in the core deterministic encoding of RFC 8949: shortest heads, definite
+    /// lengths, and map keys sorted by their encoded bytes.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode_into(&mut out);
+        out
+    }
+
+    fn encode_into(&self, out: &mut Vec<u8>) {
+        match self {
+            CborValue::Unsigned(value) => write_head(out, 0, *value),
+            CborValue::Negative(value) => write_head(out, 1, *value),
+            CborValue::Bytes(bytes) => {
+                write_head(out, 2, bytes.len() as u64);
+                out.extend_from_slice(bytes);
+            }
+            CborValue::Text(text) => {
+                write_head(out, 3, text.len() as u64);
+                out.extend_from_slice(text.as_bytes());
+            }
+            CborValue::Bool(value) => out.push(if *value { 0xf5 } else { 0xf4 }),
+            CborValue::Array(items) => {
+                write_head(out, 4, items.len() as u64);
+                for item in items {
+                    item.encode_into(out);
+                }
+            }
+            CborValue::Map(entries) => {
+                let mut encoded: Vec<(Vec<u8>, Vec<u8>)> = entries
+                    .iter()
+                    .map(|(key, value)| (CborValue::Text(key.clone()).encode(), value.encode()))
+                    .collect();
+                encoded.sort();
+                write_head(out, 5, encoded.len() as u64);
+                for (key, value) in encoded {
+                    out.extend(key);
+                    out.extend(value);
+                }
+            }
+            CborValue::Tag(tag, item) => {
+                write_head(out, 6, *tag);
+                item.encode_into(out);
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! Addition chains for fixed exponents, such as the p − 2 of Fermat inversion.
+//!
+//! The exponents of the primes in the registry are mostly long runs of ones (2^255 − 21, or the P-256 and
+//! P-384 primes less two), so the chain is built the way hand-written implementations do: a^(2^L − 1)
+//! for each run length L, each from the ones before, then the runs joined Horner-style. That takes one
+//! squaring per bit below the top run and one per bit of the longest run, so about one per bit of the
+//! exponent when the top run is the longest (317 for the 256 bits of the P-256 prime less two, whose longest
+//! run is in the middle), and a multiplication per run plus a few. The sequence of
+//! squarings and multiplications depends only on the exponent.
+
+use crate::field::Field;
+use crate::ops;
+use num_bigint::{BigInt, Sign};
+
+/// A fixed sequence of squarings and multiplications computing a^e for one positive exponent e.
+///
+/// Every step appends `table[from]^(2^squarings) · table[times]` to a table of powers of a that starts
+/// with a itself; entry `result`, squared once per trailing zero bit of e, is a^e.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdditionChain {
+    steps: Vec<(usize, u64, usize)>,
+    result: usize,
+    trailing_zeros: u64,
+}
+
+impl AdditionChain {
+    /// Builds the chain for a positive exponent.
+    pub fn new(exponent: &BigInt) -> AdditionChain {
+        assert!(exponent.sign() == Sign::Plus, "AdditionChain needs a positive exponent");
+        // The maximal runs of ones, from the most significant: (run length, zeros below the run)
+        let mut runs = Vec::new();
+        let mut bit = exponent.bits();
+        while bit > 0 {
+            let top = bit;
+            while bit > 0 && exponent.bit(bit - 1) {
+                bit -= 1;
+            }
+            let bottom = bit;
+            while bit > 0 && !exponent.bit(bit - 1) {
+                bit -= 1;
+            }
+            runs.push((top - bottom, bottom - bit));
+        }
+
+        // a^(2^L − 1) for each run length L in increasing order, from x_1 = a: the longest power so far is
+        // doubled, x_2m = x_m^(2^m) · x_m, while that fits in L, then extended by the longest power that
+        // fits in the rest, x_m+n = x_m^(2^n) · x_n, so the table costs one squaring per bit of the longest run
+        let mut lengths: Vec<u64> = runs.iter().map(|&(length, _)| length).collect();
+        lengths.sort_unstable();
+        lengths.dedup();
+        let mut steps = Vec::new();
+        let mut powers: Vec<(u64, usize)> = vec![(1, 0)];
+        for &length in &lengths {
+            let (mut built, mut entry) = *powers.last().expect("starts with a");
+            while built < length {
+                let rest = length - built;
+                let (add, add_entry) = if built <= rest {
+                    (built, entry)
+                } else {
+                    *powers.iter().rev().find(|&&(known, _)| known <= rest).expect("x_1 fits")
+                };
+                steps.push((entry, add, add_entry));
+                built += add;
+                entry = steps.len();
+                powers.push((built, entry));
+            }
+        }
+        let power = |length: u64| {
+            powers.iter().find(|&&(known, _)| known == length).expect("built for every run").1
+        };
+
+        // Horner over the runs: shift past the zeros above each run and its length, then multiply it in
+        let mut result = power(runs[0].0);
+        for pair in runs.windows(2) {
+            let (_, zeros) = pair[0];
+            let (length, _) = pair[1];
+            steps.push((result, zeros + length, power(length)));
+            result = steps.len();
+        }
+        let trailing_zeros = runs.last().expect("e > 0").1;
+        AdditionChain { steps, result, trailing_zeros }
+    }
+
+    /// The number of multiplications the chain takes.
+    pub fn multiplications(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// The number of squarings the chain takes.
+    pub fn squarings(&self) -> u64 {
+        self.steps.iter().map(|&(_, squarings, _)| squarings).sum::<u64>() + self.trailing_zeros
+    }
+
+    /// `base^e` in `field`, multiplying and squaring with its reduction.
+    pub fn pow(&self, field: &Field, base: &BigInt) -> BigInt {
+        ops::record(self.multiplications() as u64, self.squarings());
+        let square = |value: BigInt, times: u64| {
+            (0..times).fold(value, |value, _| field.reduce(&(&value * &value)))
+        };
+        let mut table = Vec::with_capacity(self.steps.len() + 1);
+        table.push(field.reduce(base));
+        for &(from, squarings, times) in &self.steps {
+            let shifted = square(table[from].clone(), squarings);
+            table.push(field.reduce(&(shifted * &table[times])));
+        }
+        square(table.swap_remove(self.result), self.trailing_zeros)
+    }
+}
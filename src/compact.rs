@@ -0,0 +1,140 @@
+//! Compact binary encodings of maps and points in the postcard format, for sending precomputed mapping
+//! parameters to embedded devices over constrained transports.
+//!
+//! postcard is a `no_std` serde format, and the module builds without the `std` feature, so a device can
+//! decode these bytes with this crate, or with `postcard` and `heapless` alone. Each record is a serde
+//! struct: integers are varints and every big integer is a byte string, a varint length followed by its
+//! big-endian magnitude without leading zeros. `SmallField` elements serialize as their canonical u64,
+//! which lets fixed-size messages go through `postcard::to_slice` without allocating.
+
+use crate::curve::{CurveGroup, Point};
+use crate::error::TransformError;
+use crate::map::{MontgomeryMap, MAP_FORMAT_VERSION};
+use crate::small::SmallField;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use num_bigint::{BigInt, Sign};
+use num_traits::Zero;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The map as written on the wire: the format version, then p, a, b, z0, s, s_inv, A and B.
+#[derive(Serialize, Deserialize)]
+struct CompactMap {
+    version: u32,
+    values: [Vec<u8>; 8],
+}
+
+/// A point as written on the wire: variant 0 is the point at infinity, variant 1 the affine (x, y).
+#[derive(Serialize, Deserialize)]
+enum CompactPoint {
+    Infinity,
+    Affine(Vec<u8>, Vec<u8>),
+}
+
+fn invalid(message: String) -> TransformError {
+    TransformError::InvalidParameters(message)
+}
+
+fn magnitude(value: &BigInt) -> Vec<u8> {
+    if value.is_zero() {
+        Vec::new()
+    } else {
+        value.to_bytes_be().1
+    }
+}
+
+fn integer(bytes: &[u8]) -> BigInt {
+    BigInt::from_bytes_be(Sign::Plus, bytes)
+}
+
+/// Deserializes a whole buffer, rejecting trailing bytes.
+fn decode<'a, T: Deserialize<'a>>(bytes: &'a [u8], what: &str) -> Result<T, TransformError> {
+    let (value, rest) = postcard::take_from_bytes(bytes)
+        .map_err(|error| invalid(format!("malformed {} encoding: {}", what, error)))?;
+    if !rest.is_empty() {
+        return Err(invalid(format!("{} trailing bytes after {} encoding", rest.len(), what)));
+    }
+    Ok(value)
+}
+
+impl MontgomeryMap {
+    /// Serializes the map with postcard: the version, then p, a, b, z0, s, s_inv, A and B as byte strings.
+    /// With one-byte lengths this is 31 bytes shorter than `to_bytes`.
+    pub fn to_postcard(&self) -> Vec<u8> {
+        let record = CompactMap {
+            version: MAP_FORMAT_VERSION,
+            values: [
+                magnitude(&self.p),
+                magnitude(&self.a),
+                magnitude(&self.b),
+                magnitude(&self.z0),
+                magnitude(&self.s),
+                magnitude(&self.s_inv),
+                magnitude(&self.a_montgomery),
+                magnitude(&self.b_montgomery),
+            ],
+        };
+        postcard::to_allocvec(&record).expect("a map record always serializes")
+    }
+
+    /// Parses and validates a map written by `to_postcard`.
+    pub fn from_postcard(bytes: &[u8]) -> Result<MontgomeryMap, TransformError> {
+        let record: CompactMap = decode(bytes, "map")?;
+        if record.version != MAP_FORMAT_VERSION {
+            return Err(invalid(format!("unsupported map format version {}", record.version)));
+        }
+        let [p, a, b, z0, s, s_inv, a_montgomery, b_montgomery] = record.values.map(|value| integer(&value));
+        let map = MontgomeryMap {
+            p,
+            a,
+            b,
+            z0,
+            s,
+            s_inv,
+            a_montgomery,
+            b_montgomery,
+        };
+        map.validate()?;
+        Ok(map)
+    }
+}
+
+/// Serializes a point with postcard: a variant byte, then for an affine point x and y as byte strings.
+pub fn point_to_postcard(point: &Point) -> Vec<u8> {
+    let record = match point {
+        Point::Infinity => CompactPoint::Infinity,
+        Point::Affine(x, y) => CompactPoint::Affine(magnitude(x), magnitude(y)),
+    };
+    postcard::to_allocvec(&record).expect("a point record always serializes")
+}
+
+/// Parses a point written by `point_to_postcard` and checks that it lies on `curve`.
+pub fn point_from_postcard<C: CurveGroup>(bytes: &[u8], curve: &C) -> Result<Point, TransformError> {
+    let point = match decode(bytes, "point")? {
+        CompactPoint::Infinity => Point::Infinity,
+        CompactPoint::Affine(x, y) => Point::Affine(integer(&x), integer(&y)),
+    };
+    if !curve.contains(&point) {
+        return Err(TransformError::PointNotOnCurve);
+    }
+    Ok(point)
+}
+
+impl<const P: u64> Serialize for SmallField<P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.value())
+    }
+}
+
+impl<'de, const P: u64> Deserialize<'de> for SmallField<P> {
+    /// Rejects values outside [0, P) instead of reducing them.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = u64::deserialize(deserializer)?;
+        if value >= P {
+            return Err(D::Error::custom(format!("{} is not in the range [0, {})", value, P)));
+        }
+        Ok(SmallField::new(value))
+    }
+}
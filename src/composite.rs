@@ -0,0 +1,99 @@
+use crate::error::TransformError;
+use crate::numtheory::{crt, is_probable_prime, MILLER_RABIN_ROUNDS};
+use crate::transform::{transform_with_map, TransformOptions};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::Zero;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+
+/// Adds the prime factor an error came from to its message.
+fn modulo(q: &BigInt, error: TransformError) -> TransformError {
+    match error {
+        TransformError::InvalidParameters(message) => {
+            TransformError::InvalidParameters(format!("modulo {}: {}", q, message))
+        }
+        TransformError::NoMontgomeryForm(message) => {
+            TransformError::NoMontgomeryForm(format!("modulo {}: {}", q, message))
+        }
+        TransformError::SearchLimitExceeded(message) => {
+            TransformError::SearchLimitExceeded(format!("modulo {}: {}", q, message))
+        }
+        error => error,
+    }
+}
+
+/// Checks that `factors` are distinct primes greater than 3 and returns their product n.
+fn check_factors(factors: &[BigInt]) -> Result<BigInt, TransformError> {
+    if factors.is_empty() {
+        return Err(TransformError::InvalidParameters("no prime factors given".to_string()));
+    }
+    for (index, q) in factors.iter().enumerate() {
+        if q == &BigInt::from(2) || q == &BigInt::from(3) {
+            return Err(TransformError::UnsupportedCharacteristic(q.clone()));
+        }
+        if !is_probable_prime(q, MILLER_RABIN_ROUNDS) {
+            return Err(TransformError::InvalidParameters(format!("factor {} is not a prime", q)));
+        }
+        if factors[..index].contains(q) {
+            return Err(TransformError::InvalidParameters(format!(
+                "factor {} appears more than once; only squarefree moduli are supported",
+                q
+            )));
+        }
+    }
+    Ok(factors.iter().product())
+}
+
+/// Transformation over the ring Z/nZ for a squarefree n, given its prime factors (all greater than 3).
+///
+/// Z/nZ is the product of the fields F_q, so the point is transformed modulo each factor q on its own
+/// and the coordinates and coefficients are glued back together with the Chinese remainder theorem.
+/// Every factor needs a Montgomery form, and the choices of z0 and s made for each one are independent,
+/// so where a prime gives a unique answer n has many: this is a teaching tool, not a field.
+pub fn transform_to_montgomery_composite(
+    x: &BigInt,
+    y: &BigInt,
+    a: &BigInt,
+    b: &BigInt,
+    factors: &[BigInt],
+    options: &TransformOptions,
+) -> Result<(BigInt, BigInt, BigInt, BigInt), TransformError> {
+    transform_to_montgomery_composite_with_rng(x, y, a, b, factors, options, &mut OsRng)
+}
+
+/// `transform_to_montgomery_composite`, drawing randomness from `rng`.
+pub fn transform_to_montgomery_composite_with_rng<R: RngCore + CryptoRng + ?Sized>(
+    x: &BigInt,
+    y: &BigInt,
+    a: &BigInt,
+    b: &BigInt,
+    factors: &[BigInt],
+    options: &TransformOptions,
+    rng: &mut R,
+) -> Result<(BigInt, BigInt, BigInt, BigInt), TransformError> {
+    let n = check_factors(factors)?;
+    if options.strict {
+        for (name, value) in [("a", a), ("b", b), ("x", x), ("y", y)] {
+            if value < &BigInt::zero() || value >= &n {
+                return Err(TransformError::InvalidParameters(format!(
+                    "{} = {} is not in the range [0, {})",
+                    name, value, n
+                )));
+            }
+        }
+    }
+
+    let mut parts: [Vec<(BigInt, BigInt)>; 4] = Default::default();
+    for q in factors {
+        let reduce = |value: &BigInt| value.mod_floor(q);
+        let (u, v, map) = transform_with_map(&reduce(x), &reduce(y), &reduce(a), &reduce(b), q, options, rng)
+            .map_err(|error| modulo(q, error))?;
+        for (part, value) in parts.iter_mut().zip([u, v, map.a_montgomery, map.b_montgomery]) {
+            part.push((value, q.clone()));
+        }
+    }
+    let [u, v, a_montgomery, b_montgomery] =
+        parts.map(|part| crt(&part).expect("distinct primes are pairwise coprime"));
+    Ok((u, v, a_montgomery, b_montgomery))
+}
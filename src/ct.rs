@@ -0,0 +1,61 @@
+//! Branch-free selection and swapping of integers below a modulus, behind `FieldElement::ct_select`,
+//! `FieldElement::ct_swap`, `Point::ct_select` and `Point::ct_swap`.
+//!
+//! The values are spread over as many 32-bit words as the modulus takes and combined with an all-ones
+//! or all-zeros mask, so which one is picked does not change the instructions run or the memory touched.
+//! `BigInt` itself is not constant-time: building the result strips leading zero words, and the
+//! arithmetic around these calls takes time that depends on the values.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use num_bigint::{BigInt, BigUint, Sign};
+
+/// All ones when `choice` is set, all zeros otherwise, without branching on it.
+fn mask(choice: bool) -> u32 {
+    0u32.wrapping_sub(choice as u32)
+}
+
+/// The number of 32-bit words values below `modulus` take.
+pub(crate) fn word_count(modulus: &BigInt) -> usize {
+    modulus.bits().div_ceil(32) as usize
+}
+
+/// `value` as exactly `count` little-endian words; it must be non-negative and fit.
+fn words(value: &BigInt, count: usize) -> Vec<u32> {
+    let mut words = vec![0; count];
+    for (word, digit) in words.iter_mut().zip(value.magnitude().iter_u32_digits()) {
+        *word = digit;
+    }
+    words
+}
+
+fn integer(words: Vec<u32>) -> BigInt {
+    BigInt::from_biguint(Sign::Plus, BigUint::new(words))
+}
+
+/// `second` if `choice` is set, `first` otherwise, for values of at most `count` words.
+pub(crate) fn select(first: &BigInt, second: &BigInt, choice: bool, count: usize) -> BigInt {
+    let mask = mask(choice);
+    let second = words(second, count);
+    let selected = words(first, count)
+        .into_iter()
+        .zip(second)
+        .map(|(first, second)| first ^ (mask & (first ^ second)))
+        .collect();
+    integer(selected)
+}
+
+/// Exchanges `first` and `second` if `choice` is set, for values of at most `count` words.
+#[cfg(feature = "std")]
+pub(crate) fn swap(first: &mut BigInt, second: &mut BigInt, choice: bool, count: usize) {
+    let mask = mask(choice);
+    let mut first_words = words(first, count);
+    let mut second_words = words(second, count);
+    for (first, second) in first_words.iter_mut().zip(second_words.iter_mut()) {
+        let difference = mask & (*first ^ *second);
+        *first ^= difference;
+        *second ^= difference;
+    }
+    *first = integer(first_words);
+    *second = integer(second_words);
+}
@@ -0,0 +1,43 @@
+use crate::error::TransformError;
+use crate::field::Field;
+use crate::limits::SearchLimits;
+use crate::log::debug;
+use crate::numtheory::{is_probable_prime, MILLER_RABIN_ROUNDS};
+use crate::poly::Polynomial;
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
+
+/// Finds every root of z^3 + az + b in F_p, in increasing order, for an odd prime p.
+///
+/// The product of the linear factors is gcd(z^p − z, z^3 + az + b); when all three are rational they
+/// are separated by gcd((z + δ)^((p−1)/2) − 1, ·) for random δ (Cantor-Zassenhaus), which succeeds
+/// with probability about 3/4 per δ; see `Polynomial::roots`. This takes O(log p) field operations, so
+/// it works for any size of p.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn cubic_roots<R: RngCore + CryptoRng + ?Sized>(
+    a: &BigInt,
+    b: &BigInt,
+    p: &BigInt,
+    limits: &SearchLimits,
+    rng: &mut R,
+) -> Result<Vec<BigInt>, TransformError> {
+    if p == &BigInt::from(2) || !is_probable_prime(p, MILLER_RABIN_ROUNDS) {
+        return Err(TransformError::InvalidParameters(format!("modulus {} is not an odd prime", p)));
+    }
+    cubic_roots_in(a, b, &Field::new(p), limits, rng)
+}
+
+/// `cubic_roots` over a field the caller has already checked to be of odd prime order.
+pub(crate) fn cubic_roots_in<R: RngCore + CryptoRng + ?Sized>(
+    a: &BigInt,
+    b: &BigInt,
+    field: &Field,
+    limits: &SearchLimits,
+    rng: &mut R,
+) -> Result<Vec<BigInt>, TransformError> {
+    let coefficients = [b.clone(), a.clone(), BigInt::zero(), BigInt::one()];
+    let roots = Polynomial::with_modulus(&coefficients, field.modulus()).roots(limits, rng)?;
+    debug!(rational_roots = roots.len(), "roots of the cubic");
+    Ok(roots)
+}
@@ -0,0 +1,71 @@
+use crate::ct;
+use core::fmt;
+use num_bigint::BigInt;
+
+/// A point of an elliptic curve in affine coordinates, or the point at infinity.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Point {
+    /// The neutral element of the group.
+    Infinity,
+    /// An affine point (x, y) on a Weierstrass curve, or (u, v) on a Montgomery curve.
+    Affine(BigInt, BigInt),
+}
+
+impl Point {
+    pub fn is_infinity(&self) -> bool {
+        matches!(self, Point::Infinity)
+    }
+
+    /// `other` if `choice` is set, `self` otherwise, for points with coordinates below `modulus`. The
+    /// coordinates and the infinity flag are picked word by word without branching on `choice`; see `ct`.
+    pub fn ct_select(&self, other: &Point, choice: bool, modulus: &BigInt) -> Point {
+        let count = ct::word_count(modulus);
+        let parts = |point: &Point| match point {
+            Point::Infinity => (BigInt::from(1), BigInt::from(0), BigInt::from(0)),
+            Point::Affine(x, y) => (BigInt::from(0), x.clone(), y.clone()),
+        };
+        let (first, second) = (parts(self), parts(other));
+        let x = ct::select(&first.1, &second.1, choice, count);
+        let y = ct::select(&first.2, &second.2, choice, count);
+        if ct::select(&first.0, &second.0, choice, 1) == BigInt::from(1) {
+            Point::Infinity
+        } else {
+            Point::Affine(x, y)
+        }
+    }
+
+    /// Exchanges `first` and `second` if `choice` is set, as `ct_select` does.
+    pub fn ct_swap(first: &mut Point, second: &mut Point, choice: bool, modulus: &BigInt) {
+        let swapped = (first.ct_select(second, choice, modulus), second.ct_select(first, choice, modulus));
+        (*first, *second) = swapped;
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Point::Infinity => write!(f, "O"),
+            Point::Affine(x, y) => write!(f, "({}, {})", x, y),
+        }
+    }
+}
+
+/// The group law of a curve model whose points are represented by `Point`.
+pub trait CurveGroup {
+    /// The prime modulus of the base field.
+    fn modulus(&self) -> &BigInt;
+
+    /// Returns true if `point` lies on the curve.
+    fn contains(&self, point: &Point) -> bool;
+
+    /// Returns the inverse of `point` in the group.
+    fn negate(&self, point: &Point) -> Point;
+
+    /// Returns `first + second`.
+    fn add(&self, first: &Point, second: &Point) -> Point;
+
+    /// Returns `point + point`.
+    fn double(&self, point: &Point) -> Point {
+        self.add(point, point)
+    }
+}
@@ -0,0 +1,246 @@
+//! Curve catalogs loaded at run time, so that curves outside the built-in registry can be named like
+//! registry curves without recompiling.
+//!
+//! A database is a JSON or TOML document with a `curves` array; each entry has a `name`, optional
+//! `aliases` and `oid`, a `model` (`weierstrass`, the default, `montgomery` or `twisted_edwards`), the
+//! prime `p`, the coefficients of the model (`a` and `b`, `A` and `B`, or `a` and `d`), the base point
+//! `x` and `y`, and optionally its `order` and the `cofactor`. Numbers are integers or decimal or
+//! 0x-prefixed hexadecimal strings:
+//!
+//! ```toml
+//! [[curves]]
+//! name = "internal-255"
+//! aliases = ["acme-1"]
+//! model = "montgomery"
+//! p = "0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"
+//! A = 486662
+//! B = 1
+//! x = 9
+//! y = "0x20ae19a1b8a086b4e01edd2c7748d14c923d4d7e6d7c61b229e9c5a27eced3d9"
+//! ```
+
+use crate::curve::Point;
+use crate::error::TransformError;
+#[cfg(feature = "tracing")]
+use crate::log::debug;
+use crate::registry::{self, lookup, CurveModel};
+use crate::spec::{parse_number, Number, SpecFormat};
+use crate::std_curves;
+use crate::weierstrass::WeierstrassCurve;
+use num_bigint::BigInt;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// File name looked for in the configuration directory, in this order.
+pub const DEFAULT_FILE_NAMES: [&str; 2] = ["curves.toml", "curves.json"];
+
+/// Directory under `$XDG_CONFIG_HOME` (or `~/.config`) holding the default database.
+pub const CONFIG_DIRECTORY: &str = "weierstrass_to_montgomery";
+
+/// A curve of a database, like a registry `NamedCurve` but owned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CatalogCurve {
+    pub name: String,
+    /// Other names accepted by `CurveDatabase::lookup`.
+    pub aliases: Vec<String>,
+    /// The object identifier in dotted form.
+    pub oid: Option<String>,
+    pub model: CurveModel,
+    pub p: BigInt,
+    /// The two coefficients of the curve's `model`.
+    pub coefficients: (BigInt, BigInt),
+    /// The base point, in the coordinates of the curve's `model`.
+    pub base_point: (BigInt, BigInt),
+    /// The order of the base point, if given.
+    pub order: Option<BigInt>,
+    pub cofactor: Option<u32>,
+}
+
+/// The curves of a database file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CurveDatabase {
+    pub curves: Vec<CatalogCurve>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ModelRecord {
+    Weierstrass,
+    Montgomery,
+    TwistedEdwards,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CurveRecord {
+    name: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    oid: Option<String>,
+    model: Option<ModelRecord>,
+    p: Number,
+    a: Option<Number>,
+    b: Option<Number>,
+    #[serde(rename = "A")]
+    a_montgomery: Option<Number>,
+    #[serde(rename = "B")]
+    b_montgomery: Option<Number>,
+    d: Option<Number>,
+    x: Number,
+    y: Number,
+    order: Option<Number>,
+    cofactor: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DatabaseRecord {
+    curves: Vec<CurveRecord>,
+}
+
+fn invalid(message: String) -> TransformError {
+    TransformError::InvalidParameters(message)
+}
+
+impl CatalogCurve {
+    fn from_record(mut record: CurveRecord) -> Result<CatalogCurve, TransformError> {
+        let name = record.name.clone();
+        let field = |key: &str, value: Option<Number>| match value {
+            Some(value) => parse_number(&format!("{}.{}", name, key), value),
+            None => Err(invalid(format!("curve {} has no {}", name, key))),
+        };
+        let (model, (first_key, first), (second_key, second)) = match record.model.take() {
+            None | Some(ModelRecord::Weierstrass) => {
+                (CurveModel::Weierstrass, ("a", record.a.take()), ("b", record.b.take()))
+            }
+            Some(ModelRecord::Montgomery) => {
+                let (a, b) = (record.a_montgomery.take(), record.b_montgomery.take());
+                (CurveModel::Montgomery, ("A", a), ("B", b))
+            }
+            Some(ModelRecord::TwistedEdwards) => {
+                (CurveModel::TwistedEdwards, ("a", record.a.take()), ("d", record.d.take()))
+            }
+        };
+        let leftover = [
+            ("a", &record.a),
+            ("b", &record.b),
+            ("A", &record.a_montgomery),
+            ("B", &record.b_montgomery),
+            ("d", &record.d),
+        ];
+        if let Some((key, _)) = leftover.iter().find(|(_, value)| value.is_some()) {
+            return Err(invalid(format!("curve {} has a {}, which its model does not use", name, key)));
+        }
+        let curve = CatalogCurve {
+            p: field("p", Some(record.p))?,
+            coefficients: (field(first_key, first)?, field(second_key, second)?),
+            base_point: (field("x", Some(record.x))?, field("y", Some(record.y))?),
+            order: record.order.map(|order| field("order", Some(order))).transpose()?,
+            cofactor: record.cofactor,
+            aliases: record.aliases,
+            oid: record.oid,
+            name: record.name,
+            model,
+        };
+        curve.check()?;
+        Ok(curve)
+    }
+
+    /// Checks that the curve is non-singular and the base point an affine point on it.
+    pub(crate) fn check(&self) -> Result<(), TransformError> {
+        match self.to_weierstrass() {
+            Ok((_, Point::Affine(..))) => Ok(()),
+            Ok((_, Point::Infinity)) => {
+                Err(invalid(format!("the base point of curve {} is the neutral element", self.name)))
+            }
+            Err(error) => Err(invalid(format!("curve {}: {}", self.name, error))),
+        }
+    }
+
+    /// Whether `name` is the curve's name or one of its aliases, ignoring case, or its OID.
+    pub fn matches(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+            || self.aliases.iter().any(|alias| alias.eq_ignore_ascii_case(name))
+            || self.oid.as_deref() == Some(name)
+    }
+
+    /// Returns the curve in short Weierstrass form together with the image of the base point, like
+    /// `NamedCurve::to_weierstrass`.
+    pub fn to_weierstrass(&self) -> Result<(WeierstrassCurve, Point), TransformError> {
+        let (first, second) = &self.coefficients;
+        let (x, y) = &self.base_point;
+        registry::to_weierstrass(self.model, &self.p, first, second, x, y)
+    }
+}
+
+impl CurveDatabase {
+    /// Parses a JSON or TOML database, checking every curve and base point.
+    ///
+    /// Names, aliases and OIDs must not be taken by the built-in registry or by another curve of the
+    /// file, so that a name always means the same curve.
+    pub fn parse(source: &str, format: SpecFormat) -> Result<CurveDatabase, TransformError> {
+        let record: DatabaseRecord = match format {
+            SpecFormat::Json => serde_json::from_str(source).map_err(|error| error.to_string()),
+            SpecFormat::Toml => toml::from_str(source).map_err(|error| error.to_string()),
+            SpecFormat::Yaml => serde_yaml::from_str(source).map_err(|error| error.to_string()),
+        }
+        .map_err(|error| invalid(format!("malformed {} curve database: {}", format, error)))?;
+        let mut database = CurveDatabase::default();
+        for record in record.curves {
+            database.insert(CatalogCurve::from_record(record)?)?;
+        }
+        Ok(database)
+    }
+
+    /// Adds a curve, unless its name, an alias or its OID is taken by the built-in registry or by a
+    /// curve already in the database.
+    pub fn insert(&mut self, curve: CatalogCurve) -> Result<(), TransformError> {
+        let names = std::iter::once(&curve.name).chain(&curve.aliases).chain(&curve.oid);
+        for name in names {
+            if let Some(named) = lookup(name) {
+                return Err(invalid(format!("{} is taken by the built-in curve {}", name, named.name)));
+            }
+            if let Some(other) = self.lookup(name) {
+                return Err(invalid(format!("{} is taken by the curve {} of the database", name, other.name)));
+            }
+        }
+        self.curves.push(curve);
+        Ok(())
+    }
+
+    /// Reads a database, choosing the format from the file extension. A JSON file in the std-curves
+    /// schema is imported with `std_curves::import`, leaving out the curves it cannot take.
+    pub fn from_path(path: &Path) -> Result<CurveDatabase, TransformError> {
+        let format = SpecFormat::from_path(path).ok_or_else(|| {
+            invalid(format!("cannot tell the format of {}; use .json, .toml, .yaml or .yml", path.display()))
+        })?;
+        let source = std::fs::read_to_string(path)
+            .map_err(|error| TransformError::Io(format!("cannot read {}: {}", path.display(), error)))?;
+        if format == SpecFormat::Json && std_curves::is_std_curves(&source) {
+            let import = std_curves::import(&source)?;
+            #[cfg(feature = "tracing")]
+            for (name, reason) in &import.skipped {
+                debug!(curve = name.as_str(), reason = reason.as_str(), "std-curves entry left out");
+            }
+            return Ok(import.database);
+        }
+        CurveDatabase::parse(&source, format)
+    }
+
+    /// The default database, `curves.toml` or else `curves.json` in
+    /// `$XDG_CONFIG_HOME/weierstrass_to_montgomery` (`~/.config/weierstrass_to_montgomery` when the
+    /// variable is unset), if either exists.
+    pub fn default_path() -> Option<PathBuf> {
+        let config = match std::env::var_os("XDG_CONFIG_HOME").filter(|value| !value.is_empty()) {
+            Some(config) => PathBuf::from(config),
+            None => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+        };
+        let directory = config.join(CONFIG_DIRECTORY);
+        DEFAULT_FILE_NAMES.iter().map(|name| directory.join(name)).find(|path| path.is_file())
+    }
+
+    /// Finds a curve by name or alias, ignoring case, or by its dotted OID.
+    pub fn lookup(&self, name: &str) -> Option<&CatalogCurve> {
+        self.curves.iter().find(|curve| curve.matches(name))
+    }
+}
@@ -0,0 +1,195 @@
+use crate::error::TransformError;
+use crate::registry::{lookup_oid, NamedCurve};
+
+/// DER tag of INTEGER.
+pub const TAG_INTEGER: u8 = 0x02;
+/// DER tag of BIT STRING.
+pub const TAG_BIT_STRING: u8 = 0x03;
+/// DER tag of OCTET STRING.
+pub const TAG_OCTET_STRING: u8 = 0x04;
+/// DER tag of NULL.
+pub const TAG_NULL: u8 = 0x05;
+/// DER tag of OBJECT IDENTIFIER.
+pub const TAG_OID: u8 = 0x06;
+/// DER tag of SEQUENCE.
+pub const TAG_SEQUENCE: u8 = 0x30;
+
+fn invalid(message: &str) -> TransformError {
+    TransformError::InvalidParameters(message.to_string())
+}
+
+/// Splits one DER element into its tag, its contents and the bytes after it; only the definite,
+/// minimally encoded lengths that DER allows are accepted, and only single-byte tags.
+pub fn read_element(bytes: &[u8]) -> Result<(u8, &[u8], &[u8]), TransformError> {
+    let (&tag, rest) = bytes.split_first().ok_or_else(|| invalid("truncated DER: missing tag"))?;
+    if tag & 0x1f == 0x1f {
+        return Err(invalid("multi-byte DER tags are not supported"));
+    }
+    let (&first, rest) = rest.split_first().ok_or_else(|| invalid("truncated DER: missing length"))?;
+    let (length, rest) = match first {
+        0x80 => return Err(invalid("indefinite lengths are not allowed in DER")),
+        short if short < 0x80 => (short as usize, rest),
+        long => {
+            let count = (long & 0x7f) as usize;
+            if count > std::mem::size_of::<usize>() || rest.len() < count {
+                return Err(invalid("truncated or oversized DER length"));
+            }
+            let length = rest[..count].iter().fold(0usize, |length, &byte| (length << 8) | byte as usize);
+            // The long form must not start with a zero byte, nor stand for a length the short form holds
+            if rest[0] == 0 || length < 0x80 {
+                return Err(invalid("non-minimal DER length"));
+            }
+            (length, &rest[count..])
+        }
+    };
+    if rest.len() < length {
+        return Err(invalid("truncated DER: the contents are shorter than the length"));
+    }
+    Ok((tag, &rest[..length], &rest[length..]))
+}
+
+/// Reads one DER element and checks its tag, returning its contents and the bytes after it.
+pub fn expect_element<'a>(
+    bytes: &'a [u8],
+    tag: u8,
+    what: &str,
+) -> Result<(&'a [u8], &'a [u8]), TransformError> {
+    let (found, contents, rest) = read_element(bytes)?;
+    if found != tag {
+        return Err(TransformError::InvalidParameters(format!(
+            "expected {} (tag 0x{:02x}), found tag 0x{:02x}",
+            what, tag, found
+        )));
+    }
+    Ok((contents, rest))
+}
+
+/// Decodes the contents of an OBJECT IDENTIFIER into dotted form, such as 1.2.840.10045.3.1.7.
+pub fn decode_oid(contents: &[u8]) -> Result<String, TransformError> {
+    if contents.is_empty() || contents[contents.len() - 1] & 0x80 != 0 {
+        return Err(invalid("truncated OBJECT IDENTIFIER"));
+    }
+    let mut arcs = Vec::new();
+    let mut value = 0u64;
+    let mut start = true;
+    for &byte in contents {
+        // A subidentifier starting with 0x80 has a leading zero digit, which DER forbids
+        if start && byte == 0x80 {
+            return Err(invalid("OBJECT IDENTIFIER arc with a leading 0x80 byte"));
+        }
+        start = byte & 0x80 == 0;
+        value = value
+            .checked_mul(128)
+            .ok_or_else(|| invalid("OBJECT IDENTIFIER arc does not fit in 64 bits"))?
+            | u64::from(byte & 0x7f);
+        if byte & 0x80 == 0 {
+            arcs.push(value);
+            value = 0;
+        }
+    }
+    // The first subidentifier packs the first two arcs as 40·first + second
+    let first = arcs[0].min(80) / 40;
+    let mut dotted = format!("{}.{}", first, arcs[0] - 40 * first);
+    for arc in &arcs[1..] {
+        dotted.push_str(&format!(".{}", arc));
+    }
+    Ok(dotted)
+}
+
+/// Decodes base64, ignoring whitespace and optional padding; `url` selects the URL-safe alphabet of
+/// RFC 4648, section 5.
+pub fn decode_base64(text: &str, url: bool) -> Result<Vec<u8>, TransformError> {
+    let (plus, slash) = if url { (b'-', b'_') } else { (b'+', b'/') };
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in text.bytes().filter(|byte| !byte.is_ascii_whitespace()).take_while(|&byte| byte != b'=') {
+        let sextet = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            _ if byte == plus => 62,
+            _ if byte == slash => 63,
+            _ => {
+                let message = format!("invalid base64 character {:?}", byte as char);
+                return Err(TransformError::InvalidParameters(message));
+            }
+        };
+        buffer = (buffer << 6) | u32::from(sextet);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    if bits >= 6 {
+        return Err(invalid("truncated base64"));
+    }
+    Ok(bytes)
+}
+
+/// Encodes base64 without padding in the URL-safe alphabet, as JOSE requires, or with padding in the
+/// standard alphabet, as PEM requires.
+pub fn encode_base64(bytes: &[u8], url: bool) -> String {
+    let alphabet: &[u8; 64] = if url {
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"
+    } else {
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"
+    };
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| {
+            group | u32::from(byte) << (16 - 8 * i)
+        });
+        for i in 0..=chunk.len() {
+            text.push(alphabet[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+        if !url {
+            text.push_str(&"=="[..3 - chunk.len()]);
+        }
+    }
+    text
+}
+
+/// Decodes the first PEM block of `text`, returning its label (such as `EC PARAMETERS` or
+/// `PUBLIC KEY`) and the DER bytes.
+pub fn decode_pem(text: &str) -> Result<(String, Vec<u8>), TransformError> {
+    let start = text.find("-----BEGIN ").ok_or_else(|| invalid("no PEM block found"))?;
+    let header = &text[start + "-----BEGIN ".len()..];
+    let label_end = header.find("-----").ok_or_else(|| invalid("unterminated PEM header"))?;
+    let label = &header[..label_end];
+    let body = &header[label_end + "-----".len()..];
+    let footer = format!("-----END {}-----", label);
+    let body_end = body.find(&footer).ok_or_else(|| invalid("missing PEM footer"))?;
+    Ok((label.to_string(), decode_base64(&body[..body_end], false)?))
+}
+
+/// Returns the DER bytes of `bytes`, decoding them first when they are a PEM block.
+pub fn der_or_pem(bytes: &[u8]) -> Result<Vec<u8>, TransformError> {
+    match std::str::from_utf8(bytes) {
+        Ok(text) if text.trim_start().starts_with("-----BEGIN ") => Ok(decode_pem(text)?.1),
+        _ => Ok(bytes.to_vec()),
+    }
+}
+
+/// Resolves the namedCurve OID of DER-encoded ECParameters (RFC 5480) to a registry entry.
+pub fn named_curve_from_der(der: &[u8]) -> Result<&'static NamedCurve, TransformError> {
+    let (tag, contents, _) = read_element(der)?;
+    match tag {
+        TAG_OID => {
+            let oid = decode_oid(contents)?;
+            lookup_oid(&oid)
+                .ok_or_else(|| TransformError::InvalidParameters(format!("unknown curve OID {}", oid)))
+        }
+        TAG_SEQUENCE => Err(invalid(
+            "explicit (specifiedCurve) parameters are not supported; re-encode them as a named curve",
+        )),
+        TAG_NULL => Err(invalid("implicitCurve parameters name no curve")),
+        _ => Err(invalid("ECParameters must be an OBJECT IDENTIFIER")),
+    }
+}
+
+/// Reads ECParameters in DER or PEM form (`openssl ecparam -name <curve>`) and resolves the curve.
+pub fn named_curve_from_parameters(bytes: &[u8]) -> Result<&'static NamedCurve, TransformError> {
+    named_curve_from_der(&der_or_pem(bytes)?)
+}
@@ -0,0 +1,167 @@
+use crate::curve::Point;
+use crate::edwards::TwistedEdwardsCurve;
+use crate::error::TransformError;
+use crate::limits::SearchLimits;
+use crate::montgomery::MontgomeryCurve;
+use crate::numtheory::{mod_inverse, mod_sqrt, mod_sqrt_odd};
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use std::str::FromStr;
+
+/// Length of Ed25519 and X25519 public keys.
+pub const KEY_LEN: usize = 32;
+
+/// The field prime 2^255 − 19 of Curve25519 and Ed25519.
+pub fn p25519() -> BigInt {
+    (BigInt::one() << 255u32) - 19u32
+}
+
+/// Ed25519's curve −x^2 + y^2 = 1 + d·x^2·y^2 with d = −121665 / 121666 (RFC 8032).
+pub fn ed25519() -> TwistedEdwardsCurve {
+    let p = p25519();
+    let d = BigInt::from(-121665) * mod_inverse(&BigInt::from(121666), &p).expect("121666 is invertible");
+    TwistedEdwardsCurve::new(&BigInt::from(-1), &d, &p).expect("Ed25519 is non-singular")
+}
+
+/// Curve25519, v^2 = u^3 + 486662·u^2 + u (RFC 7748).
+pub fn curve25519() -> MontgomeryCurve {
+    let curve = MontgomeryCurve::new(&BigInt::from(486662), &BigInt::one(), &p25519());
+    curve.expect("Curve25519 is non-singular")
+}
+
+/// The Ed25519 base point, with y = 4/5 and x even.
+pub fn ed25519_base_point() -> Point {
+    let x = "15112221349535400772501151409588531511454012693041857206046113283949847762202";
+    let y = "46316835694926478169428394003475163141307993866256225615783033603165251855960";
+    Point::Affine(BigInt::from_str(x).expect("decimal"), BigInt::from_str(y).expect("decimal"))
+}
+
+/// The Curve25519 base point, with u = 9.
+pub fn curve25519_base_point() -> Point {
+    let v = "14781619447589544791020593568409986887264606134616475288964881837755586237401";
+    Point::Affine(BigInt::from(9), BigInt::from_str(v).expect("decimal"))
+}
+
+/// The constant sqrt(−486664) of the maps in RFC 7748, taking the odd root so that the base points
+/// correspond.
+fn scale() -> BigInt {
+    let p = p25519();
+    mod_sqrt_odd(&BigInt::from(-486664), &p, &SearchLimits::default())
+        .expect("p25519 = 5 (mod 8) needs no non-residue search")
+        .expect("-486664 is a square modulo p25519")
+}
+
+/// Maps an Ed25519 point to Curve25519, (x, y) -> ((1 + y) / (1 − y), sqrt(−486664)·u / x), with
+/// (0, −1) going to (0, 0).
+pub fn ed25519_to_curve25519(point: &Point) -> Point {
+    let p = p25519();
+    match point {
+        Point::Infinity => Point::Infinity,
+        Point::Affine(x, _) if x.is_zero() => Point::Affine(BigInt::zero(), BigInt::zero()),
+        Point::Affine(x, y) => {
+            let denominator = mod_inverse(&(BigInt::one() - y).mod_floor(&p), &p).expect("y is not 1");
+            let u = ((BigInt::one() + y) * denominator).mod_floor(&p);
+            let x_inv = mod_inverse(x, &p).expect("x is non-zero");
+            let v = (scale() * &u * x_inv).mod_floor(&p);
+            Point::Affine(u, v)
+        }
+    }
+}
+
+/// Maps a Curve25519 point to Ed25519, (u, v) -> (sqrt(−486664)·u / v, (u − 1) / (u + 1)), with (0, 0)
+/// going to (0, −1); `None` only for inputs off the curve with v = 0 or u = −1, as Ed25519 is complete.
+pub fn curve25519_to_ed25519(point: &Point) -> Option<Point> {
+    let p = p25519();
+    match point {
+        Point::Infinity => Some(Point::Infinity),
+        Point::Affine(u, _) if u.is_zero() => Some(Point::Affine(BigInt::zero(), &p - 1u32)),
+        Point::Affine(u, v) => {
+            let x = (scale() * u * mod_inverse(v, &p)?).mod_floor(&p);
+            let y = ((u - 1u32) * mod_inverse(&(u + 1u32).mod_floor(&p), &p)?).mod_floor(&p);
+            Some(Point::Affine(x, y))
+        }
+    }
+}
+
+fn invalid(message: &str) -> TransformError {
+    TransformError::InvalidParameters(message.to_string())
+}
+
+fn read_key(bytes: &[u8]) -> Result<[u8; KEY_LEN], TransformError> {
+    bytes
+        .try_into()
+        .map_err(|_| invalid("Ed25519 and X25519 public keys are 32 bytes long"))
+}
+
+fn to_le_bytes(value: &BigInt) -> [u8; KEY_LEN] {
+    let (_, digits) = value.to_bytes_le();
+    let mut bytes = [0u8; KEY_LEN];
+    bytes[..digits.len()].copy_from_slice(&digits);
+    bytes
+}
+
+/// Decodes an Ed25519 public key (RFC 8032, section 5.1.3): y in little-endian with the parity of x in
+/// the top bit, recovering x from x^2 = (y^2 − 1) / (d·y^2 + 1).
+pub fn decode_ed25519(bytes: &[u8]) -> Result<Point, TransformError> {
+    let mut bytes = read_key(bytes)?;
+    let p = p25519();
+    let curve = ed25519();
+    let sign = bytes[KEY_LEN - 1] >> 7 == 1;
+    bytes[KEY_LEN - 1] &= 0x7f;
+    let y = BigInt::from_bytes_le(Sign::Plus, &bytes);
+    if y >= p {
+        return Err(invalid("the encoded y is not below 2^255 - 19"));
+    }
+    let y_squared = (&y * &y).mod_floor(&p);
+    let denominator = mod_inverse(&(&curve.d * &y_squared + 1u32).mod_floor(&p), &p)
+        .expect("d is not a square, so d·y^2 + 1 is non-zero");
+    let x_squared = ((y_squared - 1u32) * denominator).mod_floor(&p);
+    let x = mod_sqrt(&x_squared, &p, &SearchLimits::default())?.ok_or(TransformError::PointNotOnCurve)?;
+    if x.is_zero() && sign {
+        return Err(invalid("x = 0 cannot have its sign bit set"));
+    }
+    let x = if x.is_odd() == sign { x } else { &p - x };
+    curve.point(&x, &y)
+}
+
+/// Encodes an Ed25519 point as a public key.
+pub fn encode_ed25519(point: &Point) -> [u8; KEY_LEN] {
+    let (x, y) = match point {
+        Point::Infinity => (BigInt::zero(), BigInt::one()),
+        Point::Affine(x, y) => (x.clone(), y.clone()),
+    };
+    let mut bytes = to_le_bytes(&y);
+    bytes[KEY_LEN - 1] |= (x.is_odd() as u8) << 7;
+    bytes
+}
+
+/// Converts an Ed25519 public key to the X25519 public key u = (1 + y) / (1 − y) of the same secret
+/// scalar, rejecting keys that are not on the curve and the neutral element (which has no u).
+pub fn ed25519_public_key_to_x25519(bytes: &[u8]) -> Result<[u8; KEY_LEN], TransformError> {
+    let point = decode_ed25519(bytes)?;
+    if point.is_infinity() {
+        return Err(invalid("the neutral element has no X25519 counterpart"));
+    }
+    match ed25519_to_curve25519(&point) {
+        Point::Affine(u, _) => Ok(to_le_bytes(&u)),
+        Point::Infinity => unreachable!("only the neutral element maps to infinity"),
+    }
+}
+
+/// Converts an X25519 public key back to Ed25519, y = (u − 1) / (u + 1), with the sign of x that the
+/// u-coordinate does not carry; the top bit of the key is masked as in RFC 7748.
+pub fn x25519_public_key_to_ed25519(bytes: &[u8], sign: bool) -> Result<[u8; KEY_LEN], TransformError> {
+    let mut bytes = read_key(bytes)?;
+    let p = p25519();
+    bytes[KEY_LEN - 1] &= 0x7f;
+    let u = BigInt::from_bytes_le(Sign::Plus, &bytes).mod_floor(&p);
+    let denominator = mod_inverse(&(&u + 1u32).mod_floor(&p), &p)
+        .ok_or_else(|| invalid("u = -1 has no Ed25519 counterpart"))?;
+    let y = ((u - 1u32) * denominator).mod_floor(&p);
+    let mut encoded = to_le_bytes(&y);
+    encoded[KEY_LEN - 1] |= (sign as u8) << 7;
+    // The u-coordinate may belong to the twist, and then y belongs to no Ed25519 point
+    decode_ed25519(&encoded)?;
+    Ok(encoded)
+}
@@ -0,0 +1,153 @@
+use crate::curve::Point;
+use crate::edwards::TwistedEdwardsCurve;
+use crate::error::TransformError;
+use crate::limits::SearchLimits;
+use crate::montgomery::MontgomeryCurve;
+use crate::numtheory::{mod_inverse, mod_sqrt};
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use std::str::FromStr;
+
+/// Length of Ed448 public keys: 56 bytes of y and a final byte holding the sign of x.
+pub const ED448_KEY_LEN: usize = 57;
+
+/// Length of X448 public keys.
+pub const X448_KEY_LEN: usize = 56;
+
+/// The field prime 2^448 − 2^224 − 1 of Curve448 and Ed448.
+pub fn p448() -> BigInt {
+    (BigInt::one() << 448u32) - (BigInt::one() << 224u32) - 1u32
+}
+
+/// Ed448's untwisted Edwards curve x^2 + y^2 = 1 + d·x^2·y^2 with d = −39081 (RFC 8032).
+///
+/// It is 4-isogenous, not birationally equivalent, to Curve448: `TwistedEdwardsCurve::to_montgomery`
+/// gives a different Montgomery curve, and `ed448_to_curve448` is the isogeny.
+pub fn ed448() -> TwistedEdwardsCurve {
+    TwistedEdwardsCurve::edwards(&BigInt::from(-39081), &p448()).expect("Ed448 is non-singular")
+}
+
+/// Curve448, v^2 = u^3 + 156326·u^2 + u (RFC 7748).
+pub fn curve448() -> MontgomeryCurve {
+    MontgomeryCurve::new(&BigInt::from(156326), &BigInt::one(), &p448()).expect("Curve448 is non-singular")
+}
+
+fn decimal(digits: &str) -> BigInt {
+    BigInt::from_str(digits).expect("curve constants are decimal integers")
+}
+
+/// The Ed448 base point.
+pub fn ed448_base_point() -> Point {
+    Point::Affine(
+        decimal(
+            "224580040295924300187604334099896036246789641632564134246125461686950415467406032909029192869357\
+             953282578032075146446173674602635247710",
+        ),
+        decimal(
+            "298819210078481492676017930443930673437544040154080242095928241372331506189835876003536878655418\
+             784733982303233503462500531545062832660",
+        ),
+    )
+}
+
+/// The Curve448 base point, with u = 5.
+pub fn curve448_base_point() -> Point {
+    Point::Affine(
+        BigInt::from(5),
+        decimal(
+            "355293926785568175264127502063783334808976399387714271831880898435169088786967410002932673765864\
+             550910142774147268105838985595290606362",
+        ),
+    )
+}
+
+/// The 4-isogeny from Ed448 to Curve448 of RFC 7748, (x, y) -> (y^2 / x^2, (2 − x^2 − y^2)·y / x^3); the
+/// neutral element and (0, −1) make up its rational kernel and go to infinity.
+///
+/// It sends the Ed448 base point to the Curve448 base point, so [s]B on Ed448 goes to [s]·5 on Curve448.
+pub fn ed448_to_curve448(point: &Point) -> Point {
+    let p = p448();
+    let (x, y) = match point {
+        Point::Affine(x, y) if !x.is_zero() => (x, y),
+        _ => return Point::Infinity,
+    };
+    let x_inv = mod_inverse(x, &p).expect("x is non-zero");
+    let x_inv_squared = (&x_inv * &x_inv).mod_floor(&p);
+    let u = (y * y * &x_inv_squared).mod_floor(&p);
+    let v = ((BigInt::from(2) - x * x - y * y) * y * x_inv_squared * x_inv).mod_floor(&p);
+    Point::Affine(u, v)
+}
+
+/// The dual 4-isogeny from Curve448 to Ed448 of RFC 7748, (u, v) -> (4v(u^2 − 1) / (u^4 − 2u^2 + 4v^2 + 1),
+/// −(u^5 − 2u^3 − 4uv^2 + u) / (u^5 − 2u^2v^2 − 2u^3 − 2v^2 + u)), with the points of its kernel going to
+/// the neutral element.
+///
+/// It is not an inverse of `ed448_to_curve448`: going there and back multiplies a point by 4.
+pub fn curve448_to_ed448(point: &Point) -> Point {
+    let p = p448();
+    let (u, v) = match point {
+        Point::Infinity => return Point::Infinity,
+        Point::Affine(u, v) => (u, v),
+    };
+    let (u2, v2) = ((u * u).mod_floor(&p), (v * v).mod_floor(&p));
+    let u3 = (&u2 * u).mod_floor(&p);
+    let x_denominator = (&u2 * &u2 - BigInt::from(2) * &u2 + BigInt::from(4) * &v2 + 1u32).mod_floor(&p);
+    let two = BigInt::from(2);
+    let y_denominator = (&u3 * &u2 - &two * &u2 * &v2 - &two * &u3 - &two * &v2 + u).mod_floor(&p);
+    let (Some(x_inv), Some(y_inv)) = (mod_inverse(&x_denominator, &p), mod_inverse(&y_denominator, &p)) else {
+        return Point::Infinity;
+    };
+    let x = (BigInt::from(4) * v * (&u2 - 1u32) * x_inv).mod_floor(&p);
+    let y = (-(&u3 * &u2 - BigInt::from(2) * &u3 - BigInt::from(4) * u * &v2 + u) * y_inv).mod_floor(&p);
+    if x.is_zero() && y.is_one() {
+        Point::Infinity
+    } else {
+        Point::Affine(x, y)
+    }
+}
+
+fn invalid(message: &str) -> TransformError {
+    TransformError::InvalidParameters(message.to_string())
+}
+
+/// Decodes an Ed448 public key (RFC 8032, section 5.2.3): y in little-endian with the parity of x in the
+/// top bit of the last byte, recovering x from x^2 = (y^2 − 1) / (d·y^2 − 1).
+pub fn decode_ed448(bytes: &[u8]) -> Result<Point, TransformError> {
+    let bytes: [u8; ED448_KEY_LEN] =
+        bytes.try_into().map_err(|_| invalid("Ed448 public keys are 57 bytes long"))?;
+    let p = p448();
+    let curve = ed448();
+    if bytes[ED448_KEY_LEN - 1] & 0x7f != 0 {
+        return Err(invalid("the last byte of an Ed448 key holds only the sign of x"));
+    }
+    let sign = bytes[ED448_KEY_LEN - 1] >> 7 == 1;
+    let y = BigInt::from_bytes_le(Sign::Plus, &bytes[..ED448_KEY_LEN - 1]);
+    if y >= p {
+        return Err(invalid("the encoded y is not below 2^448 - 2^224 - 1"));
+    }
+    let y_squared = (&y * &y).mod_floor(&p);
+    let denominator = mod_inverse(&(&curve.d * &y_squared - 1u32).mod_floor(&p), &p)
+        .expect("d is not a square, so d·y^2 - 1 is non-zero");
+    let x_squared = ((y_squared - 1u32) * denominator).mod_floor(&p);
+    let x = mod_sqrt(&x_squared, &p, &SearchLimits::default())?.ok_or(TransformError::PointNotOnCurve)?;
+    if x.is_zero() && sign {
+        return Err(invalid("x = 0 cannot have its sign bit set"));
+    }
+    let x = if x.is_odd() == sign { x } else { &p - x };
+    curve.point(&x, &y)
+}
+
+/// Converts an Ed448 public key to the X448 public key u = y^2 / x^2 of the same secret scalar.
+///
+/// There is no conversion back: the dual isogeny recovers 4 times the Ed448 point, not the point.
+pub fn ed448_public_key_to_x448(bytes: &[u8]) -> Result<[u8; X448_KEY_LEN], TransformError> {
+    let u = match ed448_to_curve448(&decode_ed448(bytes)?) {
+        Point::Affine(u, _) => u,
+        Point::Infinity => return Err(invalid("the key lies in the kernel of the isogeny")),
+    };
+    let (_, digits) = u.to_bytes_le();
+    let mut key = [0u8; X448_KEY_LEN];
+    key[..digits.len()].copy_from_slice(&digits);
+    Ok(key)
+}
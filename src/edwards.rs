@@ -0,0 +1,272 @@
+use crate::curve::{CurveGroup, Point};
+use crate::error::TransformError;
+use crate::field::{batch_inverse, FieldElement};
+use crate::limits::SearchLimits;
+use crate::montgomery::MontgomeryCurve;
+use crate::numtheory::{mod_inverse, mod_sqrt};
+use crate::ops;
+use crate::scalar_mul;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+/// The twisted Edwards curve a·x^2 + y^2 = 1 + d·x^2·y^2 over F_p; a = 1 is an Edwards curve.
+///
+/// The neutral element (0, 1) is represented by `Point::Infinity`, so that the generic scalar
+/// multiplication and the other models agree on it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TwistedEdwardsCurve {
+    pub a: BigInt,
+    pub d: BigInt,
+    pub p: BigInt,
+}
+
+/// An isomorphism (x, y) -> (x / u, y) onto the curve with (u^2·a, u^2·d), from `rescale`,
+/// `to_twisted` or `to_edwards`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EdwardsScaling {
+    /// The image curve.
+    pub curve: TwistedEdwardsCurve,
+    pub u: BigInt,
+}
+
+impl EdwardsScaling {
+    /// Maps a point of the original curve to the image curve.
+    pub fn map_point(&self, point: &Point) -> Point {
+        let p = &self.curve.p;
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(x, y) => {
+                let u_inv = mod_inverse(&self.u, p).expect("u is non-zero");
+                Point::Affine((x * u_inv).mod_floor(p), y.clone())
+            }
+        }
+    }
+}
+
+impl TwistedEdwardsCurve {
+    /// Creates the curve, reducing the coefficients and rejecting singular curves (a = 0, d = 0 or
+    /// a = d).
+    pub fn new(a: &BigInt, d: &BigInt, p: &BigInt) -> Result<TwistedEdwardsCurve, TransformError> {
+        if p <= &BigInt::from(3) {
+            return Err(TransformError::UnsupportedCharacteristic(p.clone()));
+        }
+        let curve = TwistedEdwardsCurve {
+            a: a.mod_floor(p),
+            d: d.mod_floor(p),
+            p: p.clone(),
+        };
+        if curve.a.is_zero() || curve.d.is_zero() || curve.a == curve.d {
+            return Err(TransformError::InvalidParameters(
+                "the twisted Edwards curve is singular (a = 0, d = 0 or a = d)".to_string(),
+            ));
+        }
+        Ok(curve)
+    }
+
+    /// Creates the Edwards curve x^2 + y^2 = 1 + d·x^2·y^2.
+    pub fn edwards(d: &BigInt, p: &BigInt) -> Result<TwistedEdwardsCurve, TransformError> {
+        TwistedEdwardsCurve::new(&BigInt::one(), d, p)
+    }
+
+    /// Whether a = 1.
+    pub fn is_edwards(&self) -> bool {
+        self.a.is_one()
+    }
+
+    /// Rescales (a, d) to (u^2·a, u^2·d), an isomorphism that maps (x, y) to (x / u, y).
+    pub fn rescale(&self, u: &BigInt) -> Result<EdwardsScaling, TransformError> {
+        let p = &self.p;
+        let u = u.mod_floor(p);
+        if u.is_zero() {
+            let message = "the scale u must be non-zero modulo p".to_string();
+            return Err(TransformError::InvalidParameters(message));
+        }
+        let u_squared = (&u * &u).mod_floor(p);
+        let curve = TwistedEdwardsCurve {
+            a: (&u_squared * &self.a).mod_floor(p),
+            d: (u_squared * &self.d).mod_floor(p),
+            p: p.clone(),
+        };
+        Ok(EdwardsScaling { curve, u })
+    }
+
+    /// Finds the isomorphic curve with the given a, such as a = −1 for the fast formulas of
+    /// Hisil et al.; `None` when a_target / a is not a square, since then only a quadratic twist has it.
+    pub fn to_twisted(&self, a_target: &BigInt) -> Result<Option<EdwardsScaling>, TransformError> {
+        let p = &self.p;
+        let a_inv = mod_inverse(&self.a, p).expect("a is non-zero");
+        let ratio = (a_target * a_inv).mod_floor(p);
+        if ratio.is_zero() {
+            let message = "the target a must be non-zero modulo p".to_string();
+            return Err(TransformError::InvalidParameters(message));
+        }
+        match mod_sqrt(&ratio, p, &SearchLimits::default())? {
+            Some(u) => self.rescale(&u).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Finds the isomorphic Edwards curve (a = 1), which exists when a is a square.
+    pub fn to_edwards(&self) -> Result<Option<EdwardsScaling>, TransformError> {
+        self.to_twisted(&BigInt::one())
+    }
+
+    /// Returns the birationally equivalent Montgomery curve, A = 2(a + d) / (a − d) and B = 4 / (a − d).
+    pub fn to_montgomery(&self) -> MontgomeryCurve {
+        let p = &self.p;
+        let difference_inv = mod_inverse(&(&self.a - &self.d).mod_floor(p), p).expect("a - d is non-zero");
+        MontgomeryCurve {
+            a: (BigInt::from(2) * (&self.a + &self.d) * &difference_inv).mod_floor(p),
+            b: (BigInt::from(4) * difference_inv).mod_floor(p),
+            p: p.clone(),
+        }
+    }
+
+    /// Maps a point to the curve of `to_montgomery`, (x, y) -> (u, u / x) with u = (1 + y) / (1 − y), and
+    /// (0, −1) to (0, 0); no point is exceptional.
+    pub fn point_to_montgomery(&self, point: &Point) -> Point {
+        let p = &self.p;
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(x, _) if x.is_zero() => Point::Affine(BigInt::zero(), BigInt::zero()),
+            Point::Affine(x, y) => {
+                // y = 1 only for x = 0, as a ≠ d
+                ops::record(2, 0);
+                let denominator = mod_inverse(&(BigInt::one() - y).mod_floor(p), p).expect("y is not 1");
+                let u = ((BigInt::one() + y) * denominator).mod_floor(p);
+                let x_inv = mod_inverse(x, p).expect("x is non-zero");
+                Point::Affine(u.clone(), (u * x_inv).mod_floor(p))
+            }
+        }
+    }
+
+    /// Evaluates a·x^2 + y^2 − 1 − d·x^2·y^2, which vanishes exactly on the curve.
+    fn equation(&self, x: &BigInt, y: &BigInt) -> BigInt {
+        let x_squared = x * x;
+        let y_squared = y * y;
+        (&self.a * &x_squared + &y_squared - 1u32 - &self.d * x_squared * y_squared).mod_floor(&self.p)
+    }
+
+    /// Creates the affine point (x, y), checking that it lies on the curve; (0, 1) gives
+    /// `Point::Infinity`.
+    pub fn point(&self, x: &BigInt, y: &BigInt) -> Result<Point, TransformError> {
+        let (x, y) = (x.mod_floor(&self.p), y.mod_floor(&self.p));
+        if !self.equation(&x, &y).is_zero() {
+            return Err(TransformError::PointNotOnCurve);
+        }
+        Ok(if x.is_zero() && y.is_one() { Point::Infinity } else { Point::Affine(x, y) })
+    }
+
+    /// Multiplies a point of the curve by the `cofactor` h, forcing it into the subgroup of order #E / h;
+    /// see `scalar_mul::clear_cofactor`.
+    pub fn clear_cofactor(&self, point: &Point, cofactor: &BigInt) -> Result<Point, TransformError> {
+        scalar_mul::clear_cofactor(self, point, cofactor)
+    }
+}
+
+impl MontgomeryCurve {
+    /// Returns the birationally equivalent twisted Edwards curve of `twisted_edwards_coefficients`.
+    pub fn to_twisted_edwards(&self) -> TwistedEdwardsCurve {
+        let (a, d) = self.twisted_edwards_coefficients();
+        TwistedEdwardsCurve { a, d, p: self.p.clone() }
+    }
+
+    /// Maps a point to the curve of `to_twisted_edwards`, (u, v) -> (u / v, (u − 1) / (u + 1)), with
+    /// the point (0, 0) of order 2 going to the point (0, −1) of order 2.
+    ///
+    /// The formula is undefined at the other points with v = 0, of order 2, and at those with u = −1, of
+    /// order 4; they go to points at infinity of the Edwards curve, which exist only when it is not
+    /// complete (d a square) and have no affine coordinates. Like the rational maps of RFC 9380, these
+    /// exceptional points are sent to the neutral element (0, 1), `Point::Infinity`: the map then stays
+    /// total and agrees with the group law after multiplying by the cofactor, which kills them anyway.
+    pub fn point_to_edwards(&self, point: &Point) -> Point {
+        let p = &self.p;
+        let Point::Affine(u, v) = point else {
+            return Point::Infinity;
+        };
+        if u.is_zero() {
+            return Point::Affine(BigInt::zero(), p - 1u32);
+        }
+        let (Some(v_inv), Some(denominator)) = (mod_inverse(v, p), mod_inverse(&(u + 1u32), p)) else {
+            return Point::Infinity;
+        };
+        ops::record(2, 0);
+        Point::Affine((u * v_inv).mod_floor(p), ((u - 1u32) * denominator).mod_floor(p))
+    }
+
+    /// `point_to_edwards` for many points with a single modular inversion: the 2n denominators v and
+    /// u + 1 are inverted together by `batch_inverse`, and exceptional points, whose denominator is zero,
+    /// still go to `Point::Infinity`.
+    pub fn points_to_edwards(&self, points: &[Point]) -> Vec<Point> {
+        let p = &self.p;
+        let mut denominators = Vec::with_capacity(2 * points.len());
+        for point in points {
+            let (v, u_plus_one) = match point {
+                Point::Affine(u, v) => (v.clone(), u + 1u32),
+                Point::Infinity => (BigInt::zero(), BigInt::zero()),
+            };
+            denominators.push(FieldElement::new(&v, p));
+            denominators.push(FieldElement::new(&u_plus_one, p));
+        }
+        let inverses = batch_inverse(&denominators);
+        points
+            .iter()
+            .zip(inverses.chunks(2))
+            .map(|(point, inverses)| match point {
+                Point::Infinity => Point::Infinity,
+                Point::Affine(u, _) if u.is_zero() => Point::Affine(BigInt::zero(), p - 1u32),
+                Point::Affine(u, _) if inverses[0].is_zero() || inverses[1].is_zero() => Point::Infinity,
+                Point::Affine(u, _) => {
+                    ops::record(2, 0);
+                    let x = (u * &inverses[0].value).mod_floor(p);
+                    Point::Affine(x, ((u - 1u32) * &inverses[1].value).mod_floor(p))
+                }
+            })
+            .collect()
+    }
+}
+
+impl CurveGroup for TwistedEdwardsCurve {
+    fn modulus(&self) -> &BigInt {
+        &self.p
+    }
+
+    fn contains(&self, point: &Point) -> bool {
+        match point {
+            Point::Infinity => true,
+            Point::Affine(x, y) => self.equation(x, y).is_zero(),
+        }
+    }
+
+    fn negate(&self, point: &Point) -> Point {
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(x, y) => Point::Affine((-x).mod_floor(&self.p), y.clone()),
+        }
+    }
+
+    /// The unified addition law, which has no exceptions when a is a square and d is not; on other
+    /// curves it panics for the pairs whose sum is a point at infinity.
+    fn add(&self, first: &Point, second: &Point) -> Point {
+        let p = &self.p;
+        let (x1, y1, x2, y2) = match (first, second) {
+            (Point::Infinity, _) => return second.clone(),
+            (_, Point::Infinity) => return first.clone(),
+            (Point::Affine(x1, y1), Point::Affine(x2, y2)) => (x1, y1, x2, y2),
+        };
+        ops::record(11, 0);
+        let t = (&self.d * x1 * x2 * y1 * y2).mod_floor(p);
+        let x_denominator =
+            mod_inverse(&(BigInt::one() + &t).mod_floor(p), p).expect("1 + d·x1·x2·y1·y2 is non-zero");
+        let y_denominator =
+            mod_inverse(&(BigInt::one() - &t).mod_floor(p), p).expect("1 - d·x1·x2·y1·y2 is non-zero");
+        let x3 = ((x1 * y2 + y1 * x2) * x_denominator).mod_floor(p);
+        let y3 = ((y1 * y2 - &self.a * x1 * x2) * y_denominator).mod_floor(p);
+        if x3.is_zero() && y3.is_one() {
+            Point::Infinity
+        } else {
+            Point::Affine(x3, y3)
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::map::MontgomeryMap;
+use num_bigint::BigInt;
+
+/// Writes the forward and inverse maps of `map` with every constant substituted, so that they can be
+/// re-implemented elsewhere without re-deriving z0 or s.
+pub fn formulas(map: &MontgomeryMap) -> String {
+    let MontgomeryMap { p, a, b, z0, s, s_inv, a_montgomery, b_montgomery } = map;
+    let mut text = String::new();
+    text.push_str("Weierstrass curve: y^2 = x^3 + a*x + b (mod p)\n");
+    text.push_str("Montgomery curve:  B*v^2 = u^3 + A*u^2 + u (mod p)\n\n");
+    for (name, value) in [("p", p), ("a", a), ("b", b), ("A", a_montgomery), ("B", b_montgomery)] {
+        text.push_str(&format!("{} = {}\n", name, value));
+    }
+    text.push_str(&format!("z0 = {}    (root of z^3 + a*z + b)\n", z0));
+    text.push_str(&format!("s = {}    (square root of 3*z0^2 + a)\n", s));
+    text.push_str(&format!("s_inv = {}    (s^-1 mod p; also B, while A = 3*z0*s_inv)\n\n", s_inv));
+    text.push_str("Forward, (x, y) on the Weierstrass curve to (u, v) on the Montgomery curve:\n");
+    text.push_str(&format!("  u = s_inv * (x - z0) mod p = {} * (x - {}) mod {}\n", s_inv, z0, p));
+    text.push_str(&format!("  v = s_inv * y mod p = {} * y mod {}\n", s_inv, p));
+    text.push_str("Inverse, (u, v) on the Montgomery curve to (x, y) on the Weierstrass curve:\n");
+    text.push_str(&format!("  x = s * u + z0 mod p = {} * u + {} mod {}\n", s, z0, p));
+    text.push_str(&format!("  y = s * v mod p = {} * v mod {}\n", s, p));
+    text.push_str("The point at infinity maps to itself in both directions.\n");
+    text
+}
+
+/// The constants of `map` in snippet order, with the names the snippets give them.
+fn constants(map: &MontgomeryMap) -> [(&'static str, &BigInt); 8] {
+    [
+        ("P", &map.p),
+        ("A_WEIERSTRASS", &map.a),
+        ("B_WEIERSTRASS", &map.b),
+        ("A_MONTGOMERY", &map.a_montgomery),
+        ("B_MONTGOMERY", &map.b_montgomery),
+        ("Z0", &map.z0),
+        ("S", &map.s),
+        ("S_INV", &map.s_inv),
+    ]
+}
+
+/// Writes a self-contained Rust module with the constants of `map` and `to_montgomery` and
+/// `to_weierstrass` functions; its only dependency is the `num-bigint` crate.
+pub fn rust_snippet(map: &MontgomeryMap) -> String {
+    let mut text = String::new();
+    text.push_str(&format!(
+        "//! Maps between y^2 = x^3 + {}x + {} and {}v^2 = u^3 + {}u^2 + u over F_{}.\n",
+        map.a, map.b, map.b_montgomery, map.a_montgomery, map.p
+    ));
+    text.push_str("//! Generated by weierstrass_to_montgomery; needs only the num-bigint crate.\n\n");
+    text.push_str("use num_bigint::BigUint;\n\n");
+    for (name, value) in constants(map) {
+        text.push_str(&format!("pub const {}: &str = \"{}\";\n", name, value));
+    }
+    text.push_str(
+        r#"
+fn constant(decimal: &str) -> BigUint {
+    BigUint::parse_bytes(decimal.as_bytes(), 10).expect("constants are decimal integers")
+}
+
+/// Maps (x, y) on the Weierstrass curve to (u, v) = (s^-1 (x - z0), s^-1 y) on the Montgomery curve.
+pub fn to_montgomery(x: &BigUint, y: &BigUint) -> (BigUint, BigUint) {
+    let (p, z0, s_inv) = (constant(P), constant(Z0), constant(S_INV));
+    let u = &s_inv * (x % &p + &p - &z0) % &p;
+    let v = &s_inv * y % &p;
+    (u, v)
+}
+
+/// Maps (u, v) on the Montgomery curve back to (x, y) = (s u + z0, s v) on the Weierstrass curve.
+pub fn to_weierstrass(u: &BigUint, v: &BigUint) -> (BigUint, BigUint) {
+    let (p, z0, s) = (constant(P), constant(Z0), constant(S));
+    let x = (&s * u + &z0) % &p;
+    let y = &s * v % &p;
+    (x, y)
+}
+"#,
+    );
+    text
+}
+
+/// Writes a self-contained Python module with the constants of `map` and `to_montgomery` and
+/// `to_weierstrass` functions, using only built-in integers.
+pub fn python_snippet(map: &MontgomeryMap) -> String {
+    let mut text = String::new();
+    text.push_str(&format!(
+        "\"\"\"Maps between y^2 = x^3 + {}x + {} and {}v^2 = u^3 + {}u^2 + u over F_{}.\n\n",
+        map.a, map.b, map.b_montgomery, map.a_montgomery, map.p
+    ));
+    text.push_str("Generated by weierstrass_to_montgomery; needs no third-party packages.\n\"\"\"\n\n");
+    for (name, value) in constants(map) {
+        text.push_str(&format!("{} = {}\n", name, value));
+    }
+    text.push_str(
+        r#"
+
+def to_montgomery(x, y):
+    """Maps (x, y) on the Weierstrass curve to (u, v) = (s^-1 (x - z0), s^-1 y) on the Montgomery curve."""
+    return S_INV * (x - Z0) % P, S_INV * y % P
+
+
+def to_weierstrass(u, v):
+    """Maps (u, v) on the Montgomery curve back to (x, y) = (s u + z0, s v) on the Weierstrass curve."""
+    return (S * u + Z0) % P, S * v % P
+"#,
+    );
+    text
+}
+
+/// Writes a PARI/GP script that checks the map with PARI's own curve arithmetic: z0, s and the Montgomery
+/// coefficients satisfy their equations, both curves have the same j-invariant and number of points, and
+/// the map sends random points onto the Montgomery curve and sums to sums. It prints `ok` or stops with
+/// an error naming the failed check.
+pub fn pari_script(map: &MontgomeryMap) -> String {
+    let mut text = String::new();
+    text.push_str(&format!(
+        "\\\\ Checks the map between y^2 = x^3 + {}x + {} and {}v^2 = u^3 + {}u^2 + u over F_{}.\n",
+        map.a, map.b, map.b_montgomery, map.a_montgomery, map.p
+    ));
+    text.push_str("\\\\ Generated by weierstrass_to_montgomery; run with gp -q script.gp.\n\n");
+    text.push_str(&format!("P = {};\n", map.p));
+    for (name, value) in constants(map).into_iter().skip(1) {
+        text.push_str(&format!("{} = Mod({}, P);\n", name, value));
+    }
+    text.push_str(
+        r#"
+E = ellinit([A_WEIERSTRASS, B_WEIERSTRASS]);
+\\ B v^2 = u^3 + A u^2 + u is y^2 = x^3 + (A/B) x^2 + x/B^2 in x = u/B, y = v/B
+M = ellinit([0, A_MONTGOMERY / B_MONTGOMERY, 0, 1 / B_MONTGOMERY^2, 0]);
+check(ok, message) = if (!ok, error(message));
+check(Z0^3 + A_WEIERSTRASS * Z0 + B_WEIERSTRASS == 0, "z0 is not a root of z^3 + a z + b");
+check(S^2 == 3 * Z0^2 + A_WEIERSTRASS, "s^2 is not 3 z0^2 + a");
+check(S * S_INV == 1, "s_inv is not the inverse of s");
+check(A_MONTGOMERY == 3 * Z0 * S_INV && B_MONTGOMERY == S_INV, "A is not 3 z0 s_inv or B is not s_inv");
+check(ellj(E) == ellj(M), "the j-invariants differ");
+check(ellcard(E) == ellcard(M), "the numbers of points differ");
+\\ (x, y) -> (u, v) = (s_inv (x - z0), s_inv y), written in the coordinates of M
+to_montgomery(Q) = if (#Q == 1, [0], [S_INV * (Q[1] - Z0) / B_MONTGOMERY, S_INV * Q[2] / B_MONTGOMERY]);
+for (i = 1, 20, \
+    Q = random(E); R = random(E); \
+    check(ellisoncurve(M, to_montgomery(Q)), "a point maps off the Montgomery curve"); \
+    check(to_montgomery(elladd(E, Q, R)) == elladd(M, to_montgomery(Q), to_montgomery(R)), \
+        "the map does not preserve addition"));
+print("ok");
+"#,
+    );
+    text
+}
+
+/// Writes a Magma script with the checks of `pari_script`, using Magma's curve arithmetic; the
+/// assertions fail on the first check that does not hold, and it prints `ok` otherwise.
+pub fn magma_script(map: &MontgomeryMap) -> String {
+    let mut text = String::new();
+    text.push_str(&format!(
+        "// Checks the map between y^2 = x^3 + {}x + {} and {}v^2 = u^3 + {}u^2 + u over F_{}.\n",
+        map.a, map.b, map.b_montgomery, map.a_montgomery, map.p
+    ));
+    text.push_str("// Generated by weierstrass_to_montgomery.\n\n");
+    text.push_str(&format!("F := GF({});\n", map.p));
+    for (name, value) in constants(map).into_iter().skip(1) {
+        text.push_str(&format!("{} := F!{};\n", name, value));
+    }
+    text.push_str(
+        r#"
+E := EllipticCurve([A_WEIERSTRASS, B_WEIERSTRASS]);
+// B v^2 = u^3 + A u^2 + u is y^2 = x^3 + (A/B) x^2 + x/B^2 in x = u/B, y = v/B
+M := EllipticCurve([0, A_MONTGOMERY / B_MONTGOMERY, 0, 1 / B_MONTGOMERY^2, 0]);
+assert Z0^3 + A_WEIERSTRASS * Z0 + B_WEIERSTRASS eq 0;
+assert S^2 eq 3 * Z0^2 + A_WEIERSTRASS;
+assert S * S_INV eq 1;
+assert A_MONTGOMERY eq 3 * Z0 * S_INV and B_MONTGOMERY eq S_INV;
+assert jInvariant(E) eq jInvariant(M);
+assert #E eq #M;
+// (x, y) -> (u, v) = (s_inv (x - z0), s_inv y), written in the coordinates of M; coercion checks the
+// image lies on M
+function ToMontgomery(Q)
+    if IsZero(Q) then
+        return M!0;
+    end if;
+    return M![S_INV * (Q[1] - Z0) / B_MONTGOMERY, S_INV * Q[2] / B_MONTGOMERY];
+end function;
+for i in [1..20] do
+    Q := Random(E);
+    R := Random(E);
+    assert ToMontgomery(Q + R) eq ToMontgomery(Q) + ToMontgomery(R);
+end for;
+print "ok";
+"#,
+    );
+    text
+}
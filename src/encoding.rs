@@ -0,0 +1,96 @@
+use crate::curve::Point;
+use crate::error::TransformError;
+use crate::limits::SearchLimits;
+use crate::numtheory::mod_sqrt;
+use num_bigint::{BigInt, Sign};
+use num_traits::Zero;
+
+/// SEC1 tag for the point at infinity.
+pub(crate) const TAG_INFINITY: u8 = 0x00;
+/// SEC1 tag for a compressed point with an even second coordinate; `| 1` marks an odd one.
+pub(crate) const TAG_COMPRESSED: u8 = 0x02;
+/// SEC1 tag for an uncompressed point.
+pub(crate) const TAG_UNCOMPRESSED: u8 = 0x04;
+
+/// Number of bytes needed for a field element modulo `p`.
+pub fn field_byte_len(p: &BigInt) -> usize {
+    (p.bits() as usize).div_ceil(8)
+}
+
+/// Big-endian encoding of a canonical field element, left-padded to `len` bytes.
+pub(crate) fn to_fixed_bytes(value: &BigInt, len: usize) -> Vec<u8> {
+    let (_, digits) = value.to_bytes_be();
+    let mut bytes = vec![0u8; len.saturating_sub(digits.len())];
+    bytes.extend_from_slice(&digits);
+    bytes
+}
+
+/// Decodes a big-endian coordinate, rejecting values outside [0, p).
+pub(crate) fn read_coordinate(bytes: &[u8], p: &BigInt) -> Result<BigInt, TransformError> {
+    let value = BigInt::from_bytes_be(Sign::Plus, bytes);
+    if &value >= p {
+        return Err(TransformError::InvalidParameters(format!(
+            "encoded coordinate {} is not below the modulus",
+            value
+        )));
+    }
+    Ok(value)
+}
+
+/// Encodes a point as a SEC1-style tag followed by fixed-width big-endian coordinates.
+///
+/// Only the first coordinate and the parity of the second are kept when `compressed` is set.
+pub(crate) fn encode_point(point: &Point, p: &BigInt, compressed: bool) -> Vec<u8> {
+    let len = field_byte_len(p);
+    match point {
+        Point::Infinity => vec![TAG_INFINITY],
+        Point::Affine(first, second) if compressed => {
+            let mut bytes = vec![TAG_COMPRESSED | second.bit(0) as u8];
+            bytes.extend(to_fixed_bytes(first, len));
+            bytes
+        }
+        Point::Affine(first, second) => {
+            let mut bytes = vec![TAG_UNCOMPRESSED];
+            bytes.extend(to_fixed_bytes(first, len));
+            bytes.extend(to_fixed_bytes(second, len));
+            bytes
+        }
+    }
+}
+
+/// Decodes an encoding produced by `encode_point`, recovering a compressed second coordinate as the
+/// square root of `square_of_second(first)` with the stored parity.
+///
+/// The caller still has to check that an uncompressed point lies on its curve.
+pub(crate) fn decode_point(
+    bytes: &[u8],
+    p: &BigInt,
+    square_of_second: impl Fn(&BigInt) -> BigInt,
+) -> Result<Point, TransformError> {
+    let len = field_byte_len(p);
+    let malformed =
+        || TransformError::InvalidParameters(format!("malformed point encoding of {} bytes", bytes.len()));
+    let (&tag, body) = bytes.split_first().ok_or_else(malformed)?;
+    match tag {
+        TAG_INFINITY if body.is_empty() => Ok(Point::Infinity),
+        TAG_UNCOMPRESSED if body.len() == 2 * len => Ok(Point::Affine(
+            read_coordinate(&body[..len], p)?,
+            read_coordinate(&body[len..], p)?,
+        )),
+        0x02 | 0x03 if body.len() == len => {
+            let first = read_coordinate(body, p)?;
+            let root = mod_sqrt(&square_of_second(&first), p, &SearchLimits::default())?
+                .ok_or(TransformError::PointNotOnCurve)?;
+            let odd = tag & 1 == 1;
+            if root.bit(0) == odd {
+                Ok(Point::Affine(first, root))
+            } else if root.is_zero() {
+                // Zero has no odd square root
+                Err(malformed())
+            } else {
+                Ok(Point::Affine(first, p - root))
+            }
+        }
+        _ => Err(malformed()),
+    }
+}
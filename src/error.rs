@@ -0,0 +1,71 @@
+use alloc::string::String;
+use core::fmt;
+use num_bigint::BigInt;
+
+/// Reasons a transformation can fail, each mapped to its own process exit code.
+#[derive(Debug)]
+pub enum TransformError {
+    /// The curve or point parameters are malformed or describe a singular curve.
+    InvalidParameters(String),
+    /// The curve has no Montgomery model over F_p.
+    NoMontgomeryForm(String),
+    /// The supplied point does not satisfy the curve equation.
+    PointNotOnCurve,
+    /// The field has characteristic 2 or 3, where short Weierstrass and Montgomery models do not apply.
+    UnsupportedCharacteristic(BigInt),
+    /// A randomized search ran out of attempts or time before succeeding.
+    SearchLimitExceeded(String),
+    /// A computation produced an inconsistent result, as a fault injected into it would.
+    FaultDetected(String),
+    /// Reading the input or writing the output failed.
+    Io(String),
+}
+
+impl TransformError {
+    /// Exit code reported to the shell; 1 and 6 are left to the command-line tool's negative checks and
+    /// warnings, and 2 to its usage errors.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            TransformError::InvalidParameters(_) | TransformError::UnsupportedCharacteristic(_) => 8,
+            TransformError::NoMontgomeryForm(_) => 3,
+            TransformError::PointNotOnCurve => 4,
+            TransformError::SearchLimitExceeded(_) => 5,
+            TransformError::FaultDetected(_) => 7,
+            TransformError::Io(_) => 9,
+        }
+    }
+
+    /// Stable machine-readable identifier of the failure cause.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TransformError::InvalidParameters(_) => "invalid_parameters",
+            TransformError::NoMontgomeryForm(_) => "no_montgomery_form",
+            TransformError::PointNotOnCurve => "point_not_on_curve",
+            TransformError::UnsupportedCharacteristic(_) => "unsupported_characteristic",
+            TransformError::SearchLimitExceeded(_) => "search_limit_exceeded",
+            TransformError::FaultDetected(_) => "fault_detected",
+            TransformError::Io(_) => "io",
+        }
+    }
+}
+
+impl fmt::Display for TransformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransformError::InvalidParameters(reason) => write!(f, "invalid parameters: {}", reason),
+            TransformError::NoMontgomeryForm(reason) => write!(f, "no Montgomery form exists: {}", reason),
+            TransformError::PointNotOnCurve => write!(f, "the point is not on the curve"),
+            TransformError::UnsupportedCharacteristic(p) => write!(
+                f,
+                "fields of characteristic {} are not supported: the short Weierstrass and Montgomery \
+                 models, and the map between them, require a characteristic other than 2 and 3",
+                p
+            ),
+            TransformError::SearchLimitExceeded(search) => write!(f, "gave up {}", search),
+            TransformError::FaultDetected(reason) => write!(f, "fault detected: {}", reason),
+            TransformError::Io(reason) => write!(f, "I/O error: {}", reason),
+        }
+    }
+}
+
+impl core::error::Error for TransformError {}
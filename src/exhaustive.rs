@@ -0,0 +1,181 @@
+//! An exhaustive check of a map on a small curve: every point of the Weierstrass group is mapped, and
+//! the images must be exactly the points of the Montgomery curve, with the group structure carried over.
+//!
+//! The structure is checked through discrete logarithms. The group is Z/n1 × Z/n2 with n2 | n1, so every
+//! point is i·P + j·Q for unique i < n1 and j < n2, given generators P of order n1 and Q of order n2 that
+//! span it. The map is a group isomorphism exactly when it sends each i·P + j·Q to i·P' + j·Q', where P'
+//! and Q' are the images of P and Q, which takes one walk over the group on each side.
+
+use crate::curve::{CurveGroup, Point};
+use crate::error::TransformError;
+use crate::map::MontgomeryMap;
+use crate::montgomery::MontgomeryCurve;
+use crate::numtheory::mod_inverse;
+use crate::scalar_mul::scalar_mul;
+use crate::weierstrass::WeierstrassCurve;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::ToPrimitive;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Largest modulus `check_map_exhaustively` accepts, exclusive; the check walks the whole group.
+pub const MAX_EXHAUSTIVE_MODULUS: u64 = 1000;
+
+/// The structure of the group of a curve, Z/n1 × Z/n2 with n2 | n1, from `check_map_exhaustively`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GroupStructure {
+    /// n1, then n2 when the group is not cyclic.
+    pub invariants: Vec<u64>,
+    /// Points of the Weierstrass curve of orders n1 and n2 that generate the group, one per invariant.
+    pub generators: Vec<Point>,
+}
+
+impl GroupStructure {
+    /// The number of points, the product of the invariants.
+    pub fn order(&self) -> u64 {
+        self.invariants.iter().product()
+    }
+
+    /// Whether the group is cyclic, with a single generator.
+    pub fn is_cyclic(&self) -> bool {
+        self.invariants.len() == 1
+    }
+}
+
+/// Writes the group as `Z/12` or `Z/8 x Z/2`.
+impl fmt::Display for GroupStructure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let factors: Vec<String> = self.invariants.iter().map(|n| format!("Z/{}", n)).collect();
+        write!(f, "{}", factors.join(" x "))
+    }
+}
+
+/// Maps every point of the Weierstrass curve of `map` over F_p, p < `MAX_EXHAUSTIVE_MODULUS`, and checks
+/// that the images are the points of the Montgomery curve, each once, and that i·P + j·Q goes to
+/// i·P' + j·Q' for the generators P and Q of the group and their images; see the module documentation.
+///
+/// Returns the structure of the group, which the map has then been proven to preserve, or
+/// `InvalidParameters` naming the first point where it fails.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+pub fn check_map_exhaustively(map: &MontgomeryMap) -> Result<GroupStructure, TransformError> {
+    let p = &map.p;
+    let size = p.to_u64().filter(|&size| size > 3 && size < MAX_EXHAUSTIVE_MODULUS).ok_or_else(|| {
+        let message = format!("the exhaustive check needs 3 < p < {}, got {}", MAX_EXHAUSTIVE_MODULUS, p);
+        TransformError::InvalidParameters(message)
+    })?;
+    let weierstrass = WeierstrassCurve::new(&map.a, &map.b, p)?;
+    let montgomery = MontgomeryCurve::new(&map.a_montgomery, &map.b_montgomery, p)?;
+    let reduced = |value: &BigInt| value.mod_floor(p).to_u64().expect("reduced below p");
+    let (a, b) = (reduced(&map.a), reduced(&map.b));
+    let a_montgomery = reduced(&map.a_montgomery);
+    let b_inv = reduced(&mod_inverse(&map.b_montgomery, p).expect("B is non-zero"));
+    let points = curve_points(size, |x| (x * x % size * x + a * x + b) % size);
+    let expected = curve_points(size, |u| (u * u % size * (u + a_montgomery) + u) % size * b_inv % size);
+
+    let forward = map.forward();
+    let images: HashSet<Point> = points.iter().map(|point| forward.apply(point)).collect();
+    if images.len() != points.len() {
+        return Err(TransformError::InvalidParameters(
+            "the map sends two points of the Weierstrass curve to the same image".to_string(),
+        ));
+    }
+    if images != expected.into_iter().collect() {
+        return Err(TransformError::InvalidParameters(
+            "the images are not the points of the Montgomery curve".to_string(),
+        ));
+    }
+
+    let structure = group_structure(&weierstrass, &points);
+    let generators = match structure.generators.as_slice() {
+        [first] => [first.clone(), Point::Infinity],
+        [first, second] => [first.clone(), second.clone()],
+        _ => unreachable!("one or two generators"),
+    };
+    let [n1, n2] = [structure.invariants[0], structure.invariants.get(1).copied().unwrap_or(1)];
+    let image_generators = generators.clone().map(|generator| forward.apply(&generator));
+    let mut logarithms = HashMap::with_capacity(points.len());
+    let (mut row, mut row_image) = (Point::Infinity, Point::Infinity);
+    for j in 0..n2 {
+        let (mut point, mut image) = (row.clone(), row_image.clone());
+        for i in 0..n1 {
+            let mapped = forward.apply(&point);
+            if mapped != image {
+                return Err(TransformError::InvalidParameters(format!(
+                    "the map breaks the group law: {}·P + {}·Q = {} goes to {}, not {}",
+                    i, j, point, mapped, image
+                )));
+            }
+            logarithms.insert(point.clone(), (i, j));
+            point = weierstrass.add(&point, &generators[0]);
+            image = montgomery.add(&image, &image_generators[0]);
+        }
+        row = weierstrass.add(&row, &generators[1]);
+        row_image = montgomery.add(&row_image, &image_generators[1]);
+    }
+    assert_eq!(logarithms.len(), points.len(), "the generators span the group");
+    Ok(structure)
+}
+
+/// The points of y^2 = rhs(x) over F_size, the point at infinity first.
+fn curve_points(size: u64, rhs: impl Fn(u64) -> u64) -> Vec<Point> {
+    let mut roots = vec![Vec::new(); size as usize];
+    for y in 0..size {
+        roots[(y * y % size) as usize].push(y);
+    }
+    let mut points = vec![Point::Infinity];
+    for x in 0..size {
+        for &y in &roots[rhs(x) as usize] {
+            points.push(Point::Affine(BigInt::from(x), BigInt::from(y)));
+        }
+    }
+    points
+}
+
+/// Finds Z/n1 × Z/n2 and its generators for the group of `points`: P is a point of the largest order n1,
+/// and for n2 = #E / n1 > 1, Q is a point of order n2 none of whose non-zero multiples lies in ⟨P⟩, which
+/// exists as E[n2] ≅ Z/n2 × Z/n2.
+///
+/// The orders are found by walking the multiples of each point that no earlier walk passed through: a
+/// point on an earlier walk lies in a cyclic subgroup already seen, so its order is no larger.
+fn group_structure(curve: &WeierstrassCurve, points: &[Point]) -> GroupStructure {
+    let mut walked = HashSet::with_capacity(points.len());
+    let mut first = (1, Point::Infinity);
+    for point in points {
+        if walked.contains(point) {
+            continue;
+        }
+        let (mut multiple, mut order) = (point.clone(), 1);
+        while !multiple.is_infinity() {
+            walked.insert(multiple.clone());
+            multiple = curve.add(&multiple, point);
+            order += 1;
+        }
+        if order > first.0 {
+            first = (order, point.clone());
+        }
+    }
+    let (n1, generator) = first;
+    let n2 = points.len() as u64 / n1;
+    if n2 == 1 {
+        return GroupStructure { invariants: vec![n1], generators: vec![generator] };
+    }
+    let mut subgroup = HashSet::with_capacity(n1 as usize);
+    let mut multiple = Point::Infinity;
+    for _ in 0..n1 {
+        multiple = curve.add(&multiple, &generator);
+        subgroup.insert(multiple.clone());
+    }
+    let complement = points.iter().find(|point| {
+        scalar_mul(curve, point, &BigInt::from(n2)).is_infinity() && {
+            let mut multiple = (*point).clone();
+            (1..n2).all(|_| {
+                let outside = !subgroup.contains(&multiple);
+                multiple = curve.add(&multiple, point);
+                outside
+            })
+        }
+    });
+    let complement = complement.expect("E[n2] has a point of order n2 outside ⟨P⟩").clone();
+    GroupStructure { invariants: vec![n1, n2], generators: vec![generator, complement] }
+}
@@ -0,0 +1,671 @@
+use crate::chain::AdditionChain;
+use crate::ct;
+use crate::error::TransformError;
+use crate::limits::SearchLimits;
+use crate::numtheory::{
+    cipolla_sqrt, mod_inverse, mod_sqrt, mod_sqrt_both, mod_sqrt_even, mod_sqrt_odd, SqrtAlgorithm,
+    TonelliShanks,
+};
+use crate::ops;
+use crate::solinas::NistPrime;
+use num_bigint::{BigInt, BigUint, RandBigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::sync::OnceLock;
+
+/// An element of the prime field F_p, carrying its modulus so it can be used with the arithmetic operators.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FieldElement {
+    /// Canonical representative in [0, p).
+    pub value: BigInt,
+    /// The prime modulus p.
+    pub modulus: BigInt,
+}
+
+impl FieldElement {
+    /// Creates an element, reducing `value` into [0, modulus).
+    pub fn new(value: &BigInt, modulus: &BigInt) -> FieldElement {
+        FieldElement {
+            value: value.mod_floor(modulus),
+            modulus: modulus.clone(),
+        }
+    }
+
+    /// Builds an element of the same field as `self`.
+    pub fn with(&self, value: BigInt) -> FieldElement {
+        FieldElement {
+            value: value.mod_floor(&self.modulus),
+            modulus: self.modulus.clone(),
+        }
+    }
+
+    /// The additive identity of this element's field.
+    pub fn zero(&self) -> FieldElement {
+        self.with(BigInt::zero())
+    }
+
+    /// The multiplicative identity of this element's field.
+    pub fn one(&self) -> FieldElement {
+        self.with(BigInt::one())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+
+    /// Samples a uniformly random element of this element's field.
+    pub fn random<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> FieldElement {
+        self.with(rng.gen_bigint_range(&BigInt::zero(), &self.modulus))
+    }
+
+    pub fn square(&self) -> FieldElement {
+        ops::record(0, 1);
+        self.with(&self.value * &self.value)
+    }
+
+    /// Raises the element to a non-negative power.
+    pub fn pow(&self, exponent: &BigInt) -> FieldElement {
+        self.with(modpow(&self.value, exponent, &self.modulus))
+    }
+
+    /// Returns the multiplicative inverse, or `None` for zero.
+    pub fn inverse(&self) -> Option<FieldElement> {
+        mod_inverse(&self.value, &self.modulus).map(|inverse| self.with(inverse))
+    }
+
+    /// Returns true if the element is a square in F_p (zero included).
+    pub fn is_square(&self) -> bool {
+        self.is_zero() || modpow(&self.value, &((&self.modulus - 1u32) / 2u32), &self.modulus).is_one()
+    }
+
+    /// Computes a square root with `mod_sqrt`, or `None` if the element is not a square; an error, as
+    /// from `mod_sqrt`, for a composite modulus or a non-residue search that runs out of attempts.
+    pub fn sqrt(&self) -> Result<Option<FieldElement>, TransformError> {
+        let root = mod_sqrt(&self.value, &self.modulus, &SearchLimits::default())?;
+        Ok(root.map(|root| self.with(root)))
+    }
+
+    /// Both square roots (r, −r), the one with the smaller representative first.
+    pub fn sqrt_both(&self) -> Result<Option<(FieldElement, FieldElement)>, TransformError> {
+        let roots = mod_sqrt_both(&self.value, &self.modulus, &SearchLimits::default())?;
+        Ok(roots.map(|(low, high)| (self.with(low), self.with(high))))
+    }
+
+    /// The square root with an even representative.
+    pub fn sqrt_even(&self) -> Result<Option<FieldElement>, TransformError> {
+        let root = mod_sqrt_even(&self.value, &self.modulus, &SearchLimits::default())?;
+        Ok(root.map(|root| self.with(root)))
+    }
+
+    /// The square root with an odd representative (zero for zero).
+    pub fn sqrt_odd(&self) -> Result<Option<FieldElement>, TransformError> {
+        let root = mod_sqrt_odd(&self.value, &self.modulus, &SearchLimits::default())?;
+        Ok(root.map(|root| self.with(root)))
+    }
+
+    /// `other` if `choice` is set, `self` otherwise, picked word by word without branching on `choice`;
+    /// see `ct`.
+    pub fn ct_select(&self, other: &FieldElement, choice: bool) -> FieldElement {
+        self.assert_same_field(other);
+        let count = ct::word_count(&self.modulus);
+        FieldElement {
+            value: ct::select(&self.value, &other.value, choice, count),
+            modulus: self.modulus.clone(),
+        }
+    }
+
+    /// Exchanges `first` and `second` if `choice` is set, word by word without branching on `choice`.
+    pub fn ct_swap(first: &mut FieldElement, second: &mut FieldElement, choice: bool) {
+        first.assert_same_field(second);
+        let count = ct::word_count(&first.modulus);
+        ct::swap(&mut first.value, &mut second.value, choice, count);
+    }
+
+    fn assert_same_field(&self, other: &FieldElement) {
+        assert!(self.modulus == other.modulus, "field elements with different moduli");
+    }
+}
+
+/// Window width used by `window_modpow` for an exponent of the given bit length.
+fn window_width(bits: u64) -> u64 {
+    match bits {
+        0..=24 => 1,
+        25..=80 => 3,
+        81..=240 => 4,
+        241..=672 => 5,
+        _ => 6,
+    }
+}
+
+/// Computes `base^exponent mod modulus` with left-to-right sliding-window exponentiation.
+///
+/// Only the odd powers base, base^3, ..., base^(2^w - 1) are precomputed, and every run of
+/// up to w exponent bits ending in a one costs a single multiplication.
+pub fn window_modpow(base: &BigInt, exponent: &BigInt, modulus: &BigInt) -> BigInt {
+    assert!(!exponent.is_negative(), "negative exponent in window_modpow");
+    if modulus.is_one() {
+        return BigInt::zero();
+    }
+    let bits = exponent.bits();
+    let width = window_width(bits);
+    let base = base.mod_floor(modulus);
+
+    // odd_powers[k] = base^(2k + 1)
+    let base_squared = (&base * &base) % modulus;
+    let mut odd_powers = Vec::with_capacity(1 << (width - 1));
+    odd_powers.push(base.clone());
+    for k in 1..(1usize << (width - 1)) {
+        let next = (&odd_powers[k - 1] * &base_squared) % modulus;
+        odd_powers.push(next);
+    }
+
+    ops::record(odd_powers.len() as u64 - 1, 1);
+    let mut result = BigInt::one();
+    let mut i = bits as i64 - 1;
+    while i >= 0 {
+        if !exponent.bit(i as u64) {
+            ops::record(0, 1);
+            result = (&result * &result) % modulus;
+            i -= 1;
+            continue;
+        }
+        // Take the longest window [j, i] of at most `width` bits that ends in a one
+        let mut j = (i - width as i64 + 1).max(0);
+        while !exponent.bit(j as u64) {
+            j += 1;
+        }
+        let mut window = 0usize;
+        for k in (j..=i).rev() {
+            result = (&result * &result) % modulus;
+            window = (window << 1) | exponent.bit(k as u64) as usize;
+        }
+        ops::record(1, (i - j + 1) as u64);
+        result = (&result * &odd_powers[window >> 1]) % modulus;
+        i = j - 1;
+    }
+    result
+}
+
+/// Largest modulus size, in bits, for which `modpow` prefers `window_modpow`.
+///
+/// Below this size every product fits in one machine word and the plain reductions beat the
+/// Montgomery setup inside `BigInt::modpow` by about 2x; above it `BigInt::modpow` is 1.5-3x
+/// faster (see `benches/modpow.rs`).
+const WINDOW_MODPOW_MAX_BITS: u64 = 32;
+
+/// Computes `base^exponent mod modulus` with whichever of `window_modpow` and `BigInt::modpow`
+/// is faster for the size of `modulus`.
+pub fn modpow(base: &BigInt, exponent: &BigInt, modulus: &BigInt) -> BigInt {
+    if modulus.bits() <= WINDOW_MODPOW_MAX_BITS {
+        window_modpow(base, exponent, modulus)
+    } else {
+        // counted as left-to-right square-and-multiply, whatever `BigInt::modpow` does inside
+        let bits = exponent.bits();
+        ops::record(exponent.magnitude().count_ones().saturating_sub(1), bits.saturating_sub(1));
+        base.mod_floor(modulus).modpow(exponent, modulus)
+    }
+}
+
+/// Precomputed constants for Barrett reduction modulo a fixed modulus.
+///
+/// Replaces the long division behind `mod_floor` by two multiplications and shifts. Against
+/// num-bigint's division this breaks even around 256-bit moduli and loses on small ones
+/// (see `benches/reduction.rs`), but it gives the transform a single per-field reduction context.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Barrett {
+    modulus: BigUint,
+    /// floor(4^k / modulus), where k is the bit length of the modulus.
+    mu: BigUint,
+    k: u64,
+}
+
+impl Barrett {
+    /// Precomputes the reduction constants for a modulus greater than one.
+    pub fn new(modulus: &BigInt) -> Barrett {
+        assert!(modulus > &BigInt::one(), "Barrett reduction needs a modulus greater than one");
+        let modulus = modulus.magnitude().clone();
+        let k = modulus.bits();
+        let mu = (BigUint::one() << (2 * k)) / &modulus;
+        Barrett { modulus, mu, k }
+    }
+
+    /// The modulus this context reduces by.
+    pub fn modulus(&self) -> BigInt {
+        BigInt::from_biguint(Sign::Plus, self.modulus.clone())
+    }
+
+    /// Reduces a non-negative value below modulus^2 without division.
+    fn reduce_magnitude(&self, value: &BigUint) -> BigUint {
+        let quotient = ((value >> (self.k - 1)) * &self.mu) >> (self.k + 1);
+        let mut remainder = value - quotient * &self.modulus;
+        // The quotient estimate is at most two too small
+        while remainder >= self.modulus {
+            remainder -= &self.modulus;
+        }
+        remainder
+    }
+
+    /// Returns `value` reduced into [0, modulus), like `value.mod_floor(modulus)`.
+    pub fn reduce(&self, value: &BigInt) -> BigInt {
+        let magnitude = value.magnitude();
+        let remainder = if magnitude.bits() <= 2 * self.k {
+            self.reduce_magnitude(magnitude)
+        } else {
+            magnitude % &self.modulus
+        };
+        if value.is_negative() && !remainder.is_zero() {
+            BigInt::from_biguint(Sign::Plus, &self.modulus - remainder)
+        } else {
+            BigInt::from_biguint(Sign::Plus, remainder)
+        }
+    }
+}
+
+/// Reduction modulo a pseudo-Mersenne prime p = 2^k − c with a small c, such as 2^255 − 19 or the
+/// Mersenne prime 2^521 − 1.
+///
+/// Since 2^k ≡ c (mod p), the bits of a value above bit k fold back in as a multiplication by c, so
+/// reducing costs a few shifts, masks and small multiplications instead of a division: about 1.5x faster
+/// than `mod_floor` for 2^255 − 19 and 5x for 2^521 − 1 (see `benches/reduction.rs`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PseudoMersenne {
+    modulus: BigUint,
+    /// 2^k − 1, the low k bits of a value.
+    mask: BigUint,
+    c: BigUint,
+    k: u64,
+}
+
+impl PseudoMersenne {
+    /// The reduction context for `modulus` if it is 2^k − c with c below 2^(k/2), where k is its bit
+    /// length, or `None` otherwise.
+    pub fn detect(modulus: &BigInt) -> Option<PseudoMersenne> {
+        if modulus <= &BigInt::one() {
+            return None;
+        }
+        let modulus = modulus.magnitude().clone();
+        let k = modulus.bits();
+        let c = (BigUint::one() << k) - &modulus;
+        if c.bits() > k / 2 {
+            return None;
+        }
+        let mask = (BigUint::one() << k) - 1u32;
+        Some(PseudoMersenne { modulus, mask, c, k })
+    }
+
+    /// The modulus this context reduces by.
+    pub fn modulus(&self) -> BigInt {
+        BigInt::from_biguint(Sign::Plus, self.modulus.clone())
+    }
+
+    /// Reduces a non-negative value of any size by folding its high bits.
+    fn reduce_magnitude(&self, value: &BigUint) -> BigUint {
+        let mut remainder = value.clone();
+        // Each fold removes at least k/2 bits, and a value of k + 1 bits folds below 2^k in two
+        while remainder.bits() > self.k {
+            let high = &remainder >> self.k;
+            remainder &= &self.mask;
+            remainder += high * &self.c;
+        }
+        if remainder >= self.modulus {
+            remainder -= &self.modulus;
+        }
+        remainder
+    }
+
+    /// Returns `value` reduced into [0, modulus), like `value.mod_floor(modulus)`.
+    pub fn reduce(&self, value: &BigInt) -> BigInt {
+        let remainder = self.reduce_magnitude(value.magnitude());
+        if value.is_negative() && !remainder.is_zero() {
+            BigInt::from_biguint(Sign::Plus, &self.modulus - remainder)
+        } else {
+            BigInt::from_biguint(Sign::Plus, remainder)
+        }
+    }
+}
+
+/// How a `Field` reduces: with the Solinas routines for the NIST primes, by folding for other
+/// pseudo-Mersenne primes, with Barrett reduction otherwise.
+#[derive(Clone, Debug)]
+enum Reduction {
+    Solinas(NistPrime),
+    PseudoMersenne(PseudoMersenne),
+    Barrett(Barrett),
+}
+
+/// Algorithms for inverses in a `Field`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum InversionAlgorithm {
+    /// Pick one from the build, see `InversionAlgorithm::select`.
+    #[default]
+    Auto,
+    /// `mod_inverse`, the fastest; the number of its division steps depends on the value.
+    ExtendedGcd,
+    /// value^(p − 2) by Fermat's little theorem over a fixed `AdditionChain`, which takes the same
+    /// squarings and multiplications for every value.
+    Fermat,
+}
+
+impl InversionAlgorithm {
+    /// The algorithm `Auto` stands for: `Fermat` with the `ct` feature, `ExtendedGcd` otherwise.
+    pub fn select() -> InversionAlgorithm {
+        if cfg!(feature = "ct") {
+            InversionAlgorithm::Fermat
+        } else {
+            InversionAlgorithm::ExtendedGcd
+        }
+    }
+}
+
+/// The prime field F_p as a context for repeated operations, holding everything that depends only on p:
+/// its reduction constants, the Euler exponent (p − 1)/2, the square-root exponent (p + 1)/4
+/// when p ≡ 3 (mod 4), the Tonelli-Shanks parameters and the addition chain for p − 2.
+///
+/// The NIST primes of P-256, P-384 and P-521 reduce with `NistPrime`, other pseudo-Mersenne primes such
+/// as 2^255 − 19 with `PseudoMersenne`, and all others with `Barrett`.
+/// The transform, `cubic_roots` and `generate_curve` build one per call and pass it down instead of p.
+/// For primes where `SqrtAlgorithm::select` picks Tonelli-Shanks, the non-residue z and the split
+/// p − 1 = q·2^s are found by the first `sqrt` and reused by later ones, and likewise the chain is
+/// built by the first Fermat inversion; clones share nothing but carry over what was found so far.
+#[derive(Clone, Debug)]
+pub struct Field {
+    p: BigInt,
+    reduction: Reduction,
+    euler_exponent: BigInt,
+    sqrt_exponent: Option<BigInt>,
+    tonelli_shanks: OnceLock<TonelliShanks>,
+    inversion_chain: OnceLock<AdditionChain>,
+}
+
+impl Field {
+    /// Creates the context for a prime p > 2; like `mod_sqrt`, `sqrt` may fail or return `None` otherwise.
+    pub fn new(p: &BigInt) -> Field {
+        let sqrt_exponent = (p % 4u32 == BigInt::from(3)).then(|| (p + 1u32) / 4u32);
+        let reduction = if let Some(prime) = NistPrime::detect(p) {
+            Reduction::Solinas(prime)
+        } else if let Some(pseudo_mersenne) = PseudoMersenne::detect(p) {
+            Reduction::PseudoMersenne(pseudo_mersenne)
+        } else {
+            Reduction::Barrett(Barrett::new(p))
+        };
+        Field {
+            p: p.clone(),
+            reduction,
+            euler_exponent: (p - 1u32) / 2u32,
+            sqrt_exponent,
+            tonelli_shanks: OnceLock::new(),
+            inversion_chain: OnceLock::new(),
+        }
+    }
+
+    /// The prime p.
+    pub fn modulus(&self) -> &BigInt {
+        &self.p
+    }
+
+    /// Whether p was recognized as a pseudo-Mersenne prime 2^k − c with a small c.
+    pub fn is_pseudo_mersenne(&self) -> bool {
+        matches!(self.reduction, Reduction::PseudoMersenne(_))
+    }
+
+    /// The NIST prime p is, if it is one and reduces with the Solinas routines.
+    pub fn nist_prime(&self) -> Option<NistPrime> {
+        match self.reduction {
+            Reduction::Solinas(prime) => Some(prime),
+            _ => None,
+        }
+    }
+
+    /// `value` reduced into [0, p); see `NistPrime::reduce`, `PseudoMersenne::reduce` and `Barrett::reduce`.
+    pub fn reduce(&self, value: &BigInt) -> BigInt {
+        match &self.reduction {
+            Reduction::Solinas(prime) => prime.reduce(value),
+            Reduction::PseudoMersenne(pseudo_mersenne) => pseudo_mersenne.reduce(value),
+            Reduction::Barrett(barrett) => barrett.reduce(value),
+        }
+    }
+
+    /// `base^exponent` in F_p for a non-negative exponent.
+    pub fn pow(&self, base: &BigInt, exponent: &BigInt) -> BigInt {
+        modpow(&self.reduce(base), exponent, &self.p)
+    }
+
+    /// The multiplicative inverse, or `None` for zero, with the algorithm of `InversionAlgorithm::select`.
+    pub fn inverse(&self, value: &BigInt) -> Option<BigInt> {
+        self.inverse_with(value, InversionAlgorithm::Auto)
+    }
+
+    /// `inverse` with a given algorithm.
+    pub fn inverse_with(&self, value: &BigInt, algorithm: InversionAlgorithm) -> Option<BigInt> {
+        let value = self.reduce(value);
+        if value.is_zero() {
+            return None;
+        }
+        let algorithm = match algorithm {
+            InversionAlgorithm::Auto => InversionAlgorithm::select(),
+            algorithm => algorithm,
+        };
+        match algorithm {
+            InversionAlgorithm::Fermat => {
+                let chain = self.inversion_chain.get_or_init(|| AdditionChain::new(&(&self.p - 2u32)));
+                Some(chain.pow(self, &value))
+            }
+            _ => mod_inverse(&value, &self.p),
+        }
+    }
+
+    /// The Legendre symbol (value / p) as 1, −1 or 0, by Euler's criterion.
+    pub fn legendre(&self, value: &BigInt) -> i8 {
+        let power = self.pow(value, &self.euler_exponent);
+        if power.is_zero() {
+            0
+        } else if power.is_one() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// `mod_sqrt` over this field: `None` for a non-residue, or an error if a search for a non-residue
+    /// runs past `limits`.
+    pub fn sqrt(&self, value: &BigInt, limits: &SearchLimits) -> Result<Option<BigInt>, TransformError> {
+        let value = &self.reduce(value);
+        match self.legendre(value) {
+            0 => return Ok(Some(BigInt::zero())),
+            1 => {}
+            _ => return Ok(None),
+        }
+        if let Some(exponent) = &self.sqrt_exponent {
+            let root = modpow(value, exponent, &self.p);
+            ops::record(0, 1);
+            return Ok(if &self.reduce(&(&root * &root)) == value { Some(root) } else { None });
+        }
+        if SqrtAlgorithm::select(&self.p) == SqrtAlgorithm::Cipolla {
+            return cipolla_sqrt(value, &self.p, limits);
+        }
+        let parameters = match self.tonelli_shanks.get() {
+            Some(parameters) => parameters,
+            None => {
+                let found = TonelliShanks::find(&self.p, limits)?;
+                self.tonelli_shanks.get_or_init(|| found)
+            }
+        };
+        Ok(parameters.sqrt(value, &self.p))
+    }
+}
+
+/// Inverts every element with a single modular inversion using Montgomery's trick.
+///
+/// Zero entries have no inverse and are returned as zero; all elements must share the same modulus.
+pub fn batch_inverse(elements: &[FieldElement]) -> Vec<FieldElement> {
+    let first = match elements.first() {
+        Some(first) => first,
+        None => return Vec::new(),
+    };
+    // prefix[i] holds the product of all non-zero elements before index i
+    let mut prefix = Vec::with_capacity(elements.len());
+    let mut accumulator = first.one();
+    for element in elements {
+        first.assert_same_field(element);
+        prefix.push(accumulator.clone());
+        if !element.is_zero() {
+            accumulator = &accumulator * element;
+        }
+    }
+
+    let mut inverse = accumulator.inverse().expect("product of non-zero elements is invertible");
+    let mut result = vec![first.zero(); elements.len()];
+    for (index, element) in elements.iter().enumerate().rev() {
+        if element.is_zero() {
+            continue;
+        }
+        result[index] = &inverse * &prefix[index];
+        inverse = &inverse * element;
+    }
+    result
+}
+
+impl fmt::Display for FieldElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl Add for &FieldElement {
+    type Output = FieldElement;
+
+    fn add(self, other: &FieldElement) -> FieldElement {
+        self.assert_same_field(other);
+        self.with(&self.value + &other.value)
+    }
+}
+
+impl Sub for &FieldElement {
+    type Output = FieldElement;
+
+    fn sub(self, other: &FieldElement) -> FieldElement {
+        self.assert_same_field(other);
+        self.with(&self.value - &other.value)
+    }
+}
+
+impl Mul for &FieldElement {
+    type Output = FieldElement;
+
+    fn mul(self, other: &FieldElement) -> FieldElement {
+        self.assert_same_field(other);
+        ops::record(1, 0);
+        self.with(&self.value * &other.value)
+    }
+}
+
+impl Neg for &FieldElement {
+    type Output = FieldElement;
+
+    fn neg(self) -> FieldElement {
+        self.with(-&self.value)
+    }
+}
+
+/// Adds in place, keeping the modulus and the buffer of `self`.
+impl AddAssign<&FieldElement> for FieldElement {
+    fn add_assign(&mut self, other: &FieldElement) {
+        self.assert_same_field(other);
+        self.value += &other.value;
+        reduce_in_place(&mut self.value, &self.modulus);
+    }
+}
+
+/// Subtracts in place, keeping the modulus and the buffer of `self`.
+impl SubAssign<&FieldElement> for FieldElement {
+    fn sub_assign(&mut self, other: &FieldElement) {
+        self.assert_same_field(other);
+        self.value -= &other.value;
+        reduce_in_place(&mut self.value, &self.modulus);
+    }
+}
+
+/// Multiplies in place, keeping the modulus of `self`.
+impl MulAssign<&FieldElement> for FieldElement {
+    fn mul_assign(&mut self, other: &FieldElement) {
+        self.assert_same_field(other);
+        ops::record(1, 0);
+        self.value *= &other.value;
+        self.value %= &self.modulus;
+        reduce_in_place(&mut self.value, &self.modulus);
+    }
+}
+
+/// Brings `value` into [0, modulus) in place: one addition or subtraction of the modulus for a sum or
+/// difference of canonical values, a full reduction for anything further out.
+fn reduce_in_place(value: &mut BigInt, modulus: &BigInt) {
+    if value.is_negative() {
+        *value += modulus;
+    } else if &*value >= modulus {
+        *value -= modulus;
+    }
+    if value.is_negative() || &*value >= modulus {
+        *value = value.mod_floor(modulus);
+    }
+}
+
+impl Add<&FieldElement> for FieldElement {
+    type Output = FieldElement;
+
+    fn add(mut self, other: &FieldElement) -> FieldElement {
+        self += other;
+        self
+    }
+}
+
+impl Sub<&FieldElement> for FieldElement {
+    type Output = FieldElement;
+
+    fn sub(mut self, other: &FieldElement) -> FieldElement {
+        self -= other;
+        self
+    }
+}
+
+impl Mul<&FieldElement> for FieldElement {
+    type Output = FieldElement;
+
+    fn mul(mut self, other: &FieldElement) -> FieldElement {
+        self *= other;
+        self
+    }
+}
+
+impl Add for FieldElement {
+    type Output = FieldElement;
+
+    fn add(self, other: FieldElement) -> FieldElement {
+        self + &other
+    }
+}
+
+impl Sub for FieldElement {
+    type Output = FieldElement;
+
+    fn sub(self, other: FieldElement) -> FieldElement {
+        self - &other
+    }
+}
+
+impl Mul for FieldElement {
+    type Output = FieldElement;
+
+    fn mul(self, other: FieldElement) -> FieldElement {
+        self * &other
+    }
+}
+
+impl Neg for FieldElement {
+    type Output = FieldElement;
+
+    fn neg(self) -> FieldElement {
+        -&self
+    }
+}
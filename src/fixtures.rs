@@ -0,0 +1,126 @@
+//! Ready-made cross-model test data for other projects: small curves with a generator and random points,
+//! each with its image on the Montgomery curve.
+
+use crate::error::TransformError;
+use crate::field::Field;
+use crate::generate::{generate_curve_with_prime_subgroup, random_point};
+use crate::limits::{SearchBudget, SearchLimits};
+use crate::map::MontgomeryMap;
+use crate::numtheory::{is_probable_prime, MILLER_RABIN_ROUNDS};
+use crate::order::MAX_COUNT_MODULUS;
+use crate::transform::TransformOptions;
+use num_bigint::{BigInt, RandBigInt};
+use rand::{CryptoRng, RngCore};
+
+/// Version tag written by `to_json`.
+pub const FIXTURE_FORMAT_VERSION: u32 = 1;
+
+/// A small curve of order 4·q for a prime q, a generator of the subgroup of order q and random points,
+/// with the map to the Montgomery form and the images of them all.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fixture {
+    /// The map, which also holds the curve (p, a, b) and its Montgomery form (A, B).
+    pub map: MontgomeryMap,
+    /// The group order 4·q.
+    pub order: BigInt,
+    /// The prime order q of the generator.
+    pub subgroup_order: BigInt,
+    /// The generator (x, y) and its image (u, v).
+    pub generator: ((BigInt, BigInt), (BigInt, BigInt)),
+    /// Random points (x, y) and their images (u, v).
+    pub points: Vec<((BigInt, BigInt), (BigInt, BigInt))>,
+}
+
+/// Generates `count` fixtures over random primes of `bits` bits, each with `points` random points.
+///
+/// The group orders are counted naively, so `bits` must be between 5 and 24; 12 bits take a few
+/// milliseconds per curve. A seeded `rng` gives the same fixtures every time.
+pub fn generate_fixtures<R: RngCore + CryptoRng + ?Sized>(
+    count: usize,
+    bits: u64,
+    points: usize,
+    limits: &SearchLimits,
+    rng: &mut R,
+) -> Result<Vec<Fixture>, TransformError> {
+    if !(5..=MAX_COUNT_MODULUS.ilog2() as u64).contains(&bits) {
+        return Err(TransformError::InvalidParameters(format!(
+            "fixtures need primes of 5 to {} bits, got {}",
+            MAX_COUNT_MODULUS.ilog2(),
+            bits
+        )));
+    }
+    let options = TransformOptions { limits: *limits, ..TransformOptions::default() };
+    let mut fixtures = Vec::with_capacity(count);
+    for _ in 0..count {
+        let p = random_prime(bits, limits, rng)?;
+        let (spec, order) = generate_curve_with_prime_subgroup(&p, limits, rng)?;
+        let map = MontgomeryMap::new_with_rng(&spec.a, &spec.b, &p, &options, rng)?;
+        let field = Field::new(&p);
+        let mut sampled = Vec::with_capacity(points);
+        for _ in 0..points {
+            let (x, y) = random_point(&field, &spec.a, &spec.b, limits, rng)?;
+            let image = map.map_point(&x, &y);
+            sampled.push(((x, y), image));
+        }
+        let generator = map.map_point(&spec.x, &spec.y);
+        fixtures.push(Fixture {
+            subgroup_order: &order / 4u32,
+            order,
+            generator: ((spec.x, spec.y), generator),
+            points: sampled,
+            map,
+        });
+    }
+    Ok(fixtures)
+}
+
+/// Draws a prime of exactly `bits` bits.
+fn random_prime<R: RngCore + CryptoRng + ?Sized>(
+    bits: u64,
+    limits: &SearchLimits,
+    rng: &mut R,
+) -> Result<BigInt, TransformError> {
+    let mut budget = SearchBudget::new(limits);
+    loop {
+        budget.tick("searching for a prime")?;
+        let candidate = BigInt::from(rng.gen_biguint(bits - 1)) + (BigInt::from(1) << (bits - 1));
+        if is_probable_prime(&candidate, MILLER_RABIN_ROUNDS) {
+            return Ok(candidate);
+        }
+    }
+}
+
+/// Writes fixtures as `{"version": 1, "fixtures": [...]}`, each fixture an object with `p`, `a`, `b`,
+/// `order`, `subgroup_order`, the map's `A`, `B`, `z0` and `s`, the `generator` and the `points`, every
+/// point an object with `x`, `y`, `u` and `v`. Numbers are decimal strings, like in the map encoding.
+#[cfg(feature = "serde")]
+pub fn to_json(fixtures: &[Fixture]) -> String {
+    let point = |((x, y), (u, v)): &((BigInt, BigInt), (BigInt, BigInt))| {
+        serde_json::json!({
+            "x": x.to_string(),
+            "y": y.to_string(),
+            "u": u.to_string(),
+            "v": v.to_string(),
+        })
+    };
+    let fixtures: Vec<serde_json::Value> = fixtures
+        .iter()
+        .map(|fixture| {
+            let map = &fixture.map;
+            serde_json::json!({
+                "p": map.p.to_string(),
+                "a": map.a.to_string(),
+                "b": map.b.to_string(),
+                "order": fixture.order.to_string(),
+                "subgroup_order": fixture.subgroup_order.to_string(),
+                "A": map.a_montgomery.to_string(),
+                "B": map.b_montgomery.to_string(),
+                "z0": map.z0.to_string(),
+                "s": map.s.to_string(),
+                "generator": point(&fixture.generator),
+                "points": fixture.points.iter().map(point).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    serde_json::json!({"version": FIXTURE_FORMAT_VERSION, "fixtures": fixtures}).to_string()
+}
@@ -0,0 +1,105 @@
+use crate::cubic::cubic_roots;
+use crate::error::TransformError;
+use crate::fp2::Fp2;
+use crate::map::MontgomeryMap;
+use crate::numtheory::{legendre_symbol, mod_inverse};
+use crate::transform::TransformOptions;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::One;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use std::fmt;
+
+/// How a Montgomery curve relates to the Weierstrass curve it was derived from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Relation {
+    /// Isomorphic over F_p: points map one-to-one with the Weierstrass curve.
+    Isomorphic,
+    /// The quadratic twist, isomorphic only over F_p²; B differs from an isomorphic B by a non-square.
+    QuadraticTwist,
+}
+
+impl fmt::Display for Relation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Relation::Isomorphic => "isomorphic",
+            Relation::QuadraticTwist => "twist",
+        })
+    }
+}
+
+/// Montgomery parameters (A, B) reachable from a Weierstrass curve, with the choices that produce them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MontgomeryForm {
+    pub a: BigInt,
+    /// Either 1 or the smallest non-residue modulo p: B is only meaningful up to B -> d^2·B.
+    pub b: BigInt,
+    /// The root of z^3 + az + b the parameters come from.
+    pub z0: BigInt,
+    /// The square root of 3·z0^2 + a, whose sign decides the sign of A.
+    pub s: BigInt,
+    pub relation: Relation,
+}
+
+/// Lists every Montgomery form of y^2 = x^3 + ax + b over F_p, together with its quadratic twists.
+///
+/// Each root z0 with 3·z0^2 + a a square and each sign of s give A = 3·z0/s and B = 1/s. Since
+/// B·v^2 = u^3 + A·u^2 + u is isomorphic to the curve with d^2·B for any d ≠ 0, B is reported as the
+/// representative 1 of the squares or the smallest non-residue n; the other representative gives the
+/// twist. Duplicates are removed, so the list holds at most 12 forms, isomorphic ones first.
+pub fn montgomery_forms(
+    a: &BigInt,
+    b: &BigInt,
+    p: &BigInt,
+    options: &TransformOptions,
+) -> Result<Vec<MontgomeryForm>, TransformError> {
+    montgomery_forms_with_rng(a, b, p, options, &mut OsRng)
+}
+
+/// `montgomery_forms`, drawing randomness for root finding from `rng`.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+pub fn montgomery_forms_with_rng<R: RngCore + CryptoRng + ?Sized>(
+    a: &BigInt,
+    b: &BigInt,
+    p: &BigInt,
+    options: &TransformOptions,
+    rng: &mut R,
+) -> Result<Vec<MontgomeryForm>, TransformError> {
+    // Validates the curve and fails with NoMontgomeryForm when no root qualifies
+    let first = MontgomeryMap::new_with_rng(a, b, p, options, rng)?;
+    let non_residue = Fp2::find_non_residue(p, &options.limits)?;
+
+    let mut forms = Vec::new();
+    for z0 in cubic_roots(&first.a, &first.b, p, &options.limits, rng)? {
+        let map = match MontgomeryMap::from_root(&first.a, &first.b, p, &z0, options) {
+            Ok(map) => map,
+            Err(TransformError::NoMontgomeryForm(_)) => continue,
+            Err(error) => return Err(error),
+        };
+        for s in [map.s.clone(), (-&map.s).mod_floor(p)] {
+            let s_inv = mod_inverse(&s, p).expect("s is non-zero");
+            let a_montgomery = (BigInt::from(3) * &z0 * &s_inv).mod_floor(p);
+            let (isomorphic, twist) = if legendre_symbol(&s_inv, p) == 1 {
+                (BigInt::one(), non_residue.clone())
+            } else {
+                (non_residue.clone(), BigInt::one())
+            };
+            let choices = [(isomorphic, Relation::Isomorphic), (twist, Relation::QuadraticTwist)];
+            for (b_montgomery, relation) in choices {
+                let seen = |form: &MontgomeryForm| form.a == a_montgomery && form.b == b_montgomery;
+                if !forms.iter().any(seen) {
+                    forms.push(MontgomeryForm {
+                        a: a_montgomery.clone(),
+                        b: b_montgomery,
+                        z0: z0.clone(),
+                        s: s.clone(),
+                        relation,
+                    });
+                }
+            }
+        }
+    }
+    forms.sort_by_key(|form| form.relation == Relation::QuadraticTwist);
+    Ok(forms)
+}
@@ -0,0 +1,404 @@
+use crate::error::TransformError;
+use crate::field::modpow;
+use crate::limits::{SearchBudget, SearchLimits};
+use crate::numtheory::{mod_inverse, mod_sqrt};
+use crate::ops;
+use num_bigint::{BigInt, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// An element c0 + c1·i of the quadratic extension F_p² = F_p[i] / (i² − non_residue).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Fp2 {
+    /// Coefficient of 1, in [0, p).
+    pub c0: BigInt,
+    /// Coefficient of i, in [0, p).
+    pub c1: BigInt,
+    /// Characteristic of the base field.
+    pub p: BigInt,
+    /// The quadratic non-residue that i squares to.
+    pub non_residue: BigInt,
+}
+
+/// Returns true if `value` is a quadratic non-residue modulo the odd prime `p` (Euler's criterion).
+fn is_non_residue(value: &BigInt, p: &BigInt) -> bool {
+    modpow(value, &((p - 1u32) / 2u32), p) == p - 1u32
+}
+
+/// Square root in the base field, flattening the search-limit error (which only occurs for composite p).
+fn base_sqrt(value: &BigInt, p: &BigInt) -> Option<BigInt> {
+    mod_sqrt(value, p, &SearchLimits::default()).ok().flatten()
+}
+
+impl Fp2 {
+    /// Creates c0 + c1·i, reducing the coefficients and checking that `non_residue` really is one modulo `p`.
+    pub fn new(c0: &BigInt, c1: &BigInt, p: &BigInt, non_residue: &BigInt) -> Result<Fp2, TransformError> {
+        if p <= &BigInt::from(2) || p.is_even() {
+            return Err(TransformError::InvalidParameters(format!(
+                "F_p^2 needs an odd prime p, got {}",
+                p
+            )));
+        }
+        let non_residue = non_residue.mod_floor(p);
+        if !is_non_residue(&non_residue, p) {
+            return Err(TransformError::InvalidParameters(format!(
+                "{} is not a quadratic non-residue modulo {}",
+                non_residue, p
+            )));
+        }
+        Ok(Fp2 {
+            c0: c0.mod_floor(p),
+            c1: c1.mod_floor(p),
+            p: p.clone(),
+            non_residue,
+        })
+    }
+
+    /// Returns the smallest quadratic non-residue modulo the odd prime `p`, the default choice for i².
+    pub fn find_non_residue(p: &BigInt, limits: &SearchLimits) -> Result<BigInt, TransformError> {
+        let mut budget = SearchBudget::new(limits);
+        let mut candidate = BigInt::from(2);
+        while !is_non_residue(&candidate, p) {
+            budget.tick("searching for a quadratic non-residue")?;
+            candidate += 1u32;
+        }
+        Ok(candidate)
+    }
+
+    /// Builds an element of this element's field from raw coefficients.
+    fn with(&self, c0: BigInt, c1: BigInt) -> Fp2 {
+        Fp2 {
+            c0: c0.mod_floor(&self.p),
+            c1: c1.mod_floor(&self.p),
+            p: self.p.clone(),
+            non_residue: self.non_residue.clone(),
+        }
+    }
+
+    /// Embeds a base-field value into the same extension as `self`.
+    pub fn from_base(&self, value: &BigInt) -> Fp2 {
+        self.with(value.clone(), BigInt::zero())
+    }
+
+    /// The additive identity of this element's field.
+    pub fn zero(&self) -> Fp2 {
+        self.with(BigInt::zero(), BigInt::zero())
+    }
+
+    /// The multiplicative identity of this element's field.
+    pub fn one(&self) -> Fp2 {
+        self.with(BigInt::one(), BigInt::zero())
+    }
+
+    /// Samples a uniformly random element of this element's field.
+    pub fn random<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Fp2 {
+        let c0 = rng.gen_bigint_range(&BigInt::zero(), &self.p);
+        let c1 = rng.gen_bigint_range(&BigInt::zero(), &self.p);
+        self.with(c0, c1)
+    }
+
+    /// Returns true if both elements live in the same extension field.
+    pub fn same_field(&self, other: &Fp2) -> bool {
+        self.p == other.p && self.non_residue == other.non_residue
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.c0.is_zero() && self.c1.is_zero()
+    }
+
+    /// Returns true if the element lies in the base field F_p.
+    pub fn is_base(&self) -> bool {
+        self.c1.is_zero()
+    }
+
+    /// Returns c0 − c1·i, which is also the Frobenius image of the element.
+    pub fn conjugate(&self) -> Fp2 {
+        self.with(self.c0.clone(), -&self.c1)
+    }
+
+    /// Returns the norm c0² − non_residue·c1², an element of F_p.
+    pub fn norm(&self) -> BigInt {
+        (&self.c0 * &self.c0 - &self.non_residue * &self.c1 * &self.c1).mod_floor(&self.p)
+    }
+
+    pub fn square(&self) -> Fp2 {
+        self * self
+    }
+
+    /// Returns the multiplicative inverse via the conjugate over the norm, or `None` for zero.
+    pub fn inverse(&self) -> Option<Fp2> {
+        let norm_inv = mod_inverse(&self.norm(), &self.p)?;
+        Some(self.with(&self.c0 * &norm_inv, -&self.c1 * &norm_inv))
+    }
+
+    /// Raises the element to a non-negative power by square-and-multiply.
+    pub fn pow(&self, exponent: &BigInt) -> Fp2 {
+        let mut result = self.one();
+        for bit in (0..exponent.bits()).rev() {
+            result = result.square();
+            if exponent.bit(bit) {
+                result = &result * self;
+            }
+        }
+        result
+    }
+
+    /// Returns true if the element is a square in F_p², which holds exactly when its norm is a square in F_p.
+    pub fn is_square(&self) -> bool {
+        !is_non_residue(&self.norm(), &self.p)
+    }
+
+    /// Computes a square root using the norm-based "complex" method, or `None` if the element is not a square.
+    pub fn sqrt(&self) -> Option<Fp2> {
+        if self.is_zero() {
+            return Some(self.zero());
+        }
+        if self.is_base() {
+            // Either c0 is a square in F_p, or c0 / non_residue is and the root is a multiple of i
+            if let Some(root) = base_sqrt(&self.c0, &self.p) {
+                return Some(self.with(root, BigInt::zero()));
+            }
+            let ratio = &self.c0 * mod_inverse(&self.non_residue, &self.p)?;
+            let root = base_sqrt(&ratio.mod_floor(&self.p), &self.p)?;
+            return Some(self.with(BigInt::zero(), root));
+        }
+        let lambda = base_sqrt(&self.norm(), &self.p)?;
+        let two_inv = mod_inverse(&BigInt::from(2), &self.p)?;
+        let mut delta = ((&self.c0 + &lambda) * &two_inv).mod_floor(&self.p);
+        if is_non_residue(&delta, &self.p) {
+            delta = ((&self.c0 - &lambda) * &two_inv).mod_floor(&self.p);
+        }
+        let x0 = base_sqrt(&delta, &self.p)?;
+        let x1 = &self.c1 * mod_inverse(&(BigInt::from(2) * &x0), &self.p)?;
+        let root = self.with(x0, x1);
+        if &root.square() == self {
+            Some(root)
+        } else {
+            None
+        }
+    }
+
+    fn assert_same_field(&self, other: &Fp2) {
+        assert!(self.same_field(other), "F_p^2 elements from different fields");
+    }
+}
+
+impl fmt::Display for Fp2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} + {}*i", self.c0, self.c1)
+    }
+}
+
+impl Add for &Fp2 {
+    type Output = Fp2;
+
+    fn add(self, other: &Fp2) -> Fp2 {
+        self.assert_same_field(other);
+        self.with(&self.c0 + &other.c0, &self.c1 + &other.c1)
+    }
+}
+
+impl Sub for &Fp2 {
+    type Output = Fp2;
+
+    fn sub(self, other: &Fp2) -> Fp2 {
+        self.assert_same_field(other);
+        self.with(&self.c0 - &other.c0, &self.c1 - &other.c1)
+    }
+}
+
+impl Mul for &Fp2 {
+    type Output = Fp2;
+
+    fn mul(self, other: &Fp2) -> Fp2 {
+        self.assert_same_field(other);
+        // (a + bi)(c + di) = (ac + n·bd) + (ad + bc)i
+        ops::record(5, 0);
+        let c0 = &self.c0 * &other.c0 + &self.non_residue * &self.c1 * &other.c1;
+        let c1 = &self.c0 * &other.c1 + &self.c1 * &other.c0;
+        self.with(c0, c1)
+    }
+}
+
+impl Neg for &Fp2 {
+    type Output = Fp2;
+
+    fn neg(self) -> Fp2 {
+        self.with(-&self.c0, -&self.c1)
+    }
+}
+
+impl Add for Fp2 {
+    type Output = Fp2;
+
+    fn add(self, other: Fp2) -> Fp2 {
+        &self + &other
+    }
+}
+
+impl Sub for Fp2 {
+    type Output = Fp2;
+
+    fn sub(self, other: Fp2) -> Fp2 {
+        &self - &other
+    }
+}
+
+impl Mul for Fp2 {
+    type Output = Fp2;
+
+    fn mul(self, other: Fp2) -> Fp2 {
+        &self * &other
+    }
+}
+
+impl Neg for Fp2 {
+    type Output = Fp2;
+
+    fn neg(self) -> Fp2 {
+        -&self
+    }
+}
+
+/// A polynomial over F_p², lowest degree first with no trailing zeros, with just the arithmetic that
+/// root finding needs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Fp2Polynomial {
+    coefficients: Vec<Fp2>,
+    /// The zero of the field, which carries p and the non-residue.
+    zero: Fp2,
+}
+
+impl Fp2Polynomial {
+    /// The polynomial with `coefficients`, lowest degree first, over the field of `zero`.
+    pub(crate) fn new(mut coefficients: Vec<Fp2>, zero: &Fp2) -> Fp2Polynomial {
+        while coefficients.last().is_some_and(Fp2::is_zero) {
+            coefficients.pop();
+        }
+        Fp2Polynomial { coefficients, zero: zero.zero() }
+    }
+
+    fn with(&self, coefficients: Vec<Fp2>) -> Fp2Polynomial {
+        Fp2Polynomial::new(coefficients, &self.zero)
+    }
+
+    fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    fn monic(&self) -> Fp2Polynomial {
+        match self.coefficients.last() {
+            Some(lead) => {
+                let lead_inv = lead.inverse().expect("the leading coefficient is non-zero");
+                self.with(self.coefficients.iter().map(|c| c * &lead_inv).collect())
+            }
+            None => self.clone(),
+        }
+    }
+
+    fn sub(&self, other: &Fp2Polynomial) -> Fp2Polynomial {
+        let len = self.coefficients.len().max(other.coefficients.len());
+        let at = |coefficients: &[Fp2], i: usize| coefficients.get(i).unwrap_or(&self.zero).clone();
+        self.with((0..len).map(|i| &at(&self.coefficients, i) - &at(&other.coefficients, i)).collect())
+    }
+
+    fn mul(&self, other: &Fp2Polynomial) -> Fp2Polynomial {
+        if self.coefficients.is_empty() || other.coefficients.is_empty() {
+            return self.with(Vec::new());
+        }
+        let mut product = vec![self.zero.clone(); self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, c) in self.coefficients.iter().enumerate() {
+            for (j, d) in other.coefficients.iter().enumerate() {
+                product[i + j] = &product[i + j] + &(c * d);
+            }
+        }
+        self.with(product)
+    }
+
+    /// The quotient and remainder of the division by the non-zero `divisor`.
+    fn div_rem(&self, divisor: &Fp2Polynomial) -> (Fp2Polynomial, Fp2Polynomial) {
+        let lead_inv = divisor.coefficients.last().and_then(Fp2::inverse).expect("the divisor is non-zero");
+        let divisor = &divisor.coefficients;
+        let mut remainder = self.coefficients.clone();
+        let mut quotient = vec![self.zero.clone(); (remainder.len() + 1).saturating_sub(divisor.len())];
+        while remainder.len() >= divisor.len() {
+            let shift = remainder.len() - divisor.len();
+            let factor = remainder.last().expect("non-empty") * &lead_inv;
+            for (i, c) in divisor.iter().enumerate() {
+                remainder[shift + i] = &remainder[shift + i] - &(&factor * c);
+            }
+            quotient[shift] = factor;
+            while remainder.last().is_some_and(Fp2::is_zero) {
+                remainder.pop();
+            }
+        }
+        (self.with(quotient), self.with(remainder))
+    }
+
+    /// The monic greatest common divisor.
+    fn gcd(&self, other: &Fp2Polynomial) -> Fp2Polynomial {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while b.degree().is_some() {
+            let (_, remainder) = a.div_rem(&b);
+            (a, b) = (b, remainder);
+        }
+        a.monic()
+    }
+
+    /// `self^exponent` modulo the non-zero `modulus`, by square-and-multiply.
+    fn pow_mod(&self, exponent: &BigInt, modulus: &Fp2Polynomial) -> Fp2Polynomial {
+        let (_, base) = self.div_rem(modulus);
+        let (_, mut result) = self.with(vec![self.zero.one()]).div_rem(modulus);
+        for bit in (0..exponent.bits()).rev() {
+            result = result.mul(&result).div_rem(modulus).1;
+            if exponent.bit(bit) {
+                result = result.mul(&base).div_rem(modulus).1;
+            }
+        }
+        result
+    }
+
+    /// Every root in F_p² of a non-zero polynomial, each once, like `Polynomial::roots` over F_p with
+    /// q = p² in place of p: gcd(z^q − z, f) collects the linear factors, and splitting with
+    /// gcd((z + δ)^((q−1)/2) − 1, g) for random δ in F_p² separates them, each δ counting against `limits`.
+    pub(crate) fn roots<R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        limits: &SearchLimits,
+        rng: &mut R,
+    ) -> Result<Vec<Fp2>, TransformError> {
+        if self.coefficients.is_empty() {
+            return Err(TransformError::InvalidParameters(
+                "every element is a root of the zero polynomial".to_string(),
+            ));
+        }
+        let q = &self.zero.p * &self.zero.p;
+        let half = (&q - 1u32) / 2u32;
+        let z = self.with(vec![self.zero.zero(), self.zero.one()]);
+        let one = self.with(vec![self.zero.one()]);
+        let mut budget = SearchBudget::new(limits);
+        let mut roots = Vec::new();
+        let mut pending = vec![self.gcd(&z.pow_mod(&q, self).sub(&z))];
+        while let Some(factor) = pending.pop() {
+            match factor.degree() {
+                None | Some(0) => {}
+                Some(1) => roots.push(-&factor.coefficients[0]),
+                Some(degree) => {
+                    budget.tick("splitting a polynomial over F_p^2 into linear factors")?;
+                    let delta = self.zero.random(rng);
+                    let shifted = self.with(vec![delta, self.zero.one()]).pow_mod(&half, &factor);
+                    let part = factor.gcd(&shifted.sub(&one));
+                    if part.degree().is_some_and(|part_degree| (1..degree).contains(&part_degree)) {
+                        let (cofactor, _) = factor.div_rem(&part);
+                        pending.extend([part, cofactor]);
+                    } else {
+                        pending.push(factor);
+                    }
+                }
+            }
+        }
+        Ok(roots)
+    }
+}
@@ -0,0 +1,109 @@
+use crate::curve::Point;
+use crate::error::TransformError;
+use crate::field::Field;
+use crate::limits::{SearchBudget, SearchLimits};
+use crate::log::debug;
+use crate::numtheory::{is_probable_prime, MILLER_RABIN_ROUNDS};
+use crate::order::count_points;
+use crate::scalar_mul::scalar_mul;
+use crate::spec::CurveSpec;
+use crate::weierstrass::WeierstrassCurve;
+use num_bigint::{BigInt, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
+
+/// Samples a curve y^2 = x^3 + ax + b over F_p that is guaranteed to have a Montgomery form, and a point
+/// on it.
+///
+/// Such a curve is non-singular, has a rational 2-torsion point (z0, 0) and has 3·z0^2 + a a square.
+/// Rather than testing random (a, b), which needs a root of the cubic, this samples z0 and s ≠ 0 and
+/// solves a = s^2 − 3·z0^2 and b = −z0^3 − a·z0, retrying singular curves.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+pub fn generate_curve<R: RngCore + CryptoRng + ?Sized>(
+    p: &BigInt,
+    limits: &SearchLimits,
+    rng: &mut R,
+) -> Result<CurveSpec, TransformError> {
+    if p <= &BigInt::from(3) || p.is_even() {
+        return Err(TransformError::InvalidParameters(format!(
+            "curve generation needs an odd prime p > 3, got {}",
+            p
+        )));
+    }
+    let field = Field::new(p);
+    let mut budget = SearchBudget::new(limits);
+    let (a, b) = loop {
+        budget.tick("sampling a curve with a Montgomery form")?;
+        let z0 = rng.gen_bigint_range(&BigInt::zero(), p);
+        let s = rng.gen_bigint_range(&BigInt::one(), p);
+        let a = field.reduce(&(&s * &s - BigInt::from(3) * &z0 * &z0));
+        let b = field.reduce(&(-(&z0 * &z0 + &a) * &z0));
+        let a_cubed = field.reduce(&(field.reduce(&(&a * &a)) * &a));
+        if !field.reduce(&(BigInt::from(4) * a_cubed + BigInt::from(27) * &b * &b)).is_zero() {
+            debug!(%z0, %s, "sampled a curve with a Montgomery form");
+            break (a, b);
+        }
+    };
+
+    let (x, y) = random_point(&field, &a, &b, limits, rng)?;
+    Ok(CurveSpec {
+        a,
+        b,
+        p: p.clone(),
+        x,
+        y,
+    })
+}
+
+/// Samples a uniformly random x with a point (x, y) on y^2 = x^3 + ax + b over `field`, and one of the
+/// two square roots y at random.
+pub fn random_point<R: RngCore + CryptoRng + ?Sized>(
+    field: &Field,
+    a: &BigInt,
+    b: &BigInt,
+    limits: &SearchLimits,
+    rng: &mut R,
+) -> Result<(BigInt, BigInt), TransformError> {
+    // Half of all x give a square right-hand side
+    let p = field.modulus();
+    let mut budget = SearchBudget::new(limits);
+    loop {
+        budget.tick("searching for a point on the curve")?;
+        let x = rng.gen_bigint_range(&BigInt::zero(), p);
+        let rhs = field.reduce(&(field.reduce(&(&x * &x + a)) * &x + b));
+        if let Some(y) = field.sqrt(&rhs, limits)? {
+            let y = if rng.next_u32() & 1 == 1 { field.reduce(&-y) } else { y };
+            return Ok((x, y));
+        }
+    }
+}
+
+/// Samples curves with `generate_curve` until the group order is 4·q for a prime q, like Curve25519.
+///
+/// Returns the curve, with its point replaced by one of order q (4 times a random point), and the
+/// group order. Point counting limits this to p up to `MAX_COUNT_MODULUS`.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+pub fn generate_curve_with_prime_subgroup<R: RngCore + CryptoRng + ?Sized>(
+    p: &BigInt,
+    limits: &SearchLimits,
+    rng: &mut R,
+) -> Result<(CurveSpec, BigInt), TransformError> {
+    let mut budget = SearchBudget::new(limits);
+    loop {
+        budget.tick("searching for a curve of order 4 times a prime")?;
+        let spec = generate_curve(p, limits, rng)?;
+        let order = count_points(&spec.a, &spec.b, p)?;
+        let (subgroup_order, cofactor_part) = order.div_rem(&BigInt::from(4));
+        if !cofactor_part.is_zero() || !is_probable_prime(&subgroup_order, MILLER_RABIN_ROUNDS) {
+            continue;
+        }
+        debug!(%order, "found a curve of order 4 times a prime");
+        let curve = WeierstrassCurve::new(&spec.a, &spec.b, p)?;
+        let point = curve.point(&spec.x, &spec.y)?;
+        // 4·P has order q unless P lies in the 4-torsion; then pick a fresh curve
+        if let Point::Affine(x, y) = scalar_mul(&curve, &point, &BigInt::from(4)) {
+            return Ok((CurveSpec { x, y, ..spec }, order));
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::error::TransformError;
+use crate::limits::{SearchBudget, SearchLimits};
+use crate::log::{debug, info};
+use ff::PrimeField;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+
+/// Searches for a root z0 of z^3 + az + b in `F` by random sampling, giving up once `limits` are exhausted.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+pub fn find_cubic_root_generic<F: PrimeField, R: RngCore + CryptoRng + ?Sized>(
+    a: &F,
+    b: &F,
+    limits: &SearchLimits,
+    rng: &mut R,
+) -> Result<F, TransformError> {
+    let mut budget = SearchBudget::new(limits);
+    loop {
+        budget.tick("searching for a root of z^3 + az + b")?;
+        let candidate = F::random(&mut *rng);
+        if bool::from((candidate.cube() + *a * candidate + b).is_zero()) {
+            info!(attempts = budget.attempts, z0 = ?candidate, "found root of the cubic");
+            return Ok(candidate);
+        }
+    }
+}
+
+/// Transformation from a Weierstrass to a Montgomery curve over any `ff::PrimeField`.
+///
+/// Uses the same formulas as `transform_to_montgomery`, but with the caller's field arithmetic instead of `BigInt`.
+pub fn transform_to_montgomery_generic<F: PrimeField>(
+    x: &F,
+    y: &F,
+    a: &F,
+    b: &F,
+    limits: &SearchLimits,
+) -> Result<(F, F, F, F), TransformError> {
+    transform_to_montgomery_generic_with_rng(x, y, a, b, limits, &mut OsRng)
+}
+
+/// `transform_to_montgomery_generic`, drawing randomness from `rng`.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+pub fn transform_to_montgomery_generic_with_rng<F: PrimeField, R: RngCore + CryptoRng + ?Sized>(
+    x: &F,
+    y: &F,
+    a: &F,
+    b: &F,
+    limits: &SearchLimits,
+    rng: &mut R,
+) -> Result<(F, F, F, F), TransformError> {
+    let three = F::from(3);
+    if bool::from(F::from(2).is_zero() | three.is_zero()) {
+        return Err(TransformError::UnsupportedCharacteristic(
+            num_bigint::BigInt::from(if bool::from(three.is_zero()) { 3 } else { 2 }),
+        ));
+    }
+    // The curve is singular when the discriminant 4a^3 + 27b^2 vanishes
+    if bool::from((F::from(4) * a.cube() + F::from(27) * b.square()).is_zero()) {
+        return Err(TransformError::InvalidParameters(
+            "the curve is singular (4a^3 + 27b^2 = 0)".to_string(),
+        ));
+    }
+    if !bool::from((y.square() - x.cube() - *a * x - b).is_zero()) {
+        return Err(TransformError::PointNotOnCurve);
+    }
+
+    //Find a root z0 of the polynomial z^3 + az + b in the field F
+    let root = find_cubic_root_generic(a, b, limits, rng)?;
+
+    // The other roots solve z^2 + z0·z + (z0^2 + a) = 0, whose discriminant is −3·z0^2 − 4a
+    let mut roots = vec![root];
+    if let Some(sqrt) = Option::<F>::from((-(three * root.square()) - F::from(4) * a).sqrt()) {
+        let two_inv = F::TWO_INV;
+        roots.extend([(sqrt - root) * two_inv, (-sqrt - root) * two_inv]);
+    }
+
+    // Compute s = (sqrt(3 * z0^2 + a))^{-1} in F, trying each root until 3 * z0^2 + a is a square
+    let (z0, s) = roots
+        .into_iter()
+        .find_map(|z0| {
+            let s_squared = three * z0.square() + a;
+            debug!(?z0, ?s_squared);
+            Option::<F>::from(s_squared.sqrt()).map(|s| (z0, s))
+        })
+        .ok_or_else(|| {
+            let message = "3*z0^2 + a is not a square in the field for any root z0";
+            TransformError::NoMontgomeryForm(message.to_string())
+        })?;
+    debug!(?s);
+
+    let s_inv: F = Option::from(s.invert())
+        .ok_or_else(|| TransformError::NoMontgomeryForm(format!("s = {:?} is not invertible in the field", s)))?;
+    debug!(?s_inv);
+
+    let a_montgomery = three * z0 * s_inv;
+    let x_montgomery = s_inv * (*x - z0);
+    let y_montgomery = s_inv * y;
+    Ok((x_montgomery, y_montgomery, a_montgomery, s_inv))
+}
@@ -0,0 +1,226 @@
+use crate::curve::{CurveGroup, Point};
+use crate::error::TransformError;
+use crate::limits::SearchLimits;
+use crate::numtheory::{mod_inverse, mod_nth_root};
+use crate::ops;
+use crate::scalar_mul::scalar_mul;
+use crate::weierstrass::WeierstrassCurve;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+/// The Hessian curve x^3 + y^3 + 1 = 3d·xy over F_p, for p ≡ 2 (mod 3).
+///
+/// The neutral element is the point at infinity (1 : −1 : 0), and −(x, y) = (y, x). Over these fields
+/// it is the only point at infinity, every curve with a point of order 3 has a Hessian form, and the
+/// addition law is complete apart from doubling.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HessianCurve {
+    pub d: BigInt,
+    pub p: BigInt,
+}
+
+/// The birational map between y^2 = x^3 + ax + b and a Hessian curve, built around a point (x0, y0) of
+/// order 3 that goes to (−1, 0).
+///
+/// Moving (x0, y0) to the origin and its inflectional tangent, of slope λ, to the x-axis gives
+/// Y^2 + 2λ·XY + 2y0·Y = X^3 with X = x − x0 and Y = y − y0 − λ·X, and then
+/// X = μ(x + y + 1) / (x + y + d), Y = 2y0(x + d·y + 1) / (x + y + d) in the Hessian coordinates.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HessianMap {
+    pub weierstrass: WeierstrassCurve,
+    pub hessian: HessianCurve,
+    pub x0: BigInt,
+    pub y0: BigInt,
+    pub lambda: BigInt,
+    pub mu: BigInt,
+}
+
+fn invalid(message: &str) -> TransformError {
+    TransformError::InvalidParameters(message.to_string())
+}
+
+fn check_modulus(p: &BigInt) -> Result<(), TransformError> {
+    if p <= &BigInt::from(3) {
+        return Err(TransformError::UnsupportedCharacteristic(p.clone()));
+    }
+    if p % 3u32 != BigInt::from(2) {
+        return Err(invalid("the Hessian model is supported for p = 2 (mod 3) only"));
+    }
+    Ok(())
+}
+
+impl HessianCurve {
+    /// Creates the curve, rejecting p ≢ 2 (mod 3) and singular curves (d^3 = 1, that is d = 1).
+    pub fn new(d: &BigInt, p: &BigInt) -> Result<HessianCurve, TransformError> {
+        check_modulus(p)?;
+        let curve = HessianCurve { d: d.mod_floor(p), p: p.clone() };
+        if curve.d.is_one() {
+            return Err(invalid("the Hessian curve is singular (d^3 = 1)"));
+        }
+        Ok(curve)
+    }
+
+    /// Evaluates x^3 + y^3 + 1 − 3d·xy, which vanishes exactly on the curve.
+    fn equation(&self, x: &BigInt, y: &BigInt) -> BigInt {
+        (x.pow(3) + y.pow(3) + 1u32 - BigInt::from(3) * &self.d * x * y).mod_floor(&self.p)
+    }
+
+    /// Creates the affine point (x, y), checking that it lies on the curve.
+    pub fn point(&self, x: &BigInt, y: &BigInt) -> Result<Point, TransformError> {
+        let point = Point::Affine(x.mod_floor(&self.p), y.mod_floor(&self.p));
+        if self.contains(&point) {
+            Ok(point)
+        } else {
+            Err(TransformError::PointNotOnCurve)
+        }
+    }
+
+    /// Returns the map to the Weierstrass curve y^2 = x^3 + ax + b it is birationally equivalent to.
+    pub fn to_weierstrass(&self) -> Result<HessianMap, TransformError> {
+        let p = &self.p;
+        let d = &self.d;
+        let third = mod_inverse(&BigInt::from(3), p).expect("p is not 3");
+        // The long form Y^2 + a1·XY + a3·Y = X^3 with a1 = −(d + 2) and a3 = −(d^2 + d + 1) / 3, which
+        // `HessianMap::new` takes back to this curve
+        let a1 = (-(d + 2u32)).mod_floor(p);
+        let a3 = (-(d * d + d + 1u32) * &third).mod_floor(p);
+        let half = mod_inverse(&BigInt::from(2), p).expect("p is odd");
+        let lambda = (&a1 * &half).mod_floor(p);
+        let y0 = (&a3 * &half).mod_floor(p);
+        // Completing the square: η^2 = X^3 + a2·X^2 + a4·X + a6; then X = x − x0 with x0 = a2 / 3
+        let a2 = (&lambda * &lambda).mod_floor(p);
+        let a4 = (BigInt::from(2) * &lambda * &y0).mod_floor(p);
+        let a6 = (&y0 * &y0).mod_floor(p);
+        let x0 = (&a2 * &third).mod_floor(p);
+        let a = (BigInt::from(3) * &x0 * &x0 - BigInt::from(2) * &a2 * &x0 + &a4).mod_floor(p);
+        let b = (-x0.pow(3) + &a2 * &x0 * &x0 - &a4 * &x0 + a6).mod_floor(p);
+        Ok(HessianMap {
+            weierstrass: WeierstrassCurve::new(&a, &b, p)?,
+            hessian: self.clone(),
+            x0,
+            y0,
+            lambda,
+            mu: a3,
+        })
+    }
+}
+
+impl HessianMap {
+    /// Computes the map for `curve` from `torsion`, a point of order 3 on it.
+    pub fn new(curve: &WeierstrassCurve, torsion: &Point) -> Result<HessianMap, TransformError> {
+        let p = &curve.p;
+        check_modulus(p)?;
+        let (x0, y0) = match torsion {
+            Point::Affine(x0, y0) if curve.contains(torsion) => (x0.mod_floor(p), y0.mod_floor(p)),
+            Point::Affine(..) => return Err(TransformError::PointNotOnCurve),
+            Point::Infinity => return Err(invalid("the torsion point must have order 3, not 1")),
+        };
+        if !scalar_mul(curve, torsion, &BigInt::from(3)).is_infinity() {
+            return Err(invalid("the torsion point does not have order 3"));
+        }
+        let y0_inv = mod_inverse(&(BigInt::from(2) * &y0), p).expect("a point of order 3 has y != 0");
+        let lambda = ((BigInt::from(3) * &x0 * &x0 + &curve.a) * y0_inv).mod_floor(p);
+        let a1 = (BigInt::from(2) * &lambda).mod_floor(p);
+        let a3 = (BigInt::from(2) * &y0).mod_floor(p);
+        // With c^3 = 27·a3 − a1^3, non-zero as the curve is non-singular: d = (a1 − 2c) / (a1 + c) and
+        // μ = −3·a3 / (a1 + c)
+        let radicand = (BigInt::from(27) * &a3 - a1.pow(3)).mod_floor(p);
+        let c = mod_nth_root(&radicand, 3, p, &SearchLimits::default())?.expect("every element is a cube");
+        let denominator = mod_inverse(&(&a1 + &c).mod_floor(p), p).expect("a1 + c = 0 only for a3 = 0");
+        let d = ((&a1 - BigInt::from(2) * &c) * &denominator).mod_floor(p);
+        let mu = (BigInt::from(-3) * &a3 * denominator).mod_floor(p);
+        Ok(HessianMap {
+            weierstrass: curve.clone(),
+            hessian: HessianCurve::new(&d, p)?,
+            x0,
+            y0,
+            lambda,
+            mu,
+        })
+    }
+
+    /// Maps a point of the Weierstrass curve to the Hessian curve; the point of order 3 goes to (−1, 0).
+    pub fn to_hessian(&self, point: &Point) -> Point {
+        let p = &self.hessian.p;
+        let d = &self.hessian.d;
+        let (x, y) = match point {
+            Point::Infinity => return Point::Infinity,
+            Point::Affine(x, y) => (x, y),
+        };
+        let big_x = (x - &self.x0).mod_floor(p);
+        let big_y = (y - &self.y0 - &self.lambda * &big_x).mod_floor(p);
+        // (X : Y : 1) = (μ·S : 2y0·T : U) in terms of the Hessian (x' : y' : z'), where S = x' + y' + z',
+        // T = x' + d·y' + z' and U = x' + y' + d·z'
+        let s = big_x * mod_inverse(&self.mu, p).expect("μ is non-zero");
+        let t = big_y * mod_inverse(&(BigInt::from(2) * &self.y0), p).expect("y0 is non-zero");
+        let d_minus_one_inv = mod_inverse(&(d - 1u32).mod_floor(p), p).expect("d is not 1");
+        let y_h = (&t - &s) * &d_minus_one_inv;
+        let z_h = (BigInt::one() - &s) * &d_minus_one_inv;
+        let x_h = &s - &y_h - &z_h;
+        let z_inv = mod_inverse(&z_h.mod_floor(p), p).expect("only the neutral element is at infinity");
+        Point::Affine((x_h * &z_inv).mod_floor(p), (y_h * z_inv).mod_floor(p))
+    }
+
+    /// Maps a point of the Hessian curve back to the Weierstrass curve.
+    pub fn to_weierstrass(&self, point: &Point) -> Point {
+        let p = &self.hessian.p;
+        let d = &self.hessian.d;
+        let (x, y) = match point {
+            Point::Infinity => return Point::Infinity,
+            Point::Affine(x, y) => (x, y),
+        };
+        let u_inv =
+            mod_inverse(&(x + y + d).mod_floor(p), p).expect("only the neutral element has x + y + d = 0");
+        let big_x = (&self.mu * (x + y + 1u32) * &u_inv).mod_floor(p);
+        let big_y = (BigInt::from(2) * &self.y0 * (x + d * y + 1u32) * u_inv).mod_floor(p);
+        let y = (&big_y + &self.lambda * &big_x + &self.y0).mod_floor(p);
+        Point::Affine((big_x + &self.x0).mod_floor(p), y)
+    }
+}
+
+impl CurveGroup for HessianCurve {
+    fn modulus(&self) -> &BigInt {
+        &self.p
+    }
+
+    fn contains(&self, point: &Point) -> bool {
+        match point {
+            Point::Infinity => true,
+            Point::Affine(x, y) => self.equation(x, y).is_zero(),
+        }
+    }
+
+    fn negate(&self, point: &Point) -> Point {
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(x, y) => Point::Affine(y.clone(), x.clone()),
+        }
+    }
+
+    /// Sylvester's addition law in projective coordinates, with the doubling law of Chudnovsky and
+    /// Chudnovsky for equal points.
+    fn add(&self, first: &Point, second: &Point) -> Point {
+        let p = &self.p;
+        let (x1, y1, x2, y2) = match (first, second) {
+            (Point::Infinity, _) => return second.clone(),
+            (_, Point::Infinity) => return first.clone(),
+            (Point::Affine(x1, y1), Point::Affine(x2, y2)) => (x1, y1, x2, y2),
+        };
+        let (x3, y3, z3) = if x1 == x2 && y1 == y2 {
+            ops::record(4, 2);
+            let (x_cubed, y_cubed) = (x1.pow(3), y1.pow(3));
+            (y1 * (BigInt::one() - &x_cubed), x1 * (&y_cubed - 1u32), x_cubed - y_cubed)
+        } else {
+            ops::record(6, 4);
+            (y1 * y1 * x2 - y2 * y2 * x1, x1 * x1 * y2 - x2 * x2 * y1, x2 * y2 - x1 * y1)
+        };
+        let z3 = z3.mod_floor(p);
+        if z3.is_zero() {
+            return Point::Infinity;
+        }
+        ops::record(2, 0);
+        let z_inv = mod_inverse(&z3, p).expect("z3 is non-zero");
+        Point::Affine((x3 * &z_inv).mod_floor(p), (y3 * z_inv).mod_floor(p))
+    }
+}
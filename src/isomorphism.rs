@@ -0,0 +1,162 @@
+//! Point maps between curve models behind one trait, so that a chain such as Wei25519 → Curve25519 →
+//! Ed25519 can be built once, stored, inverted and applied as a single object.
+
+use crate::curve::Point;
+use crate::edwards::{EdwardsScaling, TwistedEdwardsCurve};
+use crate::map::PointMap;
+use crate::montgomery::{MontgomeryCurve, Rescaling};
+use crate::numtheory::mod_inverse;
+use crate::weierstrass::Scaling;
+
+/// A map of points from one curve onto another that preserves the group law, with its inverse.
+///
+/// The maps between Montgomery and twisted Edwards curves are birational: the few points of order 2 or 4
+/// without an affine image go to the neutral element (see `MontgomeryCurve::point_to_edwards`), so the
+/// group law is kept up to the cofactor. Every map here is thus defined everywhere; `apply` returns `None`
+/// only for maps of other kinds that cannot place a point.
+pub trait Isomorphism {
+    /// The type of the map in the other direction.
+    type Inverse: Isomorphism;
+
+    /// Maps `point`, or returns `None` if the map cannot place it.
+    fn apply(&self, point: &Point) -> Option<Point>;
+
+    /// Maps every point of `points`, in order; the same as `apply` on each, but maps that divide share
+    /// one inversion across the batch.
+    fn apply_all(&self, points: &[Point]) -> Vec<Option<Point>> {
+        points.iter().map(|point| self.apply(point)).collect()
+    }
+
+    /// The map in the other direction.
+    fn invert(&self) -> Self::Inverse;
+
+    /// `self` followed by `next`, as one map.
+    fn compose<T: Isomorphism>(self, next: T) -> Chain<Self, T>
+    where
+        Self: Sized,
+    {
+        Chain { first: self, second: next }
+    }
+}
+
+/// Two maps applied one after the other, from `Isomorphism::compose`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Chain<F, S> {
+    pub first: F,
+    pub second: S,
+}
+
+impl<F: Isomorphism, S: Isomorphism> Isomorphism for Chain<F, S> {
+    type Inverse = Chain<S::Inverse, F::Inverse>;
+
+    fn apply(&self, point: &Point) -> Option<Point> {
+        self.second.apply(&self.first.apply(point)?)
+    }
+
+    /// Maps the batch through both maps, so that each can use its own `apply_all`.
+    fn apply_all(&self, points: &[Point]) -> Vec<Option<Point>> {
+        let images = self.first.apply_all(points);
+        let placed: Vec<Point> = images.iter().flatten().cloned().collect();
+        let mut second = self.second.apply_all(&placed).into_iter();
+        images.into_iter().map(|image| image.and_then(|_| second.next().flatten())).collect()
+    }
+
+    fn invert(&self) -> Self::Inverse {
+        Chain { first: self.second.invert(), second: self.first.invert() }
+    }
+}
+
+/// The birational map from a Montgomery curve to the twisted Edwards curve of `to_twisted_edwards`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MontgomeryToEdwards {
+    pub curve: MontgomeryCurve,
+}
+
+/// The birational map from a twisted Edwards curve to the Montgomery curve of `to_montgomery`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EdwardsToMontgomery {
+    pub curve: TwistedEdwardsCurve,
+}
+
+impl Isomorphism for PointMap {
+    type Inverse = PointMap;
+
+    fn apply(&self, point: &Point) -> Option<Point> {
+        Some(PointMap::apply(self, point))
+    }
+
+    fn invert(&self) -> PointMap {
+        self.inverse()
+    }
+}
+
+impl Isomorphism for MontgomeryToEdwards {
+    type Inverse = EdwardsToMontgomery;
+
+    fn apply(&self, point: &Point) -> Option<Point> {
+        Some(self.curve.point_to_edwards(point))
+    }
+
+    fn apply_all(&self, points: &[Point]) -> Vec<Option<Point>> {
+        self.curve.points_to_edwards(points).into_iter().map(Some).collect()
+    }
+
+    fn invert(&self) -> EdwardsToMontgomery {
+        // (A + 2) / B and (A − 2) / B give back A = 2(a + d) / (a − d) and B = 4 / (a − d)
+        EdwardsToMontgomery { curve: self.curve.to_twisted_edwards() }
+    }
+}
+
+impl Isomorphism for EdwardsToMontgomery {
+    type Inverse = MontgomeryToEdwards;
+
+    fn apply(&self, point: &Point) -> Option<Point> {
+        Some(self.curve.point_to_montgomery(point))
+    }
+
+    fn invert(&self) -> MontgomeryToEdwards {
+        MontgomeryToEdwards { curve: self.curve.to_montgomery() }
+    }
+}
+
+impl Isomorphism for Scaling {
+    type Inverse = Scaling;
+
+    fn apply(&self, point: &Point) -> Option<Point> {
+        Some(self.map_point(point))
+    }
+
+    /// The scaling of the image curve by 1 / u.
+    fn invert(&self) -> Scaling {
+        let u_inv = mod_inverse(&self.u, &self.curve.p).expect("u is non-zero");
+        self.curve.scale(&u_inv).expect("1 / u is non-zero")
+    }
+}
+
+impl Isomorphism for Rescaling {
+    type Inverse = Rescaling;
+
+    fn apply(&self, point: &Point) -> Option<Point> {
+        Some(self.map_point(point))
+    }
+
+    /// The rescaling of the image curve by 1 / d, with the same negation.
+    fn invert(&self) -> Rescaling {
+        let d_inv = mod_inverse(&self.d, &self.curve.p).expect("d is non-zero");
+        self.curve.rescaling(self.negate, &d_inv).expect("1 / d is non-zero")
+    }
+}
+
+impl Isomorphism for EdwardsScaling {
+    type Inverse = EdwardsScaling;
+
+    fn apply(&self, point: &Point) -> Option<Point> {
+        Some(self.map_point(point))
+    }
+
+    /// The rescaling of the image curve by 1 / u.
+    fn invert(&self) -> EdwardsScaling {
+        let u_inv = mod_inverse(&self.u, &self.curve.p).expect("u is non-zero");
+        self.curve.rescale(&u_inv).expect("1 / u is non-zero")
+    }
+}
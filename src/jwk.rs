@@ -0,0 +1,80 @@
+use crate::curve::{CurveGroup, Point};
+use crate::der::{decode_base64, encode_base64};
+use crate::encoding::field_byte_len;
+use crate::error::TransformError;
+use crate::montgomery::MontgomeryCurve;
+use crate::registry::{lookup, CurveModel};
+use crate::spec::CurveSpec;
+use num_bigint::{BigInt, Sign};
+use serde::Deserialize;
+
+/// The members of a JSON Web Key (RFC 7517) used here; `kid`, `use` and the rest are ignored.
+#[derive(Deserialize)]
+struct JwkRecord {
+    kty: String,
+    crv: String,
+    x: String,
+    y: Option<String>,
+}
+
+fn invalid(message: String) -> TransformError {
+    TransformError::InvalidParameters(message)
+}
+
+/// Decodes a base64url coordinate, which RFC 7518 requires to be exactly as long as the field.
+fn coordinate(name: &str, text: &str, len: usize) -> Result<BigInt, TransformError> {
+    let bytes = decode_base64(text, true)?;
+    if bytes.len() != len {
+        return Err(invalid(format!("the JWK {} is {} bytes long instead of {}", name, bytes.len(), len)));
+    }
+    Ok(BigInt::from_bytes_be(Sign::Plus, &bytes))
+}
+
+/// Reads an EC public key JWK (RFC 7518, section 6.2), `{"kty": "EC", "crv": ..., "x": ..., "y": ...}`,
+/// into the curve and point to transform.
+///
+/// `crv` is resolved with the registry, so besides P-256, P-384 and P-521 any Weierstrass entry such as
+/// Wei25519 or brainpoolP256r1 is accepted; the point must lie on the curve.
+pub fn ec_jwk_to_spec(json: &str) -> Result<CurveSpec, TransformError> {
+    let record: JwkRecord =
+        serde_json::from_str(json).map_err(|error| invalid(format!("malformed JWK: {}", error)))?;
+    if record.kty != "EC" {
+        return Err(invalid(format!("expected an EC key, found kty {:?}", record.kty)));
+    }
+    let named = lookup(&record.crv)
+        .filter(|named| named.model == CurveModel::Weierstrass)
+        .ok_or_else(|| invalid(format!("unknown or non-Weierstrass JWK curve {:?}", record.crv)))?;
+    let (curve, _) = named.to_weierstrass()?;
+    let len = field_byte_len(&curve.p);
+    let y = record.y.ok_or_else(|| invalid("an EC JWK needs a y coordinate".to_string()))?;
+    let (x, y) = (coordinate("x", &record.x, len)?, coordinate("y", &y, len)?);
+    curve.point(&x, &y)?;
+    Ok(CurveSpec { a: curve.a, b: curve.b, p: curve.p, x, y })
+}
+
+/// Writes a point of Curve25519 or Curve448 as an OKP public key JWK (RFC 8037), `{"kty": "OKP",
+/// "crv": "X25519", "x": ...}`, with x the base64url little-endian u-coordinate.
+///
+/// Only these two curves have OKP names, so `curve` must be one of them exactly; canonicalize the output
+/// of a transform first.
+pub fn okp_jwk(curve: &MontgomeryCurve, point: &Point) -> Result<String, TransformError> {
+    let crv = [("Curve25519", "X25519"), ("Curve448", "X448")]
+        .into_iter()
+        .find(|(name, _)| {
+            lookup(name).is_some_and(|named| {
+                named.p() == curve.p && named.coefficients() == (curve.a.clone(), curve.b.clone())
+            })
+        })
+        .map(|(_, crv)| crv)
+        .ok_or_else(|| invalid("the Montgomery curve is neither Curve25519 nor Curve448".to_string()))?;
+    let u = match point {
+        Point::Affine(u, _) if curve.contains(point) => u,
+        Point::Affine(..) => return Err(TransformError::PointNotOnCurve),
+        Point::Infinity => return Err(invalid("the point at infinity has no OKP encoding".to_string())),
+    };
+    let (_, digits) = u.to_bytes_le();
+    let mut bytes = vec![0u8; field_byte_len(&curve.p)];
+    bytes[..digits.len()].copy_from_slice(&digits);
+    let object = serde_json::json!({"kty": "OKP", "crv": crv, "x": encode_base64(&bytes, true)});
+    Ok(object.to_string())
+}
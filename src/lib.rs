@@ -0,0 +1,204 @@
+//! Transformation of points on short Weierstrass curves to their equivalents on Montgomery curves over F_p.
+//!
+//! With `default-features = false` the library depends only on `num-bigint`, `num-integer`,
+//! `num-traits` and `rand`; the `cli`, `default-rng`, `serde`, `tracing`, `ff`, `postcard`, `protobuf`
+//! and `async` features add the rest. Without the `std` feature it is
+//! `no_std` (with `alloc`) and keeps `SmallField`, `Point`, `MontgomeryMap::map_point` and the
+//! `compact` encodings, for microcontroller firmware.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+extern crate num_bigint;
+extern crate num_integer;
+extern crate num_traits;
+extern crate rand;
+
+#[cfg(feature = "std")]
+pub mod admissibility;
+#[cfg(feature = "std")]
+pub mod analysis;
+#[cfg(feature = "std")]
+pub mod attacks;
+#[cfg(feature = "std")]
+pub mod batch;
+#[cfg(feature = "std")]
+pub mod cbor;
+#[cfg(feature = "std")]
+pub mod chain;
+#[cfg(feature = "postcard")]
+pub mod compact;
+#[cfg(feature = "std")]
+pub mod composite;
+mod ct;
+#[cfg(feature = "std")]
+pub mod cubic;
+pub mod curve;
+#[cfg(feature = "serde")]
+pub mod curve_db;
+#[cfg(feature = "std")]
+pub mod der;
+#[cfg(feature = "std")]
+pub mod ed25519;
+#[cfg(feature = "std")]
+pub mod ed448;
+#[cfg(feature = "std")]
+pub mod edwards;
+#[cfg(feature = "std")]
+pub mod emit;
+#[cfg(feature = "std")]
+pub mod encoding;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod exhaustive;
+#[cfg(feature = "std")]
+pub mod field;
+#[cfg(feature = "std")]
+pub mod fingerprint;
+#[cfg(feature = "std")]
+pub mod fixtures;
+#[cfg(feature = "std")]
+pub mod forms;
+#[cfg(feature = "std")]
+pub mod fp2;
+#[cfg(feature = "std")]
+pub mod generate;
+#[cfg(feature = "ff")]
+pub mod generic;
+#[cfg(feature = "std")]
+pub mod hessian;
+#[cfg(feature = "std")]
+pub mod isomorphism;
+#[cfg(feature = "serde")]
+pub mod jwk;
+pub mod limits;
+mod log;
+pub mod map;
+#[cfg(feature = "std")]
+pub mod montgomery;
+#[cfg(feature = "std")]
+pub mod numtheory;
+pub mod ops;
+#[cfg(feature = "std")]
+pub mod order;
+#[cfg(feature = "std")]
+pub mod plot;
+#[cfg(feature = "std")]
+pub mod poly;
+pub mod progress;
+#[cfg(feature = "protobuf")]
+pub mod proto;
+#[cfg(feature = "std")]
+pub mod quartic;
+pub mod radix;
+#[cfg(feature = "std")]
+pub mod registry;
+#[cfg(feature = "default-rng")]
+pub mod rng;
+#[cfg(feature = "std")]
+pub mod scalar_mul;
+#[cfg(feature = "default-rng")]
+pub mod selftest;
+#[cfg(feature = "async")]
+pub mod service;
+pub mod small;
+pub mod solinas;
+#[cfg(feature = "std")]
+pub mod spec;
+#[cfg(feature = "std")]
+pub mod spki;
+#[cfg(feature = "serde")]
+pub mod std_curves;
+#[cfg(feature = "std")]
+pub mod transform;
+#[cfg(feature = "std")]
+pub mod weierstrass;
+
+#[cfg(feature = "std")]
+pub use admissibility::{can_transform, can_transform_with_rng, AdmissibilityReport, Obstruction};
+#[cfg(feature = "std")]
+pub use analysis::{analyze, embedding_degree, weaknesses, CurveAnalysis, Weakness};
+#[cfg(feature = "std")]
+pub use attacks::{demonstrate_attacks, demonstrate_attacks_with, AttackDemo, Leak};
+#[cfg(feature = "std")]
+pub use batch::{map_labeled_lines, map_lines, BatchSummary};
+#[cfg(feature = "std")]
+pub use composite::{transform_to_montgomery_composite, transform_to_montgomery_composite_with_rng};
+#[cfg(feature = "std")]
+pub use cubic::cubic_roots;
+pub use curve::{CurveGroup, Point};
+#[cfg(feature = "serde")]
+pub use curve_db::{CatalogCurve, CurveDatabase};
+#[cfg(feature = "std")]
+pub use edwards::{EdwardsScaling, TwistedEdwardsCurve};
+#[cfg(feature = "std")]
+pub use encoding::field_byte_len;
+pub use error::TransformError;
+#[cfg(feature = "std")]
+pub use exhaustive::{check_map_exhaustively, GroupStructure};
+#[cfg(feature = "std")]
+pub use field::{batch_inverse, Field, FieldElement, InversionAlgorithm};
+#[cfg(feature = "std")]
+pub use fingerprint::{fingerprint, short_fingerprint};
+#[cfg(feature = "std")]
+pub use fixtures::{generate_fixtures, Fixture};
+#[cfg(feature = "std")]
+pub use forms::{montgomery_forms, montgomery_forms_with_rng, MontgomeryForm, Relation};
+#[cfg(feature = "std")]
+pub use fp2::Fp2;
+#[cfg(feature = "std")]
+pub use generate::{generate_curve, generate_curve_with_prime_subgroup, random_point};
+#[cfg(feature = "ff")]
+pub use generic::{
+    find_cubic_root_generic, transform_to_montgomery_generic, transform_to_montgomery_generic_with_rng,
+};
+#[cfg(feature = "std")]
+pub use hessian::{HessianCurve, HessianMap};
+#[cfg(feature = "std")]
+pub use isomorphism::{Chain, EdwardsToMontgomery, Isomorphism, MontgomeryToEdwards};
+pub use limits::SearchLimits;
+pub use map::{MontgomeryMap, PointMap, MAP_FORMAT_VERSION};
+#[cfg(feature = "std")]
+pub use montgomery::{MontgomeryCurve, Rescaling};
+#[cfg(feature = "std")]
+pub use numtheory::{
+    crt, extended_gcd, is_probable_prime, is_probable_prime_with_rng, largest_prime_factor, legendre_symbol,
+    mod_inverse, mod_nth_root, mod_sqrt, mod_sqrt_both, mod_sqrt_even, mod_sqrt_odd, mod_sqrt_with,
+    multiplicative_order, SqrtAlgorithm, MILLER_RABIN_ROUNDS,
+};
+#[cfg(feature = "op-count")]
+pub use ops::count_ops;
+pub use ops::OpCounts;
+#[cfg(feature = "std")]
+pub use order::{count_points, hasse_interval, point_order, trace_of_frobenius};
+#[cfg(feature = "std")]
+pub use plot::{render_ascii, render_svg};
+#[cfg(feature = "std")]
+pub use poly::Polynomial;
+#[cfg(feature = "std")]
+pub use quartic::{JacobiQuartic, QuarticMap, QuarticScaling};
+pub use radix::{NumberFormat, Radix};
+#[cfg(feature = "std")]
+pub use registry::{lookup, lookup_oid, named_curves, CurveModel, NamedCurve};
+#[cfg(feature = "default-rng")]
+pub use rng::{default_rng, DefaultRng};
+#[cfg(feature = "std")]
+pub use scalar_mul::{
+    clear_cofactor, msm, scalar_mul, scalar_mul_checked, scalar_mul_protected, Countermeasures,
+    FixedBaseTable, GlvEndomorphism,
+};
+#[cfg(feature = "std")]
+pub use small::transform_to_montgomery_small;
+pub use small::{enumerate_points, transform_to_montgomery_small_with_rng, SmallField};
+#[cfg(feature = "std")]
+pub use spec::{CurveSpec, SpecFormat};
+#[cfg(feature = "parallel")]
+pub use transform::find_cubic_root_parallel;
+#[cfg(feature = "std")]
+pub use transform::{
+    find_cubic_root, find_cubic_root_fp2, transform_to_montgomery, transform_to_montgomery_fp2,
+    transform_to_montgomery_fp2_with_rng, transform_to_montgomery_with_rng, transform_with_map,
+    transform_with_root, transform_with_scale, TransformOptions,
+};
+#[cfg(feature = "std")]
+pub use weierstrass::{Scaling, WeierstrassCurve};
@@ -0,0 +1,70 @@
+use crate::error::TransformError;
+use crate::progress::{report, ATTEMPTS_PER_REPORT};
+use alloc::format;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// Bounds on the randomized searches, which would otherwise loop forever on unlucky inputs.
+#[derive(Clone, Copy, Debug)]
+pub struct SearchLimits {
+    /// Maximum number of candidates tried by a single search.
+    pub max_attempts: u64,
+    /// Optional wall-clock limit for a single search; ignored without the `std` feature, which has no clock.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for SearchLimits {
+    fn default() -> Self {
+        SearchLimits {
+            max_attempts: 1_000_000,
+            timeout: None,
+        }
+    }
+}
+
+/// Tracks the attempts and elapsed time of one search against its `SearchLimits`.
+pub(crate) struct SearchBudget<'a> {
+    limits: &'a SearchLimits,
+    #[cfg(feature = "std")]
+    started: Instant,
+    pub(crate) attempts: u64,
+}
+
+impl<'a> SearchBudget<'a> {
+    pub(crate) fn new(limits: &'a SearchLimits) -> Self {
+        SearchBudget {
+            limits,
+            #[cfg(feature = "std")]
+            started: Instant::now(),
+            attempts: 0,
+        }
+    }
+
+    /// Records one attempt, failing once either limit is exhausted.
+    ///
+    /// Progress is reported against `max_attempts`, the most the search may take.
+    pub(crate) fn tick(&mut self, search: &str) -> Result<(), TransformError> {
+        self.attempts += 1;
+        if self.attempts.is_multiple_of(ATTEMPTS_PER_REPORT) {
+            report(search, self.attempts, self.limits.max_attempts);
+        }
+        if self.attempts > self.limits.max_attempts {
+            return Err(TransformError::SearchLimitExceeded(format!(
+                "{} after {} attempts",
+                search, self.limits.max_attempts
+            )));
+        }
+        #[cfg(feature = "std")]
+        if let Some(timeout) = self.limits.timeout {
+            if self.started.elapsed() > timeout {
+                return Err(TransformError::SearchLimitExceeded(format!(
+                    "{} after {:.3}s",
+                    search,
+                    timeout.as_secs_f64()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
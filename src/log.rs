@@ -0,0 +1,13 @@
+//! Logging macros: those of `tracing` with the `tracing` feature, and no-ops that discard their arguments without it.
+
+#[cfg(feature = "tracing")]
+pub(crate) use tracing::{debug, info, trace};
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! discard {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(not(feature = "tracing"))]
+#[cfg_attr(not(feature = "std"), allow(unused_imports))]
+pub(crate) use {discard as debug, discard as info, discard as trace};
@@ -1,138 +1,1933 @@
-extern crate num_bigint;
-extern crate num_integer;
-extern crate num_traits;
-extern crate rand;
-
-use num_bigint::{BigInt, RandBigInt};
+use clap::builder::{PossibleValue, StringValueParser, TypedValueParser};
+use clap::{Arg, Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use indicatif::{ProgressBar, ProgressStyle};
+use num_bigint::BigInt;
 use num_integer::Integer;
-use num_traits::{One, Zero};
+use rand::SeedableRng;
+use std::ffi::OsStr;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::iter;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tracing::field::{Field, Visit};
+use tracing::span::Id;
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::{filter_fn, LevelFilter};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+use weierstrass_to_montgomery::cbor::CborValue;
+use weierstrass_to_montgomery::analysis::DEFAULT_EMBEDDING_DEGREE_CAP;
+use weierstrass_to_montgomery::batch::{map_labeled_lines, map_lines, BatchSummary, DEFAULT_CHUNK};
+use weierstrass_to_montgomery::emit::{formulas, magma_script, pari_script, python_snippet, rust_snippet};
+use weierstrass_to_montgomery::fixtures;
+use weierstrass_to_montgomery::jwk::{ec_jwk_to_spec, okp_jwk};
+use weierstrass_to_montgomery::numtheory::MILLER_RABIN_ROUNDS;
+use weierstrass_to_montgomery::order::MAX_COUNT_MODULUS;
+use weierstrass_to_montgomery::progress::PROGRESS_TARGET;
+use weierstrass_to_montgomery::selftest::run_selftest;
+use weierstrass_to_montgomery::spki::{parse_public_key, EcPublicKey};
+use weierstrass_to_montgomery::{
+    analyze, can_transform_with_rng, check_map_exhaustively, count_ops, count_points, cubic_roots,
+    default_rng, demonstrate_attacks_with, fingerprint, generate_curve, generate_curve_with_prime_subgroup,
+    generate_fixtures, is_probable_prime, largest_prime_factor, legendre_symbol, lookup,
+    montgomery_forms_with_rng, named_curves, point_order, random_point, render_ascii, render_svg, scalar_mul,
+    short_fingerprint, trace_of_frobenius, transform_to_montgomery_composite_with_rng, transform_with_map,
+    transform_with_root, transform_with_scale, weaknesses, AdmissibilityReport, Countermeasures,
+    CurveDatabase, CurveSpec, DefaultRng, Isomorphism, MontgomeryCurve, MontgomeryForm, MontgomeryMap,
+    MontgomeryToEdwards, NumberFormat, Obstruction, OpCounts, Point, Radix, Relation, SearchLimits,
+    TransformError, TransformOptions, WeierstrassCurve, Weakness,
+};
 
-/// Computes the modular inverse of `value` modulo `modulus` using the extended Euclidean algorithm.
-fn mod_inverse(value: &BigInt, modulus: &BigInt) -> Option<BigInt> {
-    let (gcd, x, _) = extended_gcd(value, modulus);
-    if gcd != BigInt::one() {
-        None
-    } else {
-        Some((x % modulus + modulus) % modulus)
+/// Transforms a point on a short Weierstrass curve to its Montgomery form.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Increase log verbosity (-v for progress and timing, -vv for intermediate values).
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Do not draw progress bars for long searches and point counts on stderr.
+    #[arg(long)]
+    no_progress: bool,
+
+    /// Coefficient a of the curve y^2 = x^3 + ax + b.
+    #[arg(short, default_value = "8", allow_hyphen_values = true)]
+    a: String,
+
+    /// Coefficient b of the curve y^2 = x^3 + ax + b.
+    #[arg(short, default_value = "2", allow_hyphen_values = true)]
+    b: String,
+
+    /// Prime modulus of the base field.
+    #[arg(short, default_value = "17")]
+    p: String,
+
+    /// x-coordinate of the point to transform.
+    #[arg(short, default_value = "14", allow_hyphen_values = true)]
+    x: String,
+
+    /// y-coordinate of the point to transform.
+    #[arg(short, default_value = "6", allow_hyphen_values = true)]
+    y: String,
+
+    /// Read a, b, p, x and y from a JSON, TOML or YAML file instead of the flags above.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["a", "b", "p", "x", "y"])]
+    input: Option<PathBuf>,
+
+    /// Use a named curve and its base point, in short Weierstrass form, instead of the flags above: a
+    /// registry name or alias (P-256, secp384r1, brainpoolP256r1, Curve25519, Ed448, ...) or a dotted OID,
+    /// or a curve of the curve database.
+    #[arg(
+        long,
+        value_name = "NAME",
+        value_parser = CurveNameParser,
+        hide_possible_values = true,
+        conflicts_with_all = ["a", "b", "p", "x", "y", "input"]
+    )]
+    curve: Option<String>,
+
+    /// Read further named curves from this JSON or TOML database instead of curves.toml or curves.json
+    /// in $XDG_CONFIG_HOME/weierstrass_to_montgomery (~/.config when unset).
+    #[arg(long, value_name = "PATH")]
+    curve_db: Option<PathBuf>,
+
+    /// Read the curve and point from an EC public key in JWK form (`kty` EC, `crv`, base64url `x` and
+    /// `y`), resolving `crv` with the registry.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["a", "b", "p", "x", "y", "input", "curve"])]
+    jwk: Option<PathBuf>,
+
+    /// Read the curve and point from an EC key file, PEM or DER: a SubjectPublicKeyInfo, or a PKCS#8 or
+    /// SEC1 private key, whose public key is used. The curve may be named or explicit.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["a", "b", "p", "x", "y", "input", "curve", "jwk"])]
+    from_key: Option<PathBuf>,
+
+    /// Maximum number of candidates tried by each randomized search before giving up.
+    #[arg(long, default_value_t = SearchLimits::default().max_attempts)]
+    max_attempts: u64,
+
+    /// Wall-clock limit in seconds for each randomized search.
+    #[arg(long, value_name = "SECONDS")]
+    timeout: Option<f64>,
+
+    /// Reject coefficients and coordinates outside [0, p) instead of reducing them.
+    #[arg(long)]
+    strict: bool,
+
+    /// How the result is written to stdout.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text, global = true)]
+    format: OutputFormat,
+
+    /// How failures are reported on stderr.
+    #[arg(long, value_enum, default_value_t = ErrorFormat::Text, global = true)]
+    error_format: ErrorFormat,
+
+    /// Write numbers in base 10, 16 (0x-prefixed) or 2 (0b-prefixed), in text and JSON; CBOR bignums
+    /// and the map and fixture encodings stay as they are.
+    #[arg(long, value_name = "10|16|2", default_value = "10", value_parser = parse_radix, global = true)]
+    radix: Radix,
+
+    /// With --radix 16, write the hexadecimal digits in upper case.
+    #[arg(long, global = true)]
+    uppercase: bool,
+
+    #[command(flatten)]
+    transform: TransformArgs,
+}
+
+/// The flags of the transform, accepted both before any command and after `transform`.
+#[derive(Args)]
+struct TransformArgs {
+    /// Accept a squarefree composite p, given as --factors: transform modulo each prime factor and
+    /// recombine the results with the Chinese remainder theorem. Z/pZ is then a ring, not a field.
+    #[arg(long, requires = "factors", conflicts_with_all = ["plot", "canonical", "emit", "intermediates"])]
+    allow_composite: bool,
+
+    /// The distinct prime factors of p for --allow-composite, separated by commas.
+    #[arg(long, value_delimiter = ',', requires = "allow_composite")]
+    factors: Vec<String>,
+
+    /// Plot the curve's points for small p: `ascii` prints a grid, anything else is the path of an SVG
+    /// showing both curves with each point linked to its image by color.
+    #[arg(long, value_name = "ascii|PATH", value_parser = parse_plot_target)]
+    plot: Option<PlotTarget>,
+
+    /// With `--plot ascii`, also print the grid of the mapped Montgomery curve next to it.
+    #[arg(long, requires = "plot")]
+    plot_mapped: bool,
+
+    /// Rescale the result to the isomorphic curve with B = 1 (or the smallest non-residue when 1 is out
+    /// of reach) and the smaller of A and p - A, for comparison with published constants.
+    #[arg(long)]
+    canonical: bool,
+
+    /// Print generated material for the computed map instead of the result: `formulas` writes the
+    /// forward and inverse maps with the constants substituted, `rust` and `python` a self-contained
+    /// module implementing them, `pari` and `magma` a script checking them with that system, and `map`
+    /// the map itself as JSON, for `inverse --map` and `verify --map`.
+    #[arg(long, value_enum, conflicts_with = "canonical")]
+    emit: Option<Emit>,
+
+    /// Print the mapped point as an OKP JWK (`crv` X25519 or X448) instead of the result, after
+    /// canonicalizing the image curve, which must then be Curve25519 or Curve448.
+    #[arg(long, conflicts_with_all = ["emit", "allow_composite", "intermediates"])]
+    to_jwk: bool,
+
+    /// Map this point too, given as `x,y`; repeat the flag to map several points of the curve with one
+    /// map, computed once, instead of the point of -x and -y.
+    #[arg(
+        long = "point",
+        value_name = "X,Y",
+        value_parser = parse_point_arg,
+        allow_hyphen_values = true,
+        conflicts_with_all = [
+            "allow_composite", "plot", "canonical", "emit", "to_jwk", "intermediates", "check_order"
+        ]
+    )]
+    points: Vec<(BigInt, BigInt)>,
+
+    /// Use this root of z^3 + az + b as the z0 of the map instead of searching for one, such as the
+    /// standard value for a known curve; it must be a root with 3·z0^2 + a a square.
+    #[arg(long, allow_hyphen_values = true, conflicts_with = "allow_composite")]
+    z0: Option<String>,
+
+    /// Use this s in the map (x, y) -> ((x - z0)/s, y/s), such as a standard's constant, where the
+    /// derived map may take -s; it must square to 3·z0^2 + a. Without --z0 the root it fits is used.
+    #[arg(long, allow_hyphen_values = true, conflicts_with = "allow_composite")]
+    scale: Option<String>,
+
+    /// Also report z0, s^2, s, s^-1 and the Legendre checks behind the result.
+    #[arg(long)]
+    intermediates: bool,
+
+    /// Compute the order of the point and of its image, by baby-step giant-step or from --order, and
+    /// fail unless they match; the result then includes `point_order`.
+    #[arg(long, conflicts_with_all = ["allow_composite", "emit", "to_jwk"])]
+    check_order: bool,
+
+    /// A multiple of the point's order for --check-order, such as the group order, instead of searching.
+    #[arg(long, requires = "check_order")]
+    order: Option<String>,
+
+    /// Map every point of the curve, for p < 1000, and fail unless the images are exactly the points of
+    /// the Montgomery curve with the same group structure, checked by discrete logarithms to generators;
+    /// the result then includes `group_order` and `group_structure`.
+    #[arg(long, conflicts_with_all = ["allow_composite", "emit", "to_jwk", "points"])]
+    check_group: bool,
+
+    /// Also report the short SHA-256 fingerprint of the mapped point, as the `fingerprint` command does.
+    #[arg(long, conflicts_with_all = ["allow_composite", "emit", "to_jwk", "points"])]
+    fingerprint: bool,
+
+    /// Also report the field multiplications, squarings and inversions of the transform, and of
+    /// multiplying the point and its image by p with double-and-add and the image's u by p with the
+    /// Montgomery ladder, as `12M + 4S + 1I` in an `operations` section.
+    #[arg(long, conflicts_with_all = ["allow_composite", "emit", "to_jwk", "points"])]
+    op_count: bool,
+
+    /// Also carry the point through this model and back, Weierstrass -> Montgomery -> Edwards ->
+    /// Montgomery -> Weierstrass, and report its image there and whether it came back unchanged as
+    /// `round_trip`; exits with 1 when it did not, as for the points of order 2 or 4 that go to the
+    /// neutral element of an Edwards curve that is not complete.
+    #[arg(
+        long,
+        value_enum,
+        conflicts_with_all = ["allow_composite", "canonical", "emit", "to_jwk", "points"]
+    )]
+    via: Option<Via>,
+
+    /// Exit with 6 after writing the result when it carries warnings: a point order of fewer than 200
+    /// bits or not prime, or an anomalous curve. The order is known for --curve and from --check-order.
+    #[arg(long)]
+    deny_warnings: bool,
+}
+
+/// The commands; without one, the binary runs `transform`.
+#[derive(Subcommand)]
+enum Command {
+    /// Transform the point of -x and -y (or --input) on the curve given by -a, -b and -p to the Montgomery
+    /// form; the default command.
+    Transform(TransformArgs),
+    /// Map a point (u, v) of the Montgomery form back to the curve given by -a, -b and -p (or --input).
+    ///
+    /// A curve whose cubic has several roots has several maps, and a fresh run may pick another one; pass
+    /// the map written by `transform --emit map` to invert the one that produced (u, v).
+    Inverse {
+        /// u-coordinate of the Montgomery point.
+        #[arg(allow_hyphen_values = true)]
+        u: String,
+
+        /// v-coordinate of the Montgomery point.
+        #[arg(allow_hyphen_values = true)]
+        v: String,
+
+        /// Read the map from a JSON file written by `transform --emit map` instead of computing it.
+        #[arg(long, value_name = "PATH")]
+        map: Option<PathBuf>,
+    },
+    /// Check that the point of -x and -y (or --input) maps to (u, v), printing the root z0 and the s of
+    /// the map that does it. Exits with 1 when no map does.
+    ///
+    /// Without --map, every map of the curve is tried: each root z0 of the cubic with 3·z0^2 + a a
+    /// square, and both signs of s.
+    Verify {
+        /// Expected u-coordinate.
+        #[arg(allow_hyphen_values = true)]
+        u: String,
+
+        /// Expected v-coordinate.
+        #[arg(allow_hyphen_values = true)]
+        v: String,
+
+        /// Only accept the map in this JSON file written by `transform --emit map`.
+        #[arg(long, value_name = "PATH")]
+        map: Option<PathBuf>,
+    },
+    /// Map a file of points of the curve given by -a, -b and -p (or --input), one `x y` or `x,y` per line,
+    /// writing one `u v` line per point (JSON Lines with --format json).
+    ///
+    /// The file is read, mapped and written a chunk at a time, so memory stays bounded however large it
+    /// is. Blank lines and lines starting with # are skipped; a point off the curve or a line that is not
+    /// a point gets an error line in its place, and the command then exits with 1.
+    Batch {
+        /// The file of points; stdin when omitted or `-`.
+        path: Option<PathBuf>,
+
+        /// Points held in memory at a time.
+        #[arg(long, default_value_t = DEFAULT_CHUNK)]
+        chunk: usize,
+
+        /// Threads mapping each chunk (all available cores when 0).
+        #[arg(long, default_value_t = 0)]
+        threads: usize,
+
+        /// Read the map from a JSON file written by `transform --emit map` instead of computing it.
+        #[arg(long, value_name = "PATH")]
+        map: Option<PathBuf>,
+
+        /// Take the rest of each line after the point, such as a key ID, as its label and write it
+        /// after the image: `u v label` as text, a `label` field in JSON.
+        #[arg(long)]
+        labels: bool,
+    },
+    /// Print a short fingerprint of the point (X, Y) of a curve over the p of -p (or --input, or
+    /// --curve), the first 8 bytes of the SHA-256 hash of its uncompressed encoding, with the full hash.
+    ///
+    /// Compare the fingerprints of the points two tools produced instead of their coordinates.
+    Fingerprint {
+        #[arg(allow_hyphen_values = true)]
+        x: String,
+
+        #[arg(allow_hyphen_values = true)]
+        y: String,
+    },
+    /// Run the built-in known-answer tests and print a pass/fail report.
+    Selftest,
+    /// Print a completion script for bash, zsh, fish, elvish or PowerShell.
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    /// Summarize the curve given by -a, -b and -p (or --input): field size, discriminant, j-invariant,
+    /// group order, cofactor and trace (p up to 2^24), and its Montgomery and Edwards forms if any.
+    Info,
+    /// Vet the curve given by -a, -b and -p (or --input) against known discrete-logarithm attacks:
+    /// anomalous curves (#E = p, Smart's attack) and small embedding degree (MOV/Frey-Rück).
+    ///
+    /// Counts the points for p up to 2^24; pass --order for larger curves.
+    Analyze {
+        /// The known group order, checked against the Hasse bound and a point of the curve.
+        #[arg(long)]
+        order: Option<String>,
+
+        /// Give up on the embedding degree beyond this many powers of p.
+        #[arg(long, default_value_t = DEFAULT_EMBEDDING_DEGREE_CAP)]
+        max_embedding_degree: u64,
+    },
+    /// Explain whether the curve given by -a, -b and -p (or --input) admits a Montgomery form.
+    ///
+    /// Exits with status 3 when it does not, like a transform that fails for the same reason.
+    CanTransform,
+    /// List every (A, B) reachable from the curve given by -a, -b and -p (or --input), per root of the
+    /// cubic and sign of s, with B reduced to 1 or the smallest non-residue, plus the quadratic twists.
+    ListForms,
+    /// Rewrite the curve and point of -a, -b, -p, -x, -y (or --input) into the isomorphic a = -3 form,
+    /// (x, y) -> (u^2 x, u^3 y) with a u^4 = -3, printing it as a valid --input file plus u.
+    Normalize,
+    /// Print a random curve over F_p that admits a Montgomery form, with a point on it.
+    ///
+    /// The text output is a valid `--input` YAML file.
+    GenerateCurve {
+        /// Prime modulus of the base field.
+        #[arg(short)]
+        p: String,
+
+        /// Seed the generator for a reproducible curve.
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Count points and keep only curves of order 4 times a prime, printing a point of prime order
+        /// (p up to 2^24).
+        #[arg(long)]
+        prime_order: bool,
+    },
+    /// Print reproducible test vectors: random points of the curve with their Montgomery images and the
+    /// intermediates of the map, for other implementations to check against.
+    GenerateVectors {
+        /// Named curve to draw points from, like the top-level --curve; defaults to the curve given by -a,
+        /// -b and -p (or --input).
+        #[arg(long, value_name = "NAME", value_parser = CurveNameParser, hide_possible_values = true)]
+        curve: Option<String>,
+
+        /// Number of vectors.
+        #[arg(long, default_value_t = 10)]
+        count: usize,
+
+        /// Seed of the generator; the same seed, curve and count always give the same vectors.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Print JSON fixtures for the tests of other projects: small curves of order 4 times a prime, each
+    /// with a generator of prime order and random points, all with their Montgomery images.
+    Fixtures {
+        /// Number of curves.
+        #[arg(long, default_value_t = 5)]
+        count: usize,
+
+        /// Bit length of the random primes p (5 to 24).
+        #[arg(long, default_value_t = 12)]
+        bits: u64,
+
+        /// Number of random points per curve, besides the generator.
+        #[arg(long, default_value_t = 4)]
+        points: usize,
+
+        /// Seed of the generator; the same seed and sizes always give the same fixtures.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Walk through teaching scenarios on the curve given by -a, -b and -p (or --input).
+    Demo {
+        #[command(subcommand)]
+        demo: DemoCommand,
+    },
+}
+
+/// Scenarios of the `demo` command.
+#[derive(Subcommand)]
+enum DemoCommand {
+    /// Show how a victim that multiplies unvalidated points by its secret leaks it: points of small
+    /// order from the cofactor, the quadratic twist and invalid curves each reveal the secret modulo a
+    /// small prime, and CRT puts the residues together (p up to 2^24).
+    Attacks {
+        /// Seed the generator for a reproducible secret.
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Have the victim blind its secret with random multiples of the group order.
+        #[arg(long)]
+        blind_scalar: bool,
+        /// Have the victim compute in randomized projective coordinates.
+        #[arg(long)]
+        randomize_coordinates: bool,
+    },
+}
+
+/// Output format for results.
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    /// One `name: value` line per result.
+    Text,
+    /// A JSON object with decimal-string values.
+    Json,
+    /// A deterministic CBOR map with values as tagged bignums (byte strings).
+    Cbor,
+}
+
+/// What `--emit` generates.
+#[derive(Clone, Copy, ValueEnum)]
+enum Emit {
+    /// The forward and inverse maps as formulas with concrete constants.
+    Formulas,
+    /// A Rust module with the constants and both maps, depending only on num-bigint.
+    Rust,
+    /// A Python module with the constants and both maps.
+    Python,
+    /// A PARI/GP script that checks the map with PARI's curve arithmetic.
+    Pari,
+    /// A Magma script that checks the map with Magma's curve arithmetic.
+    Magma,
+    /// The map as JSON, which `inverse --map` and `verify --map` read back.
+    Map,
+}
+
+/// The model `--via` routes the point through.
+#[derive(Clone, Copy, ValueEnum)]
+enum Via {
+    /// The twisted Edwards curve of the Montgomery curve, by the birational map.
+    Edwards,
+}
+
+/// Output format for error reports.
+#[derive(Clone, Copy, ValueEnum)]
+enum ErrorFormat {
+    Text,
+    Json,
+}
+
+/// Where `--plot` sends its output.
+#[derive(Clone)]
+enum PlotTarget {
+    Ascii,
+    Svg(PathBuf),
+}
+
+fn parse_plot_target(value: &str) -> Result<PlotTarget, String> {
+    match value {
+        "ascii" => Ok(PlotTarget::Ascii),
+        "" => Err("expected `ascii` or a file path".to_string()),
+        path => Ok(PlotTarget::Svg(PathBuf::from(path))),
+    }
+}
+
+/// The value parser of `--curve`: any name goes, as OIDs and curve-database names are resolved later,
+/// but the registry names and aliases are offered as possible values to shell completions.
+#[derive(Clone)]
+struct CurveNameParser;
+
+impl TypedValueParser for CurveNameParser {
+    type Value = String;
+
+    fn parse_ref(
+        &self,
+        command: &clap::Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<String, clap::Error> {
+        StringValueParser::new().parse_ref(command, arg, value)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        let names = named_curves()
+            .iter()
+            .flat_map(|curve| iter::once(curve.name).chain(curve.aliases.iter().copied()));
+        Some(Box::new(names.map(PossibleValue::new)))
+    }
+}
+
+/// Parses an `x,y` point of `--point`.
+fn parse_point_arg(value: &str) -> Result<(BigInt, BigInt), String> {
+    let (x, y) = value.split_once(',').ok_or_else(|| format!("expected X,Y, got {:?}", value))?;
+    let x = parse_param("x", x.trim()).map_err(|error| error.to_string())?;
+    let y = parse_param("y", y.trim()).map_err(|error| error.to_string())?;
+    Ok((x, y))
+}
+
+fn parse_radix(value: &str) -> Result<Radix, String> {
+    let radix = value.parse().ok().and_then(Radix::from_base);
+    radix.ok_or_else(|| format!("expected 10, 16 or 2, got {:?}", value))
+}
+
+/// The format of printed numbers, from `--radix` and `--uppercase`.
+static NUMBER_FORMAT: OnceLock<NumberFormat> = OnceLock::new();
+
+/// Writes `value` in the format of `--radix`.
+fn number(value: &BigInt) -> String {
+    NUMBER_FORMAT.get().copied().unwrap_or_default().display(value).to_string()
+}
+
+fn radix() -> Radix {
+    NUMBER_FORMAT.get().map_or(Radix::Decimal, |format| format.radix)
+}
+
+/// Parses a decimal integer argument, naming the offending parameter on failure.
+fn parse_param(name: &str, value: &str) -> Result<BigInt, TransformError> {
+    BigInt::from_str(value)
+        .map_err(|_| TransformError::InvalidParameters(format!("{} is not an integer: {:?}", name, value)))
+}
+
+/// Installs a stderr subscriber whose level follows the number of `-v` flags.
+fn init_tracing(verbosity: u8, progress: bool) {
+    let level = match verbosity {
+        0 => Level::WARN,
+        1 => Level::INFO,
+        2 => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+    let logs = tracing_subscriber::fmt::layer()
+        .with_span_events(FmtSpan::CLOSE)
+        .with_target(false)
+        .with_writer(std::io::stderr)
+        .with_filter(LevelFilter::from_level(level))
+        .with_filter(filter_fn(|metadata| metadata.target() != PROGRESS_TARGET));
+    // A bar would garble the log lines, so it is only drawn without -v
+    let bar = (progress && verbosity == 0).then(|| {
+        ProgressLayer::default()
+            .with_filter(filter_fn(|metadata| metadata.is_span() || metadata.target() == PROGRESS_TARGET))
+    });
+    tracing_subscriber::registry().with(logs).with(bar).init();
+}
+
+/// The fields of a progress event.
+#[derive(Default)]
+struct ProgressFields {
+    task: String,
+    done: u64,
+    total: u64,
+}
+
+impl Visit for ProgressFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "task" {
+            self.task = value.to_string();
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "done" => self.done = value,
+            "total" => self.total = value,
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+/// The bar of the task in progress and the span it was started in.
+struct ActiveBar {
+    task: String,
+    span: Option<Id>,
+    bar: ProgressBar,
+}
+
+/// Draws the library's progress events as a bar with an ETA on stderr (indicatif hides it when stderr
+/// is not a terminal), clearing it once the span that started the task closes.
+#[derive(Default)]
+struct ProgressLayer {
+    active: Mutex<Option<ActiveBar>>,
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for ProgressLayer {
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = ProgressFields::default();
+        event.record(&mut fields);
+        let mut active = self.active.lock().expect("the progress bar lock is never poisoned");
+        if active.as_ref().is_none_or(|active| active.task != fields.task) {
+            if let Some(previous) = active.take() {
+                previous.bar.finish_and_clear();
+            }
+            let template = "{spinner} {msg} [{bar:30}] {human_pos}/{human_len} (ETA {eta})";
+            let style = ProgressStyle::with_template(template)
+                .expect("the template is valid")
+                .progress_chars("=> ");
+            let bar = ProgressBar::new(fields.total).with_style(style).with_message(fields.task.clone());
+            let span = ctx.current_span().id().cloned();
+            *active = Some(ActiveBar { task: fields.task, span, bar });
+        }
+        if let Some(active) = active.as_ref() {
+            active.bar.set_position(fields.done);
+        }
+    }
+
+    fn on_close(&self, id: Id, _ctx: Context<'_, S>) {
+        let mut active = self.active.lock().expect("the progress bar lock is never poisoned");
+        if active.as_ref().is_some_and(|active| active.span.as_ref() == Some(&id)) {
+            if let Some(finished) = active.take() {
+                finished.bar.finish_and_clear();
+            }
+        }
     }
 }
 
-/// Computes the extended Euclidean algorithm, returning (gcd, x, y) such that gcd = value * x + modulus * y.
-fn extended_gcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
-    if b.is_zero() {
-        (a.clone(), BigInt::one(), BigInt::zero())
+/// A value in the printed report.
+enum Entry {
+    Number(BigInt),
+    /// A Legendre symbol or other small signed check value.
+    Symbol(i8),
+    Flag(bool),
+    Text(String),
+}
+
+impl Entry {
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Entry::Number(value) => number(value).into(),
+            Entry::Symbol(value) => (*value).into(),
+            Entry::Flag(value) => (*value).into(),
+            Entry::Text(value) => value.clone().into(),
+        }
+    }
+
+    fn to_cbor(&self) -> CborValue {
+        match self {
+            Entry::Number(value) => CborValue::bignum(value),
+            Entry::Symbol(value) => CborValue::integer(*value as i64),
+            Entry::Flag(value) => CborValue::Bool(*value),
+            Entry::Text(value) => CborValue::Text(value.clone()),
+        }
+    }
+}
+
+impl std::fmt::Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Entry::Number(value) => write!(f, "{}", number(value)),
+            Entry::Symbol(value) => write!(f, "{}", value),
+            Entry::Flag(value) => write!(f, "{}", if *value { "yes" } else { "no" }),
+            Entry::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// The result of a run, with the intermediate values behind it when `--intermediates` is given and the
+/// field operations of its steps with `--op-count`.
+struct Report {
+    results: Vec<(&'static str, Entry)>,
+    intermediates: Option<Vec<(&'static str, Entry)>>,
+    operations: Option<Vec<(&'static str, OpCounts)>>,
+    /// Red flags of the curve and point, written after the results.
+    warnings: Vec<Weakness>,
+}
+
+/// Lists the values an auditor needs to re-check each step of the transform of (x, y).
+fn intermediates(map: &MontgomeryMap, x: &BigInt) -> Vec<(&'static str, Entry)> {
+    let p = &map.p;
+    let s_squared = (BigInt::from(3) * &map.z0 * &map.z0 + &map.a).mod_floor(p);
+    let cubic_at_z0 = (&map.z0 * &map.z0 * &map.z0 + &map.a * &map.z0 + &map.b).mod_floor(p);
+    let rhs_at_x = (x * x * x + &map.a * x + &map.b).mod_floor(p);
+    vec![
+        ("z0", Entry::Number(map.z0.clone())),
+        ("cubic_at_z0", Entry::Number(cubic_at_z0)),
+        ("s_squared", Entry::Number(s_squared.clone())),
+        ("legendre_s_squared", Entry::Symbol(legendre_symbol(&s_squared, p))),
+        ("legendre_rhs_x", Entry::Symbol(legendre_symbol(&rhs_at_x, p))),
+        ("s", Entry::Number(map.s.clone())),
+        ("s_inv", Entry::Number(map.s_inv.clone())),
+    ]
+}
+
+/// Writes the report to stdout in the requested format; intermediates go in a nested `intermediates` object.
+fn write_report(report: &Report, format: OutputFormat) -> Result<(), TransformError> {
+    let bytes = match format {
+        OutputFormat::Text => {
+            let mut text: String =
+                report.results.iter().map(|(name, value)| format!("{}: {}\n", name, value)).collect();
+            for warning in &report.warnings {
+                text.push_str(&format!("warning: {}\n", warning));
+            }
+            if let Some(intermediates) = &report.intermediates {
+                text.push_str("intermediates:\n");
+                for (name, value) in intermediates {
+                    text.push_str(&format!("  {}: {}\n", name, value));
+                }
+            }
+            if let Some(operations) = &report.operations {
+                text.push_str("operations:\n");
+                for (name, counts) in operations {
+                    text.push_str(&format!("  {}: {}\n", name, counts));
+                }
+            }
+            text.into_bytes()
+        }
+        OutputFormat::Json => {
+            let to_object = |entries: &[(&str, Entry)]| -> serde_json::Map<String, serde_json::Value> {
+                entries.iter().map(|(name, value)| (name.to_string(), value.to_json())).collect()
+            };
+            let mut object = to_object(&report.results);
+            if let Some(intermediates) = &report.intermediates {
+                object.insert("intermediates".to_string(), to_object(intermediates).into());
+            }
+            if let Some(operations) = &report.operations {
+                let operations = operations.iter().map(|(name, counts)| {
+                    let counts = serde_json::json!({
+                        "multiplications": counts.multiplications,
+                        "squarings": counts.squarings,
+                        "inversions": counts.inversions,
+                    });
+                    (name.to_string(), counts)
+                });
+                object.insert("operations".to_string(), operations.collect::<serde_json::Map<_, _>>().into());
+            }
+            if !report.warnings.is_empty() {
+                let warnings = report.warnings.iter().map(|warning| {
+                    serde_json::json!({"kind": warning.kind(), "message": warning.to_string()})
+                });
+                object.insert("warnings".to_string(), warnings.collect::<Vec<_>>().into());
+            }
+            format!("{}\n", serde_json::Value::Object(object)).into_bytes()
+        }
+        OutputFormat::Cbor => {
+            let to_map = |entries: &[(&str, Entry)]| {
+                let entries = entries.iter().map(|(name, value)| (name.to_string(), value.to_cbor()));
+                CborValue::Map(entries.collect())
+            };
+            let mut map = to_map(&report.results);
+            if let (CborValue::Map(entries), Some(intermediates)) = (&mut map, &report.intermediates) {
+                entries.push(("intermediates".to_string(), to_map(intermediates)));
+            }
+            if let (CborValue::Map(entries), Some(operations)) = (&mut map, &report.operations) {
+                let operations = operations.iter().map(|(name, counts)| {
+                    let counts = CborValue::Map(vec![
+                        ("multiplications".to_string(), CborValue::Unsigned(counts.multiplications)),
+                        ("squarings".to_string(), CborValue::Unsigned(counts.squarings)),
+                        ("inversions".to_string(), CborValue::Unsigned(counts.inversions)),
+                    ]);
+                    (name.to_string(), counts)
+                });
+                entries.push(("operations".to_string(), CborValue::Map(operations.collect())));
+            }
+            if let (CborValue::Map(entries), false) = (&mut map, report.warnings.is_empty()) {
+                let warnings = report.warnings.iter().map(|warning| {
+                    CborValue::Map(vec![
+                        ("kind".to_string(), CborValue::Text(warning.kind().to_string())),
+                        ("message".to_string(), CborValue::Text(warning.to_string())),
+                    ])
+                });
+                entries.push(("warnings".to_string(), CborValue::Array(warnings.collect())));
+            }
+            map.encode()
+        }
+    };
+    write_stdout(&bytes)
+}
+
+fn write_stdout(bytes: &[u8]) -> Result<(), TransformError> {
+    std::io::stdout()
+        .write_all(bytes)
+        .map_err(|error| TransformError::Io(format!("cannot write output: {}", error)))
+}
+
+/// Prints a failure on stderr in the requested format.
+///
+/// For a curve without a Montgomery form, the JSON object also names the `obstruction`.
+fn report_error(error: &TransformError, format: ErrorFormat, obstruction: Option<Obstruction>) {
+    match format {
+        ErrorFormat::Text => eprintln!("error: {}", error),
+        ErrorFormat::Json => {
+            let mut object = serde_json::json!({
+                "error": error.kind(),
+                "message": error.to_string(),
+                "exit_code": error.exit_code(),
+            });
+            if let Some(obstruction) = obstruction {
+                object["obstruction"] = obstruction.kind().into();
+            }
+            eprintln!("{}", object)
+        }
+    }
+}
+
+/// Exit code of a usage error found by clap, which also uses it when it reports the error itself.
+const USAGE_EXIT_CODE: u8 = 2;
+
+/// Whether the last `--error-format` among the raw arguments asks for JSON, for the usage errors that
+/// stop clap before it has parsed the flag.
+fn wants_json_errors() -> bool {
+    let args: Vec<String> = std::env::args_os().map(|arg| arg.to_string_lossy().into_owned()).collect();
+    let mut json = false;
+    for (index, arg) in args.iter().enumerate() {
+        let value = match arg.strip_prefix("--error-format") {
+            Some("") => args.get(index + 1).map(String::as_str),
+            Some(rest) => rest.strip_prefix('='),
+            None => None,
+        };
+        if let Some(value) = value {
+            json = value == "json";
+        }
+    }
+    json
+}
+
+/// Reports a failure to parse the arguments: help and version requests and text errors as clap prints
+/// them, and with `--error-format json` a JSON object like `report_error`'s, of kind `usage`.
+fn report_usage_error(error: clap::Error) -> ExitCode {
+    if !error.use_stderr() || !wants_json_errors() {
+        error.exit();
+    }
+    let rendered = error.render().to_string();
+    let message = rendered.lines().next().unwrap_or_default();
+    let object = serde_json::json!({
+        "error": "usage",
+        "message": message.strip_prefix("error: ").unwrap_or(message),
+        "exit_code": USAGE_EXIT_CODE,
+    });
+    eprintln!("{}", object);
+    ExitCode::from(USAGE_EXIT_CODE)
+}
+
+/// What keeps the curve of the flags from having a Montgomery form, if it is a curve over a prime field
+/// and something does.
+fn obstruction(cli: &Cli) -> Option<Obstruction> {
+    let spec = curve_spec(cli).ok()?;
+    if !is_probable_prime(&spec.p, MILLER_RABIN_ROUNDS) {
+        return None;
+    }
+    let curve = WeierstrassCurve::new(&spec.a, &spec.b, &spec.p).ok()?;
+    can_transform_with_rng(&curve, &options(cli).ok()?.limits, &mut default_rng()).ok()?.obstruction()
+}
+
+/// Builds the transform options from the search and strictness flags.
+fn options(cli: &Cli) -> Result<TransformOptions, TransformError> {
+    Ok(TransformOptions {
+        limits: SearchLimits {
+            max_attempts: cli.max_attempts,
+            timeout: match cli.timeout {
+                Some(seconds) => Some(Duration::try_from_secs_f64(seconds).map_err(|_| {
+                    TransformError::InvalidParameters(format!(
+                        "timeout must be a non-negative number of seconds, got {}",
+                        seconds
+                    ))
+                })?),
+                None => None,
+            },
+        },
+        strict: cli.strict,
+    })
+}
+
+fn run(cli: &Cli) -> Result<ExitCode, TransformError> {
+    match &cli.command {
+        Some(Command::CanTransform) => admissibility(cli),
+        Some(Command::Info) => info(cli).map(|()| ExitCode::SUCCESS),
+        Some(Command::Analyze { order, max_embedding_degree }) => {
+            analyze_curve(cli, order.as_deref(), *max_embedding_degree).map(|()| ExitCode::SUCCESS)
+        }
+        Some(Command::ListForms) => list_forms(cli).map(|()| ExitCode::SUCCESS),
+        Some(Command::Normalize) => normalize(cli).map(|()| ExitCode::SUCCESS),
+        Some(Command::GenerateCurve { p, seed, prime_order }) => {
+            generate(cli, p, *seed, *prime_order).map(|()| ExitCode::SUCCESS)
+        }
+        Some(Command::GenerateVectors { curve, count, seed }) => {
+            generate_vectors(cli, curve.as_deref(), *count, *seed).map(|()| ExitCode::SUCCESS)
+        }
+        Some(Command::Fixtures { count, bits, points, seed }) => {
+            write_fixtures(cli, *count, *bits, *points, *seed).map(|()| ExitCode::SUCCESS)
+        }
+        Some(Command::Demo { demo: DemoCommand::Attacks { seed, blind_scalar, randomize_coordinates } }) => {
+            let countermeasures = Countermeasures {
+                scalar_blinding: *blind_scalar,
+                randomize_coordinates: *randomize_coordinates,
+            };
+            demo_attacks(cli, *seed, countermeasures).map(|()| ExitCode::SUCCESS)
+        }
+        Some(Command::Transform(args)) => transform(cli, args),
+        Some(Command::Inverse { u, v, map }) => {
+            inverse(cli, u, v, map.as_deref()).map(|()| ExitCode::SUCCESS)
+        }
+        Some(Command::Verify { u, v, map }) => verify(cli, u, v, map.as_deref()),
+        Some(Command::Batch { path, chunk, threads, map, labels }) => {
+            batch(cli, path.as_deref(), *chunk, *threads, map.as_deref(), *labels)
+        }
+        Some(Command::Fingerprint { x, y }) => fingerprint_point(cli, x, y).map(|()| ExitCode::SUCCESS),
+        _ => transform(cli, &cli.transform),
+    }
+}
+
+/// Reads a file named on the command line.
+fn read_file(path: &Path) -> Result<Vec<u8>, TransformError> {
+    std::fs::read(path).map_err(|error| {
+        TransformError::Io(format!("cannot read {}: {}", path.display(), error))
+    })
+}
+
+/// Reads the curve and point from `--curve`, `--jwk`, `--from-key`, `--input`, or from the flags.
+fn curve_spec(cli: &Cli) -> Result<CurveSpec, TransformError> {
+    if let Some(path) = &cli.jwk {
+        let json = String::from_utf8(read_file(path)?)
+            .map_err(|_| TransformError::InvalidParameters(format!("{} is not UTF-8", path.display())))?;
+        return ec_jwk_to_spec(&json);
+    }
+    if let Some(path) = &cli.from_key {
+        let EcPublicKey { curve, point, .. } = parse_public_key(&read_file(path)?)?;
+        let Point::Affine(x, y) = point else {
+            return Err(TransformError::InvalidParameters("the key is the point at infinity".to_string()));
+        };
+        return Ok(CurveSpec { a: curve.a, b: curve.b, p: curve.p, x, y });
+    }
+    if let Some(name) = &cli.curve {
+        return named_spec(cli, name);
+    }
+    Ok(match &cli.input {
+        Some(path) => CurveSpec::from_path(path)?,
+        None => CurveSpec {
+            a: parse_param("a", &cli.a)?,
+            b: parse_param("b", &cli.b)?,
+            p: parse_param("p", &cli.p)?,
+            x: parse_param("x", &cli.x)?,
+            y: parse_param("y", &cli.y)?,
+        },
+    })
+}
+
+/// Reads the curve database of `--curve-db`, or the default one if it exists.
+fn curve_database(cli: &Cli) -> Result<Option<CurveDatabase>, TransformError> {
+    match cli.curve_db.clone().or_else(CurveDatabase::default_path) {
+        Some(path) => CurveDatabase::from_path(&path).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Reads the curve and base point of a registry name or alias, or of a curve of the curve database, in
+/// short Weierstrass form.
+fn named_spec(cli: &Cli, name: &str) -> Result<CurveSpec, TransformError> {
+    let database = curve_database(cli)?;
+    let (curve, point) = match (lookup(name), database.as_ref().and_then(|database| database.lookup(name))) {
+        (Some(named), _) => named.to_weierstrass()?,
+        (None, Some(catalog)) => catalog.to_weierstrass()?,
+        (None, None) => {
+            let mut known: Vec<&str> = named_curves().iter().map(|curve| curve.name).collect();
+            let catalog = database.iter().flat_map(|database| &database.curves);
+            known.extend(catalog.map(|curve| curve.name.as_str()));
+            let message = format!("unknown curve {:?}; known: {}", name, known.join(", "));
+            return Err(TransformError::InvalidParameters(message));
+        }
+    };
+    let Point::Affine(x, y) = point else {
+        unreachable!("base points are affine");
+    };
+    Ok(CurveSpec { a: curve.a, b: curve.b, p: curve.p, x, y })
+}
+
+/// The base point order of the `--curve` curve when it gives the point, with the group order when the
+/// cofactor is known too; registry curves know both, database curves what their entry gives.
+fn named_order(cli: &Cli, name: &str) -> Result<(Option<BigInt>, Option<BigInt>), TransformError> {
+    if cli.jwk.is_some() || cli.from_key.is_some() {
+        return Ok((None, None));
+    }
+    if let Some(named) = lookup(name) {
+        let order = named.order();
+        return Ok((Some(order.clone()), Some(order * named.cofactor)));
+    }
+    let database = curve_database(cli)?;
+    let Some(catalog) = database.as_ref().and_then(|database| database.lookup(name)) else {
+        return Ok((None, None));
+    };
+    let group = catalog.order.as_ref().zip(catalog.cofactor).map(|(order, cofactor)| order * cofactor);
+    Ok((catalog.order.clone(), group))
+}
+
+/// Writes an admissibility report: the explanation as text, or `montgomery_form`, `roots`, the
+/// `obstruction` kind when there is no form, and `explanation` as a JSON object or CBOR map.
+fn write_admissibility(report: &AdmissibilityReport, format: OutputFormat) -> Result<(), TransformError> {
+    let explanation = report.to_string();
+    let bytes = match format {
+        OutputFormat::Text => explanation.into_bytes(),
+        OutputFormat::Json => {
+            let roots: Vec<serde_json::Value> = report
+                .roots
+                .iter()
+                .map(|root| {
+                    serde_json::json!({
+                        "z0": number(&root.z0),
+                        "s_squared": number(&root.s_squared),
+                        "is_square": root.is_square,
+                    })
+                })
+                .collect();
+            let mut object = serde_json::json!({
+                "montgomery_form": report.admits_montgomery_form(),
+                "roots": roots,
+                "explanation": explanation.trim_end(),
+            });
+            if let Some(obstruction) = report.obstruction() {
+                object["obstruction"] = obstruction.kind().into();
+            }
+            format!("{}\n", object).into_bytes()
+        }
+        OutputFormat::Cbor => {
+            let roots = report.roots.iter().map(|root| {
+                CborValue::Map(vec![
+                    ("z0".to_string(), CborValue::bignum(&root.z0)),
+                    ("s_squared".to_string(), CborValue::bignum(&root.s_squared)),
+                    ("is_square".to_string(), CborValue::Bool(root.is_square)),
+                ])
+            });
+            let mut entries = vec![
+                ("montgomery_form".to_string(), CborValue::Bool(report.admits_montgomery_form())),
+                ("roots".to_string(), CborValue::Array(roots.collect())),
+                ("explanation".to_string(), CborValue::Text(explanation.trim_end().to_string())),
+            ];
+            if let Some(obstruction) = report.obstruction() {
+                entries.push(("obstruction".to_string(), CborValue::Text(obstruction.kind().to_string())));
+            }
+            CborValue::Map(entries).encode()
+        }
+    };
+    write_stdout(&bytes)
+}
+
+/// The numbers describing one Montgomery form, in output order.
+fn form_fields(form: &MontgomeryForm) -> [(&'static str, &BigInt); 4] {
+    [("A", &form.a), ("B", &form.b), ("z0", &form.z0), ("s", &form.s)]
+}
+
+/// Prints every Montgomery form of the curve: one line each as text, otherwise an array of objects
+/// with `A`, `B`, `z0`, `s` and `relation`.
+fn list_forms(cli: &Cli) -> Result<(), TransformError> {
+    let spec = curve_spec(cli)?;
+    let forms = montgomery_forms_with_rng(&spec.a, &spec.b, &spec.p, &options(cli)?, &mut default_rng())?;
+    let bytes = match cli.format {
+        OutputFormat::Text => {
+            let mut text = String::new();
+            for form in &forms {
+                let fields = form_fields(form).map(|(name, value)| format!("{}: {}", name, number(value)));
+                text.push_str(&format!("{} ({})\n", fields.join(", "), form.relation));
+            }
+            text.into_bytes()
+        }
+        OutputFormat::Json => {
+            let forms: Vec<serde_json::Value> = forms
+                .iter()
+                .map(|form| {
+                    let mut object: serde_json::Map<String, serde_json::Value> = form_fields(form)
+                        .iter()
+                        .map(|(name, value)| (name.to_string(), number(value).into()))
+                        .collect();
+                    object.insert("relation".to_string(), form.relation.to_string().into());
+                    object.into()
+                })
+                .collect();
+            format!("{}\n", serde_json::Value::Array(forms)).into_bytes()
+        }
+        OutputFormat::Cbor => {
+            let forms = forms.iter().map(|form| {
+                let mut entries: Vec<(String, CborValue)> = form_fields(form)
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), CborValue::bignum(value)))
+                    .collect();
+                entries.push(("relation".to_string(), CborValue::Text(form.relation.to_string())));
+                CborValue::Map(entries)
+            });
+            CborValue::Array(forms.collect()).encode()
+        }
+    };
+    write_stdout(&bytes)
+}
+
+/// Prints the group structure and attack checks of `analyze`.
+fn analyze_curve(cli: &Cli, order: Option<&str>, max_embedding_degree: u64) -> Result<(), TransformError> {
+    let spec = curve_spec(cli)?;
+    let curve = WeierstrassCurve::new(&spec.a, &spec.b, &spec.p)?;
+    let order = order.map(|order| parse_param("order", order)).transpose()?;
+    let analysis = analyze(&curve, order.as_ref(), max_embedding_degree)?;
+    let mut results = vec![
+        ("p", Entry::Number(curve.p.clone())),
+        ("order", Entry::Number(analysis.order.clone())),
+        ("subgroup_order", Entry::Number(analysis.subgroup_order.clone())),
+        ("cofactor", Entry::Number(analysis.cofactor.clone())),
+        ("trace", Entry::Number(analysis.trace.clone())),
+        ("anomalous", Entry::Flag(analysis.is_anomalous())),
+        ("mov_vulnerable", Entry::Flag(analysis.is_mov_vulnerable())),
+    ];
+    // An anomalous curve has no embedding degree; otherwise report it or how far it was searched
+    match analysis.embedding_degree {
+        Some(degree) => results.push(("embedding_degree", Entry::Number(BigInt::from(degree)))),
+        None if !analysis.is_anomalous() => {
+            let cap = BigInt::from(analysis.embedding_degree_cap);
+            results.push(("embedding_degree_above", Entry::Number(cap)));
+        }
+        None => {}
+    }
+    let warnings = analysis.weaknesses();
+    write_report(&Report { results, intermediates: None, operations: None, warnings }, cli.format)
+}
+
+/// Prints the narrated small-subgroup, twist and invalid-curve attacks on the curve, against a victim
+/// using `countermeasures`.
+fn demo_attacks(
+    cli: &Cli,
+    seed: Option<u64>,
+    countermeasures: Countermeasures,
+) -> Result<(), TransformError> {
+    let spec = curve_spec(cli)?;
+    let curve = WeierstrassCurve::new(&spec.a, &spec.b, &spec.p)?;
+    let mut rng = match seed {
+        Some(seed) => DefaultRng::seed_from_u64(seed),
+        None => default_rng(),
+    };
+    let demo = demonstrate_attacks_with(&curve, countermeasures, &mut rng)?;
+    write_stdout(demo.to_string().as_bytes())
+}
+
+/// Prints the diagnostics of `info`; the order is only counted for p up to `MAX_COUNT_MODULUS`.
+fn info(cli: &Cli) -> Result<(), TransformError> {
+    let spec = curve_spec(cli)?;
+    let curve = WeierstrassCurve::new(&spec.a, &spec.b, &spec.p)?;
+    let p = &curve.p;
+    let mut results = vec![
+        ("p", Entry::Number(p.clone())),
+        ("p_bits", Entry::Number(BigInt::from(p.bits()))),
+        ("p_prime", Entry::Flag(is_probable_prime(p, MILLER_RABIN_ROUNDS))),
+        ("a", Entry::Number(curve.a.clone())),
+        ("b", Entry::Number(curve.b.clone())),
+        ("discriminant", Entry::Number(curve.discriminant())),
+        ("j_invariant", Entry::Number(curve.j_invariant())),
+    ];
+    if p <= &BigInt::from(MAX_COUNT_MODULUS) {
+        let order = count_points(&curve.a, &curve.b, p)?;
+        let subgroup_order = largest_prime_factor(&order);
+        results.push(("cofactor", Entry::Number(&order / &subgroup_order)));
+        results.push(("subgroup_order", Entry::Number(subgroup_order)));
+        results.push(("trace", Entry::Number(trace_of_frobenius(&order, p)?)));
+        results.push(("anomalous", Entry::Flag(&order == p)));
+        results.push(("order", Entry::Number(order)));
+    }
+    let options = options(cli)?;
+    let report = can_transform_with_rng(&curve, &options.limits, &mut default_rng())?;
+    results.push(("two_torsion_points", Entry::Number(BigInt::from(report.roots.len()))));
+    results.push(("montgomery_form", Entry::Flag(report.admits_montgomery_form())));
+    if report.admits_montgomery_form() {
+        // Every Montgomery curve is a twisted Edwards curve, and one whose `a` is a square can be scaled
+        // to a = 1; which Montgomery form is used matters, so prefer one that gives an Edwards curve
+        let mut candidates = Vec::new();
+        for form in montgomery_forms_with_rng(&curve.a, &curve.b, p, &options, &mut default_rng())? {
+            if form.relation == Relation::Isomorphic {
+                let edwards = MontgomeryCurve::new(&form.a, &form.b, p)?.twisted_edwards_coefficients();
+                candidates.push((form, edwards));
+            }
+        }
+        let edwards_form =
+            candidates.iter().position(|(_, (edwards_a, _))| legendre_symbol(edwards_a, p) == 1);
+        let (form, (edwards_a, edwards_d)) = candidates.swap_remove(edwards_form.unwrap_or(0));
+        results.push(("z0", Entry::Number(form.z0)));
+        results.push(("a_montgomery", Entry::Number(form.a)));
+        results.push(("b_montgomery", Entry::Number(form.b)));
+        results.push(("edwards_form", Entry::Flag(edwards_form.is_some())));
+        results.push(("edwards_a", Entry::Number(edwards_a)));
+        results.push(("edwards_d", Entry::Number(edwards_d)));
+    }
+    write_report(&Report { results, intermediates: None, operations: None, warnings: Vec::new() }, cli.format)
+}
+
+/// Prints why the curve does or does not admit a Montgomery form.
+fn admissibility(cli: &Cli) -> Result<ExitCode, TransformError> {
+    let spec = curve_spec(cli)?;
+    let curve = WeierstrassCurve::new(&spec.a, &spec.b, &spec.p)?;
+    let report = can_transform_with_rng(&curve, &options(cli)?.limits, &mut default_rng())?;
+    write_admissibility(&report, cli.format)?;
+    Ok(if report.admits_montgomery_form() {
+        ExitCode::SUCCESS
     } else {
-        let (gcd, x1, y1) = extended_gcd(b, &(a % b));
-        (gcd, y1.clone(), x1 - (a / b) * y1)
-    }
-}
-
-/// Computes the modular square root using the Tonelli-Shanks algorithm.
-/// Returns `None` if no square root exists.
-fn mod_sqrt(value: &BigInt, p: &BigInt) -> Option<BigInt> {
-    if value.is_zero() {
-        return Some(BigInt::zero());
-    }
-    if p == &BigInt::from(2) {
-        return Some(value.clone());
-    }
-    if value.modpow(&((p - 1u32) / 2u32), p) != BigInt::one() {
-        return None; // No square root exists
-    }
-    
-    let mut q = p - 1u32;
-    let mut s = 0;
-    while q.is_even() {
-        q /= 2u32;
-        s += 1;
-    }
-    
-    let z = (2u32..)
-        .map(BigInt::from)
-        .find(|n| n.modpow(&((p - 1u32) / 2u32), p) == p - 1u32)
-        .unwrap();
-    
-    let mut m = s;
-    let mut c = z.modpow(&q, p);
-    let mut t = value.modpow(&q, p);
-    let mut r = value.modpow(&((q + 1u32) / 2u32), p);
-
-    while t != BigInt::one() {
-        let mut i = 0;
-        let mut t2i = t.clone();
-        while t2i != BigInt::one() {
-            t2i = t2i.modpow(&BigInt::from(2), p);
-            i += 1;
-            if i == m {
-                return None;
-            }
-        }
-        
-        let b = c.modpow(&BigInt::from(1u32 << (m - i - 1)), p);
-        m = i;
-        c = b.modpow(&BigInt::from(2), p);
-        t = (t * &c) % p;
-        r = (r * b) % p;
-    }
-    
-    Some(r)
-}
-
-/// Transformation function from Weierstrass to Montgomery curve.
-fn transform_to_montgomery(
-    x: &BigInt,
-    y: &BigInt,
+        ExitCode::from(TransformError::NoMontgomeryForm(String::new()).exit_code())
+    })
+}
+
+/// Prints a generated curve and point as `a`, `b`, `p`, `x` and `y`, plus the group order if it was counted.
+fn generate(cli: &Cli, p: &str, seed: Option<u64>, prime_order: bool) -> Result<(), TransformError> {
+    let p = parse_param("p", p)?;
+    let limits = options(cli)?.limits;
+    let mut rng = match seed {
+        Some(seed) => DefaultRng::seed_from_u64(seed),
+        None => default_rng(),
+    };
+    let (spec, order) = if prime_order {
+        let (spec, order) = generate_curve_with_prime_subgroup(&p, &limits, &mut rng)?;
+        (spec, Some(order))
+    } else {
+        (generate_curve(&p, &limits, &mut rng)?, None)
+    };
+    let mut results = vec![
+        ("a", Entry::Number(spec.a)),
+        ("b", Entry::Number(spec.b)),
+        ("p", Entry::Number(spec.p)),
+        ("x", Entry::Number(spec.x)),
+        ("y", Entry::Number(spec.y)),
+    ];
+    if let Some(order) = order {
+        results.push(("subgroup_order", Entry::Number(&order / 4u32)));
+        results.push(("order", Entry::Number(order)));
+    }
+    write_curve(results, cli.format)
+}
+
+/// Prints `count` points drawn with a generator seeded by `seed`, each with its Montgomery image and the
+/// intermediates of the map: as text, a YAML file with the curve, the Montgomery coefficients and a
+/// `vectors` list; as JSON or CBOR, the same fields in an object or map.
+fn generate_vectors(cli: &Cli, curve: Option<&str>, count: usize, seed: u64) -> Result<(), TransformError> {
+    let CurveSpec { a, b, p, .. } = match curve {
+        Some(name) => named_spec(cli, name)?,
+        None => curve_spec(cli)?,
+    };
+    let options = options(cli)?;
+    let mut rng = DefaultRng::seed_from_u64(seed);
+    let map = MontgomeryMap::new_with_rng(&a, &b, &p, &options, &mut rng)?;
+    let field = weierstrass_to_montgomery::Field::new(&p);
+    let mut vectors = Vec::with_capacity(count);
+    for _ in 0..count {
+        let (x, y) = random_point(&field, &a, &b, &options.limits, &mut rng)?;
+        let (u, v) = map.map_point(&x, &y);
+        let point = vec![
+            ("x", Entry::Number(x.clone())),
+            ("y", Entry::Number(y)),
+            ("x_montgomery", Entry::Number(u)),
+            ("y_montgomery", Entry::Number(v)),
+        ];
+        vectors.push((point, intermediates(&map, &x)));
+    }
+    let header = vec![
+        ("seed", Entry::Number(BigInt::from(seed))),
+        ("a", Entry::Number(a)),
+        ("b", Entry::Number(b)),
+        ("p", Entry::Number(p)),
+        ("a_montgomery", Entry::Number(map.a_montgomery.clone())),
+        ("b_montgomery", Entry::Number(map.b_montgomery.clone())),
+    ];
+
+    let bytes = match cli.format {
+        OutputFormat::Text => {
+            let mut yaml: String =
+                header.iter().map(|(name, value)| format!("{}: {}\n", name, yaml_value(value))).collect();
+            yaml.push_str("vectors:\n");
+            for (point, intermediates) in &vectors {
+                for (index, (name, value)) in point.iter().enumerate() {
+                    let marker = if index == 0 { "  - " } else { "    " };
+                    yaml.push_str(&format!("{}{}: {}\n", marker, name, yaml_value(value)));
+                }
+                yaml.push_str("    intermediates:\n");
+                for (name, value) in intermediates {
+                    yaml.push_str(&format!("      {}: {}\n", name, yaml_value(value)));
+                }
+            }
+            yaml.into_bytes()
+        }
+        OutputFormat::Json => {
+            let to_object = |entries: &[(&str, Entry)]| -> serde_json::Map<String, serde_json::Value> {
+                entries.iter().map(|(name, value)| (name.to_string(), value.to_json())).collect()
+            };
+            let mut object = to_object(&header);
+            let vectors = vectors.iter().map(|(point, intermediates)| {
+                let mut vector = to_object(point);
+                vector.insert("intermediates".to_string(), to_object(intermediates).into());
+                serde_json::Value::Object(vector)
+            });
+            object.insert("vectors".to_string(), vectors.collect());
+            format!("{}\n", serde_json::Value::Object(object)).into_bytes()
+        }
+        OutputFormat::Cbor => {
+            let to_entries = |entries: &[(&str, Entry)]| -> Vec<(String, CborValue)> {
+                entries.iter().map(|(name, value)| (name.to_string(), value.to_cbor())).collect()
+            };
+            let mut map = to_entries(&header);
+            let vectors = vectors.iter().map(|(point, intermediates)| {
+                let mut vector = to_entries(point);
+                vector.push(("intermediates".to_string(), CborValue::Map(to_entries(intermediates))));
+                CborValue::Map(vector)
+            });
+            map.push(("vectors".to_string(), CborValue::Array(vectors.collect())));
+            CborValue::Map(map).encode()
+        }
+    };
+    write_stdout(&bytes)
+}
+
+/// Prints `count` fixtures drawn with a generator seeded by `seed`, always as JSON.
+fn write_fixtures(
+    cli: &Cli,
+    count: usize,
+    bits: u64,
+    points: usize,
+    seed: u64,
+) -> Result<(), TransformError> {
+    let limits = options(cli)?.limits;
+    let fixtures = generate_fixtures(count, bits, points, &limits, &mut DefaultRng::seed_from_u64(seed))?;
+    write_stdout(format!("{}\n", fixtures::to_json(&fixtures)).as_bytes())
+}
+
+/// Formats a value for YAML, which reads unquoted integers beyond 64 bits as floats, so those are quoted.
+fn yaml_value(value: &Entry) -> String {
+    match value {
+        Entry::Number(value) if i64::try_from(value).is_err() || radix() != Radix::Decimal => {
+            format!("\"{}\"", number(value))
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// Writes a curve and point like `write_report`, except that text output is a valid `--input` YAML file.
+fn write_curve(results: Vec<(&'static str, Entry)>, format: OutputFormat) -> Result<(), TransformError> {
+    if let OutputFormat::Text = format {
+        let yaml: String =
+            results.iter().map(|(name, value)| format!("{}: {}\n", name, yaml_value(value))).collect();
+        return write_stdout(yaml.as_bytes());
+    }
+    write_report(&Report { results, intermediates: None, operations: None, warnings: Vec::new() }, format)
+}
+
+/// Prints the curve and point of the flags or `--input` rewritten into the a = -3 form, with the scale u.
+fn normalize(cli: &Cli) -> Result<(), TransformError> {
+    let spec = curve_spec(cli)?;
+    let curve = WeierstrassCurve::new(&spec.a, &spec.b, &spec.p)?;
+    let point = curve.point(&spec.x, &spec.y)?;
+    let scaling = curve.normalize_a_minus_3()?.ok_or_else(|| {
+        let message = "-3/a is not a fourth power modulo p, so no u gives a = -3";
+        TransformError::InvalidParameters(message.to_string())
+    })?;
+    let Point::Affine(x, y) = scaling.map_point(&point) else {
+        unreachable!("affine points map to affine points");
+    };
+    let results = vec![
+        ("a", Entry::Number(scaling.curve.a)),
+        ("b", Entry::Number(scaling.curve.b)),
+        ("p", Entry::Number(scaling.curve.p)),
+        ("x", Entry::Number(x)),
+        ("y", Entry::Number(y)),
+        ("u", Entry::Number(scaling.u)),
+    ];
+    write_curve(results, cli.format)
+}
+
+/// Runs the transform modulo each factor of a composite p and prints the recombined result.
+fn composite(cli: &Cli, args: &TransformArgs) -> Result<(), TransformError> {
+    let CurveSpec { a, b, p, x, y } = curve_spec(cli)?;
+    let factors = args
+        .factors
+        .iter()
+        .map(|factor| parse_param("factors", factor))
+        .collect::<Result<Vec<_>, _>>()?;
+    let product: BigInt = factors.iter().product();
+    if product != p {
+        return Err(TransformError::InvalidParameters(format!(
+            "the factors multiply to {}, not to p = {}",
+            product, p
+        )));
+    }
+    let options = options(cli)?;
+    let (x_montgomery, y_montgomery, a_montgomery, b_montgomery) =
+        transform_to_montgomery_composite_with_rng(&x, &y, &a, &b, &factors, &options, &mut default_rng())?;
+    let report = Report {
+        results: vec![
+            ("x_montgomery", Entry::Number(x_montgomery)),
+            ("y_montgomery", Entry::Number(y_montgomery)),
+            ("a_montgomery", Entry::Number(a_montgomery)),
+            ("b_montgomery", Entry::Number(b_montgomery)),
+        ],
+        intermediates: None,
+        operations: None,
+        warnings: Vec::new(),
+    };
+    write_report(&report, cli.format)
+}
+
+/// Maps each `--point` with the one map and writes the Montgomery curve followed by the points and their
+/// images: `(x, y) -> (u, v)` lines under `points:` as text, or a `points` array of objects with `x`, `y`,
+/// `x_montgomery` and `y_montgomery`. Fails on the first point that is not on the curve.
+fn transform_points(
+    map: &MontgomeryMap,
+    points: &[(BigInt, BigInt)],
+    options: &TransformOptions,
+    format: OutputFormat,
+) -> Result<(), TransformError> {
+    if options.strict {
+        let mut coordinates = points.iter().flat_map(|(x, y)| [("x", x), ("y", y)]);
+        if let Some((name, value)) = coordinates.find(|(_, value)| &value.mod_floor(&map.p) != *value) {
+            let message = format!("{} = {} is not in the range [0, p)", name, value);
+            return Err(TransformError::InvalidParameters(message));
+        }
+    }
+    let mut images = Vec::with_capacity(points.len());
+    for (index, image) in map.map_points(points).into_iter().enumerate() {
+        let (x, y) = &points[index];
+        match image {
+            Ok(image) => images.push(image),
+            Err(error) => {
+                tracing::warn!(point = index + 1, %x, %y, "the point is not on the curve");
+                return Err(error);
+            }
+        }
+    }
+    let pairs = points.iter().zip(&images);
+    let bytes = match format {
+        OutputFormat::Text => {
+            let (a, b) = (&map.a_montgomery, &map.b_montgomery);
+            let mut text = format!("a_montgomery: {}\nb_montgomery: {}\npoints:\n", number(a), number(b));
+            for ((x, y), (u, v)) in pairs {
+                let (x, y, u, v) = (number(x), number(y), number(u), number(v));
+                text.push_str(&format!("  ({}, {}) -> ({}, {})\n", x, y, u, v));
+            }
+            text.into_bytes()
+        }
+        OutputFormat::Json => {
+            let points = pairs.map(|((x, y), (u, v))| {
+                serde_json::json!({
+                    "x": number(x),
+                    "y": number(y),
+                    "x_montgomery": number(u),
+                    "y_montgomery": number(v),
+                })
+            });
+            let object = serde_json::json!({
+                "a_montgomery": number(&map.a_montgomery),
+                "b_montgomery": number(&map.b_montgomery),
+                "points": points.collect::<Vec<_>>(),
+            });
+            format!("{}\n", object).into_bytes()
+        }
+        OutputFormat::Cbor => {
+            let points = pairs.map(|((x, y), (u, v))| {
+                CborValue::Map(vec![
+                    ("x".to_string(), CborValue::bignum(x)),
+                    ("y".to_string(), CborValue::bignum(y)),
+                    ("x_montgomery".to_string(), CborValue::bignum(u)),
+                    ("y_montgomery".to_string(), CborValue::bignum(v)),
+                ])
+            });
+            CborValue::Map(vec![
+                ("a_montgomery".to_string(), CborValue::bignum(&map.a_montgomery)),
+                ("b_montgomery".to_string(), CborValue::bignum(&map.b_montgomery)),
+                ("points".to_string(), CborValue::Array(points.collect())),
+            ])
+            .encode()
+        }
+    };
+    write_stdout(&bytes)
+}
+
+/// The root z0 of the cubic with 3·z0^2 + a = s^2, for `--scale` without `--z0`.
+fn root_for_scale(
     a: &BigInt,
     b: &BigInt,
     p: &BigInt,
-) -> Option<(BigInt, BigInt, BigInt, BigInt)> {
-    //Find a root z0 of the polynomial z^3 + az + b in the field F_p
-    let mut rng = rand::thread_rng();
-    let z0 = loop {
-        let candidate = rng.gen_bigint_range(&BigInt::zero(), p);
-        if (&candidate.pow(3) + a * &candidate + b).mod_floor(p).is_zero() {
-            println!("Found z0: {}", candidate); // Debug print
-            break candidate;
+    s: &BigInt,
+    options: &TransformOptions,
+) -> Result<BigInt, TransformError> {
+    let s_squared = (s * s).mod_floor(p);
+    let roots = cubic_roots(a, b, p, &options.limits, &mut default_rng())?;
+    let root = roots.iter().find(|z0| (BigInt::from(3) * *z0 * *z0 + a).mod_floor(p) == s_squared);
+    root.cloned().ok_or_else(|| {
+        let roots: Vec<String> = roots.iter().map(BigInt::to_string).collect();
+        let roots = roots.join(", ");
+        let message = format!("s^2 = {} is not 3*z0^2 + a for a root z0 of the cubic ({})", s_squared, roots);
+        TransformError::InvalidParameters(message)
+    })
+}
+
+/// Transforms the curve and point given by the flags or `--input`, then prints and plots the result.
+fn transform(cli: &Cli, args: &TransformArgs) -> Result<ExitCode, TransformError> {
+    if args.allow_composite {
+        return composite(cli, args).map(|()| ExitCode::SUCCESS);
+    }
+    let CurveSpec { a, b, p, x, y } = curve_spec(cli)?;
+
+    let options = options(cli)?;
+    let z0 = args.z0.as_deref().map(|z0| parse_param("z0", z0)).transpose()?;
+    let s = args.scale.as_deref().map(|s| parse_param("s", s)).transpose()?;
+    let z0 = match (z0, &s) {
+        (None, Some(s)) => Some(root_for_scale(&a, &b, &p, s, &options)?),
+        (z0, _) => z0,
+    };
+    if !args.points.is_empty() {
+        let map = match (&z0, &s) {
+            (Some(z0), Some(s)) => MontgomeryMap::from_root_and_scale(&a, &b, &p, z0, s, &options)?,
+            (Some(z0), None) => MontgomeryMap::from_root(&a, &b, &p, z0, &options)?,
+            (None, _) => MontgomeryMap::new_with_rng(&a, &b, &p, &options, &mut default_rng())?,
+        };
+        return transform_points(&map, &args.points, &options, cli.format).map(|()| ExitCode::SUCCESS);
+    }
+    let (transformed, transform_ops) = count_ops(|| match (&z0, &s) {
+        (Some(z0), Some(s)) => transform_with_scale(&x, &y, &a, &b, &p, z0, s, &options),
+        (Some(z0), None) => transform_with_root(&x, &y, &a, &b, &p, z0, &options),
+        (None, _) => transform_with_map(&x, &y, &a, &b, &p, &options, &mut default_rng()),
+    });
+    let (mut x_montgomery, mut y_montgomery, map) = transformed?;
+    let operations = args
+        .op_count
+        .then(|| operation_counts(&map, &x, &y, &x_montgomery, &y_montgomery, transform_ops))
+        .transpose()?;
+    if let Some(emit) = args.emit {
+        let text = match emit {
+            Emit::Formulas => formulas(&map),
+            Emit::Rust => rust_snippet(&map),
+            Emit::Python => python_snippet(&map),
+            Emit::Pari => pari_script(&map),
+            Emit::Magma => magma_script(&map),
+            Emit::Map => format!("{}\n", map.to_json()),
+        };
+        return write_stdout(text.as_bytes()).map(|()| ExitCode::SUCCESS);
+    }
+    let point_order = if args.check_order {
+        Some(check_order(&map, &x, &y, &x_montgomery, &y_montgomery, args.order.as_deref(), &options)?)
+    } else {
+        None
+    };
+    let group = args.check_group.then(|| check_map_exhaustively(&map)).transpose()?;
+    let (mut a_montgomery, mut b_montgomery) = (map.a_montgomery.clone(), map.b_montgomery.clone());
+    if args.canonical || args.to_jwk {
+        let rescaling = MontgomeryCurve::new(&a_montgomery, &b_montgomery, &p)?.canonicalize()?;
+        let image = rescaling.map_point(&Point::Affine(x_montgomery.clone(), y_montgomery.clone()));
+        if let Point::Affine(u, v) = image {
+            (x_montgomery, y_montgomery) = (u, v);
         }
+        (a_montgomery, b_montgomery) = (rescaling.curve.a, rescaling.curve.b);
+    }
+    if args.to_jwk {
+        let curve = MontgomeryCurve::new(&a_montgomery, &b_montgomery, &p)?;
+        let jwk = okp_jwk(&curve, &Point::Affine(x_montgomery, y_montgomery))?;
+        return write_stdout(format!("{}\n", jwk).as_bytes()).map(|()| ExitCode::SUCCESS);
+    }
+    let fingerprint = args.fingerprint.then(|| {
+        let image = Point::Affine(x_montgomery.clone(), y_montgomery.clone());
+        ("fingerprint", Entry::Text(short_fingerprint(&image, &p)))
+    });
+    let mut results = vec![
+        ("x_montgomery", Entry::Number(x_montgomery)),
+        ("y_montgomery", Entry::Number(y_montgomery)),
+        ("a_montgomery", Entry::Number(a_montgomery.clone())),
+        ("b_montgomery", Entry::Number(b_montgomery.clone())),
+    ];
+    results.extend(fingerprint);
+    let round_trip = match args.via {
+        Some(Via::Edwards) => via_edwards(&map, &x, &y, &mut results)?,
+        None => true,
+    };
+    // The order of a named curve's base point, with the group order when the cofactor is known too
+    let (named_order, group_order) = match &cli.curve {
+        Some(name) => named_order(cli, name)?,
+        None => (None, None),
+    };
+    let warnings = match point_order.as_ref().or(named_order.as_ref()) {
+        Some(order) => weaknesses(&p, order, group_order.as_ref()),
+        None => Vec::new(),
+    };
+    let deny = args.deny_warnings && !warnings.is_empty();
+    if let Some(order) = point_order {
+        results.push(("point_order", Entry::Number(order)));
+    }
+    if let Some(group) = group {
+        results.push(("group_order", Entry::Number(BigInt::from(group.order()))));
+        results.push(("group_structure", Entry::Text(group.to_string())));
+    }
+    let intermediates = args.intermediates.then(|| intermediates(&map, &x));
+    let report = Report { results, intermediates, operations, warnings };
+    write_report(&report, cli.format)?;
+
+    match &args.plot {
+        Some(PlotTarget::Ascii) => {
+            let mapped = args.plot_mapped.then_some((&a_montgomery, &b_montgomery));
+            print!("\n{}", render_ascii(&a, &b, &p, mapped)?);
+        }
+        Some(PlotTarget::Svg(path)) => {
+            let svg = render_svg(&a, &b, &p, &a_montgomery, &b_montgomery)?;
+            std::fs::write(path, svg).map_err(|error| {
+                TransformError::Io(format!("cannot write plot to {}: {}", path.display(), error))
+            })?;
+        }
+        None => {}
+    }
+    Ok(if deny {
+        ExitCode::from(6)
+    } else if !round_trip {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+/// Maps (x, y) with `map` onto the Montgomery curve, on to its twisted Edwards curve and back the same
+/// way, adds the Edwards curve and image to `results`, with the neutral element as (0, 1), and returns
+/// whether the point came back unchanged.
+fn via_edwards(
+    map: &MontgomeryMap,
+    x: &BigInt,
+    y: &BigInt,
+    results: &mut Vec<(&'static str, Entry)>,
+) -> Result<bool, TransformError> {
+    let p = &map.p;
+    let curve = MontgomeryCurve::new(&map.a_montgomery, &map.b_montgomery, p)?;
+    let edwards = curve.to_twisted_edwards();
+    let route = map.forward().compose(MontgomeryToEdwards { curve });
+    let point = Point::Affine(x.mod_floor(p), y.mod_floor(p));
+    let image = route.apply(&point).expect("the maps are total");
+    let back = route.invert().apply(&image).expect("the maps are total");
+    let (x_edwards, y_edwards) = match image {
+        Point::Infinity => (BigInt::from(0), BigInt::from(1)),
+        Point::Affine(x, y) => (x, y),
     };
+    let round_trip = back == point;
+    if !round_trip {
+        tracing::warn!(%x_edwards, %y_edwards, "the point does not come back from the Edwards curve");
+    }
+    results.extend([
+        ("a_edwards", Entry::Number(edwards.a)),
+        ("d_edwards", Entry::Number(edwards.d)),
+        ("x_edwards", Entry::Number(x_edwards)),
+        ("y_edwards", Entry::Number(y_edwards)),
+        ("round_trip", Entry::Flag(round_trip)),
+    ]);
+    Ok(round_trip)
+}
 
-    // Compute s = (sqrt(3 * z0^2 + a))^{-1} modulo p
-    let s_squared = (BigInt::from(3) * &z0 * &z0 + a).mod_floor(p);
-    println!("s_squared: {}", s_squared); // Debug print
+/// The field operations of the transform, `transform_ops`, followed by those of multiplying (x, y) and its
+/// image (u, v) by p with `scalar_mul`, and u with `MontgomeryCurve::ladder`.
+fn operation_counts(
+    map: &MontgomeryMap,
+    x: &BigInt,
+    y: &BigInt,
+    u: &BigInt,
+    v: &BigInt,
+    transform_ops: OpCounts,
+) -> Result<Vec<(&'static str, OpCounts)>, TransformError> {
+    let p = &map.p;
+    let weierstrass = WeierstrassCurve::new(&map.a, &map.b, p)?;
+    let montgomery = MontgomeryCurve::new(&map.a_montgomery, &map.b_montgomery, p)?;
+    let point = weierstrass.point(x, y)?;
+    let image = montgomery.point(u, v)?;
+    let (_, weierstrass_ops) = count_ops(|| scalar_mul(&weierstrass, &point, p));
+    let (_, montgomery_ops) = count_ops(|| scalar_mul(&montgomery, &image, p));
+    let (_, ladder_ops) = count_ops(|| montgomery.ladder(u, p));
+    Ok(vec![
+        ("transform", transform_ops),
+        ("scalar_mul_weierstrass", weierstrass_ops),
+        ("scalar_mul_montgomery", montgomery_ops),
+        ("ladder", ladder_ops),
+    ])
+}
 
-    let s = mod_sqrt(&s_squared, p)?;
-    println!("s: {}", s); // Debug print
+/// Computes the orders of (x, y) and of its image (u, v) under `map`, failing unless they are equal.
+fn check_order(
+    map: &MontgomeryMap,
+    x: &BigInt,
+    y: &BigInt,
+    u: &BigInt,
+    v: &BigInt,
+    multiple: Option<&str>,
+    options: &TransformOptions,
+) -> Result<BigInt, TransformError> {
+    let multiple = multiple.map(|multiple| parse_param("order", multiple)).transpose()?;
+    let weierstrass = WeierstrassCurve::new(&map.a, &map.b, &map.p)?;
+    let montgomery = MontgomeryCurve::new(&map.a_montgomery, &map.b_montgomery, &map.p)?;
+    let order = point_order(&weierstrass, &weierstrass.point(x, y)?, multiple.as_ref(), &options.limits)?;
+    let image_order = point_order(&montgomery, &montgomery.point(u, v)?, multiple.as_ref(), &options.limits)?;
+    if order != image_order {
+        return Err(TransformError::InvalidParameters(format!(
+            "the map does not preserve the order of the point: {} before, {} after",
+            order, image_order
+        )));
+    }
+    Ok(order)
+}
 
-    let s_inv = mod_inverse(&s, p)?;
-    println!("s_inv: {}", s_inv); // Debug print
+/// Reads a map written by `transform --emit map`.
+fn read_map(path: &Path) -> Result<MontgomeryMap, TransformError> {
+    let json = String::from_utf8(read_file(path)?)
+        .map_err(|_| TransformError::InvalidParameters(format!("{} is not UTF-8", path.display())))?;
+    MontgomeryMap::from_json(&json)
+}
 
-    // Compute the new parameters a and b
-    let a_montgomery = (BigInt::from(3) * &z0 * &s_inv).mod_floor(p);
-    let b_montgomery = s_inv.mod_floor(p);
+/// Maps (u, v) back to the Weierstrass curve with the map of `--map`, or a map of the curve of the flags,
+/// and prints `x` and `y`.
+fn inverse(cli: &Cli, u: &str, v: &str, map: Option<&Path>) -> Result<(), TransformError> {
+    let map = match map {
+        Some(path) => read_map(path)?,
+        None => {
+            let CurveSpec { a, b, p, .. } = curve_spec(cli)?;
+            MontgomeryMap::new_with_rng(&a, &b, &p, &options(cli)?, &mut default_rng())?
+        }
+    };
+    let curve = MontgomeryCurve::new(&map.a_montgomery, &map.b_montgomery, &map.p)?;
+    let Point::Affine(u, v) = curve.point(&parse_param("u", u)?, &parse_param("v", v)?)? else {
+        unreachable!("curve points built from coordinates are affine");
+    };
+    let (x, y) = map.inverse_point(&u, &v);
+    let report = Report {
+        results: vec![
+            ("x", Entry::Number(x)),
+            ("y", Entry::Number(y)),
+            ("a", Entry::Number(map.a.clone())),
+            ("b", Entry::Number(map.b.clone())),
+        ],
+        intermediates: None,
+        operations: None,
+        warnings: Vec::new(),
+    };
+    write_report(&report, cli.format)
+}
 
-    // Map (x, y) to (x_montgomery, y_montgomery) on the Montgomery curve
-    let x_montgomery = (s_inv.clone() * (x - &z0)).mod_floor(p); // Clone s_inv for the first usage
-    let y_montgomery = (s_inv * y).mod_floor(p);                 // Use s_inv directly for the second usage
-    Some((x_montgomery, y_montgomery, a_montgomery, b_montgomery))
+/// Prints whether the point of the flags maps to (u, v) under the map of `--map`, or under any map of its
+/// curve, with the `z0` and `s` of the map that does; exits with 1 when none does.
+fn verify(cli: &Cli, u: &str, v: &str, map: Option<&Path>) -> Result<ExitCode, TransformError> {
+    let CurveSpec { a, b, p, x, y } = curve_spec(cli)?;
+    let curve = WeierstrassCurve::new(&a, &b, &p)?;
+    let Point::Affine(x, y) = curve.point(&x, &y)? else {
+        unreachable!("curve points built from coordinates are affine");
+    };
+    let expected = (parse_param("u", u)?.mod_floor(&p), parse_param("v", v)?.mod_floor(&p));
+    let options = options(cli)?;
+    let maps = match map {
+        Some(path) => {
+            let map = read_map(path)?;
+            if (&map.a, &map.b, &map.p) != (&a, &b, &p) {
+                return Err(TransformError::InvalidParameters(
+                    "the map is for another curve than the one given".to_string(),
+                ));
+            }
+            vec![map]
+        }
+        None => {
+            // Negating s negates the image, so each root's map stands for both signs
+            let mut maps = Vec::new();
+            for z0 in cubic_roots(&a, &b, &p, &options.limits, &mut default_rng())? {
+                match MontgomeryMap::from_root(&a, &b, &p, &z0, &options) {
+                    Ok(map) => {
+                        let negate = |value: &BigInt| (-value).mod_floor(&p);
+                        let negated = MontgomeryMap {
+                            s: negate(&map.s),
+                            s_inv: negate(&map.s_inv),
+                            a_montgomery: negate(&map.a_montgomery),
+                            b_montgomery: negate(&map.b_montgomery),
+                            ..map.clone()
+                        };
+                        maps.push(map);
+                        maps.push(negated);
+                    }
+                    Err(TransformError::NoMontgomeryForm(_)) => {}
+                    Err(error) => return Err(error),
+                }
+            }
+            maps
+        }
+    };
+    let found = maps.into_iter().find(|map| map.map_point(&x, &y) == expected);
+    let mut results = vec![("matches", Entry::Flag(found.is_some()))];
+    if let Some(map) = &found {
+        results.push(("z0", Entry::Number(map.z0.clone())));
+        results.push(("s", Entry::Number(map.s.clone())));
+    }
+    let report = Report { results, intermediates: None, operations: None, warnings: Vec::new() };
+    write_report(&report, cli.format)?;
+    Ok(if found.is_some() { ExitCode::SUCCESS } else { ExitCode::from(1) })
 }
 
-fn main() {
-    // Example values for a Weierstrass curve over F_p
-    let a = BigInt::from_str("8").unwrap();
-    let b = BigInt::from_str("2").unwrap();
-    let p = BigInt::from_str("17").unwrap(); // Example prime modulus
+/// Streams the points of `path` (or stdin) through the map of `--map`, or a map of the curve of the flags,
+/// writing `u v` lines or JSON Lines; exits with 1 when some point is not on the curve.
+fn batch(
+    cli: &Cli,
+    path: Option<&Path>,
+    chunk: usize,
+    threads: usize,
+    map: Option<&Path>,
+    labels: bool,
+) -> Result<ExitCode, TransformError> {
+    if let OutputFormat::Cbor = cli.format {
+        let message = "batch writes text or JSON lines, not CBOR".to_string();
+        return Err(TransformError::InvalidParameters(message));
+    }
+    let map = match map {
+        Some(path) => read_map(path)?,
+        None => {
+            let CurveSpec { a, b, p, .. } = curve_spec(cli)?;
+            MontgomeryMap::new_with_rng(&a, &b, &p, &options(cli)?, &mut default_rng())?
+        }
+    };
+    let input: Box<dyn BufRead> = match path {
+        Some(path) if path != Path::new("-") => Box::new(BufReader::new(std::fs::File::open(path).map_err(
+            |error| TransformError::Io(format!("cannot read {}: {}", path.display(), error)),
+        )?)),
+        _ => Box::new(std::io::stdin().lock()),
+    };
+    let mut output = BufWriter::new(std::io::stdout().lock());
+    let json = matches!(cli.format, OutputFormat::Json);
+    let mut sink = |line: u64, label: &str, image: Result<(BigInt, BigInt), TransformError>| {
+        let written = match (image, json) {
+            (Ok((u, v)), false) if label.is_empty() => writeln!(output, "{} {}", number(&u), number(&v)),
+            (Ok((u, v)), false) => writeln!(output, "{} {} {}", number(&u), number(&v), label),
+            (Ok((u, v)), true) => {
+                let mut object = serde_json::json!({"u": number(&u), "v": number(&v)});
+                if labels {
+                    object["label"] = label.into();
+                }
+                writeln!(output, "{}", object)
+            }
+            (Err(error), false) => writeln!(output, "error: line {}: {}", line, error),
+            (Err(error), true) => {
+                let mut object = serde_json::json!({
+                    "line": line,
+                    "error": error.kind(),
+                    "message": error.to_string(),
+                });
+                if labels {
+                    object["label"] = label.into();
+                }
+                writeln!(output, "{}", object)
+            }
+        };
+        written.map_err(|error| TransformError::Io(format!("cannot write output: {}", error)))
+    };
+    let summary = if labels {
+        map_labeled_lines(&map, input, chunk, threads, &mut sink)?
+    } else {
+        map_lines(&map, input, chunk, threads, |line, image| sink(line, "", image))?
+    };
+    output
+        .flush()
+        .map_err(|error| TransformError::Io(format!("cannot write output: {}", error)))?;
+    let BatchSummary { points, failures, malformed } = summary;
+    tracing::info!(points, failures, malformed, "batch done");
+    Ok(if failures == 0 { ExitCode::SUCCESS } else { ExitCode::from(1) })
+}
 
-    let x = BigInt::from_str("14").unwrap();
-    let y = BigInt::from_str("6").unwrap();
+/// Prints the short fingerprint and the SHA-256 hash of the point (x, y) modulo the curve's p.
+fn fingerprint_point(cli: &Cli, x: &str, y: &str) -> Result<(), TransformError> {
+    let CurveSpec { p, .. } = curve_spec(cli)?;
+    let point = Point::Affine(parse_param("x", x)?, parse_param("y", y)?);
+    let digest: String = fingerprint(&point, &p).iter().map(|byte| format!("{:02x}", byte)).collect();
+    let results = vec![
+        ("fingerprint", Entry::Text(short_fingerprint(&point, &p))),
+        ("sha256", Entry::Text(digest)),
+    ];
+    write_report(&Report { results, intermediates: None, operations: None, warnings: Vec::new() }, cli.format)
+}
+
+/// Prints one line per known-answer test and returns whether all of them passed.
+fn selftest() -> bool {
+    let results = run_selftest();
+    for result in &results {
+        match &result.failure {
+            None => println!("PASS  {}", result.name),
+            Some(failure) => println!("FAIL  {}: {}", result.name, failure),
+        }
+    }
+    let passed = results.iter().filter(|result| result.passed()).count();
+    println!("{}/{} known-answer tests passed", passed, results.len());
+    passed == results.len()
+}
+
+fn main() -> ExitCode {
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(error) => return report_usage_error(error),
+    };
+    init_tracing(cli.verbose, !cli.no_progress);
+    let _ = NUMBER_FORMAT.set(NumberFormat { radix: cli.radix, uppercase: cli.uppercase });
+
+    match cli.command {
+        Some(Command::Selftest) => return if selftest() { ExitCode::SUCCESS } else { ExitCode::FAILURE },
+        Some(Command::Completions { shell }) => {
+            let mut command = Cli::command();
+            let name = command.get_name().to_string();
+            clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
+            return ExitCode::SUCCESS;
+        }
+        _ => {}
+    }
 
-    match transform_to_montgomery(&x, &y, &a, &b, &p) {
-        Some((x_montgomery, y_montgomery, a_montgomery, b_montgomery)) => {
-            println!("x_montgomery: {}", x_montgomery);
-            println!("y_montgomery: {}", y_montgomery);
-            println!("a_montgomery: {}", a_montgomery);
-           println!("b_montgomery: {}", b_montgomery);
+    match run(&cli) {
+        Ok(code) => code,
+        Err(error) => {
+            let obstruction = match error {
+                TransformError::NoMontgomeryForm(_) => obstruction(&cli),
+                _ => None,
+            };
+            report_error(&error, cli.error_format, obstruction);
+            ExitCode::from(error.exit_code())
         }
-        None => println!("No valid transformation found."),
     }
 }
@@ -0,0 +1,401 @@
+use crate::curve::Point;
+use crate::error::TransformError;
+#[cfg(feature = "parallel")]
+use crate::log::debug;
+use crate::ops;
+#[cfg(feature = "std")]
+use crate::numtheory::{is_probable_prime, MILLER_RABIN_ROUNDS};
+#[cfg(feature = "std")]
+use crate::transform::{derive_map, derive_map_from_root, TransformOptions};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use core::str::FromStr;
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+#[cfg(feature = "std")]
+use rand::rngs::OsRng;
+#[cfg(feature = "std")]
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Version tag written by `to_json` and `to_bytes`; readers reject any other version.
+pub const MAP_FORMAT_VERSION: u32 = 1;
+
+/// Magic prefix of the binary encoding.
+const MAGIC: &[u8; 4] = b"WMAP";
+
+/// The isomorphism from y^2 = x^3 + ax + b to B·v^2 = u^3 + A·u^2 + u over F_p, with the values it was built from.
+///
+/// A point maps as (x, y) -> (s_inv·(x − z0), s_inv·y), where z0 is a root of z^3 + az + b and
+/// s^2 = 3·z0^2 + a; then A = 3·z0·s_inv and B = s_inv.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MontgomeryMap {
+    pub p: BigInt,
+    pub a: BigInt,
+    pub b: BigInt,
+    pub z0: BigInt,
+    pub s: BigInt,
+    pub s_inv: BigInt,
+    pub a_montgomery: BigInt,
+    pub b_montgomery: BigInt,
+}
+
+/// The JSON schema, with every number as a decimal string so that no precision is lost.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MapRecord {
+    version: u32,
+    p: String,
+    a: String,
+    b: String,
+    z0: String,
+    s: String,
+    s_inv: String,
+    #[serde(rename = "A")]
+    a_montgomery: String,
+    #[serde(rename = "B")]
+    b_montgomery: String,
+}
+
+/// One direction of a `MontgomeryMap` on its own, (x, y) -> (scale·x + offset, scale·y) mod p, to store
+/// or pass around without the rest of the map. The forward map has scale s_inv and offset −s_inv·z0, the
+/// inverse scale s and offset z0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PointMap {
+    pub p: BigInt,
+    pub scale: BigInt,
+    /// The inverse of `scale`, kept so that `inverse` needs no inversion.
+    pub scale_inv: BigInt,
+    pub offset: BigInt,
+}
+
+impl PointMap {
+    /// Maps the affine point (x, y).
+    pub fn apply_coordinates(&self, x: &BigInt, y: &BigInt) -> (BigInt, BigInt) {
+        ops::record(2, 0);
+        ((&self.scale * x + &self.offset).mod_floor(&self.p), (&self.scale * y).mod_floor(&self.p))
+    }
+
+    /// Maps `point`; the point at infinity maps to itself.
+    pub fn apply(&self, point: &Point) -> Point {
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(x, y) => {
+                let (x, y) = self.apply_coordinates(x, y);
+                Point::Affine(x, y)
+            }
+        }
+    }
+
+    /// The map in the other direction.
+    pub fn inverse(&self) -> PointMap {
+        PointMap {
+            p: self.p.clone(),
+            scale: self.scale_inv.clone(),
+            scale_inv: self.scale.clone(),
+            offset: (-&self.scale_inv * &self.offset).mod_floor(&self.p),
+        }
+    }
+
+    /// `self` followed by `next`, as one map; both must be over the same field.
+    pub fn then(&self, next: &PointMap) -> PointMap {
+        PointMap {
+            p: self.p.clone(),
+            scale: (&next.scale * &self.scale).mod_floor(&self.p),
+            scale_inv: (&self.scale_inv * &next.scale_inv).mod_floor(&self.p),
+            offset: (&next.scale * &self.offset + &next.offset).mod_floor(&self.p),
+        }
+    }
+}
+
+fn invalid(message: String) -> TransformError {
+    TransformError::InvalidParameters(message)
+}
+
+#[cfg(feature = "serde")]
+fn parse_decimal(name: &str, value: &str) -> Result<BigInt, TransformError> {
+    BigInt::from_str(value).map_err(|_| invalid(format!("map field {} is not an integer: {:?}", name, value)))
+}
+
+impl MontgomeryMap {
+    /// Computes the map for the curve y^2 = x^3 + ax + b over F_p.
+    #[cfg(feature = "std")]
+    pub fn new(
+        a: &BigInt,
+        b: &BigInt,
+        p: &BigInt,
+        options: &TransformOptions,
+    ) -> Result<MontgomeryMap, TransformError> {
+        MontgomeryMap::new_with_rng(a, b, p, options, &mut OsRng)
+    }
+
+    /// `MontgomeryMap::new`, drawing randomness for the root search from `rng`.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+    pub fn new_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        a: &BigInt,
+        b: &BigInt,
+        p: &BigInt,
+        options: &TransformOptions,
+        rng: &mut R,
+    ) -> Result<MontgomeryMap, TransformError> {
+        derive_map(a, b, p, options, rng)
+    }
+
+    /// Computes the map from a known root z0 of z^3 + az + b instead of searching for one.
+    ///
+    /// Useful to pin the map to a particular root when the cubic has several, or to skip root finding
+    /// when z0 is already known (for Wei25519 it is A/3 of Curve25519).
+    #[cfg(feature = "std")]
+    pub fn from_root(
+        a: &BigInt,
+        b: &BigInt,
+        p: &BigInt,
+        z0: &BigInt,
+        options: &TransformOptions,
+    ) -> Result<MontgomeryMap, TransformError> {
+        derive_map_from_root(a, b, p, z0, None, options)
+    }
+
+    /// Computes the map from a known root z0 and scale s, for a standard that fixes the map exactly;
+    /// s must square to 3·z0^2 + a. `from_root` takes one of the two square roots, which may be −s.
+    #[cfg(feature = "std")]
+    pub fn from_root_and_scale(
+        a: &BigInt,
+        b: &BigInt,
+        p: &BigInt,
+        z0: &BigInt,
+        s: &BigInt,
+        options: &TransformOptions,
+    ) -> Result<MontgomeryMap, TransformError> {
+        derive_map_from_root(a, b, p, z0, Some(s), options)
+    }
+
+    /// Maps the point (x, y) of the Weierstrass curve to (u, v) on the Montgomery curve.
+    pub fn map_point(&self, x: &BigInt, y: &BigInt) -> (BigInt, BigInt) {
+        ops::record(2, 0);
+        (
+            (&self.s_inv * (x - &self.z0)).mod_floor(&self.p),
+            (&self.s_inv * y).mod_floor(&self.p),
+        )
+    }
+
+    /// Maps the point (u, v) of the Montgomery curve back to (x, y) = (s·u + z0, s·v) on the Weierstrass
+    /// curve.
+    pub fn inverse_point(&self, u: &BigInt, v: &BigInt) -> (BigInt, BigInt) {
+        ops::record(2, 0);
+        ((&self.s * u + &self.z0).mod_floor(&self.p), (&self.s * v).mod_floor(&self.p))
+    }
+
+    /// The map from the Weierstrass to the Montgomery curve, (x, y) -> (s_inv·(x − z0), s_inv·y).
+    pub fn forward(&self) -> PointMap {
+        PointMap {
+            p: self.p.clone(),
+            scale: self.s_inv.clone(),
+            scale_inv: self.s.clone(),
+            offset: (-&self.s_inv * &self.z0).mod_floor(&self.p),
+        }
+    }
+
+    /// The map from the Montgomery back to the Weierstrass curve, (u, v) -> (s·u + z0, s·v).
+    pub fn inverse(&self) -> PointMap {
+        PointMap {
+            p: self.p.clone(),
+            scale: self.s.clone(),
+            scale_inv: self.s_inv.clone(),
+            offset: self.z0.clone(),
+        }
+    }
+
+    /// Maps a batch of points of the Weierstrass curve, checking each against y^2 = x^3 + ax + b; a point
+    /// off the curve gets `Err(PointNotOnCurve)` in its slot without stopping the others.
+    pub fn map_points(&self, points: &[(BigInt, BigInt)]) -> Vec<Result<(BigInt, BigInt), TransformError>> {
+        points
+            .iter()
+            .map(|(x, y)| {
+                if !(y * y - x * x * x - &self.a * x - &self.b).mod_floor(&self.p).is_zero() {
+                    return Err(TransformError::PointNotOnCurve);
+                }
+                Ok(self.map_point(x, y))
+            })
+            .collect()
+    }
+
+    /// `map_points` spread over `threads` scoped threads (all available cores when 0), each mapping one
+    /// contiguous chunk of `points`; the results keep their order. Needs nothing beyond `std::thread`.
+    #[cfg(feature = "parallel")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+    pub fn map_points_parallel(
+        &self,
+        points: &[(BigInt, BigInt)],
+        threads: usize,
+    ) -> Vec<Result<(BigInt, BigInt), TransformError>> {
+        let threads = match threads {
+            0 => std::thread::available_parallelism().map_or(1, |count| count.get()),
+            threads => threads,
+        };
+        let chunk = points.len().div_ceil(threads).max(1);
+        debug!(threads, chunk, "mapping points in parallel");
+        std::thread::scope(|scope| {
+            let workers: Vec<_> =
+                points.chunks(chunk).map(|chunk| scope.spawn(|| self.map_points(chunk))).collect();
+            let joined = workers.into_iter().map(|worker| worker.join().expect("mapping does not panic"));
+            joined.flatten().collect()
+        })
+    }
+
+    /// Checks that the stored values are canonical and consistent with each other, and with the `std`
+    /// feature that p is a probable prime.
+    ///
+    /// Deserialization runs this, so a corrupted or hand-edited record is rejected instead of mapping points wrongly.
+    pub fn validate(&self) -> Result<(), TransformError> {
+        let p = &self.p;
+        if p <= &BigInt::from(3) {
+            return Err(invalid(format!("map modulus {} must be a prime greater than 3", p)));
+        }
+        #[cfg(feature = "std")]
+        if !is_probable_prime(p, MILLER_RABIN_ROUNDS) {
+            return Err(invalid(format!("map modulus {} is not a prime", p)));
+        }
+        let fields = [
+            ("a", &self.a),
+            ("b", &self.b),
+            ("z0", &self.z0),
+            ("s", &self.s),
+            ("s_inv", &self.s_inv),
+            ("A", &self.a_montgomery),
+            ("B", &self.b_montgomery),
+        ];
+        for (name, value) in fields {
+            if value < &BigInt::zero() || value >= p {
+                return Err(invalid(format!("map field {} = {} is not in the range [0, p)", name, value)));
+            }
+        }
+        let checks = [
+            ("z0 is not a root of z^3 + az + b", &self.z0 * &self.z0 * &self.z0 + &self.a * &self.z0 + &self.b),
+            ("s^2 != 3*z0^2 + a", &self.s * &self.s - BigInt::from(3) * &self.z0 * &self.z0 - &self.a),
+            ("s * s_inv != 1", &self.s * &self.s_inv - BigInt::one()),
+            ("A != 3*z0*s_inv", &self.a_montgomery - BigInt::from(3) * &self.z0 * &self.s_inv),
+            ("B != s_inv", &self.b_montgomery - &self.s_inv),
+        ];
+        for (message, difference) in checks {
+            if !difference.mod_floor(p).is_zero() {
+                return Err(invalid(format!("inconsistent map: {}", message)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Serializes the map as a JSON object with a `version` tag and decimal-string values.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        let record = MapRecord {
+            version: MAP_FORMAT_VERSION,
+            p: self.p.to_string(),
+            a: self.a.to_string(),
+            b: self.b.to_string(),
+            z0: self.z0.to_string(),
+            s: self.s.to_string(),
+            s_inv: self.s_inv.to_string(),
+            a_montgomery: self.a_montgomery.to_string(),
+            b_montgomery: self.b_montgomery.to_string(),
+        };
+        serde_json::to_string(&record).expect("a map record always serializes")
+    }
+
+    /// Parses and validates a map written by `to_json`.
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<MontgomeryMap, TransformError> {
+        let record: MapRecord =
+            serde_json::from_str(json).map_err(|error| invalid(format!("malformed map JSON: {}", error)))?;
+        if record.version != MAP_FORMAT_VERSION {
+            return Err(invalid(format!("unsupported map format version {}", record.version)));
+        }
+        let map = MontgomeryMap {
+            p: parse_decimal("p", &record.p)?,
+            a: parse_decimal("a", &record.a)?,
+            b: parse_decimal("b", &record.b)?,
+            z0: parse_decimal("z0", &record.z0)?,
+            s: parse_decimal("s", &record.s)?,
+            s_inv: parse_decimal("s_inv", &record.s_inv)?,
+            a_montgomery: parse_decimal("A", &record.a_montgomery)?,
+            b_montgomery: parse_decimal("B", &record.b_montgomery)?,
+        };
+        map.validate()?;
+        Ok(map)
+    }
+
+    /// The values in the fixed order used by the binary encoding.
+    fn values(&self) -> [&BigInt; 8] {
+        [
+            &self.p,
+            &self.a,
+            &self.b,
+            &self.z0,
+            &self.s,
+            &self.s_inv,
+            &self.a_montgomery,
+            &self.b_montgomery,
+        ]
+    }
+
+    /// Serializes the map as `WMAP`, a big-endian u32 version, then p, a, b, z0, s, s_inv, A and B,
+    /// each as a big-endian u32 length followed by that many big-endian magnitude bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&MAP_FORMAT_VERSION.to_be_bytes());
+        for value in self.values() {
+            let magnitude = if value.is_zero() { Vec::new() } else { value.to_bytes_be().1 };
+            bytes.extend_from_slice(&(magnitude.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(&magnitude);
+        }
+        bytes
+    }
+
+    /// Parses and validates a map written by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<MontgomeryMap, TransformError> {
+        let truncated = || invalid("truncated map encoding".to_string());
+        let rest = bytes.strip_prefix(MAGIC).ok_or_else(|| invalid("missing WMAP header".to_string()))?;
+        let (version, mut rest) = rest.split_first_chunk::<4>().ok_or_else(truncated)?;
+        let version = u32::from_be_bytes(*version);
+        if version != MAP_FORMAT_VERSION {
+            return Err(invalid(format!("unsupported map format version {}", version)));
+        }
+        let mut values = Vec::with_capacity(8);
+        for _ in 0..8 {
+            let (length, tail) = rest.split_first_chunk::<4>().ok_or_else(truncated)?;
+            let length = u32::from_be_bytes(*length) as usize;
+            if tail.len() < length {
+                return Err(truncated());
+            }
+            // Zero is the empty magnitude, so each map has a single encoding
+            if tail[..length].first() == Some(&0) {
+                return Err(invalid("map value encoded with a leading zero byte".to_string()));
+            }
+            values.push(BigInt::from_bytes_be(Sign::Plus, &tail[..length]));
+            rest = &tail[length..];
+        }
+        if !rest.is_empty() {
+            return Err(invalid(format!("{} trailing bytes after map encoding", rest.len())));
+        }
+        let [p, a, b, z0, s, s_inv, a_montgomery, b_montgomery]: [BigInt; 8] =
+            values.try_into().expect("exactly eight values were read");
+        let map = MontgomeryMap {
+            p,
+            a,
+            b,
+            z0,
+            s,
+            s_inv,
+            a_montgomery,
+            b_montgomery,
+        };
+        map.validate()?;
+        Ok(map)
+    }
+}
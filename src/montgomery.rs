@@ -0,0 +1,285 @@
+use crate::curve::{CurveGroup, Point};
+use crate::encoding::{decode_point, encode_point};
+use crate::error::TransformError;
+use crate::field::FieldElement;
+use crate::fp2::Fp2;
+use crate::limits::SearchLimits;
+use crate::numtheory::{legendre_symbol, mod_inverse, mod_sqrt};
+use crate::ops;
+use crate::scalar_mul;
+use crate::weierstrass::{lift, WeierstrassCurve};
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+/// The Montgomery curve B·v^2 = u^3 + A·u^2 + u over F_p.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MontgomeryCurve {
+    pub a: BigInt,
+    pub b: BigInt,
+    pub p: BigInt,
+}
+
+/// An isomorphism (u, v) -> (±u, v / d) onto the curve with (±A, ±d^2·B), from `rescale` or
+/// `canonicalize`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rescaling {
+    /// The image curve.
+    pub curve: MontgomeryCurve,
+    /// Whether u (and with it A and B) changes sign.
+    pub negate: bool,
+    pub d: BigInt,
+}
+
+impl Rescaling {
+    /// Maps a point of the original curve to the image curve.
+    pub fn map_point(&self, point: &Point) -> Point {
+        let p = &self.curve.p;
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(u, v) => {
+                let u = if self.negate { (-u).mod_floor(p) } else { u.clone() };
+                let d_inv = mod_inverse(&self.d, p).expect("d is non-zero");
+                Point::Affine(u, (v * d_inv).mod_floor(p))
+            }
+        }
+    }
+}
+
+impl MontgomeryCurve {
+    /// Creates the curve, reducing the coefficients and rejecting singular curves (B = 0 or A = ±2).
+    pub fn new(a: &BigInt, b: &BigInt, p: &BigInt) -> Result<MontgomeryCurve, TransformError> {
+        if p <= &BigInt::from(3) {
+            return Err(TransformError::UnsupportedCharacteristic(p.clone()));
+        }
+        let curve = MontgomeryCurve {
+            a: a.mod_floor(p),
+            b: b.mod_floor(p),
+            p: p.clone(),
+        };
+        if curve.b.is_zero() || (&curve.a * &curve.a - 4u32).mod_floor(p).is_zero() {
+            return Err(TransformError::InvalidParameters(
+                "the Montgomery curve is singular (B = 0 or A^2 = 4)".to_string(),
+            ));
+        }
+        Ok(curve)
+    }
+
+    /// The u-coordinate of scalar·P for a point P with u-coordinate `u`, by the x-only Montgomery ladder
+    /// of RFC 7748, with 0 for the point at infinity. As in X25519, `u` need not be on the curve: for a u
+    /// of the quadratic twist the result is on the twist.
+    ///
+    /// Every step runs the same field operations and exchanges its two points with
+    /// `FieldElement::ct_swap` instead of branching on the scalar bit, over p.bits() + 1 bits, or more for
+    /// a longer scalar, so the sequence of operations does not depend on the scalar's value.
+    pub fn ladder(&self, u: &BigInt, scalar: &BigInt) -> BigInt {
+        assert!(scalar.sign() != Sign::Minus, "the ladder takes a non-negative scalar");
+        let p = &self.p;
+        let element = |value: BigInt| FieldElement::new(&value, p);
+        let a24 = element(&self.a - 2u32) * element(BigInt::from(4)).inverse().expect("p is odd");
+        let x1 = element(u.clone());
+        let (mut x2, mut z2) = (x1.one(), x1.zero());
+        let (mut x3, mut z3) = (x1.clone(), x1.one());
+        let mut swap = false;
+        for bit in (0..(p.bits() + 1).max(scalar.bits())).rev() {
+            let set = scalar.bit(bit);
+            swap ^= set;
+            FieldElement::ct_swap(&mut x2, &mut x3, swap);
+            FieldElement::ct_swap(&mut z2, &mut z3, swap);
+            swap = set;
+            // x2 and x3 are rebuilt below, so their buffers are reused for b and d
+            let (a, b) = (&x2 + &z2, x2 - &z2);
+            let (c, d) = (&x3 + &z3, x3 - &z3);
+            let (da, cb) = (d * &a, c * &b);
+            let (aa, bb) = (a.square(), b.square());
+            let e = &aa - &bb;
+            x3 = (&da + &cb).square();
+            z3 = (da - &cb).square() * &x1;
+            z2 = (&a24 * &e + &aa) * &e;
+            x2 = aa * &bb;
+        }
+        FieldElement::ct_swap(&mut x2, &mut x3, swap);
+        FieldElement::ct_swap(&mut z2, &mut z3, swap);
+        // z2^(p − 2) is zero for z2 = 0, the point at infinity
+        (x2 * z2.pow(&(p - 2u32))).value
+    }
+
+    /// Rescales B to d^2·B, an isomorphism that maps (u, v) to (u, v / d).
+    pub fn rescale(&self, d: &BigInt) -> Result<Rescaling, TransformError> {
+        self.rescaling(false, d)
+    }
+
+    pub(crate) fn rescaling(&self, negate: bool, d: &BigInt) -> Result<Rescaling, TransformError> {
+        let p = &self.p;
+        let d = d.mod_floor(p);
+        if d.is_zero() {
+            let message = "the scale d must be non-zero modulo p".to_string();
+            return Err(TransformError::InvalidParameters(message));
+        }
+        let sign = if negate { -BigInt::one() } else { BigInt::one() };
+        let curve = MontgomeryCurve {
+            a: (&sign * &self.a).mod_floor(p),
+            b: (sign * &d * &d * &self.b).mod_floor(p),
+            p: p.clone(),
+        };
+        Ok(Rescaling { curve, negate, d })
+    }
+
+    /// Finds the isomorphic curve with B = 1, or B = the smallest non-residue when no isomorphism
+    /// reaches 1, and the smaller of A and p − A among the choices left; the map to it is returned too.
+    ///
+    /// Both (u, v) -> (u, v / d) and (u, v) -> (−u, v), which turns (A, B) into (−A, −B), preserve the
+    /// curve up to isomorphism, so this is how outputs are compared with constants like (486662, 1).
+    pub fn canonicalize(&self) -> Result<Rescaling, TransformError> {
+        let p = &self.p;
+        let limits = SearchLimits::default();
+        let non_residue = Fp2::find_non_residue(p, &limits)?;
+        let square_b = legendre_symbol(&self.b, p) == 1;
+        let square_minus_b = legendre_symbol(&-&self.b, p) == 1;
+        let target = if square_b || square_minus_b { BigInt::one() } else { non_residue };
+        // Negation is needed when only −B is in the square class of the target; when both are, it may
+        // still be used to make A smaller
+        let same_class = |value: &BigInt| legendre_symbol(&(value * &target), p) == 1;
+        let negate = match (same_class(&self.b), same_class(&-&self.b)) {
+            (true, true) => self.a > p - &self.a,
+            (sign_kept, _) => !sign_kept,
+        };
+        let b = if negate { (-&self.b).mod_floor(p) } else { self.b.clone() };
+        // d^2 = target / B'
+        let b_inv = mod_inverse(&b, p).expect("B is non-zero");
+        let d = mod_sqrt(&(&target * b_inv).mod_floor(p), p, &limits)?.expect("target / B' is a square");
+        let d = d.clone().min(p - &d);
+        self.rescaling(negate, &d)
+    }
+
+    /// Returns (a, d) of the birationally equivalent twisted Edwards curve a·x^2 + y^2 = 1 + d·x^2·y^2,
+    /// a = (A + 2) / B and d = (A − 2) / B; rescaling x reaches a = 1, an Edwards curve, when a is a
+    /// square.
+    pub fn twisted_edwards_coefficients(&self) -> (BigInt, BigInt) {
+        let p = &self.p;
+        let b_inv = mod_inverse(&self.b, p).expect("B is non-zero");
+        let a = ((&self.a + 2u32) * &b_inv).mod_floor(p);
+        let d = ((&self.a - 2u32) * b_inv).mod_floor(p);
+        (a, d)
+    }
+
+    /// Returns the isomorphic short Weierstrass curve, a = (3 − A^2) / (3B^2) and b = (2A^3 − 9A) / (27B^3).
+    pub fn to_weierstrass(&self) -> WeierstrassCurve {
+        let p = &self.p;
+        let b_inv = mod_inverse(&self.b, p).expect("B is non-zero");
+        let inv = |value: u32| mod_inverse(&BigInt::from(value), p).expect("p is greater than 3");
+        let a = ((BigInt::from(3) - &self.a * &self.a) * inv(3) * &b_inv * &b_inv).mod_floor(p);
+        let b = (BigInt::from(2) * self.a.pow(3) - BigInt::from(9) * &self.a) * inv(27) * b_inv.pow(3);
+        let b = b.mod_floor(p);
+        WeierstrassCurve { a, b, p: p.clone() }
+    }
+
+    /// Maps a point to the curve of `to_weierstrass`, (u, v) -> (u / B + A / (3B), v / B).
+    pub fn point_to_weierstrass(&self, point: &Point) -> Point {
+        let p = &self.p;
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(u, v) => {
+                let b_inv = mod_inverse(&self.b, p).expect("B is non-zero");
+                let third = mod_inverse(&BigInt::from(3), p).expect("p is greater than 3");
+                let x = ((u + &self.a * third) * &b_inv).mod_floor(p);
+                Point::Affine(x, (v * b_inv).mod_floor(p))
+            }
+        }
+    }
+
+    /// Evaluates the right-hand side u^3 + A·u^2 + u.
+    pub fn rhs(&self, u: &BigInt) -> BigInt {
+        (u.pow(3) + &self.a * u * u + u).mod_floor(&self.p)
+    }
+
+    /// Creates the affine point (u, v), checking that it lies on the curve.
+    pub fn point(&self, u: &BigInt, v: &BigInt) -> Result<Point, TransformError> {
+        let point = Point::Affine(u.mod_floor(&self.p), v.mod_floor(&self.p));
+        if self.contains(&point) {
+            Ok(point)
+        } else {
+            Err(TransformError::PointNotOnCurve)
+        }
+    }
+
+    /// The points with abscissa u: none when (u^3 + A·u^2 + u)/B is a non-residue, one when it is zero
+    /// and two, (u, v) and (u, p − v) with the smaller v first, otherwise. X25519 and X448 keys are
+    /// u-coordinates alone; this recovers the full points they stand for.
+    pub fn lift_u(&self, u: &BigInt) -> Result<Vec<Point>, TransformError> {
+        let b_inv = mod_inverse(&self.b, &self.p).expect("B is invertible on a non-singular curve");
+        lift(u, &(self.rhs(u) * b_inv).mod_floor(&self.p), &self.p)
+    }
+
+    /// Multiplies a point of the curve by the `cofactor` h, forcing it into the subgroup of order #E / h,
+    /// as needed for the images of arbitrary Weierstrass points; see `scalar_mul::clear_cofactor`.
+    pub fn clear_cofactor(&self, point: &Point, cofactor: &BigInt) -> Result<Point, TransformError> {
+        scalar_mul::clear_cofactor(self, point, cofactor)
+    }
+
+    /// Compresses a point to the u-coordinate and the parity of v, using the same layout as SEC1:
+    /// 0x00 for infinity and 0x02/0x03 || u otherwise.
+    pub fn compress(&self, point: &Point) -> Vec<u8> {
+        encode_point(point, &self.p, true)
+    }
+
+    /// Decompresses a point produced by `compress`, solving B·v^2 = u^3 + A·u^2 + u for v.
+    ///
+    /// The uncompressed 0x04 || u || v form is accepted as well.
+    pub fn decompress(&self, bytes: &[u8]) -> Result<Point, TransformError> {
+        let b_inv = mod_inverse(&self.b, &self.p).expect("B is invertible on a non-singular curve");
+        let point = decode_point(bytes, &self.p, |u| (self.rhs(u) * &b_inv).mod_floor(&self.p))?;
+        if self.contains(&point) {
+            Ok(point)
+        } else {
+            Err(TransformError::PointNotOnCurve)
+        }
+    }
+}
+
+impl CurveGroup for MontgomeryCurve {
+    fn modulus(&self) -> &BigInt {
+        &self.p
+    }
+
+    fn contains(&self, point: &Point) -> bool {
+        match point {
+            Point::Infinity => true,
+            Point::Affine(u, v) => (&self.b * v * v).mod_floor(&self.p) == self.rhs(u),
+        }
+    }
+
+    fn negate(&self, point: &Point) -> Point {
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(u, v) => Point::Affine(u.clone(), (-v).mod_floor(&self.p)),
+        }
+    }
+
+    fn add(&self, first: &Point, second: &Point) -> Point {
+        let p = &self.p;
+        let (u1, v1, u2, v2) = match (first, second) {
+            (Point::Infinity, _) => return second.clone(),
+            (_, Point::Infinity) => return first.clone(),
+            (Point::Affine(u1, v1), Point::Affine(u2, v2)) => (u1, v1, u2, v2),
+        };
+        let lambda = if u1 == u2 {
+            if (v1 + v2).mod_floor(p).is_zero() {
+                return Point::Infinity;
+            }
+            // Tangent slope (3u^2 + 2Au + 1) / 2Bv
+            ops::record(5, 2);
+            let denominator =
+                mod_inverse(&(BigInt::from(2) * &self.b * v1).mod_floor(p), p).expect("2Bv is invertible");
+            (BigInt::from(3) * u1 * u1 + BigInt::from(2) * &self.a * u1 + 1u32) * denominator
+        } else {
+            ops::record(3, 1);
+            let denominator = mod_inverse(&(u2 - u1).mod_floor(p), p).expect("u2 - u1 is invertible");
+            (v2 - v1) * denominator
+        }
+        .mod_floor(p);
+        let u3 = (&self.b * &lambda * &lambda - &self.a - u1 - u2).mod_floor(p);
+        let v3 = (lambda * (u1 - &u3) - v1).mod_floor(p);
+        Point::Affine(u3, v3)
+    }
+}
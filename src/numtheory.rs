@@ -0,0 +1,540 @@
+use crate::error::TransformError;
+use crate::field::modpow;
+use crate::fp2::Fp2;
+use crate::limits::{SearchBudget, SearchLimits};
+use crate::log::{debug, trace};
+use crate::ops;
+use num_bigint::{BigInt, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+
+/// Computes the modular inverse of `value` modulo `modulus` using the extended Euclidean algorithm.
+///
+/// The result lies in [0, |modulus|): `value` may be negative or larger than the modulus, a negative
+/// modulus stands for its absolute value, and modulo 1 every value has the inverse 0. There is none when
+/// `value` and the modulus share a factor, including for `value` ≡ 0, or when the modulus is 0.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn mod_inverse(value: &BigInt, modulus: &BigInt) -> Option<BigInt> {
+    let modulus = modulus.abs();
+    if modulus.is_zero() {
+        return None;
+    }
+    ops::record_inversion();
+    // The extended Euclidean algorithm, tracking only the coefficient of `value`
+    let (mut old_r, mut r) = (value.mod_floor(&modulus), modulus.clone());
+    let (mut old_x, mut x) = (BigInt::one(), BigInt::zero());
+    while !r.is_zero() {
+        let (quotient, remainder) = old_r.div_rem(&r);
+        old_r = std::mem::replace(&mut r, remainder);
+        bezout_step(&mut old_x, &mut x, &quotient);
+    }
+    old_r.is_one().then(|| old_x.mod_floor(&modulus))
+}
+
+/// Computes the extended Euclidean algorithm, returning (gcd, x, y) such that gcd = a * x + b * y.
+///
+/// The gcd is never negative, whatever the signs of a and b; it is |a| when b is 0, and (0, 1, 0) is
+/// returned for two zeros.
+pub fn extended_gcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
+    let (mut old_r, mut r) = (a.clone(), b.clone());
+    let (mut old_x, mut x) = (BigInt::one(), BigInt::zero());
+    let (mut old_y, mut y) = (BigInt::zero(), BigInt::one());
+    while !r.is_zero() {
+        let (quotient, remainder) = old_r.div_rem(&r);
+        old_r = std::mem::replace(&mut r, remainder);
+        bezout_step(&mut old_x, &mut x, &quotient);
+        bezout_step(&mut old_y, &mut y, &quotient);
+    }
+    if old_r.is_negative() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// One step of a Bézout coefficient, (old, current) -> (current, old − quotient·current), in place.
+fn bezout_step(old: &mut BigInt, current: &mut BigInt, quotient: &BigInt) {
+    *old -= quotient * &*current;
+    std::mem::swap(old, current);
+}
+
+/// Returns the Legendre symbol (value / p) for an odd prime p as 1, −1 or 0, using Euler's criterion.
+pub fn legendre_symbol(value: &BigInt, p: &BigInt) -> i8 {
+    let power = modpow(value, &((p - 1u32) / 2u32), p);
+    if power.is_zero() {
+        0
+    } else if power.is_one() {
+        1
+    } else {
+        -1
+    }
+}
+
+/// Algorithms for square roots modulo an odd prime p.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SqrtAlgorithm {
+    /// Pick one from p, see `SqrtAlgorithm::select`.
+    #[default]
+    Auto,
+    /// Works for every p; its cost grows with the square of the 2-adicity of p − 1.
+    TonelliShanks,
+    /// Works for every p with one exponentiation in F_p², whatever the 2-adicity.
+    Cipolla,
+    /// The single exponentiation value^((p + 1) / 4); only for p ≡ 3 (mod 4).
+    ThreeModFour,
+}
+
+/// 2-adicity from which Cipolla beats Tonelli-Shanks for 64- to 384-bit p, measured with `benches/sqrt.rs`;
+/// below it the crossover moves with the size of p, so Tonelli-Shanks is kept.
+const CIPOLLA_MIN_TWO_ADICITY: u64 = 12;
+
+impl SqrtAlgorithm {
+    /// The algorithm `Auto` stands for: the 3 mod 4 shortcut when it applies, Tonelli-Shanks for small
+    /// 2-adicity s of p − 1, and Cipolla once the s^2 steps of Tonelli-Shanks cost more than working in F_p².
+    pub fn select(p: &BigInt) -> SqrtAlgorithm {
+        let two_adicity = (p - 1u32).trailing_zeros().unwrap_or(0);
+        if two_adicity == 1 {
+            SqrtAlgorithm::ThreeModFour
+        } else if two_adicity >= CIPOLLA_MIN_TWO_ADICITY {
+            SqrtAlgorithm::Cipolla
+        } else {
+            SqrtAlgorithm::TonelliShanks
+        }
+    }
+}
+
+/// Combines congruences x ≡ residue (mod modulus) into the unique x in [0, ∏ moduli) with the Chinese
+/// remainder theorem, or `None` if two moduli share a factor.
+pub fn crt(congruences: &[(BigInt, BigInt)]) -> Option<BigInt> {
+    let mut value = BigInt::zero();
+    let mut modulus = BigInt::one();
+    for (residue, next) in congruences {
+        // value + modulus·k ≡ residue (mod next)
+        let k = ((residue - &value) * mod_inverse(&modulus.mod_floor(next), next)?).mod_floor(next);
+        value += &modulus * k;
+        modulus *= next;
+    }
+    Some(value.mod_floor(&modulus))
+}
+
+/// Computes the modular square root, choosing the algorithm with `SqrtAlgorithm::select`.
+/// Returns `None` if no square root exists, or an error if no non-residue is found within `limits`.
+///
+/// `value` may be any integer and is reduced modulo p first. p must be prime: an even p other than 2, a
+/// p below 2 and a composite p caught by a base-2 strong probable-prime test or by Euler's criterion are
+/// `InvalidParameters`, which keeps the cost to a few exponentiations where a full primality test would
+/// take a dozen.
+pub fn mod_sqrt(value: &BigInt, p: &BigInt, limits: &SearchLimits) -> Result<Option<BigInt>, TransformError> {
+    mod_sqrt_with(value, p, SqrtAlgorithm::Auto, limits)
+}
+
+/// `mod_sqrt` with a given algorithm; forcing `ThreeModFour` when p ≢ 3 (mod 4) is an error.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn mod_sqrt_with(
+    value: &BigInt,
+    p: &BigInt,
+    algorithm: SqrtAlgorithm,
+    limits: &SearchLimits,
+) -> Result<Option<BigInt>, TransformError> {
+    let composite =
+        || TransformError::InvalidParameters(format!("square roots need a prime modulus, got {}", p));
+    let two = BigInt::from(2);
+    if p < &two || (p.is_even() && p != &two) {
+        return Err(composite());
+    }
+    let value = &value.mod_floor(p);
+    if value.is_zero() || p == &two {
+        return Ok(Some(value.clone()));
+    }
+    let p_minus_one = p - 1u32;
+    let (d, s) = odd_part(&p_minus_one);
+    if !is_strong_probable_prime(p, &two, &d, s) {
+        return Err(composite());
+    }
+    let euler = modpow(value, &(&p_minus_one / 2u32), p);
+    if euler == p_minus_one {
+        debug!("value is a quadratic non-residue");
+        return Ok(None); // No square root exists
+    }
+    if !euler.is_one() {
+        return Err(composite());
+    }
+    let algorithm = match algorithm {
+        SqrtAlgorithm::Auto => SqrtAlgorithm::select(p),
+        algorithm => algorithm,
+    };
+    debug!(?algorithm);
+    match algorithm {
+        SqrtAlgorithm::ThreeModFour => three_mod_four_sqrt(value, p),
+        SqrtAlgorithm::Cipolla => cipolla_sqrt(value, p, limits),
+        _ => tonelli_shanks_sqrt(value, p, limits),
+    }
+}
+
+/// Both square roots (r, p − r) of `value` with r ≤ p − r, `None` for a non-residue and (0, 0) for zero.
+pub fn mod_sqrt_both(
+    value: &BigInt,
+    p: &BigInt,
+    limits: &SearchLimits,
+) -> Result<Option<(BigInt, BigInt)>, TransformError> {
+    Ok(mod_sqrt(value, p, limits)?.map(|root| {
+        let other = (p - &root).mod_floor(p);
+        if root <= other {
+            (root, other)
+        } else {
+            (other, root)
+        }
+    }))
+}
+
+/// The square root of `value` with an even representative in [0, p), for parity-based conventions.
+pub fn mod_sqrt_even(
+    value: &BigInt,
+    p: &BigInt,
+    limits: &SearchLimits,
+) -> Result<Option<BigInt>, TransformError> {
+    Ok(mod_sqrt_both(value, p, limits)?.map(|(low, high)| if low.is_even() { low } else { high }))
+}
+
+/// The square root of `value` whose representative in [0, p) is odd; for zero, which has no odd root, 0.
+pub fn mod_sqrt_odd(
+    value: &BigInt,
+    p: &BigInt,
+    limits: &SearchLimits,
+) -> Result<Option<BigInt>, TransformError> {
+    Ok(mod_sqrt_both(value, p, limits)?.map(|(low, high)| if low.is_odd() { low } else { high }))
+}
+
+/// value^((p + 1) / 4), checked, since for a composite p it need not be a root.
+fn three_mod_four_sqrt(value: &BigInt, p: &BigInt) -> Result<Option<BigInt>, TransformError> {
+    if p % 4u32 != BigInt::from(3) {
+        return Err(TransformError::InvalidParameters(format!(
+            "the 3 mod 4 square root needs p ≡ 3 (mod 4), got p = {}",
+            p
+        )));
+    }
+    let r = modpow(value, &((p + 1u32) / 4u32), p);
+    ops::record(0, 1);
+    Ok(if (&r * &r - value).mod_floor(p).is_zero() { Some(r) } else { None })
+}
+
+/// Cipolla's algorithm: with t such that w = t^2 − value is a non-residue, (t + sqrt(w))^((p + 1) / 2) lies
+/// in F_p and squares to value.
+pub(crate) fn cipolla_sqrt(
+    value: &BigInt,
+    p: &BigInt,
+    limits: &SearchLimits,
+) -> Result<Option<BigInt>, TransformError> {
+    let mut budget = SearchBudget::new(limits);
+    let mut t = BigInt::one();
+    let w = loop {
+        budget.tick("searching for t with t^2 - value a non-residue")?;
+        ops::record(0, 1);
+        let w = (&t * &t - value).mod_floor(p);
+        if modpow(&w, &((p - 1u32) / 2u32), p) == p - 1u32 {
+            break w;
+        }
+        t += 1u32;
+    };
+    trace!(%t, %w, "Cipolla parameters");
+    let root = Fp2::new(&t, &BigInt::one(), p, &w)?.pow(&((p + 1u32) / 2u32));
+    ops::record(0, 1);
+    Ok(if root.is_base() && (&root.c0 * &root.c0 - value).mod_floor(p).is_zero() {
+        Some(root.c0)
+    } else {
+        None
+    })
+}
+
+/// Tonelli-Shanks, for a value already known to be a quadratic residue.
+fn tonelli_shanks_sqrt(
+    value: &BigInt,
+    p: &BigInt,
+    limits: &SearchLimits,
+) -> Result<Option<BigInt>, TransformError> {
+    Ok(TonelliShanks::find(p, limits)?.sqrt(value, p))
+}
+
+/// The per-field constants of Tonelli-Shanks: p − 1 = q·2^s with q odd, and a quadratic non-residue z.
+/// They depend only on p, so `Field` keeps them for repeated square roots.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TonelliShanks {
+    q: BigInt,
+    s: u32,
+    z: BigInt,
+}
+
+impl TonelliShanks {
+    /// Splits p − 1 and searches for the smallest non-residue z ≥ 2.
+    pub(crate) fn find(p: &BigInt, limits: &SearchLimits) -> Result<TonelliShanks, TransformError> {
+        let mut q = p - 1u32;
+        let mut s = 0;
+        while q.is_even() {
+            q /= 2u32;
+            s += 1;
+        }
+
+        // Only a prime p guarantees that a non-residue turns up quickly
+        let mut budget = SearchBudget::new(limits);
+        let mut z = BigInt::from(2);
+        while modpow(&z, &((p - 1u32) / 2u32), p) != p - 1u32 {
+            budget.tick("searching for a quadratic non-residue")?;
+            z += 1u32;
+        }
+        trace!(s, %z, "Tonelli-Shanks parameters");
+        Ok(TonelliShanks { q, s, z })
+    }
+
+    /// The square root of a quadratic residue `value`, or `None` when p turns out not to be prime.
+    pub(crate) fn sqrt(&self, value: &BigInt, p: &BigInt) -> Option<BigInt> {
+        let mut m = self.s;
+        let mut c = modpow(&self.z, &self.q, p);
+        let mut t = modpow(value, &self.q, p);
+        let mut r = modpow(value, &((&self.q + 1u32) / 2u32), p);
+
+        while t != BigInt::one() {
+            let mut i = 0;
+            let mut t2i = t.clone();
+            while t2i != BigInt::one() {
+                t2i = modpow(&t2i, &BigInt::from(2), p);
+                i += 1;
+                if i == m {
+                    return None;
+                }
+            }
+
+            trace!(i, "Tonelli-Shanks step");
+            let b = modpow(&c, &(BigInt::one() << (m - i - 1)), p);
+            m = i;
+            c = modpow(&b, &BigInt::from(2), p);
+            ops::record(2, 0);
+            t = (t * &c) % p;
+            r = (r * b) % p;
+        }
+
+        Some(r)
+    }
+}
+
+/// Computes an n-th root of `value` modulo the prime `p` (n ≥ 1), e.g. a cube root for n = 3.
+/// Returns `None` if `value` is not an n-th power, or an error if a search runs past `limits`.
+///
+/// With g = gcd(n, p − 1), raising to the inverse of n / g modulo (p − 1) / g reduces the problem to a
+/// g-th root, which is taken one prime factor q of g at a time with the Adleman-Manders-Miller method.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn mod_nth_root(
+    value: &BigInt,
+    n: u64,
+    p: &BigInt,
+    limits: &SearchLimits,
+) -> Result<Option<BigInt>, TransformError> {
+    if n == 0 {
+        return Err(TransformError::InvalidParameters("there is no 0-th root".to_string()));
+    }
+    let value = value.mod_floor(p);
+    if value.is_zero() || p == &BigInt::from(2) {
+        return Ok(Some(value));
+    }
+    let order = p - 1u32;
+    let g = BigInt::from(n).gcd(&order);
+    if !modpow(&value, &(&order / &g), p).is_one() {
+        debug!(%g, "value is not an n-th power");
+        return Ok(None);
+    }
+    let cofactor_inverse = mod_inverse(&(BigInt::from(n) / &g), &(&order / &g)).unwrap_or_else(BigInt::zero);
+    let mut root = modpow(&value, &cofactor_inverse, p);
+    let mut rest = u64::try_from(&g).expect("g divides n");
+    let mut q = 2u64;
+    while rest > 1 {
+        if q * q > rest {
+            q = rest;
+        }
+        while rest % q == 0 {
+            root = match prime_root(&root, q, p, limits)? {
+                Some(root) => root,
+                None => return Ok(None),
+            };
+            rest /= q;
+        }
+        q += 1;
+    }
+    Ok(Some(root))
+}
+
+/// A q-th root of `value` for a prime q dividing p − 1 (Adleman-Manders-Miller), or `None` if there is none.
+fn prime_root(
+    value: &BigInt,
+    q: u64,
+    p: &BigInt,
+    limits: &SearchLimits,
+) -> Result<Option<BigInt>, TransformError> {
+    let q_big = BigInt::from(q);
+    let order = p - 1u32;
+    if !modpow(value, &(&order / &q_big), p).is_one() {
+        return Ok(None);
+    }
+    // p − 1 = q^t · s with q ∤ s
+    let mut s = order.clone();
+    let mut t = 0u32;
+    while s.is_multiple_of(&q_big) {
+        s /= &q_big;
+        t += 1;
+    }
+    // alpha ≥ 1 with s | q·alpha − 1, so that value^alpha is a root up to an element of q-power order
+    let alpha = mod_inverse(&q_big, &s).filter(|alpha| !alpha.is_zero()).unwrap_or_else(BigInt::one);
+
+    let mut budget = SearchBudget::new(limits);
+    let mut rho = BigInt::from(2);
+    while modpow(&rho, &(&order / &q_big), p).is_one() {
+        budget.tick("searching for a q-th power non-residue")?;
+        rho += 1u32;
+    }
+    trace!(q, t, %rho, "Adleman-Manders-Miller parameters");
+
+    // a has order q; c generates the Sylow q-subgroup and b measures how far value^alpha is from a root
+    let a = modpow(&rho, &(q_big.pow(t - 1) * &s), p);
+    let mut b = modpow(value, &(&q_big * &alpha - 1u32), p);
+    let mut c = modpow(&rho, &s, p);
+    let mut h = BigInt::one();
+    for i in 1..t {
+        let d = modpow(&b, &q_big.pow(t - 1 - i), p);
+        // j = −log_a(d), by stepping through the q powers of a
+        let mut j = 0u64;
+        let mut power = BigInt::one();
+        while power != d {
+            budget.tick("searching for a discrete logarithm in the subgroup of order q")?;
+            power = (power * &a) % p;
+            j += 1;
+        }
+        let j = BigInt::from((q - j) % q);
+        let c_to_q = modpow(&c, &q_big, p);
+        b = (b * modpow(&c_to_q, &j, p)) % p;
+        h = (h * modpow(&c, &j, p)) % p;
+        c = c_to_q;
+    }
+    Ok(Some((modpow(value, &alpha, p) * h) % p))
+}
+
+/// The multiplicative order of `value` modulo `modulus`: the smallest k ≥ 1 with value^k ≡ 1, found by
+/// stepping through the powers. Returns `None` if it exceeds `cap` or `value` is not invertible.
+pub fn multiplicative_order(value: &BigInt, modulus: &BigInt, cap: u64) -> Option<u64> {
+    let value = value.mod_floor(modulus);
+    if !value.gcd(modulus).is_one() {
+        return None;
+    }
+    let mut power = value.clone();
+    for k in 1..=cap {
+        if power.is_one() {
+            return Some(k);
+        }
+        power = (power * &value) % modulus;
+    }
+    None
+}
+
+/// Primes `is_probable_prime` divides by before testing.
+const SMALL_PRIMES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+/// Sinclair's witnesses: n < 2^64 is prime exactly when it is a strong probable prime to each of them,
+/// reduced modulo n, those divisible by n skipped.
+const MILLER_RABIN_BASES_64: [u64; 7] = [2, 325, 9375, 28178, 450775, 9780504, 1795265022];
+
+/// The number of random bases the crate uses with `is_probable_prime` for n ≥ 2^64; a composite passes
+/// with probability at most 4^-20, and a random one with far less.
+pub const MILLER_RABIN_ROUNDS: u32 = 20;
+
+/// Miller-Rabin test, after trial division by the primes up to 41.
+///
+/// Exact for every n below 2^64, with the deterministic witnesses of `MILLER_RABIN_BASES_64`. From 2^64
+/// on it runs base 2 and then `rounds` bases drawn at random from [2, n − 2], so a composite passes with
+/// probability at most 4^-rounds.
+pub fn is_probable_prime(n: &BigInt, rounds: u32) -> bool {
+    is_probable_prime_with_rng(n, rounds, &mut OsRng)
+}
+
+/// `is_probable_prime`, drawing the bases for n ≥ 2^64 from `rng`.
+pub fn is_probable_prime_with_rng<R: RngCore + CryptoRng + ?Sized>(
+    n: &BigInt,
+    rounds: u32,
+    rng: &mut R,
+) -> bool {
+    if n < &BigInt::from(2) {
+        return false;
+    }
+    for prime in SMALL_PRIMES {
+        if n == &BigInt::from(prime) {
+            return true;
+        }
+        if (n % prime).is_zero() {
+            return false;
+        }
+    }
+    let (d, s) = odd_part(&(n - 1u32));
+    if n.bits() <= 64 {
+        return MILLER_RABIN_BASES_64.iter().all(|&base| {
+            let base = BigInt::from(base) % n;
+            base.is_zero() || is_strong_probable_prime(n, &base, &d, s)
+        });
+    }
+    let n_minus_one = n - 1u32;
+    is_strong_probable_prime(n, &BigInt::from(2), &d, s)
+        && (0..rounds).all(|_| {
+            let base = rng.gen_bigint_range(&BigInt::from(2), &n_minus_one);
+            is_strong_probable_prime(n, &base, &d, s)
+        })
+}
+
+/// (d, s) with n = d·2^s and d odd, for n > 0.
+fn odd_part(n: &BigInt) -> (BigInt, u32) {
+    let mut d = n.clone();
+    let mut s = 0u32;
+    while d.is_even() {
+        d /= 2u32;
+        s += 1;
+    }
+    (d, s)
+}
+
+/// One round of Miller-Rabin: whether the odd n > 2 is a strong probable prime to `base`, with
+/// n − 1 = d·2^s from `odd_part`.
+fn is_strong_probable_prime(n: &BigInt, base: &BigInt, d: &BigInt, s: u32) -> bool {
+    let n_minus_one = n - 1u32;
+    // Testing a modulus is validation, not field work of whatever `count_ops` measures around it
+    let mut x = ops::uncounted(|| modpow(base, d, n));
+    if x.is_one() || x == n_minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = (&x * &x) % n;
+        if x == n_minus_one {
+            return true;
+        }
+    }
+    false
+}
+
+/// Returns the largest prime factor of `n` > 1 by trial division, so it is only practical when all
+/// but one prime factor are small, e.g. for group orders of the size `count_points` handles.
+pub fn largest_prime_factor(n: &BigInt) -> BigInt {
+    let mut rest = n.clone();
+    let mut largest = BigInt::one();
+    let mut divisor = BigInt::from(2);
+    // Only a division changes the rest, so that is when it is tested again
+    let mut rest_is_prime = is_probable_prime(&rest, MILLER_RABIN_ROUNDS);
+    while !rest_is_prime && &divisor * &divisor <= rest {
+        if rest.is_multiple_of(&divisor) {
+            rest /= &divisor;
+            largest = divisor.clone();
+            rest_is_prime = is_probable_prime(&rest, MILLER_RABIN_ROUNDS);
+        } else {
+            divisor += 1u32;
+        }
+    }
+    if rest.is_one() {
+        largest
+    } else {
+        rest
+    }
+}
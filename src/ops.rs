@@ -0,0 +1,103 @@
+//! Counts of the field operations behind a computation, multiplications, squarings and inversions, to
+//! compare curve models and formulas by cost independently of the machine, and to catch a change that
+//! makes a transform or a scalar multiplication do more work than before.
+//!
+//! The counts are kept per thread, and only with the `op-count` feature: without it the recording calls
+//! are empty and compile away. A product counts as a multiplication when both factors are field elements,
+//! curve constants included, and not when one is a small integer such as 2 or 3; additions, subtractions
+//! and reductions are not counted. Exponentiations count their squarings and multiplications, those of
+//! `BigInt::modpow` as left-to-right square-and-multiply would do them, and every `mod_inverse` is one
+//! inversion. Covered are the group laws of the curve models, the Jacobian formulas and the ladder behind
+//! the scalar multiplications, `FieldElement` and `Field` arithmetic, polynomial arithmetic and the point
+//! maps; the checks of `contains` and primality tests are not.
+
+use core::fmt;
+use core::ops::Add;
+
+/// Numbers of field operations, from `count_ops`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct OpCounts {
+    pub multiplications: u64,
+    pub squarings: u64,
+    pub inversions: u64,
+}
+
+/// Writes the counts in the usual cost notation, such as `12M + 4S + 1I`.
+impl fmt::Display for OpCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}M + {}S + {}I", self.multiplications, self.squarings, self.inversions)
+    }
+}
+
+impl Add for OpCounts {
+    type Output = OpCounts;
+
+    fn add(self, other: OpCounts) -> OpCounts {
+        OpCounts {
+            multiplications: self.multiplications + other.multiplications,
+            squarings: self.squarings + other.squarings,
+            inversions: self.inversions + other.inversions,
+        }
+    }
+}
+
+#[cfg(feature = "op-count")]
+thread_local! {
+    static COUNTS: std::cell::Cell<OpCounts> = const {
+        std::cell::Cell::new(OpCounts { multiplications: 0, squarings: 0, inversions: 0 })
+    };
+}
+
+/// Runs `f` and returns its result with the field operations it performed on this thread. Calls nest: the
+/// operations of an inner `count_ops` count towards the outer one too.
+#[cfg(feature = "op-count")]
+pub fn count_ops<T>(f: impl FnOnce() -> T) -> (T, OpCounts) {
+    let outer = COUNTS.with(|counts| counts.replace(OpCounts::default()));
+    let result = f();
+    let inner = COUNTS.with(|counts| counts.replace(OpCounts::default()));
+    COUNTS.with(|counts| counts.set(outer + inner));
+    crate::log::trace!(
+        multiplications = inner.multiplications,
+        squarings = inner.squarings,
+        inversions = inner.inversions,
+        "field operations"
+    );
+    (result, inner)
+}
+
+/// Records `multiplications` multiplications and `squarings` squarings.
+#[cfg_attr(not(feature = "op-count"), allow(unused_variables))]
+#[inline]
+pub(crate) fn record(multiplications: u64, squarings: u64) {
+    #[cfg(feature = "op-count")]
+    COUNTS.with(|counts| {
+        let mut current = counts.get();
+        current.multiplications += multiplications;
+        current.squarings += squarings;
+        counts.set(current);
+    });
+}
+
+/// Records one inversion.
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn record_inversion() {
+    #[cfg(feature = "op-count")]
+    COUNTS.with(|counts| {
+        let mut current = counts.get();
+        current.inversions += 1;
+        counts.set(current);
+    });
+}
+
+/// Runs `f` without recording its operations, for work that belongs to validating the input rather than
+/// to the computation, such as the primality test of p.
+#[cfg(feature = "std")]
+pub(crate) fn uncounted<T>(f: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "op-count")]
+    let outer = COUNTS.with(std::cell::Cell::get);
+    let result = f();
+    #[cfg(feature = "op-count")]
+    COUNTS.with(|counts| counts.set(outer));
+    result
+}
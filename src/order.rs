@@ -0,0 +1,172 @@
+use crate::curve::{CurveGroup, Point};
+use crate::error::TransformError;
+use crate::limits::{SearchBudget, SearchLimits};
+use crate::log::debug;
+use crate::numtheory::{is_probable_prime, MILLER_RABIN_ROUNDS};
+use crate::progress::report;
+use crate::scalar_mul::scalar_mul;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive};
+use std::collections::HashMap;
+
+/// Largest modulus `count_points` accepts; the count walks over every element of F_p.
+pub const MAX_COUNT_MODULUS: u64 = 1 << 24;
+
+/// Number of x-coordinates between progress events.
+const POINTS_PER_REPORT: u64 = 1 << 16;
+
+/// Counts the points of y^2 = x^3 + ax + b over F_p, including the point at infinity.
+///
+/// Uses #E = p + 1 + Σ_x (x^3 + ax + b / p), reading the Legendre symbols from a table of the squares
+/// in F_p, so it is only practical for toy-sized p.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+pub fn count_points(a: &BigInt, b: &BigInt, p: &BigInt) -> Result<BigInt, TransformError> {
+    let size = p.to_u64().filter(|&size| size > 3 && size <= MAX_COUNT_MODULUS).ok_or_else(|| {
+        let message = format!("point counting needs 3 < p <= {}, got {}", MAX_COUNT_MODULUS, p);
+        TransformError::InvalidParameters(message)
+    })?;
+    let a = a.mod_floor(p).to_u64().expect("reduced below p");
+    let b = b.mod_floor(p).to_u64().expect("reduced below p");
+    let mut is_square = vec![false; size as usize];
+    for y in 0..size {
+        is_square[(y * y % size) as usize] = true;
+    }
+    // Every square other than zero has two roots, zero has one
+    let mut count = 1 + size;
+    for x in 0..size {
+        if x.is_multiple_of(POINTS_PER_REPORT) {
+            report("counting points", x, size);
+        }
+        let rhs = ((x * x % size) * x + a * x + b) % size;
+        if rhs == 0 {
+            continue;
+        }
+        if is_square[rhs as usize] {
+            count += 1;
+        } else {
+            count -= 1;
+        }
+    }
+    Ok(BigInt::from(count))
+}
+
+/// The Hasse interval [p + 1 − 2√p, p + 1 + 2√p], rounded inwards, which contains the order of every
+/// elliptic curve over F_p.
+pub fn hasse_interval(p: &BigInt) -> (BigInt, BigInt) {
+    let width = (BigInt::from(4) * p).sqrt();
+    (p + 1u32 - &width, p + 1u32 + width)
+}
+
+/// The trace of Frobenius t = p + 1 − #E of a curve over F_p with `order` points, rejecting orders that
+/// break the Hasse bound |t| ≤ 2√p.
+///
+/// Anomalous curves (#E = p, i.e. t = 1), whose discrete logarithms Smart's attack computes in
+/// polynomial time, are logged at debug level; `CurveAnalysis::weaknesses` reports them.
+pub fn trace_of_frobenius(order: &BigInt, p: &BigInt) -> Result<BigInt, TransformError> {
+    let trace = p + 1u32 - order;
+    if &trace * &trace > BigInt::from(4) * p {
+        let (low, high) = hasse_interval(p);
+        return Err(TransformError::InvalidParameters(format!(
+            "{} points is outside the Hasse interval [{}, {}] for p = {}",
+            order, low, high, p
+        )));
+    }
+    if order == p {
+        debug!(%p, "the curve is anomalous (#E = p): Smart's attack solves its discrete logarithms");
+    }
+    Ok(trace)
+}
+
+/// The order of `point`, the least n > 0 with n·P = O.
+///
+/// From a known multiple, such as the group order, the order follows by dividing out prime factors while
+/// the point stays annihilated. Without one, baby-step giant-step finds a multiple in the Hasse interval,
+/// which holds the group order, in about 2·p^(1/4) group operations. Both steps count against `limits`:
+/// the baby-step table and the giant steps, and the trial divisions that factor the multiple, so this is
+/// for p up to about 2^64 and multiples whose prime factors are all but one small.
+pub fn point_order<C: CurveGroup>(
+    curve: &C,
+    point: &Point,
+    multiple: Option<&BigInt>,
+    limits: &SearchLimits,
+) -> Result<BigInt, TransformError> {
+    let mut order = match multiple {
+        Some(multiple) => {
+            if !scalar_mul(curve, point, multiple).is_infinity() {
+                return Err(TransformError::InvalidParameters(format!(
+                    "{} is not a multiple of the order of the point",
+                    multiple
+                )));
+            }
+            multiple.clone()
+        }
+        None => hasse_multiple(curve, point, limits)?,
+    };
+    for factor in prime_factors(&order, limits)? {
+        while order.is_multiple_of(&factor) && scalar_mul(curve, point, &(&order / &factor)).is_infinity() {
+            order /= &factor;
+        }
+    }
+    Ok(order)
+}
+
+/// A multiple of the order of `point` in the Hasse interval, by baby-step giant-step: with the interval
+/// [low, low + width] and m ≈ √width, it looks for i and j < m with (low + i·m)·P = −j·P.
+fn hasse_multiple<C: CurveGroup>(
+    curve: &C,
+    point: &Point,
+    limits: &SearchLimits,
+) -> Result<BigInt, TransformError> {
+    let (low, high) = hasse_interval(curve.modulus());
+    let steps = (&high - &low + 1u32).sqrt() + 1u32;
+    let mut budget = SearchBudget::new(limits);
+    let mut baby_steps = HashMap::new();
+    let mut baby = Point::Infinity;
+    let mut j = BigInt::from(0);
+    while j < steps {
+        budget.tick("computing baby steps for the point order")?;
+        baby_steps.entry(baby.clone()).or_insert_with(|| j.clone());
+        baby = curve.add(&baby, point);
+        j += 1u32;
+    }
+    // `baby` is now m·P, the giant step
+    let mut giant = scalar_mul(curve, point, &low);
+    let mut start = low;
+    while start <= high {
+        budget.tick("taking giant steps for the point order")?;
+        // low = (√p − 1)^2 > 0, so every candidate is positive
+        if let Some(j) = baby_steps.get(&curve.negate(&giant)) {
+            return Ok(&start + j);
+        }
+        giant = curve.add(&giant, &baby);
+        start += &steps;
+    }
+    Err(TransformError::PointNotOnCurve)
+}
+
+/// The distinct prime factors of `n` > 0 by trial division, one attempt per divisor, taking the cofactor
+/// left once it is a probable prime.
+fn prime_factors(n: &BigInt, limits: &SearchLimits) -> Result<Vec<BigInt>, TransformError> {
+    let mut budget = SearchBudget::new(limits);
+    let mut rest = n.clone();
+    let mut factors = Vec::new();
+    let mut divisor = BigInt::from(2);
+    // Only a division changes the rest, so that is when it is tested again
+    let mut rest_is_prime = is_probable_prime(&rest, MILLER_RABIN_ROUNDS);
+    while !rest.is_one() && !rest_is_prime && &divisor * &divisor <= rest {
+        budget.tick("factoring a multiple of the point order")?;
+        if rest.is_multiple_of(&divisor) {
+            factors.push(divisor.clone());
+            while rest.is_multiple_of(&divisor) {
+                rest /= &divisor;
+            }
+            rest_is_prime = is_probable_prime(&rest, MILLER_RABIN_ROUNDS);
+        }
+        divisor += 1u32;
+    }
+    if !rest.is_one() {
+        factors.push(rest);
+    }
+    Ok(factors)
+}
@@ -0,0 +1,178 @@
+use crate::error::TransformError;
+use crate::field::Field;
+use crate::limits::SearchLimits;
+use crate::numtheory::mod_inverse;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{ToPrimitive, Zero};
+use std::fmt::Write;
+
+/// Largest modulus accepted by the plotting functions; beyond this the point clouds are unreadable.
+pub const MAX_PLOT_MODULUS: u64 = 4096;
+
+/// Largest modulus accepted by `render_ascii`, so that each grid fits in a terminal.
+pub const MAX_ASCII_MODULUS: u64 = 64;
+
+/// Side length, in pixels, of each plot panel.
+const PANEL_SIZE: f64 = 360.0;
+/// Gap around and between the panels.
+const MARGIN: f64 = 40.0;
+
+/// Lists every affine point of y^2 = x^3 + ax + b over a small F_p, ordered by x and then y.
+pub fn affine_points(a: &BigInt, b: &BigInt, p: &BigInt) -> Result<Vec<(BigInt, BigInt)>, TransformError> {
+    let size = p.to_u64().filter(|&size| size > 3 && size <= MAX_PLOT_MODULUS).ok_or_else(|| {
+        TransformError::InvalidParameters(format!("plotting needs 3 < p <= {}, got {}", MAX_PLOT_MODULUS, p))
+    })?;
+    let field = Field::new(p);
+    let mut points = Vec::new();
+    for x in 0..size {
+        let x = BigInt::from(x);
+        let rhs = (&x * &x * &x + a * &x + b).mod_floor(p);
+        if let Some(y) = field.sqrt(&rhs, &SearchLimits::default())? {
+            if y.is_zero() {
+                points.push((x, y));
+            } else {
+                let other = p - &y;
+                points.push((x.clone(), y.clone().min(other.clone())));
+                points.push((x, y.max(other)));
+            }
+        }
+    }
+    Ok(points)
+}
+
+/// Maps Weierstrass points to the Montgomery curve with parameters (A, B) returned by `transform_to_montgomery`.
+///
+/// The transform has B = s^{-1} and A = 3·z0·B, so z0 = A / (3B) is recovered rather than searched for again.
+pub fn map_points(
+    points: &[(BigInt, BigInt)],
+    a_montgomery: &BigInt,
+    b_montgomery: &BigInt,
+    p: &BigInt,
+) -> Result<Vec<(BigInt, BigInt)>, TransformError> {
+    let three_b_inv = mod_inverse(&(BigInt::from(3) * b_montgomery), p).ok_or_else(|| {
+        TransformError::InvalidParameters(format!("B = {} is not invertible modulo p", b_montgomery))
+    })?;
+    let z0 = (a_montgomery * three_b_inv).mod_floor(p);
+    Ok(points
+        .iter()
+        .map(|(x, y)| ((b_montgomery * (x - &z0)).mod_floor(p), (b_montgomery * y).mod_floor(p)))
+        .collect())
+}
+
+/// Draws one panel with its frame, title and points, coloring point i with `colors[i]`.
+fn draw_panel(svg: &mut String, left: f64, title: &str, points: &[(BigInt, BigInt)], colors: &[String], p: u64) {
+    let scale = PANEL_SIZE / p as f64;
+    let radius = (scale / 2.0).clamp(1.0, 4.0);
+    let _ = writeln!(
+        svg,
+        r##"  <rect x="{left}" y="{MARGIN}" width="{PANEL_SIZE}" height="{PANEL_SIZE}" fill="none" stroke="#888"/>"##
+    );
+    let _ = writeln!(
+        svg,
+        r#"  <text x="{}" y="{}" text-anchor="middle" font-family="sans-serif" font-size="14">{}</text>"#,
+        left + PANEL_SIZE / 2.0,
+        MARGIN - 12.0,
+        title
+    );
+    for ((x, y), color) in points.iter().zip(colors) {
+        // Row 0 is at the bottom, as on a blackboard
+        let cx = left + (x.to_f64().unwrap_or(0.0) + 0.5) * scale;
+        let cy = MARGIN + PANEL_SIZE - (y.to_f64().unwrap_or(0.0) + 0.5) * scale;
+        let _ = writeln!(
+            svg,
+            r#"  <circle cx="{:.2}" cy="{:.2}" r="{:.2}" fill="{}"><title>({}, {})</title></circle>"#,
+            cx, cy, radius, color, x, y
+        );
+    }
+}
+
+/// Renders the Weierstrass curve and its Montgomery image side by side as an SVG document.
+///
+/// Each point and its image share a color, and hovering a point shows its coordinates.
+pub fn render_svg(
+    a: &BigInt,
+    b: &BigInt,
+    p: &BigInt,
+    a_montgomery: &BigInt,
+    b_montgomery: &BigInt,
+) -> Result<String, TransformError> {
+    let weierstrass = affine_points(a, b, p)?;
+    let montgomery = map_points(&weierstrass, a_montgomery, b_montgomery, p)?;
+    let size = p.to_u64().expect("affine_points checked the modulus");
+    let colors: Vec<String> = (0..weierstrass.len())
+        .map(|i| format!("hsl({:.1}, 70%, 45%)", i as f64 * 360.0 / weierstrass.len().max(1) as f64))
+        .collect();
+
+    let width = 3.0 * MARGIN + 2.0 * PANEL_SIZE;
+    let height = 2.0 * MARGIN + PANEL_SIZE;
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    let _ = writeln!(svg, r#"  <rect width="100%" height="100%" fill="white"/>"#);
+    let title = format!("y^2 = x^3 + {}x + {} over F_{}", a.mod_floor(p), b.mod_floor(p), p);
+    draw_panel(&mut svg, MARGIN, &title, &weierstrass, &colors, size);
+    let title = format!("{}v^2 = u^3 + {}u^2 + u over F_{}", b_montgomery, a_montgomery, p);
+    draw_panel(&mut svg, 2.0 * MARGIN + PANEL_SIZE, &title, &montgomery, &colors, size);
+    let _ = writeln!(
+        svg,
+        r#"  <text x="{}" y="{}" text-anchor="middle" font-family="sans-serif" font-size="12">{} affine points; matching colors mark a point and its image</text>"#,
+        width / 2.0,
+        height - 14.0,
+        weierstrass.len()
+    );
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+/// Lays out one curve as rows of text, top row first, with y labels on the left and x labels underneath.
+fn ascii_grid(title: &str, points: &[(BigInt, BigInt)], p: u64) -> Vec<String> {
+    let size = p as usize;
+    let mut cells = vec![vec!['.'; size]; size];
+    for (x, y) in points {
+        let (x, y) = (x.to_usize().unwrap_or(0), y.to_usize().unwrap_or(0));
+        cells[y][x] = '*';
+    }
+    let label_width = (p - 1).to_string().len();
+    let mut lines = vec![title.to_string()];
+    for (y, row) in cells.iter().enumerate().rev() {
+        let row: Vec<String> = row.iter().map(char::to_string).collect();
+        lines.push(format!("{:>label_width$} | {}", y, row.join(" ")));
+    }
+    lines.push(format!("{:>label_width$} +-{}", "", "--".repeat(size)));
+    let last = (p - 1).to_string();
+    let axis_width = 2 * size - 1;
+    lines.push(format!("{:>label_width$}   0{:>width$}", "", last, width = axis_width - 1));
+    lines
+}
+
+/// Renders the points of y^2 = x^3 + ax + b as a text grid, optionally followed on the right by its
+/// Montgomery image for the parameters (A, B) returned by `transform_to_montgomery`.
+pub fn render_ascii(
+    a: &BigInt,
+    b: &BigInt,
+    p: &BigInt,
+    montgomery: Option<(&BigInt, &BigInt)>,
+) -> Result<String, TransformError> {
+    let size = p.to_u64().filter(|&size| size <= MAX_ASCII_MODULUS).ok_or_else(|| {
+        TransformError::InvalidParameters(format!("ASCII plots need p <= {}, got {}", MAX_ASCII_MODULUS, p))
+    })?;
+    let weierstrass = affine_points(a, b, p)?;
+    let title = format!("y^2 = x^3 + {}x + {} over F_{}", a.mod_floor(p), b.mod_floor(p), p);
+    let left = ascii_grid(&title, &weierstrass, size);
+    let lines = match montgomery {
+        None => left,
+        Some((a_montgomery, b_montgomery)) => {
+            let mapped = map_points(&weierstrass, a_montgomery, b_montgomery, p)?;
+            let title = format!("{}v^2 = u^3 + {}u^2 + u over F_{}", b_montgomery, a_montgomery, p);
+            let right = ascii_grid(&title, &mapped, size);
+            let width = left.iter().map(String::len).max().unwrap_or(0);
+            left.iter().zip(&right).map(|(left, right)| format!("{:<width$}    {}", left, right)).collect()
+        }
+    };
+    let mut text = lines.join("\n");
+    text.push('\n');
+    Ok(text)
+}
@@ -0,0 +1,344 @@
+//! Polynomials over F_p, the arithmetic behind root finding and factorization: sums, products,
+//! division with remainder, monic gcds, evaluation and powers modulo another polynomial, and the roots
+//! in F_p by Cantor-Zassenhaus equal-degree factorization.
+
+use crate::error::TransformError;
+use crate::limits::{SearchBudget, SearchLimits};
+use crate::numtheory::{is_probable_prime, mod_inverse, mod_sqrt, MILLER_RABIN_ROUNDS};
+use crate::ops;
+use num_bigint::{BigInt, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A polynomial c_0 + c_1·x + … + c_n·x^n over F_p.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Polynomial {
+    /// The coefficients in [0, p), lowest degree first, with no trailing zeros; empty for zero.
+    coefficients: Vec<BigInt>,
+    /// Characteristic of the field.
+    p: BigInt,
+}
+
+impl Polynomial {
+    /// Creates the polynomial with `coefficients`, lowest degree first, reducing them modulo the prime `p`.
+    pub fn new(coefficients: &[BigInt], p: &BigInt) -> Result<Polynomial, TransformError> {
+        if !is_probable_prime(p, MILLER_RABIN_ROUNDS) {
+            return Err(TransformError::InvalidParameters(format!(
+                "polynomials need a prime modulus, got {}",
+                p
+            )));
+        }
+        Ok(Polynomial::with_modulus(coefficients, p))
+    }
+
+    /// `new` for a `p` the caller has already checked to be prime.
+    pub(crate) fn with_modulus(coefficients: &[BigInt], p: &BigInt) -> Polynomial {
+        Polynomial { coefficients: Vec::new(), p: p.clone() }.with(coefficients.to_vec())
+    }
+
+    /// Builds a polynomial over this polynomial's field from raw coefficients.
+    fn with(&self, coefficients: Vec<BigInt>) -> Polynomial {
+        let mut coefficients: Vec<BigInt> = coefficients.iter().map(|c| c.mod_floor(&self.p)).collect();
+        while coefficients.last().is_some_and(Zero::is_zero) {
+            coefficients.pop();
+        }
+        Polynomial { coefficients, p: self.p.clone() }
+    }
+
+    /// The zero polynomial over this polynomial's field.
+    pub fn zero(&self) -> Polynomial {
+        self.with(Vec::new())
+    }
+
+    /// The constant polynomial `value` over this polynomial's field.
+    pub fn constant(&self, value: &BigInt) -> Polynomial {
+        self.with(vec![value.clone()])
+    }
+
+    pub fn one(&self) -> Polynomial {
+        self.constant(&BigInt::one())
+    }
+
+    /// The polynomial x over this polynomial's field.
+    pub fn x(&self) -> Polynomial {
+        self.with(vec![BigInt::zero(), BigInt::one()])
+    }
+
+    /// The coefficients, lowest degree first, without trailing zeros.
+    pub fn coefficients(&self) -> &[BigInt] {
+        &self.coefficients
+    }
+
+    /// The characteristic p of the field.
+    pub fn modulus(&self) -> &BigInt {
+        &self.p
+    }
+
+    /// The degree, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    /// The coefficient of the highest power of x, or `None` for the zero polynomial.
+    pub fn leading_coefficient(&self) -> Option<&BigInt> {
+        self.coefficients.last()
+    }
+
+    /// Returns the polynomial divided by its leading coefficient; zero stays zero.
+    pub fn monic(&self) -> Polynomial {
+        match self.leading_coefficient() {
+            Some(lead) => {
+                let lead_inv = mod_inverse(lead, &self.p).expect("p is prime");
+                self.with(self.coefficients.iter().map(|c| c * &lead_inv).collect())
+            }
+            None => self.zero(),
+        }
+    }
+
+    /// Evaluates the polynomial at `x` by Horner's rule.
+    pub fn evaluate(&self, x: &BigInt) -> BigInt {
+        ops::record(self.coefficients.len().saturating_sub(1) as u64, 0);
+        self.coefficients.iter().rev().fold(BigInt::zero(), |value, c| (value * x + c).mod_floor(&self.p))
+    }
+
+    /// Returns the formal derivative.
+    pub fn derivative(&self) -> Polynomial {
+        self.with(self.coefficients.iter().enumerate().skip(1).map(|(i, c)| c * i).collect())
+    }
+
+    /// Returns the quotient and remainder of the division by `divisor`, the remainder of lower degree
+    /// than `divisor`, or `None` if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &Polynomial) -> Option<(Polynomial, Polynomial)> {
+        self.assert_same_field(divisor);
+        let lead_inv = mod_inverse(divisor.leading_coefficient()?, &self.p).expect("p is prime");
+        let mut remainder = self.coefficients.clone();
+        let divisor = &divisor.coefficients;
+        let mut quotient = vec![BigInt::zero(); (remainder.len() + 1).saturating_sub(divisor.len())];
+        while remainder.len() >= divisor.len() {
+            let shift = remainder.len() - divisor.len();
+            ops::record(1 + divisor.len() as u64, 0);
+            let factor = (remainder.last().expect("non-empty") * &lead_inv).mod_floor(&self.p);
+            for (i, c) in divisor.iter().enumerate() {
+                remainder[shift + i] = (&remainder[shift + i] - &factor * c).mod_floor(&self.p);
+            }
+            quotient[shift] = factor;
+            while remainder.last().is_some_and(Zero::is_zero) {
+                remainder.pop();
+            }
+        }
+        Some((self.with(quotient), self.with(remainder)))
+    }
+
+    /// Returns the remainder of the division by `divisor`, or `None` if `divisor` is zero.
+    pub fn rem(&self, divisor: &Polynomial) -> Option<Polynomial> {
+        self.div_rem(divisor).map(|(_, remainder)| remainder)
+    }
+
+    /// Returns the monic greatest common divisor, zero only if both polynomials are zero.
+    pub fn gcd(&self, other: &Polynomial) -> Polynomial {
+        self.assert_same_field(other);
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while let Some(remainder) = a.rem(&b) {
+            (a, b) = (b, remainder);
+        }
+        a.monic()
+    }
+
+    /// Returns `self · other` reduced modulo the non-zero polynomial `modulus`.
+    pub fn mul_mod(&self, other: &Polynomial, modulus: &Polynomial) -> Polynomial {
+        (self * other).rem(modulus).expect("the modulus is non-zero")
+    }
+
+    /// Raises the polynomial to a non-negative power modulo the non-zero polynomial `modulus` by
+    /// square-and-multiply, as needed for x^p mod f and the splitting steps of root finding.
+    pub fn pow_mod(&self, exponent: &BigInt, modulus: &Polynomial) -> Polynomial {
+        let base = self.rem(modulus).expect("the modulus is non-zero");
+        let mut result = self.one().rem(modulus).expect("the modulus is non-zero");
+        for bit in (0..exponent.bits()).rev() {
+            result = result.mul_mod(&result, modulus);
+            if exponent.bit(bit) {
+                result = result.mul_mod(&base, modulus);
+            }
+        }
+        result
+    }
+
+    /// Every root in F_p, in increasing order and each once, of a non-zero polynomial; the zero
+    /// polynomial, of which every element is a root, is an error.
+    ///
+    /// gcd(x^p − x, f) is the product of the distinct linear factors of f, found with O(log p) field
+    /// operations per degree. Equal-degree factorization then splits any product g of degree d > 2 into
+    /// gcd((x + δ)^((p−1)/2) − 1, g) and its cofactor for a random δ, since x + δ is a square modulo about
+    /// half the factors, and a split quadratic is solved with one square root. For the cubics of the
+    /// transform that is at most one splitting step on average, each δ counting against `limits`.
+    pub fn roots<R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        limits: &SearchLimits,
+        rng: &mut R,
+    ) -> Result<Vec<BigInt>, TransformError> {
+        if self.is_zero() {
+            return Err(TransformError::InvalidParameters(
+                "every element is a root of the zero polynomial".to_string(),
+            ));
+        }
+        let p = &self.p;
+        if p == &BigInt::from(2) {
+            let elements = [BigInt::zero(), BigInt::one()];
+            return Ok(elements.into_iter().filter(|z| self.evaluate(z).is_zero()).collect());
+        }
+        let x = self.x();
+        let half = (p - 1u32) / 2u32;
+        let mut budget = SearchBudget::new(limits);
+        let mut roots = Vec::new();
+        let mut pending = vec![self.gcd(&(&x.pow_mod(p, self) - &x))];
+        while let Some(factor) = pending.pop() {
+            match factor.degree() {
+                None | Some(0) => {}
+                Some(1) => roots.push((-&factor.coefficients[0]).mod_floor(p)),
+                Some(2) => {
+                    // x^2 + c1·x + c0 has the roots (−c1 ± sqrt(c1^2 − 4c0)) / 2
+                    let [c0, c1, _] = &factor.coefficients[..] else { unreachable!("a monic quadratic") };
+                    let discriminant = (c1 * c1 - BigInt::from(4) * c0).mod_floor(p);
+                    let root = mod_sqrt(&discriminant, p, limits)?;
+                    let root = root.expect("a split quadratic has a square discriminant");
+                    let two_inv = (p + 1u32) / 2u32;
+                    roots.push(((&root - c1) * &two_inv).mod_floor(p));
+                    roots.push(((-&root - c1) * &two_inv).mod_floor(p));
+                }
+                Some(degree) => {
+                    budget.tick("splitting a polynomial into linear factors")?;
+                    let delta = rng.gen_bigint_range(&BigInt::zero(), p);
+                    let shifted = (&x + &x.constant(&delta)).pow_mod(&half, &factor);
+                    let part = factor.gcd(&(&shifted - &x.one()));
+                    if part.degree().is_some_and(|part_degree| (1..degree).contains(&part_degree)) {
+                        let (cofactor, _) = factor.div_rem(&part).expect("the part is non-zero");
+                        pending.extend([part, cofactor]);
+                    } else {
+                        pending.push(factor);
+                    }
+                }
+            }
+        }
+        roots.sort();
+        Ok(roots)
+    }
+
+    fn assert_same_field(&self, other: &Polynomial) {
+        assert!(self.p == other.p, "polynomials over different fields");
+    }
+}
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let mut first = true;
+        for (i, c) in self.coefficients.iter().enumerate().rev().filter(|(_, c)| !c.is_zero()) {
+            if !first {
+                write!(f, " + ")?;
+            }
+            first = false;
+            match (i, c.is_one()) {
+                (0, _) => write!(f, "{}", c)?,
+                (1, true) => write!(f, "x")?,
+                (1, false) => write!(f, "{}*x", c)?,
+                (_, true) => write!(f, "x^{}", i)?,
+                (_, false) => write!(f, "{}*x^{}", c, i)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Add for &Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, other: &Polynomial) -> Polynomial {
+        self.assert_same_field(other);
+        let (long, short) = if self.coefficients.len() >= other.coefficients.len() {
+            (&self.coefficients, &other.coefficients)
+        } else {
+            (&other.coefficients, &self.coefficients)
+        };
+        let mut sum = long.clone();
+        for (c, d) in sum.iter_mut().zip(short) {
+            *c += d;
+        }
+        self.with(sum)
+    }
+}
+
+impl Sub for &Polynomial {
+    type Output = Polynomial;
+
+    fn sub(self, other: &Polynomial) -> Polynomial {
+        self + &-other
+    }
+}
+
+impl Mul for &Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, other: &Polynomial) -> Polynomial {
+        self.assert_same_field(other);
+        if self.is_zero() || other.is_zero() {
+            return self.zero();
+        }
+        ops::record((self.coefficients.len() * other.coefficients.len()) as u64, 0);
+        let mut product = vec![BigInt::zero(); self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, c) in self.coefficients.iter().enumerate() {
+            for (j, d) in other.coefficients.iter().enumerate() {
+                product[i + j] += c * d;
+            }
+        }
+        self.with(product)
+    }
+}
+
+impl Neg for &Polynomial {
+    type Output = Polynomial;
+
+    fn neg(self) -> Polynomial {
+        self.with(self.coefficients.iter().map(|c| -c).collect())
+    }
+}
+
+impl Add for Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, other: Polynomial) -> Polynomial {
+        &self + &other
+    }
+}
+
+impl Sub for Polynomial {
+    type Output = Polynomial;
+
+    fn sub(self, other: Polynomial) -> Polynomial {
+        &self - &other
+    }
+}
+
+impl Mul for Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, other: Polynomial) -> Polynomial {
+        &self * &other
+    }
+}
+
+impl Neg for Polynomial {
+    type Output = Polynomial;
+
+    fn neg(self) -> Polynomial {
+        -&self
+    }
+}
@@ -0,0 +1,14 @@
+use crate::log::trace;
+
+/// Target of the progress events, so that front ends can pick them out and render them, e.g. as a
+/// progress bar; they are ordinary TRACE events otherwise.
+pub const PROGRESS_TARGET: &str = "weierstrass_to_montgomery::progress";
+
+/// Number of search attempts between progress events.
+pub(crate) const ATTEMPTS_PER_REPORT: u64 = 1 << 10;
+
+/// Reports that `done` out of at most `total` steps of `task` are finished.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn report(task: &str, done: u64, total: u64) {
+    trace!(target: PROGRESS_TARGET, task, done, total);
+}
@@ -0,0 +1,256 @@
+//! Protocol Buffers messages for curves, points and transform results, for services that exchange them
+//! over gRPC.
+//!
+//! The structs mirror `proto/weierstrass_to_montgomery.proto` (package `weierstrass_to_montgomery.v1`)
+//! field for field, as `prost-build` would generate them, so the crate needs no `protoc` at build time;
+//! `SCHEMA` holds the file for code generation in other languages. Use `prost::Message::encode_to_vec`
+//! and `decode` on them, and the conversions below to get at the library types.
+
+use crate::curve::{CurveGroup, Point as CurvePoint};
+use crate::error::TransformError;
+use crate::map::{MontgomeryMap as Map, MAP_FORMAT_VERSION};
+use crate::montgomery::MontgomeryCurve as Montgomery;
+use crate::weierstrass::WeierstrassCurve as Weierstrass;
+use num_bigint::{BigInt, Sign};
+use num_traits::Zero;
+use prost::Message;
+
+/// The `.proto` definition the messages of this module implement.
+pub const SCHEMA: &str = include_str!("../proto/weierstrass_to_montgomery.proto");
+
+/// The short Weierstrass curve y^2 = x^3 + ax + b over F_p.
+#[derive(Clone, PartialEq, Message)]
+pub struct WeierstrassCurve {
+    #[prost(bytes = "vec", tag = "1")]
+    pub a: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub b: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub p: Vec<u8>,
+}
+
+/// The Montgomery curve B·v^2 = u^3 + A·u^2 + u over F_p.
+#[derive(Clone, PartialEq, Message)]
+pub struct MontgomeryCurve {
+    #[prost(bytes = "vec", tag = "1")]
+    pub a: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub b: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub p: Vec<u8>,
+}
+
+/// An affine point, or the point at infinity when `infinity` is set.
+#[derive(Clone, PartialEq, Message)]
+pub struct Point {
+    #[prost(bool, tag = "1")]
+    pub infinity: bool,
+    #[prost(bytes = "vec", tag = "2")]
+    pub x: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub y: Vec<u8>,
+}
+
+/// The values of a `MontgomeryMap` with its format version.
+#[derive(Clone, PartialEq, Message)]
+pub struct MontgomeryMap {
+    #[prost(uint32, tag = "1")]
+    pub version: u32,
+    #[prost(bytes = "vec", tag = "2")]
+    pub p: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub a: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub b: Vec<u8>,
+    #[prost(bytes = "vec", tag = "5")]
+    pub z0: Vec<u8>,
+    #[prost(bytes = "vec", tag = "6")]
+    pub s: Vec<u8>,
+    #[prost(bytes = "vec", tag = "7")]
+    pub s_inv: Vec<u8>,
+    #[prost(bytes = "vec", tag = "8")]
+    pub a_montgomery: Vec<u8>,
+    #[prost(bytes = "vec", tag = "9")]
+    pub b_montgomery: Vec<u8>,
+}
+
+/// A transformed point: `image` is `point` sent through `map`.
+#[derive(Clone, PartialEq, Message)]
+pub struct TransformResult {
+    #[prost(message, optional, tag = "1")]
+    pub map: Option<MontgomeryMap>,
+    #[prost(message, optional, tag = "2")]
+    pub point: Option<Point>,
+    #[prost(message, optional, tag = "3")]
+    pub image: Option<Point>,
+}
+
+fn invalid(message: String) -> TransformError {
+    TransformError::InvalidParameters(message)
+}
+
+/// Big-endian magnitude bytes without leading zeros; empty for zero.
+fn magnitude(value: &BigInt) -> Vec<u8> {
+    if value.is_zero() {
+        Vec::new()
+    } else {
+        value.to_bytes_be().1
+    }
+}
+
+fn integer(bytes: &[u8]) -> BigInt {
+    BigInt::from_bytes_be(Sign::Plus, bytes)
+}
+
+/// Reads a field element, which must already be reduced modulo p.
+fn element(name: &str, bytes: &[u8], p: &BigInt) -> Result<BigInt, TransformError> {
+    let value = integer(bytes);
+    if &value >= p {
+        return Err(invalid(format!("{} = {} is not in the range [0, p)", name, value)));
+    }
+    Ok(value)
+}
+
+impl From<&Weierstrass> for WeierstrassCurve {
+    fn from(curve: &Weierstrass) -> WeierstrassCurve {
+        WeierstrassCurve { a: magnitude(&curve.a), b: magnitude(&curve.b), p: magnitude(&curve.p) }
+    }
+}
+
+impl TryFrom<&WeierstrassCurve> for Weierstrass {
+    type Error = TransformError;
+
+    /// Rejects coefficients outside [0, p) and singular curves.
+    fn try_from(message: &WeierstrassCurve) -> Result<Weierstrass, TransformError> {
+        let p = integer(&message.p);
+        Weierstrass::new(&element("a", &message.a, &p)?, &element("b", &message.b, &p)?, &p)
+    }
+}
+
+impl From<&Montgomery> for MontgomeryCurve {
+    fn from(curve: &Montgomery) -> MontgomeryCurve {
+        MontgomeryCurve { a: magnitude(&curve.a), b: magnitude(&curve.b), p: magnitude(&curve.p) }
+    }
+}
+
+impl TryFrom<&MontgomeryCurve> for Montgomery {
+    type Error = TransformError;
+
+    /// Rejects coefficients outside [0, p) and singular curves.
+    fn try_from(message: &MontgomeryCurve) -> Result<Montgomery, TransformError> {
+        let p = integer(&message.p);
+        Montgomery::new(&element("A", &message.a, &p)?, &element("B", &message.b, &p)?, &p)
+    }
+}
+
+impl From<&CurvePoint> for Point {
+    fn from(point: &CurvePoint) -> Point {
+        match point {
+            CurvePoint::Infinity => Point { infinity: true, x: Vec::new(), y: Vec::new() },
+            CurvePoint::Affine(x, y) => Point { infinity: false, x: magnitude(x), y: magnitude(y) },
+        }
+    }
+}
+
+impl Point {
+    /// Reads the point and checks that it lies on `curve`.
+    pub fn to_point<C: CurveGroup>(&self, curve: &C) -> Result<CurvePoint, TransformError> {
+        if self.infinity {
+            if !self.x.is_empty() || !self.y.is_empty() {
+                return Err(invalid("the point at infinity has coordinates".to_string()));
+            }
+            return Ok(CurvePoint::Infinity);
+        }
+        let point = CurvePoint::Affine(integer(&self.x), integer(&self.y));
+        if !curve.contains(&point) {
+            return Err(TransformError::PointNotOnCurve);
+        }
+        Ok(point)
+    }
+}
+
+impl From<&Map> for MontgomeryMap {
+    fn from(map: &Map) -> MontgomeryMap {
+        MontgomeryMap {
+            version: MAP_FORMAT_VERSION,
+            p: magnitude(&map.p),
+            a: magnitude(&map.a),
+            b: magnitude(&map.b),
+            z0: magnitude(&map.z0),
+            s: magnitude(&map.s),
+            s_inv: magnitude(&map.s_inv),
+            a_montgomery: magnitude(&map.a_montgomery),
+            b_montgomery: magnitude(&map.b_montgomery),
+        }
+    }
+}
+
+impl TryFrom<&MontgomeryMap> for Map {
+    type Error = TransformError;
+
+    /// Checks the version and validates the map as `MontgomeryMap::from_bytes` does.
+    fn try_from(message: &MontgomeryMap) -> Result<Map, TransformError> {
+        if message.version != MAP_FORMAT_VERSION {
+            return Err(invalid(format!("unsupported map format version {}", message.version)));
+        }
+        let map = Map {
+            p: integer(&message.p),
+            a: integer(&message.a),
+            b: integer(&message.b),
+            z0: integer(&message.z0),
+            s: integer(&message.s),
+            s_inv: integer(&message.s_inv),
+            a_montgomery: integer(&message.a_montgomery),
+            b_montgomery: integer(&message.b_montgomery),
+        };
+        map.validate()?;
+        Ok(map)
+    }
+}
+
+impl Map {
+    /// Serializes the map as a `weierstrass_to_montgomery.v1.MontgomeryMap` message.
+    pub fn to_protobuf(&self) -> Vec<u8> {
+        MontgomeryMap::from(self).encode_to_vec()
+    }
+
+    /// Parses and validates a map written by `to_protobuf`.
+    pub fn from_protobuf(bytes: &[u8]) -> Result<Map, TransformError> {
+        let message = MontgomeryMap::decode(bytes)
+            .map_err(|error| invalid(format!("malformed map message: {}", error)))?;
+        Map::try_from(&message)
+    }
+}
+
+impl TransformResult {
+    /// The message for `point` on the map's Weierstrass curve and its `image` on the Montgomery curve.
+    pub fn new(map: &Map, point: &CurvePoint, image: &CurvePoint) -> TransformResult {
+        TransformResult {
+            map: Some(MontgomeryMap::from(map)),
+            point: Some(Point::from(point)),
+            image: Some(Point::from(image)),
+        }
+    }
+
+    /// Reads the map, the point and its image, checking that the point lies on the Weierstrass curve and
+    /// that the map sends it to the image.
+    pub fn to_parts(&self) -> Result<(Map, CurvePoint, CurvePoint), TransformError> {
+        let missing = |field: &str| invalid(format!("the transform result has no {}", field));
+        let map = Map::try_from(self.map.as_ref().ok_or_else(|| missing("map"))?)?;
+        let curve = Weierstrass::new(&map.a, &map.b, &map.p)?;
+        let point = self.point.as_ref().ok_or_else(|| missing("point"))?.to_point(&curve)?;
+        let montgomery = Montgomery::new(&map.a_montgomery, &map.b_montgomery, &map.p)?;
+        let image = self.image.as_ref().ok_or_else(|| missing("image"))?.to_point(&montgomery)?;
+        let expected = match &point {
+            CurvePoint::Infinity => CurvePoint::Infinity,
+            CurvePoint::Affine(x, y) => {
+                let (u, v) = map.map_point(x, y);
+                CurvePoint::Affine(u, v)
+            }
+        };
+        if image != expected {
+            return Err(invalid("the image is not the mapped point".to_string()));
+        }
+        Ok((map, point, image))
+    }
+}
@@ -0,0 +1,242 @@
+use crate::cubic::cubic_roots;
+use crate::curve::{CurveGroup, Point};
+use crate::error::TransformError;
+use crate::limits::SearchLimits;
+use crate::numtheory::{mod_inverse, mod_nth_root};
+use crate::ops;
+use crate::weierstrass::WeierstrassCurve;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+
+/// The extended Jacobi quartic y^2 = e·x^4 + 2a·x^2 + 1 over F_p; e = 1 is the Jacobi quartic
+/// y^2 = x^4 + 2a·x^2 + 1.
+///
+/// The neutral element (0, 1) is represented by `Point::Infinity`, as for `TwistedEdwardsCurve`, and
+/// −(x, y) = (−x, y).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JacobiQuartic {
+    pub e: BigInt,
+    pub a: BigInt,
+    pub p: BigInt,
+}
+
+/// An isomorphism (x, y) -> (x / u, y) onto the quartic with (u^4·e, u^2·a), from `rescale` or
+/// `to_jacobi`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuarticScaling {
+    /// The image curve.
+    pub curve: JacobiQuartic,
+    pub u: BigInt,
+}
+
+/// The birational map between y^2 = x^3 + ax + b and an extended Jacobi quartic, built around a root
+/// θ of x^3 + ax + b, so that the point (θ, 0) of order 2 goes to (0, −1).
+///
+/// A point maps as (x, y) -> (2(x − θ) / y, (2x + θ)(x − θ)^2 / y^2 − 1) onto the quartic with
+/// e = −(3θ^2 + 4a) / 16 and a = −3θ / 4 (Billet and Joye).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuarticMap {
+    pub weierstrass: WeierstrassCurve,
+    pub quartic: JacobiQuartic,
+    pub theta: BigInt,
+}
+
+impl QuarticScaling {
+    /// Maps a point of the original quartic to the image quartic.
+    pub fn map_point(&self, point: &Point) -> Point {
+        let p = &self.curve.p;
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(x, y) => {
+                let u_inv = mod_inverse(&self.u, p).expect("u is non-zero");
+                Point::Affine((x * u_inv).mod_floor(p), y.clone())
+            }
+        }
+    }
+}
+
+impl JacobiQuartic {
+    /// Creates the quartic, reducing the coefficients and rejecting singular ones (e = 0 or a^2 = e).
+    pub fn new(e: &BigInt, a: &BigInt, p: &BigInt) -> Result<JacobiQuartic, TransformError> {
+        if p <= &BigInt::from(3) {
+            return Err(TransformError::UnsupportedCharacteristic(p.clone()));
+        }
+        let curve = JacobiQuartic {
+            e: e.mod_floor(p),
+            a: a.mod_floor(p),
+            p: p.clone(),
+        };
+        if curve.e.is_zero() || (&curve.a * &curve.a - &curve.e).mod_floor(p).is_zero() {
+            return Err(TransformError::InvalidParameters(
+                "the Jacobi quartic is singular (e = 0 or a^2 = e)".to_string(),
+            ));
+        }
+        Ok(curve)
+    }
+
+    /// Rescales (e, a) to (u^4·e, u^2·a), an isomorphism that maps (x, y) to (x / u, y).
+    pub fn rescale(&self, u: &BigInt) -> Result<QuarticScaling, TransformError> {
+        let p = &self.p;
+        let u = u.mod_floor(p);
+        if u.is_zero() {
+            let message = "the scale u must be non-zero modulo p".to_string();
+            return Err(TransformError::InvalidParameters(message));
+        }
+        let u_squared = (&u * &u).mod_floor(p);
+        let curve = JacobiQuartic {
+            e: (&u_squared * &u_squared * &self.e).mod_floor(p),
+            a: (u_squared * &self.a).mod_floor(p),
+            p: p.clone(),
+        };
+        Ok(QuarticScaling { curve, u })
+    }
+
+    /// Finds u with e·u^4 = 1 and rescales to the Jacobi quartic y^2 = x^4 + 2a'·x^2 + 1; `None` when
+    /// 1 / e is not a fourth power.
+    pub fn to_jacobi(&self) -> Result<Option<QuarticScaling>, TransformError> {
+        let p = &self.p;
+        let e_inv = mod_inverse(&self.e, p).expect("e is non-zero");
+        match mod_nth_root(&e_inv, 4, p, &SearchLimits::default())? {
+            Some(u) => self.rescale(&u).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Evaluates e·x^4 + 2a·x^2 + 1.
+    pub fn rhs(&self, x: &BigInt) -> BigInt {
+        let x_squared = x * x;
+        (&self.e * &x_squared * &x_squared + BigInt::from(2) * &self.a * x_squared + 1u32).mod_floor(&self.p)
+    }
+
+    /// Creates the affine point (x, y), checking that it lies on the curve; (0, 1) gives
+    /// `Point::Infinity`.
+    pub fn point(&self, x: &BigInt, y: &BigInt) -> Result<Point, TransformError> {
+        let (x, y) = (x.mod_floor(&self.p), y.mod_floor(&self.p));
+        if (&y * &y).mod_floor(&self.p) != self.rhs(&x) {
+            return Err(TransformError::PointNotOnCurve);
+        }
+        Ok(if x.is_zero() && y.is_one() { Point::Infinity } else { Point::Affine(x, y) })
+    }
+}
+
+impl QuarticMap {
+    /// Computes the map for `curve` from a root of x^3 + ax + b found with `cubic_roots`.
+    pub fn new(curve: &WeierstrassCurve, limits: &SearchLimits) -> Result<QuarticMap, TransformError> {
+        QuarticMap::new_with_rng(curve, limits, &mut OsRng)
+    }
+
+    /// `QuarticMap::new`, drawing randomness for the root search from `rng`.
+    pub fn new_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        curve: &WeierstrassCurve,
+        limits: &SearchLimits,
+        rng: &mut R,
+    ) -> Result<QuarticMap, TransformError> {
+        match cubic_roots(&curve.a, &curve.b, &curve.p, limits, rng)?.first() {
+            Some(theta) => QuarticMap::from_root(curve, theta),
+            None => Err(TransformError::InvalidParameters(
+                "the curve has no point of order 2: z^3 + az + b has no root in F_p".to_string(),
+            )),
+        }
+    }
+
+    /// Computes the map from a known root θ of x^3 + ax + b.
+    pub fn from_root(curve: &WeierstrassCurve, theta: &BigInt) -> Result<QuarticMap, TransformError> {
+        let p = &curve.p;
+        let theta = theta.mod_floor(p);
+        if !curve.rhs(&theta).is_zero() {
+            return Err(TransformError::InvalidParameters(format!(
+                "theta = {} is not a root of z^3 + az + b",
+                theta
+            )));
+        }
+        let inv = |value: u32| mod_inverse(&BigInt::from(value), p).expect("p is greater than 3");
+        let e = -(BigInt::from(3) * &theta * &theta + BigInt::from(4) * &curve.a) * inv(16);
+        let a = BigInt::from(-3) * &theta * inv(4);
+        Ok(QuarticMap {
+            weierstrass: curve.clone(),
+            quartic: JacobiQuartic::new(&e, &a, p)?,
+            theta,
+        })
+    }
+
+    /// Maps a point of the Weierstrass curve to the quartic; `None` for the other two points of order 2,
+    /// which go to the points at infinity of the quartic.
+    pub fn to_quartic(&self, point: &Point) -> Option<Point> {
+        let p = &self.quartic.p;
+        let (x, y) = match point {
+            Point::Infinity => return Some(Point::Infinity),
+            Point::Affine(x, _) if x == &self.theta => return Some(Point::Affine(BigInt::zero(), p - 1u32)),
+            Point::Affine(x, y) => (x, y),
+        };
+        let y_inv = mod_inverse(y, p)?;
+        let u = x - &self.theta;
+        let x_quartic = (BigInt::from(2) * &u * &y_inv).mod_floor(p);
+        let y_quartic = ((BigInt::from(2) * x + &self.theta) * &u * &u * &y_inv * &y_inv - 1u32).mod_floor(p);
+        Some(Point::Affine(x_quartic, y_quartic))
+    }
+
+    /// Maps a point of the quartic back to the Weierstrass curve, (x, y) -> (2(y + 1) / x^2 − θ / 2,
+    /// 2(x' − θ) / x) with x' the first coordinate.
+    pub fn to_weierstrass(&self, point: &Point) -> Point {
+        let p = &self.quartic.p;
+        let (x, y) = match point {
+            Point::Infinity => return Point::Infinity,
+            Point::Affine(x, _) if x.is_zero() => return Point::Affine(self.theta.clone(), BigInt::zero()),
+            Point::Affine(x, y) => (x, y),
+        };
+        let x_inv = mod_inverse(x, p).expect("x is non-zero");
+        let half = mod_inverse(&BigInt::from(2), p).expect("p is odd");
+        let x_weierstrass = BigInt::from(2) * (y + 1u32) * &x_inv * &x_inv - &self.theta * half;
+        let x_weierstrass = x_weierstrass.mod_floor(p);
+        let y_weierstrass = (BigInt::from(2) * (&x_weierstrass - &self.theta) * x_inv).mod_floor(p);
+        Point::Affine(x_weierstrass, y_weierstrass)
+    }
+}
+
+impl CurveGroup for JacobiQuartic {
+    fn modulus(&self) -> &BigInt {
+        &self.p
+    }
+
+    fn contains(&self, point: &Point) -> bool {
+        match point {
+            Point::Infinity => true,
+            Point::Affine(x, y) => (y * y).mod_floor(&self.p) == self.rhs(x),
+        }
+    }
+
+    fn negate(&self, point: &Point) -> Point {
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(x, y) => Point::Affine((-x).mod_floor(&self.p), y.clone()),
+        }
+    }
+
+    /// The addition law of Hisil et al., which has no exceptions when e is not a square; on other
+    /// quartics it panics for the pairs whose sum or difference is a point at infinity.
+    fn add(&self, first: &Point, second: &Point) -> Point {
+        let p = &self.p;
+        let (x1, y1, x2, y2) = match (first, second) {
+            (Point::Infinity, _) => return second.clone(),
+            (_, Point::Infinity) => return first.clone(),
+            (Point::Affine(x1, y1), Point::Affine(x2, y2)) => (x1, y1, x2, y2),
+        };
+        ops::record(12, 3);
+        let x1x2 = (x1 * x2).mod_floor(p);
+        let t = (&self.e * &x1x2 * &x1x2).mod_floor(p);
+        let denominator =
+            mod_inverse(&(BigInt::one() - &t).mod_floor(p), p).expect("1 - e·x1^2·x2^2 is non-zero");
+        let x3 = ((x1 * y2 + y1 * x2) * &denominator).mod_floor(p);
+        let numerator = (y1 * y2 + BigInt::from(2) * &self.a * &x1x2) * (BigInt::one() + t)
+            + BigInt::from(2) * &self.e * &x1x2 * (x1 * x1 + x2 * x2);
+        let y3 = (numerator * &denominator * denominator).mod_floor(p);
+        if x3.is_zero() && y3.is_one() {
+            Point::Infinity
+        } else {
+            Point::Affine(x3, y3)
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Printing of big integers in the bases cryptographic tools exchange them in: decimal, 0x-prefixed
+//! hexadecimal and 0b-prefixed binary. `NumberFormat` picks one at run time; `Point` and `FieldElement`
+//! also implement the `{:x}`, `{:X}` and `{:b}` format traits, with the prefix under `#`.
+
+use crate::curve::Point;
+#[cfg(feature = "std")]
+use crate::field::FieldElement;
+use core::fmt;
+use num_bigint::BigInt;
+
+/// The base numbers are written in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Radix {
+    #[default]
+    Decimal,
+    /// Base 16 with a `0x` prefix.
+    Hexadecimal,
+    /// Base 2 with a `0b` prefix.
+    Binary,
+}
+
+impl Radix {
+    /// The radix of base 10, 16 or 2.
+    pub fn from_base(base: u32) -> Option<Radix> {
+        match base {
+            10 => Some(Radix::Decimal),
+            16 => Some(Radix::Hexadecimal),
+            2 => Some(Radix::Binary),
+            _ => None,
+        }
+    }
+
+    pub fn base(self) -> u32 {
+        match self {
+            Radix::Decimal => 10,
+            Radix::Hexadecimal => 16,
+            Radix::Binary => 2,
+        }
+    }
+}
+
+/// How big integers are written: the radix and, for hexadecimal, whether the digits are upper case. The
+/// prefix is always `0x` or `0b`, which the spec files, `--input` and `batch` read back.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub radix: Radix,
+    pub uppercase: bool,
+}
+
+impl NumberFormat {
+    /// Wraps `value` so that `{}` writes it in this format, with a `-` before the prefix if negative.
+    pub fn display(self, value: &BigInt) -> Formatted<'_> {
+        Formatted { value, format: self }
+    }
+}
+
+/// A number displayed in a `NumberFormat`, from `NumberFormat::display`.
+#[derive(Clone, Copy, Debug)]
+pub struct Formatted<'a> {
+    value: &'a BigInt,
+    format: NumberFormat,
+}
+
+impl fmt::Display for Formatted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.format.radix, self.format.uppercase) {
+            (Radix::Decimal, _) => write!(f, "{}", self.value),
+            (Radix::Hexadecimal, false) => write!(f, "{:#x}", self.value),
+            (Radix::Hexadecimal, true) => write!(f, "{:#X}", self.value),
+            (Radix::Binary, _) => write!(f, "{:#b}", self.value),
+        }
+    }
+}
+
+/// Writes a number with one of the format traits of `BigInt`.
+type WriteNumber = fn(&BigInt, &mut fmt::Formatter<'_>) -> fmt::Result;
+
+fn write_point(point: &Point, f: &mut fmt::Formatter<'_>, write: WriteNumber) -> fmt::Result {
+    match point {
+        Point::Infinity => f.write_str("O"),
+        Point::Affine(x, y) => {
+            f.write_str("(")?;
+            write(x, f)?;
+            f.write_str(", ")?;
+            write(y, f)?;
+            f.write_str(")")
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn write_element(element: &FieldElement, f: &mut fmt::Formatter<'_>, write: WriteNumber) -> fmt::Result {
+    write(&element.value, f)
+}
+
+/// Implements `LowerHex`, `UpperHex` and `Binary` for `$type` with `$write`, which gets the `BigInt`
+/// implementation of the trait and passes the formatter, and so the `#` flag, on to it.
+macro_rules! radix_traits {
+    ($type:ty, $write:path) => {
+        radix_traits!(@impl $type, $write, LowerHex, UpperHex, Binary);
+    };
+    (@impl $type:ty, $write:path, $($trait:ident),*) => {
+        $(impl fmt::$trait for $type {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                $write(self, f, <BigInt as fmt::$trait>::fmt)
+            }
+        })*
+    };
+}
+
+radix_traits!(Point, write_point);
+#[cfg(feature = "std")]
+radix_traits!(FieldElement, write_element);
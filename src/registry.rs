@@ -0,0 +1,538 @@
+use crate::curve::Point;
+use crate::edwards::TwistedEdwardsCurve;
+use crate::error::TransformError;
+use crate::montgomery::MontgomeryCurve;
+use crate::weierstrass::WeierstrassCurve;
+use num_bigint::BigInt;
+
+/// The curve model a registry entry is given in, which fixes what its two coefficients mean.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveModel {
+    /// y^2 = x^3 + ax + b, coefficients (a, b).
+    Weierstrass,
+    /// B·v^2 = u^3 + A·u^2 + u, coefficients (A, B).
+    Montgomery,
+    /// a·x^2 + y^2 = 1 + d·x^2·y^2, coefficients (a, d).
+    TwistedEdwards,
+}
+
+/// A standardized curve with its base point, subgroup order and cofactor; the constants are kept as
+/// hexadecimal strings so that the registry can be a static table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NamedCurve {
+    pub name: &'static str,
+    /// Other names accepted by `lookup`.
+    pub aliases: &'static [&'static str],
+    /// The object identifier of the curve in dotted form: the namedCurve of ECParameters (RFC 5480) for
+    /// Weierstrass curves, the algorithm identifier of RFC 8410 for Curve25519, Curve448 and their
+    /// Edwards forms.
+    pub oid: Option<&'static str>,
+    pub model: CurveModel,
+    p: &'static str,
+    first: &'static str,
+    second: &'static str,
+    x: &'static str,
+    y: &'static str,
+    order: &'static str,
+    pub cofactor: u32,
+}
+
+const P25519: &str = "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed";
+const ORDER25519: &str = "1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed";
+const V25519: &str = "20ae19a1b8a086b4e01edd2c7748d14c923d4d7e6d7c61b229e9c5a27eced3d9";
+const P448: &str = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffe\
+                    ffffffffffffffffffffffffffffffffffffffffffffffffffffffff";
+const ORDER448: &str = "3fffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+                        7cca23e9c44edb49aed63690216cc2728dc58f552378c292ab5844f3";
+const V448: &str = "7d235d1295f5b1f66c98ab6e58326fcecbae5d34f55545d060f75dc2\
+                    8df3f6edb8027e2346430d211312c4b150677af76fd7223d457b5b1a";
+
+/// The curves known to `lookup`: the 25519 and 448 families, where Wei25519 and Wei448 are the
+/// Weierstrass forms of draft-ietf-lwig-curve-representations, isomorphic to Curve25519 and Curve448;
+/// the NIST P-curves of FIPS 186-4; and the Brainpool curves of RFC 5639, whose t1 variants are the
+/// a = −3 twists of the r1 curves.
+static NAMED_CURVES: &[NamedCurve] = &[
+    NamedCurve {
+        name: "Curve25519",
+        aliases: &["X25519"],
+        oid: Some("1.3.101.110"),
+        model: CurveModel::Montgomery,
+        p: P25519,
+        first: "76d06",
+        second: "1",
+        x: "9",
+        y: V25519,
+        order: ORDER25519,
+        cofactor: 8,
+    },
+    NamedCurve {
+        name: "Ed25519",
+        aliases: &["Edwards25519"],
+        oid: Some("1.3.101.112"),
+        model: CurveModel::TwistedEdwards,
+        p: P25519,
+        first: "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffec",
+        second: "52036cee2b6ffe738cc740797779e89800700a4d4141d8ab75eb4dca135978a3",
+        x: "216936d3cd6e53fec0a4e231fdd6dc5c692cc7609525a7b2c9562d608f25d51a",
+        y: "6666666666666666666666666666666666666666666666666666666666666658",
+        order: ORDER25519,
+        cofactor: 8,
+    },
+    NamedCurve {
+        name: "Wei25519",
+        aliases: &[],
+        oid: None,
+        model: CurveModel::Weierstrass,
+        p: P25519,
+        first: "2aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa984914a144",
+        second: "7b425ed097b425ed097b425ed097b425ed097b425ed097b4260b5e9c7710c864",
+        x: "2aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaad245a",
+        y: V25519,
+        order: ORDER25519,
+        cofactor: 8,
+    },
+    NamedCurve {
+        name: "Curve448",
+        aliases: &["X448"],
+        oid: Some("1.3.101.111"),
+        model: CurveModel::Montgomery,
+        p: P448,
+        first: "262a6",
+        second: "1",
+        x: "5",
+        y: V448,
+        order: ORDER448,
+        cofactor: 4,
+    },
+    NamedCurve {
+        name: "Ed448",
+        aliases: &["Edwards448", "Ed448-Goldilocks"],
+        oid: Some("1.3.101.113"),
+        model: CurveModel::TwistedEdwards,
+        p: P448,
+        first: "1",
+        second: "fffffffffffffffffffffffffffffffffffffffffffffffffffffffe\
+                 ffffffffffffffffffffffffffffffffffffffffffffffffffff6756",
+        x: "4f1970c66bed0ded221d15a622bf36da9e146570470f1767ea6de324\
+            a3d3a46412ae1af72ab66511433b80e18b00938e2626a82bc70cc05e",
+        y: "693f46716eb6bc248876203756c9c7624bea73736ca3984087789c1e\
+            05a0c2d73ad3ff1ce67c39c4fdbd132c4ed7c8ad9808795bf230fa14",
+        order: ORDER448,
+        cofactor: 4,
+    },
+    NamedCurve {
+        name: "Wei448",
+        aliases: &[],
+        oid: None,
+        model: CurveModel::Weierstrass,
+        p: P448,
+        first: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa9\
+                fffffffffffffffffffffffffffffffffffffffffffffffe1a76d41f",
+        second: "5ed097b425ed097b425ed097b425ed097b425ed097b425ed097b425e\
+                 71c71c71c71c71c71c71c71c71c71c71c71c71c71c72c87b7cc69f70",
+        x: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\
+            0000000000000000000000000000000000000000000000000000cb91",
+        y: V448,
+        order: ORDER448,
+        cofactor: 4,
+    },
+    NamedCurve {
+        name: "P-192",
+        aliases: &["secp192r1", "prime192v1", "nistp192"],
+        oid: Some("1.2.840.10045.3.1.1"),
+        model: CurveModel::Weierstrass,
+        p: "fffffffffffffffffffffffffffffffeffffffffffffffff",
+        first: "fffffffffffffffffffffffffffffffefffffffffffffffc",
+        second: "64210519e59c80e70fa7e9ab72243049feb8deecc146b9b1",
+        x: "188da80eb03090f67cbf20eb43a18800f4ff0afd82ff1012",
+        y: "7192b95ffc8da78631011ed6b24cdd573f977a11e794811",
+        order: "ffffffffffffffffffffffff99def836146bc9b1b4d22831",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "P-224",
+        aliases: &["secp224r1", "nistp224"],
+        oid: Some("1.3.132.0.33"),
+        model: CurveModel::Weierstrass,
+        p: "ffffffffffffffffffffffffffffffff000000000000000000000001",
+        first: "fffffffffffffffffffffffffffffffefffffffffffffffffffffffe",
+        second: "b4050a850c04b3abf54132565044b0b7d7bfd8ba270b39432355ffb4",
+        x: "b70e0cbd6bb4bf7f321390b94a03c1d356c21122343280d6115c1d21",
+        y: "bd376388b5f723fb4c22dfe6cd4375a05a07476444d5819985007e34",
+        order: "ffffffffffffffffffffffffffff16a2e0b8f03e13dd29455c5c2a3d",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "P-256",
+        aliases: &["secp256r1", "prime256v1", "nistp256"],
+        oid: Some("1.2.840.10045.3.1.7"),
+        model: CurveModel::Weierstrass,
+        p: "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+        first: "ffffffff00000001000000000000000000000000fffffffffffffffffffffffc",
+        second: "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+        x: "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+        y: "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+        order: "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "P-384",
+        aliases: &["secp384r1", "nistp384"],
+        oid: Some("1.3.132.0.34"),
+        model: CurveModel::Weierstrass,
+        p: "ffffffffffffffffffffffffffffffffffffffffffffffff\
+            fffffffffffffffeffffffff0000000000000000ffffffff",
+        first: "ffffffffffffffffffffffffffffffffffffffffffffffff\
+                fffffffffffffffeffffffff0000000000000000fffffffc",
+        second: "b3312fa7e23ee7e4988e056be3f82d19181d9c6efe814112\
+                 0314088f5013875ac656398d8a2ed19d2a85c8edd3ec2aef",
+        x: "aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b98\
+            59f741e082542a385502f25dbf55296c3a545e3872760ab7",
+        y: "3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147c\
+            e9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f",
+        order: "ffffffffffffffffffffffffffffffffffffffffffffffff\
+                c7634d81f4372ddf581a0db248b0a77aecec196accc52973",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "P-521",
+        aliases: &["secp521r1", "nistp521"],
+        oid: Some("1.3.132.0.35"),
+        model: CurveModel::Weierstrass,
+        p: "1fffffffffffffffffffffffffffffffffffffffffff\
+            ffffffffffffffffffffffffffffffffffffffffffff\
+            fffffffffffffffffffffffffffffffffffffffffff",
+        first: "1fffffffffffffffffffffffffffffffffffffffffff\
+                ffffffffffffffffffffffffffffffffffffffffffff\
+                ffffffffffffffffffffffffffffffffffffffffffc",
+        second: "51953eb9618e1c9a1f929a21a0b68540eea2da725b99\
+                 b315f3b8b489918ef109e156193951ec7e937b1652c0\
+                 bd3bb1bf073573df883d2c34f1ef451fd46b503f00",
+        x: "c6858e06b70404e9cd9e3ecb662395b4429c64813905\
+            3fb521f828af606b4d3dbaa14b5e77efe75928fe1dc1\
+            27a2ffa8de3348b3c1856a429bf97e7e31c2e5bd66",
+        y: "11839296a789a3bc0045c8a5fb42c7d1bd998f544495\
+            79b446817afbd17273e662c97ee72995ef42640c550b\
+            9013fad0761353c7086a272c24088be94769fd16650",
+        order: "1fffffffffffffffffffffffffffffffffffffffffff\
+                ffffffffffffffffffffffa51868783bf2f966b7fcc0\
+                148f709a5d03bb5c9b8899c47aebb6fb71e91386409",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "brainpoolP160r1",
+        aliases: &[],
+        oid: Some("1.3.36.3.3.2.8.1.1.1"),
+        model: CurveModel::Weierstrass,
+        p: "e95e4a5f737059dc60dfc7ad95b3d8139515620f",
+        first: "340e7be2a280eb74e2be61bada745d97e8f7c300",
+        second: "1e589a8595423412134faa2dbdec95c8d8675e58",
+        x: "bed5af16ea3f6a4f62938c4631eb5af7bdbcdbc3",
+        y: "1667cb477a1a8ec338f94741669c976316da6321",
+        order: "e95e4a5f737059dc60df5991d45029409e60fc09",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "brainpoolP160t1",
+        aliases: &[],
+        oid: Some("1.3.36.3.3.2.8.1.1.2"),
+        model: CurveModel::Weierstrass,
+        p: "e95e4a5f737059dc60dfc7ad95b3d8139515620f",
+        first: "e95e4a5f737059dc60dfc7ad95b3d8139515620c",
+        second: "7a556b6dae535b7b51ed2c4d7daa7a0b5c55f380",
+        x: "b199b13b9b34efc1397e64baeb05acc265ff2378",
+        y: "add6718b7c7c1961f0991b842443772152c9e0ad",
+        order: "e95e4a5f737059dc60df5991d45029409e60fc09",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "brainpoolP192r1",
+        aliases: &[],
+        oid: Some("1.3.36.3.3.2.8.1.1.3"),
+        model: CurveModel::Weierstrass,
+        p: "c302f41d932a36cda7a3463093d18db78fce476de1a86297",
+        first: "6a91174076b1e0e19c39c031fe8685c1cae040e5c69a28ef",
+        second: "469a28ef7c28cca3dc721d044f4496bcca7ef4146fbf25c9",
+        x: "c0a0647eaab6a48753b033c56cb0f0900a2f5c4853375fd6",
+        y: "14b690866abd5bb88b5f4828c1490002e6773fa2fa299b8f",
+        order: "c302f41d932a36cda7a3462f9e9e916b5be8f1029ac4acc1",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "brainpoolP192t1",
+        aliases: &[],
+        oid: Some("1.3.36.3.3.2.8.1.1.4"),
+        model: CurveModel::Weierstrass,
+        p: "c302f41d932a36cda7a3463093d18db78fce476de1a86297",
+        first: "c302f41d932a36cda7a3463093d18db78fce476de1a86294",
+        second: "13d56ffaec78681e68f9deb43b35bec2fb68542e27897b79",
+        x: "3ae9e58c82f63c30282e1fe7bbf43fa72c446af6f4618129",
+        y: "97e2c5667c2223a902ab5ca449d0084b7e5b3de7ccc01c9",
+        order: "c302f41d932a36cda7a3462f9e9e916b5be8f1029ac4acc1",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "brainpoolP224r1",
+        aliases: &[],
+        oid: Some("1.3.36.3.3.2.8.1.1.5"),
+        model: CurveModel::Weierstrass,
+        p: "d7c134aa264366862a18302575d1d787b09f075797da89f57ec8c0ff",
+        first: "68a5e62ca9ce6c1c299803a6c1530b514e182ad8b0042a59cad29f43",
+        second: "2580f63ccfe44138870713b1a92369e33e2135d266dbb372386c400b",
+        x: "d9029ad2c7e5cf4340823b2a87dc68c9e4ce3174c1e6efdee12c07d",
+        y: "58aa56f772c0726f24c6b89e4ecdac24354b9e99caa3f6d3761402cd",
+        order: "d7c134aa264366862a18302575d0fb98d116bc4b6ddebca3a5a7939f",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "brainpoolP224t1",
+        aliases: &[],
+        oid: Some("1.3.36.3.3.2.8.1.1.6"),
+        model: CurveModel::Weierstrass,
+        p: "d7c134aa264366862a18302575d1d787b09f075797da89f57ec8c0ff",
+        first: "d7c134aa264366862a18302575d1d787b09f075797da89f57ec8c0fc",
+        second: "4b337d934104cd7bef271bf60ced1ed20da14c08b3bb64f18a60888d",
+        x: "6ab1e344ce25ff3896424e7ffe14762ecb49f8928ac0c76029b4d580",
+        y: "374e9f5143e568cd23f3f4d7c0d4b1e41c8cc0d1c6abd5f1a46db4c",
+        order: "d7c134aa264366862a18302575d0fb98d116bc4b6ddebca3a5a7939f",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "brainpoolP256r1",
+        aliases: &[],
+        oid: Some("1.3.36.3.3.2.8.1.1.7"),
+        model: CurveModel::Weierstrass,
+        p: "a9fb57dba1eea9bc3e660a909d838d726e3bf623d52620282013481d1f6e5377",
+        first: "7d5a0975fc2c3057eef67530417affe7fb8055c126dc5c6ce94a4b44f330b5d9",
+        second: "26dc5c6ce94a4b44f330b5d9bbd77cbf958416295cf7e1ce6bccdc18ff8c07b6",
+        x: "8bd2aeb9cb7e57cb2c4b482ffc81b7afb9de27e1e3bd23c23a4453bd9ace3262",
+        y: "547ef835c3dac4fd97f8461a14611dc9c27745132ded8e545c1d54c72f046997",
+        order: "a9fb57dba1eea9bc3e660a909d838d718c397aa3b561a6f7901e0e82974856a7",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "brainpoolP256t1",
+        aliases: &[],
+        oid: Some("1.3.36.3.3.2.8.1.1.8"),
+        model: CurveModel::Weierstrass,
+        p: "a9fb57dba1eea9bc3e660a909d838d726e3bf623d52620282013481d1f6e5377",
+        first: "a9fb57dba1eea9bc3e660a909d838d726e3bf623d52620282013481d1f6e5374",
+        second: "662c61c430d84ea4fe66a7733d0b76b7bf93ebc4af2f49256ae58101fee92b04",
+        x: "a3e8eb3cc1cfe7b7732213b23a656149afa142c47aafbc2b79a191562e1305f4",
+        y: "2d996c823439c56d7f7b22e14644417e69bcb6de39d027001dabe8f35b25c9be",
+        order: "a9fb57dba1eea9bc3e660a909d838d718c397aa3b561a6f7901e0e82974856a7",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "brainpoolP320r1",
+        aliases: &[],
+        oid: Some("1.3.36.3.3.2.8.1.1.9"),
+        model: CurveModel::Weierstrass,
+        p: "d35e472036bc4fb7e13c785ed201e065f98fcfa6\
+            f6f40def4f92b9ec7893ec28fcd412b1f1b32e27",
+        first: "3ee30b568fbab0f883ccebd46d3f3bb8a2a73513\
+                f5eb79da66190eb085ffa9f492f375a97d860eb4",
+        second: "520883949dfdbc42d3ad198640688a6fe13f4134\
+                 9554b49acc31dccd884539816f5eb4ac8fb1f1a6",
+        x: "43bd7e9afb53d8b85289bcc48ee5bfe6f20137d1\
+            0a087eb6e7871e2a10a599c710af8d0d39e20611",
+        y: "14fdd05545ec1cc8ab4093247f77275e0743ffed\
+            117182eaa9c77877aaac6ac7d35245d1692e8ee1",
+        order: "d35e472036bc4fb7e13c785ed201e065f98fcfa5\
+                b68f12a32d482ec7ee8658e98691555b44c59311",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "brainpoolP320t1",
+        aliases: &[],
+        oid: Some("1.3.36.3.3.2.8.1.1.10"),
+        model: CurveModel::Weierstrass,
+        p: "d35e472036bc4fb7e13c785ed201e065f98fcfa6\
+            f6f40def4f92b9ec7893ec28fcd412b1f1b32e27",
+        first: "d35e472036bc4fb7e13c785ed201e065f98fcfa6\
+                f6f40def4f92b9ec7893ec28fcd412b1f1b32e24",
+        second: "a7f561e038eb1ed560b3d147db782013064c19f2\
+                 7ed27c6780aaf77fb8a547ceb5b4fef422340353",
+        x: "925be9fb01afc6fb4d3e7d4990010f813408ab10\
+            6c4f09cb7ee07868cc136fff3357f624a21bed52",
+        y: "63ba3a7a27483ebf6671dbef7abb30ebee084e58\
+            a0b077ad42a5a0989d1ee71b1b9bc0455fb0d2c3",
+        order: "d35e472036bc4fb7e13c785ed201e065f98fcfa5\
+                b68f12a32d482ec7ee8658e98691555b44c59311",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "brainpoolP384r1",
+        aliases: &[],
+        oid: Some("1.3.36.3.3.2.8.1.1.11"),
+        model: CurveModel::Weierstrass,
+        p: "8cb91e82a3386d280f5d6f7e50e641df152f7109ed5456b4\
+            12b1da197fb71123acd3a729901d1a71874700133107ec53",
+        first: "7bc382c63d8c150c3c72080ace05afa0c2bea28e4fb22787\
+                139165efba91f90f8aa5814a503ad4eb04a8c7dd22ce2826",
+        second: "4a8c7dd22ce28268b39b55416f0447c2fb77de107dcd2a62\
+                 e880ea53eeb62d57cb4390295dbc9943ab78696fa504c11",
+        x: "1d1c64f068cf45ffa2a63a81b7c13f6b8847a3e77ef14fe3\
+            db7fcafe0cbd10e8e826e03436d646aaef87b2e247d4af1e",
+        y: "8abe1d7520f9c2a45cb1eb8e95cfd55262b70b29feec5864\
+            e19c054ff99129280e4646217791811142820341263c5315",
+        order: "8cb91e82a3386d280f5d6f7e50e641df152f7109ed5456b3\
+                1f166e6cac0425a7cf3ab6af6b7fc3103b883202e9046565",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "brainpoolP384t1",
+        aliases: &[],
+        oid: Some("1.3.36.3.3.2.8.1.1.12"),
+        model: CurveModel::Weierstrass,
+        p: "8cb91e82a3386d280f5d6f7e50e641df152f7109ed5456b4\
+            12b1da197fb71123acd3a729901d1a71874700133107ec53",
+        first: "8cb91e82a3386d280f5d6f7e50e641df152f7109ed5456b4\
+                12b1da197fb71123acd3a729901d1a71874700133107ec50",
+        second: "7f519eada7bda81bd826dba647910f8c4b9346ed8ccdc64e\
+                 4b1abd11756dce1d2074aa263b88805ced70355a33b471ee",
+        x: "18de98b02db9a306f2afcd7235f72a819b80ab12ebd65317\
+            2476fecd462aabffc4ff191b946a5f54d8d0aa2f418808cc",
+        y: "25ab056962d30651a114afd2755ad336747f93475b7a1fca\
+            3b88f2b6a208ccfe469408584dc2b2912675bf5b9e582928",
+        order: "8cb91e82a3386d280f5d6f7e50e641df152f7109ed5456b3\
+                1f166e6cac0425a7cf3ab6af6b7fc3103b883202e9046565",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "brainpoolP512r1",
+        aliases: &[],
+        oid: Some("1.3.36.3.3.2.8.1.1.13"),
+        model: CurveModel::Weierstrass,
+        p: "aadd9db8dbe9c48b3fd4e6ae33c9fc07cb308db3b3c9d20ed6639cca70330871\
+            7d4d9b009bc66842aecda12ae6a380e62881ff2f2d82c68528aa6056583a48f3",
+        first: "7830a3318b603b89e2327145ac234cc594cbdd8d3df91610a83441caea9863bc\
+                2ded5d5aa8253aa10a2ef1c98b9ac8b57f1117a72bf2c7b9e7c1ac4d77fc94ca",
+        second: "3df91610a83441caea9863bc2ded5d5aa8253aa10a2ef1c98b9ac8b57f1117a7\
+                 2bf2c7b9e7c1ac4d77fc94cadc083e67984050b75ebae5dd2809bd638016f723",
+        x: "81aee4bdd82ed9645a21322e9c4c6a9385ed9f70b5d916c1b43b62eef4d0098e\
+            ff3b1f78e2d0d48d50d1687b93b97d5f7c6d5047406a5e688b352209bcb9f822",
+        y: "7dde385d566332ecc0eabfa9cf7822fdf209f70024a57b1aa000c55b881f8111\
+            b2dcde494a5f485e5bca4bd88a2763aed1ca2b2fa8f0540678cd1e0f3ad80892",
+        order: "aadd9db8dbe9c48b3fd4e6ae33c9fc07cb308db3b3c9d20ed6639cca70330870\
+                553e5c414ca92619418661197fac10471db1d381085ddaddb58796829ca90069",
+        cofactor: 1,
+    },
+    NamedCurve {
+        name: "brainpoolP512t1",
+        aliases: &[],
+        oid: Some("1.3.36.3.3.2.8.1.1.14"),
+        model: CurveModel::Weierstrass,
+        p: "aadd9db8dbe9c48b3fd4e6ae33c9fc07cb308db3b3c9d20ed6639cca70330871\
+            7d4d9b009bc66842aecda12ae6a380e62881ff2f2d82c68528aa6056583a48f3",
+        first: "aadd9db8dbe9c48b3fd4e6ae33c9fc07cb308db3b3c9d20ed6639cca70330871\
+                7d4d9b009bc66842aecda12ae6a380e62881ff2f2d82c68528aa6056583a48f0",
+        second: "7cbbbcf9441cfab76e1890e46884eae321f70c0bcb4981527897504bec3e36a6\
+                 2bcdfa2304976540f6450085f2dae145c22553b465763689180ea2571867423e",
+        x: "640ece5c12788717b9c1ba06cbc2a6feba85842458c56dde9db1758d39c0313d\
+            82ba51735cdb3ea499aa77a7d6943a64f7a3f25fe26f06b51baa2696fa9035da",
+        y: "5b534bd595f5af0fa2c892376c84ace1bb4e3019b71634c01131159cae03cee9\
+            d9932184beef216bd71df2dadf86a627306ecff96dbb8bace198b61e00f8b332",
+        order: "aadd9db8dbe9c48b3fd4e6ae33c9fc07cb308db3b3c9d20ed6639cca70330870\
+                553e5c414ca92619418661197fac10471db1d381085ddaddb58796829ca90069",
+        cofactor: 1,
+    },
+];
+
+fn hex(digits: &'static str) -> BigInt {
+    BigInt::parse_bytes(digits.as_bytes(), 16).expect("registry constants are hexadecimal")
+}
+
+/// Every curve in the registry.
+pub fn named_curves() -> &'static [NamedCurve] {
+    NAMED_CURVES
+}
+
+/// Finds a curve by name or alias, ignoring case, or by its dotted OID.
+pub fn lookup(name: &str) -> Option<&'static NamedCurve> {
+    NAMED_CURVES.iter().find(|curve| {
+        curve.name.eq_ignore_ascii_case(name)
+            || curve.aliases.iter().any(|alias| alias.eq_ignore_ascii_case(name))
+            || curve.oid == Some(name)
+    })
+}
+
+/// Finds a curve by its dotted OID, such as 1.2.840.10045.3.1.7 for P-256.
+pub fn lookup_oid(oid: &str) -> Option<&'static NamedCurve> {
+    NAMED_CURVES.iter().find(|curve| curve.oid == Some(oid))
+}
+
+impl NamedCurve {
+    /// The field prime.
+    pub fn p(&self) -> BigInt {
+        hex(self.p)
+    }
+
+    /// The two coefficients of the curve's `model`.
+    pub fn coefficients(&self) -> (BigInt, BigInt) {
+        (hex(self.first), hex(self.second))
+    }
+
+    /// The standard base point, in the coordinates of the curve's `model`.
+    pub fn base_point(&self) -> Point {
+        Point::Affine(hex(self.x), hex(self.y))
+    }
+
+    /// The prime order of the base point.
+    pub fn order(&self) -> BigInt {
+        hex(self.order)
+    }
+
+    /// Multiplies `point`, in the coordinates of the curve's `model`, by the cofactor, so that it lies in
+    /// the subgroup of the base point; fails if the point is not on the curve.
+    pub fn clear_cofactor(&self, point: &Point) -> Result<Point, TransformError> {
+        let ((a, b), p) = (self.coefficients(), self.p());
+        let h = BigInt::from(self.cofactor);
+        match self.model {
+            CurveModel::Weierstrass => WeierstrassCurve::new(&a, &b, &p)?.clear_cofactor(point, &h),
+            CurveModel::Montgomery => MontgomeryCurve::new(&a, &b, &p)?.clear_cofactor(point, &h),
+            CurveModel::TwistedEdwards => TwistedEdwardsCurve::new(&a, &b, &p)?.clear_cofactor(point, &h),
+        }
+    }
+
+    /// Returns the curve in short Weierstrass form together with the image of the base point.
+    ///
+    /// Montgomery curves use the isomorphism of `MontgomeryCurve::to_weierstrass`, and twisted Edwards
+    /// curves go through the birationally equivalent Montgomery curve first. For Ed448 that curve is not
+    /// Curve448, to which Ed448 is only 4-isogenous (see `ed448::ed448_to_curve448`), so the Weierstrass
+    /// forms of Ed448 and Wei448 differ.
+    pub fn to_weierstrass(&self) -> Result<(WeierstrassCurve, Point), TransformError> {
+        let (first, second) = self.coefficients();
+        to_weierstrass(self.model, &self.p(), &first, &second, &hex(self.x), &hex(self.y))
+    }
+}
+
+/// The curve of `model` with the given coefficients over F_p in short Weierstrass form, with the image
+/// of (x, y), which must be on it; shared with the curves of a `curve_db::CurveDatabase`.
+pub(crate) fn to_weierstrass(
+    model: CurveModel,
+    p: &BigInt,
+    first: &BigInt,
+    second: &BigInt,
+    x: &BigInt,
+    y: &BigInt,
+) -> Result<(WeierstrassCurve, Point), TransformError> {
+    let (curve, point) = match model {
+        CurveModel::Weierstrass => {
+            let curve = WeierstrassCurve::new(first, second, p)?;
+            let point = curve.point(x, y)?;
+            return Ok((curve, point));
+        }
+        CurveModel::Montgomery => {
+            let curve = MontgomeryCurve::new(first, second, p)?;
+            let point = curve.point(x, y)?;
+            (curve, point)
+        }
+        CurveModel::TwistedEdwards => {
+            let curve = TwistedEdwardsCurve::new(first, second, p)?;
+            let point = curve.point(x, y)?;
+            (curve.to_montgomery(), curve.point_to_montgomery(&point))
+        }
+    };
+    Ok((curve.to_weierstrass(), curve.point_to_weierstrass(&point)))
+}
@@ -0,0 +1,14 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+/// The generator of the command-line tool and the tests, reproducible with `DefaultRng::seed_from_u64`.
+pub type DefaultRng = ChaCha20Rng;
+
+/// Returns a ChaCha20 generator seeded from operating-system entropy.
+///
+/// The functions without an `rng` parameter draw from `rand::rngs::OsRng` instead, so this is only
+/// needed to run many searches from one seed. Targets without an entropy source (no_std, some WASM
+/// hosts) should call the `_with_rng` variants with their own `RngCore + CryptoRng`.
+pub fn default_rng() -> DefaultRng {
+    ChaCha20Rng::from_entropy()
+}
@@ -0,0 +1,477 @@
+use crate::curve::{CurveGroup, Point};
+use crate::error::TransformError;
+use crate::limits::SearchLimits;
+use crate::numtheory::{is_probable_prime, mod_inverse, mod_sqrt, MILLER_RABIN_ROUNDS};
+use crate::ops;
+use crate::order::hasse_interval;
+use crate::weierstrass::WeierstrassCurve;
+use num_bigint::{BigInt, BigUint, RandBigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
+
+/// Computes `scalar * point` by left-to-right double-and-add; negative scalars use the negated point.
+pub fn scalar_mul<C: CurveGroup>(curve: &C, point: &Point, scalar: &BigInt) -> Point {
+    let base = if scalar.sign() == Sign::Minus {
+        curve.negate(point)
+    } else {
+        point.clone()
+    };
+    let magnitude = scalar.magnitude();
+    let mut result = Point::Infinity;
+    for bit in (0..magnitude.bits()).rev() {
+        result = curve.double(&result);
+        if magnitude.bit(bit) {
+            result = curve.add(&result, &base);
+        }
+    }
+    result
+}
+
+/// Multiplies `point`, which must lie on `curve`, by the cofactor h = #E / n, so that the result lies in
+/// the subgroup of prime order n whatever component of small order the point had; points of that
+/// subgroup are only multiplied by h, which is invertible modulo n.
+pub fn clear_cofactor<C: CurveGroup>(
+    curve: &C,
+    point: &Point,
+    cofactor: &BigInt,
+) -> Result<Point, TransformError> {
+    if !curve.contains(point) {
+        return Err(TransformError::PointNotOnCurve);
+    }
+    Ok(scalar_mul(curve, point, cofactor))
+}
+
+/// Bits of the random multiplier r of scalar blinding, k + r·n.
+pub const BLINDING_BITS: u64 = 64;
+
+/// Side-channel countermeasures for `scalar_mul_protected`, all off by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Countermeasures {
+    /// Multiply by k + r·n for a fresh random r of `BLINDING_BITS` bits, with n a multiple of the order
+    /// of the point, so the bits the loop goes through change on every call while the result does not.
+    pub scalar_blinding: bool,
+    /// Compute in Jacobian coordinates (λ^2·x : λ^3·y : λ) for a fresh random λ, so every intermediate
+    /// value is masked and the final division by Z removes the mask.
+    pub randomize_coordinates: bool,
+}
+
+/// Computes `scalar * point` like `scalar_mul`, with the `countermeasures` drawn from `rng`; `order` is
+/// the order of the point or a multiple, such as the group order, for scalar blinding.
+///
+/// The double-and-add loop still branches on the bits, so this illustrates the masking rather than
+/// making the arithmetic constant time: blinding hides k from an attacker averaging many traces, and
+/// randomized coordinates defeat comparing intermediate values against guesses. For a point whose order
+/// does not divide `order`, such as one of an invalid curve, blinding randomizes the result instead.
+pub fn scalar_mul_protected<R: RngCore + CryptoRng + ?Sized>(
+    curve: &WeierstrassCurve,
+    point: &Point,
+    scalar: &BigInt,
+    order: &BigInt,
+    countermeasures: Countermeasures,
+    rng: &mut R,
+) -> Point {
+    let scalar = if countermeasures.scalar_blinding {
+        blind(scalar, order, rng)
+    } else {
+        scalar.clone()
+    };
+    if !countermeasures.randomize_coordinates {
+        return scalar_mul(curve, point, &scalar);
+    }
+    let p = &curve.p;
+    let base = match point {
+        Point::Infinity => return Point::Infinity,
+        Point::Affine(x, y) if scalar.sign() == Sign::Minus => Jacobian::from_affine(x, &-y, p),
+        Point::Affine(x, y) => Jacobian::from_affine(x, y, p),
+    };
+    let lambda = rng.gen_bigint_range(&BigInt::one(), p);
+    let base = base.rescale(&lambda, p);
+    let magnitude = scalar.magnitude();
+    let mut result = Jacobian::infinity();
+    for bit in (0..magnitude.bits()).rev() {
+        result = result.double(&curve.a, p);
+        if magnitude.bit(bit) {
+            result = result.add(&base, &curve.a, p);
+        }
+    }
+    result.to_affine(p)
+}
+
+/// k + r·n for a fresh random r of `BLINDING_BITS` bits.
+fn blind<R: RngCore + CryptoRng + ?Sized>(scalar: &BigInt, order: &BigInt, rng: &mut R) -> BigInt {
+    scalar + BigInt::from(rng.gen_biguint(BLINDING_BITS)) * order
+}
+
+/// Computes `scalar * point` like `scalar_mul`, checking the result against faults: the point must lie
+/// on `curve`, and so must the result, since a fault in the middle of the loop almost always throws it
+/// off the curve. With `recompute`, the order of the point or a multiple, the product is also computed
+/// again with the scalar blinded as in `scalar_mul_protected`, which catches faults that keep it on the
+/// curve, such as a skipped addition, unless the same fault hits both runs at the same place.
+///
+/// A result off the curve or differing between the runs is `TransformError::FaultDetected`, and must not
+/// be released: a faulty result can leak the scalar.
+pub fn scalar_mul_checked<C: CurveGroup, R: RngCore + CryptoRng + ?Sized>(
+    curve: &C,
+    point: &Point,
+    scalar: &BigInt,
+    recompute: Option<&BigInt>,
+    rng: &mut R,
+) -> Result<Point, TransformError> {
+    if !curve.contains(point) {
+        return Err(TransformError::PointNotOnCurve);
+    }
+    let result = scalar_mul(curve, point, scalar);
+    if !curve.contains(&result) {
+        return Err(TransformError::FaultDetected("the product is not on the curve".to_string()));
+    }
+    if let Some(order) = recompute {
+        if scalar_mul(curve, point, &blind(scalar, order, rng)) != result {
+            return Err(TransformError::FaultDetected(
+                "the product differs from the one computed with a blinded scalar".to_string(),
+            ));
+        }
+    }
+    Ok(result)
+}
+
+/// A point in Jacobian coordinates, (X : Y : Z) for (X/Z^2, Y/Z^3), with Z = 0 at infinity.
+#[derive(Clone)]
+struct Jacobian {
+    x: BigInt,
+    y: BigInt,
+    z: BigInt,
+}
+
+impl Jacobian {
+    fn infinity() -> Jacobian {
+        Jacobian { x: BigInt::one(), y: BigInt::one(), z: BigInt::zero() }
+    }
+
+    fn from_affine(x: &BigInt, y: &BigInt, p: &BigInt) -> Jacobian {
+        Jacobian { x: x.mod_floor(p), y: y.mod_floor(p), z: BigInt::one() }
+    }
+
+    /// The same point with (λ^2·X : λ^3·Y : λ·Z).
+    fn rescale(&self, lambda: &BigInt, p: &BigInt) -> Jacobian {
+        ops::record(4, 1);
+        let square = (lambda * lambda).mod_floor(p);
+        Jacobian {
+            x: (&self.x * &square).mod_floor(p),
+            y: (&self.y * square * lambda).mod_floor(p),
+            z: (&self.z * lambda).mod_floor(p),
+        }
+    }
+
+    /// Doubling for y^2 = x^3 + ax + b (dbl-2007-bl without the a = -3 shortcut).
+    fn double(&self, a: &BigInt, p: &BigInt) -> Jacobian {
+        if self.z.is_zero() || self.y.is_zero() {
+            return Jacobian::infinity();
+        }
+        ops::record(4, 6);
+        let yy = (&self.y * &self.y).mod_floor(p);
+        let zz = (&self.z * &self.z).mod_floor(p);
+        let s = (BigInt::from(4) * &self.x * &yy).mod_floor(p);
+        let m = (BigInt::from(3) * &self.x * &self.x + a * &zz * &zz).mod_floor(p);
+        let x = (&m * &m - BigInt::from(2) * &s).mod_floor(p);
+        let y = (m * (s - &x) - BigInt::from(8) * &yy * &yy).mod_floor(p);
+        let z = (BigInt::from(2) * &self.y * &self.z).mod_floor(p);
+        Jacobian { x, y, z }
+    }
+
+    /// Addition (add-2007-bl), falling back to doubling for equal points.
+    fn add(&self, other: &Jacobian, a: &BigInt, p: &BigInt) -> Jacobian {
+        if self.z.is_zero() {
+            return other.clone();
+        }
+        if other.z.is_zero() {
+            return self.clone();
+        }
+        ops::record(6, 2);
+        let z1z1 = (&self.z * &self.z).mod_floor(p);
+        let z2z2 = (&other.z * &other.z).mod_floor(p);
+        let u1 = (&self.x * &z2z2).mod_floor(p);
+        let u2 = (&other.x * &z1z1).mod_floor(p);
+        let s1 = (&self.y * &other.z * &z2z2).mod_floor(p);
+        let s2 = (&other.y * &self.z * &z1z1).mod_floor(p);
+        if u1 == u2 {
+            return if s1 == s2 { self.double(a, p) } else { Jacobian::infinity() };
+        }
+        ops::record(6, 2);
+        let h = (u2 - &u1).mod_floor(p);
+        let r = (s2 - &s1).mod_floor(p);
+        let hh = (&h * &h).mod_floor(p);
+        let hhh = (&hh * &h).mod_floor(p);
+        let v = (u1 * hh).mod_floor(p);
+        let x = (&r * &r - &hhh - BigInt::from(2) * &v).mod_floor(p);
+        let y = (r * (v - &x) - s1 * hhh).mod_floor(p);
+        let z = (&self.z * &other.z * h).mod_floor(p);
+        Jacobian { x, y, z }
+    }
+
+    fn to_affine(&self, p: &BigInt) -> Point {
+        let Some(z_inv) = mod_inverse(&self.z, p) else {
+            return Point::Infinity;
+        };
+        ops::record(3, 1);
+        let z_inv_squared = (&z_inv * &z_inv).mod_floor(p);
+        let x = (&self.x * &z_inv_squared).mod_floor(p);
+        let y = (&self.y * z_inv_squared * z_inv).mod_floor(p);
+        Point::Affine(x, y)
+    }
+}
+
+/// Window width for Pippenger's method with `count` terms and scalars of `bits` bits.
+fn pippenger_window(count: usize, bits: u64) -> u64 {
+    let width = match count {
+        0..=3 => 1,
+        4..=31 => 3,
+        32..=255 => 5,
+        _ => (usize::BITS - count.leading_zeros()) as u64 - 2,
+    };
+    width.clamp(1, bits.max(1))
+}
+
+/// Computes the multi-scalar multiplication Σ scalars[i]·points[i] with Pippenger's bucket method.
+///
+/// Works for any curve model implementing `CurveGroup`; panics if the slices differ in length.
+pub fn msm<C: CurveGroup>(curve: &C, points: &[Point], scalars: &[BigInt]) -> Point {
+    assert_eq!(points.len(), scalars.len(), "msm needs one scalar per point");
+    // Fold signs into the points so that every scalar is non-negative
+    let terms: Vec<(Point, &BigUint)> = points
+        .iter()
+        .zip(scalars)
+        .filter(|(point, scalar)| !point.is_infinity() && !scalar.is_zero())
+        .map(|(point, scalar)| {
+            let point = if scalar.sign() == Sign::Minus {
+                curve.negate(point)
+            } else {
+                point.clone()
+            };
+            (point, scalar.magnitude())
+        })
+        .collect();
+    let bits = terms.iter().map(|(_, scalar)| scalar.bits()).max().unwrap_or(0);
+    if bits == 0 {
+        return Point::Infinity;
+    }
+    let width = pippenger_window(terms.len(), bits);
+    let windows = bits.div_ceil(width);
+
+    let mut result = Point::Infinity;
+    for window in (0..windows).rev() {
+        for _ in 0..width {
+            result = curve.double(&result);
+        }
+        // buckets[d - 1] collects the points whose current window digit is d
+        let mut buckets = vec![Point::Infinity; (1 << width) - 1];
+        for (point, scalar) in &terms {
+            let digit = (0..width).fold(0usize, |digit, offset| {
+                let bit = window * width + offset;
+                digit | ((scalar.bit(bit) as usize) << offset)
+            });
+            if digit > 0 {
+                buckets[digit - 1] = curve.add(&buckets[digit - 1], point);
+            }
+        }
+        // Σ d·bucket[d] via running sums from the highest digit down
+        let mut running = Point::Infinity;
+        let mut window_sum = Point::Infinity;
+        for bucket in buckets.iter().rev() {
+            running = curve.add(&running, bucket);
+            window_sum = curve.add(&window_sum, &running);
+        }
+        result = curve.add(&result, &window_sum);
+    }
+    result
+}
+
+/// Precomputed comb table for repeated scalar multiplications of one fixed base point.
+///
+/// With `teeth` teeth and spacing d = ceil(max_bits / teeth), entry i holds Σ 2^(j·d)·base over
+/// the set bits j of i, so each multiplication costs d doublings and at most d additions.
+#[derive(Clone, Debug)]
+pub struct FixedBaseTable {
+    base: Point,
+    teeth: u64,
+    spacing: u64,
+    table: Vec<Point>,
+}
+
+impl FixedBaseTable {
+    /// Precomputes the 2^teeth table entries for scalars of up to `max_bits` bits.
+    pub fn new<C: CurveGroup>(curve: &C, base: &Point, max_bits: u64, teeth: u64) -> FixedBaseTable {
+        assert!((1..=16).contains(&teeth), "comb tables support 1 to 16 teeth");
+        let spacing = max_bits.max(1).div_ceil(teeth);
+        // tooth_points[j] = 2^(j·spacing)·base
+        let mut tooth_points = Vec::with_capacity(teeth as usize);
+        let mut current = base.clone();
+        for _ in 0..teeth {
+            tooth_points.push(current.clone());
+            for _ in 0..spacing {
+                current = curve.double(&current);
+            }
+        }
+        let mut table = vec![Point::Infinity; 1 << teeth];
+        for index in 1..table.len() {
+            // Reuse the entry without the highest set bit
+            let high = usize::BITS - 1 - index.leading_zeros();
+            let rest = index ^ (1 << high);
+            table[index] = curve.add(&table[rest], &tooth_points[high as usize]);
+        }
+        FixedBaseTable {
+            base: base.clone(),
+            teeth,
+            spacing,
+            table,
+        }
+    }
+
+    /// Largest scalar bit length the table covers directly.
+    pub fn max_bits(&self) -> u64 {
+        self.teeth * self.spacing
+    }
+
+    /// Computes `scalar * base` with the comb method, falling back to `scalar_mul` for longer scalars.
+    pub fn mul<C: CurveGroup>(&self, curve: &C, scalar: &BigInt) -> Point {
+        let magnitude = scalar.magnitude();
+        if magnitude.bits() > self.max_bits() {
+            return scalar_mul(curve, &self.base, scalar);
+        }
+        let mut result = Point::Infinity;
+        for column in (0..self.spacing).rev() {
+            result = curve.double(&result);
+            let index = (0..self.teeth).fold(0usize, |index, tooth| {
+                index | ((magnitude.bit(tooth * self.spacing + column) as usize) << tooth)
+            });
+            if index > 0 {
+                result = curve.add(&result, &self.table[index]);
+            }
+        }
+        if scalar.sign() == Sign::Minus {
+            curve.negate(&result)
+        } else {
+            result
+        }
+    }
+}
+
+/// Divides by a positive `denominator`, rounding to the nearest integer.
+fn round_div(numerator: &BigInt, denominator: &BigInt) -> BigInt {
+    (BigInt::from(2) * numerator + denominator).div_floor(&(BigInt::from(2) * denominator))
+}
+
+/// Returns the two non-trivial cube roots of unity modulo the prime `modulus`, if it is 1 mod 3.
+fn cube_roots_of_unity(modulus: &BigInt) -> Option<(BigInt, BigInt)> {
+    if !(modulus % 3u32).is_one() {
+        return None;
+    }
+    // The roots of x^2 + x + 1 are (-1 ± sqrt(-3)) / 2
+    let root = mod_sqrt(&(modulus - 3u32), modulus, &SearchLimits::default()).ok()??;
+    let half = mod_inverse(&BigInt::from(2), modulus)?;
+    let first = ((&root - 1u32) * &half).mod_floor(modulus);
+    let second = ((-root - 1u32) * half).mod_floor(modulus);
+    Some((first, second))
+}
+
+/// The GLV endomorphism φ(x, y) = (βx, y) of a j = 0 curve y^2 = x^3 + b, acting as
+/// multiplication by λ on the subgroup of prime order n.
+///
+/// `detect` only accepts curves of prime order n, so that this subgroup is the whole curve: on a point
+/// outside it φ is not multiplication by λ, and `mul` would silently compute some other point.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GlvEndomorphism {
+    /// Cube root of unity in F_p.
+    pub beta: BigInt,
+    /// Cube root of unity modulo n with φ(P) = λP.
+    pub lambda: BigInt,
+    /// Prime order n of the subgroup the endomorphism is used on.
+    pub order: BigInt,
+    /// Short basis (a1, b1), (a2, b2) of the lattice {(x, y) : x + yλ ≡ 0 mod n}.
+    basis: [(BigInt, BigInt); 2],
+}
+
+impl GlvEndomorphism {
+    /// Detects the endomorphism for curves with a = 0 and p ≡ 1 (mod 3), pairing β with the λ
+    /// that matches its action on `point`, whose order must be the prime `order`.
+    ///
+    /// Returns `None` unless `order` is also the order of the curve, which follows from 2n exceeding the
+    /// Hasse bound p + 1 + 2√p: the cofactor is then 1, and `mul` is exact for every point of the curve.
+    pub fn detect(curve: &WeierstrassCurve, point: &Point, order: &BigInt) -> Option<GlvEndomorphism> {
+        if !curve.a.is_zero() || point.is_infinity() {
+            return None;
+        }
+        let (_, high) = hasse_interval(&curve.p);
+        if BigInt::from(2) * order <= high
+            || !is_probable_prime(order, MILLER_RABIN_ROUNDS)
+            || !scalar_mul(curve, point, order).is_infinity()
+        {
+            return None;
+        }
+        let (beta, _) = cube_roots_of_unity(&curve.p)?;
+        let (lambda_1, lambda_2) = cube_roots_of_unity(order)?;
+        let mapped = match point {
+            Point::Affine(x, y) => Point::Affine((&beta * x).mod_floor(&curve.p), y.clone()),
+            Point::Infinity => unreachable!(),
+        };
+        let lambda = [lambda_1, lambda_2]
+            .into_iter()
+            .find(|lambda| scalar_mul(curve, point, lambda) == mapped)?;
+        let basis = Self::lattice_basis(order, &lambda);
+        Some(GlvEndomorphism {
+            beta,
+            lambda,
+            order: order.clone(),
+            basis,
+        })
+    }
+
+    /// Finds a short lattice basis with the extended Euclidean algorithm on (n, λ), stopping near sqrt(n).
+    fn lattice_basis(order: &BigInt, lambda: &BigInt) -> [(BigInt, BigInt); 2] {
+        let bound = order.sqrt();
+        // Invariant: r_i ≡ t_i·λ (mod n)
+        let (mut r_prev, mut r) = (order.clone(), lambda.clone());
+        let (mut t_prev, mut t) = (BigInt::zero(), BigInt::one());
+        while r >= bound {
+            let quotient = &r_prev / &r;
+            let r_next = &r_prev - &quotient * &r;
+            let t_next = &t_prev - &quotient * &t;
+            r_prev = std::mem::replace(&mut r, r_next);
+            t_prev = std::mem::replace(&mut t, t_next);
+        }
+        // (r_prev, t_prev) is the last remainder above sqrt(n) and (r, t) the first below it
+        let first = (r.clone(), -&t);
+        let quotient = &r_prev / &r;
+        let after = (&r_prev - &quotient * &r, -(&t_prev - &quotient * &t));
+        let before = (r_prev, -t_prev);
+        let norm = |v: &(BigInt, BigInt)| &v.0 * &v.0 + &v.1 * &v.1;
+        let second = if norm(&before) <= norm(&after) { before } else { after };
+        [first, second]
+    }
+
+    /// Applies φ(x, y) = (βx, y).
+    pub fn apply(&self, curve: &WeierstrassCurve, point: &Point) -> Point {
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(x, y) => Point::Affine((&self.beta * x).mod_floor(&curve.p), y.clone()),
+        }
+    }
+
+    /// Splits `scalar` into (k1, k2) of roughly half the bit length with k ≡ k1 + k2·λ (mod n).
+    pub fn decompose(&self, scalar: &BigInt) -> (BigInt, BigInt) {
+        let k = scalar.mod_floor(&self.order);
+        let [(a1, b1), (a2, b2)] = &self.basis;
+        let c1 = round_div(&(b2 * &k), &self.order);
+        let c2 = round_div(&(-b1 * &k), &self.order);
+        let k1 = &k - &c1 * a1 - &c2 * a2;
+        let k2 = -(&c1 * b1) - &c2 * b2;
+        (k1, k2)
+    }
+
+    /// Computes `scalar * point` as k1·P + k2·φ(P) with a joint double-and-add over the half-length scalars.
+    pub fn mul(&self, curve: &WeierstrassCurve, point: &Point, scalar: &BigInt) -> Point {
+        let (k1, k2) = self.decompose(scalar);
+        let points = [point.clone(), self.apply(curve, point)];
+        msm(curve, &points, &[k1, k2])
+    }
+}
@@ -0,0 +1,250 @@
+use crate::curve::{CurveGroup, Point};
+use crate::ed25519::{
+    curve25519, curve25519_base_point, curve25519_to_ed25519, ed25519_base_point,
+    ed25519_public_key_to_x25519, ed25519_to_curve25519, x25519_public_key_to_ed25519, KEY_LEN,
+};
+use crate::ed448::{
+    curve448, curve448_base_point, curve448_to_ed448, ed448, ed448_base_point, ed448_public_key_to_x448,
+    ed448_to_curve448,
+};
+use crate::error::TransformError;
+use crate::limits::SearchLimits;
+use crate::map::MontgomeryMap;
+use crate::montgomery::MontgomeryCurve;
+use crate::numtheory::{legendre_symbol, mod_inverse, mod_sqrt};
+use crate::registry::lookup;
+use crate::scalar_mul::scalar_mul;
+use crate::transform::{transform_to_montgomery_with_rng, TransformOptions};
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use std::str::FromStr;
+
+/// Seed for the root searches, so that a failing run can be reproduced exactly.
+const SELFTEST_SEED: u64 = 0x5eed;
+
+/// The outcome of one known-answer test.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KatResult {
+    pub name: &'static str,
+    /// `None` when the test passed, otherwise what went wrong.
+    pub failure: Option<String>,
+}
+
+impl KatResult {
+    pub fn passed(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+fn big(value: &str) -> BigInt {
+    BigInt::from_str(value).expect("known-answer constants are decimal integers")
+}
+
+type Outcome = Result<(), String>;
+
+/// A named known-answer test.
+type Kat = (&'static str, fn() -> Outcome);
+
+fn check(condition: bool, message: impl FnOnce() -> String) -> Outcome {
+    if condition {
+        Ok(())
+    } else {
+        Err(message())
+    }
+}
+
+type Transformed = (BigInt, BigInt, BigInt, BigInt);
+
+fn transform(x: i64, y: i64, a: i64, b: i64, p: i64) -> Result<Transformed, TransformError> {
+    let mut rng = ChaCha20Rng::seed_from_u64(SELFTEST_SEED);
+    let [x, y, a, b, p] = [x, y, a, b, p].map(BigInt::from);
+    transform_to_montgomery_with_rng(&x, &y, &a, &b, &p, &TransformOptions::default(), &mut rng)
+}
+
+/// The transform picks one of several roots z0 and one of two signs of s, so a small curve has a
+/// short list of correct answers, all worked out by hand.
+fn small_curve(x: i64, y: i64, a: i64, b: i64, p: i64, expected: &[(i64, i64, i64, i64)]) -> Outcome {
+    let (u, v, a_m, b_m) = transform(x, y, a, b, p).map_err(|error| error.to_string())?;
+    let result = [u, v, a_m, b_m];
+    let known = expected.iter().any(|answer| {
+        let answer = [answer.0, answer.1, answer.2, answer.3].map(BigInt::from);
+        answer == result
+    });
+    check(known, || {
+        let [u, v, a_m, b_m] = &result;
+        format!("(u, v, A, B) = ({}, {}, {}, {}) is not a known answer", u, v, a_m, b_m)
+    })
+}
+
+/// Wei25519 (curve25519 in short Weierstrass form) must map its base point to u = 9 on Curve25519.
+///
+/// The map is built from the known root z0 = 486662 / 3, so this checks the square root, inverse and map
+/// formulas independently of root finding.
+fn wei25519() -> Outcome {
+    let p = (BigInt::one() << 255u32) - 19u32;
+    let a = big("19298681539552699237261830834781317975544997444273427339909597334573241639236");
+    let b = big("55751746669818908907645289078257140818241103727901012315294400837956729358436");
+    let x = big("19298681539552699237261830834781317975544997444273427339909597334652188435546");
+    let y = big("14781619447589544791020593568409986887264606134616475288964881837755586237401");
+    let three_inv = mod_inverse(&BigInt::from(3), &p).expect("3 is invertible");
+    let z0 = (BigInt::from(486662) * three_inv).mod_floor(&p);
+    let map = MontgomeryMap::from_root(&a, &b, &p, &z0, &TransformOptions::default())
+        .map_err(|error| error.to_string())?;
+    let (u, v) = map.map_point(&x, &y);
+    // With s = -1 the result is the same curve written with B = -1; canonicalization flips it back
+    let rescaling = MontgomeryCurve::new(&map.a_montgomery, &map.b_montgomery, &p)
+        .and_then(|curve| curve.canonicalize())
+        .map_err(|error| error.to_string())?;
+    let (a_m, b_m) = (&rescaling.curve.a, &rescaling.curve.b);
+    check(b_m.is_one(), || format!("B = {} instead of 1", b_m))?;
+    check(a_m == &BigInt::from(486662), || format!("A = {} instead of 486662", a_m))?;
+    let image = rescaling.map_point(&Point::Affine(u, v));
+    let Point::Affine(u, v) = &image else {
+        return Err("the image is the point at infinity".to_string());
+    };
+    check(u == &BigInt::from(9), || format!("u = {} instead of 9", u))?;
+    check(v == &y, || format!("v = {} instead of the Curve25519 base point's v", v))?;
+    check(rescaling.curve.contains(&image), || "the image is not on Curve25519".to_string())
+}
+
+/// The Ed25519 and Curve25519 base points must correspond, and so must the public keys of RFC 8032's
+/// first test vector and the X25519 key of the same (hashed and clamped) secret.
+fn ed25519_x25519() -> Outcome {
+    let image = ed25519_to_curve25519(&ed25519_base_point());
+    check(image == curve25519_base_point(), || format!("the Ed25519 base point maps to {}", image))?;
+    let back = curve25519_to_ed25519(&image);
+    check(back == Some(ed25519_base_point()), || "the Curve25519 base point does not map back".to_string())?;
+    let ed25519_key = hex("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+    let x25519_key = hex("d85e07ec22b0ad881537c2f44d662d1a143cf830c57aca4305d85c7a90f6b62e");
+    let converted = ed25519_public_key_to_x25519(&ed25519_key).map_err(|error| error.to_string())?;
+    check(converted[..] == x25519_key[..], || "the Ed25519 key gives the wrong X25519 key".to_string())?;
+    let sign = ed25519_key[KEY_LEN - 1] >> 7 == 1;
+    let restored = x25519_public_key_to_ed25519(&x25519_key, sign).map_err(|error| error.to_string())?;
+    check(restored[..] == ed25519_key[..], || "the X25519 key gives the wrong Ed25519 key".to_string())
+}
+
+/// Ed448 and Curve448 are 4-isogenous: the isogeny of RFC 7748 must take base point to base point, its
+/// dual must come back with 4 times the Ed448 base point, Curve448 must be isomorphic to Wei448, and
+/// the blank-message key of RFC 8032 must convert to the X448 key of the same scalar.
+fn ed448_x448() -> Outcome {
+    let image = ed448_to_curve448(&ed448_base_point());
+    check(image == curve448_base_point(), || format!("the Ed448 base point maps to {}", image))?;
+    check(curve448().contains(&image), || "the image is not on Curve448".to_string())?;
+    let quadrupled = scalar_mul(&ed448(), &ed448_base_point(), &BigInt::from(4));
+    let back = curve448_to_ed448(&image);
+    check(back == quadrupled, || format!("the dual isogeny gives {} instead of [4]B", back))?;
+    let registered = |name| {
+        let curve = lookup(name).ok_or_else(|| format!("{} is not in the registry", name))?;
+        curve.to_weierstrass().map_err(|error| error.to_string())
+    };
+    check(registered("Curve448")? == registered("Wei448")?, || "Curve448 does not give Wei448".to_string())?;
+    let ed448_key = hex(
+        "5fd7449b59b461fd2ce787ec616ad46a1da1342485a70e1f8a0ea75d80e96778\
+         edf124769b46c7061bd6783df1e50f6cd1fa1abeafe8256180",
+    );
+    let x448_key = hex(
+        "3bd436b72a1d011cd3845717fcc6887852a2007fd595ac970bef67c7\
+         f24a5329ffd1dfd0b05f90adc9c6e70805e5817a1f09ca229bef8619",
+    );
+    let converted = ed448_public_key_to_x448(&ed448_key).map_err(|error| error.to_string())?;
+    check(converted[..] == x448_key[..], || "the Ed448 key gives the wrong X448 key".to_string())
+}
+
+/// X25519 by the ladder on Curve25519 must give the result of the first test vector in RFC 7748,
+/// section 5.2.
+fn x25519_ladder() -> Outcome {
+    let mut scalar = hex("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4");
+    scalar[0] &= 248;
+    scalar[KEY_LEN - 1] = (scalar[KEY_LEN - 1] & 127) | 64;
+    let mut u = hex("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c");
+    u[KEY_LEN - 1] &= 127;
+    let little_endian = |bytes: &[u8]| BigInt::from_bytes_le(Sign::Plus, bytes);
+    let (_, mut result) = curve25519().ladder(&little_endian(&u), &little_endian(&scalar)).to_bytes_le();
+    result.resize(KEY_LEN, 0);
+    let expected = hex("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552");
+    check(result == expected, || "the ladder gives the wrong X25519 output".to_string())
+}
+
+fn hex(digits: &str) -> Vec<u8> {
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).expect("known-answer constants are hex"))
+        .collect()
+}
+
+/// Square roots and inverses must satisfy r^2 = value and value * inverse = 1 for assorted prime shapes.
+fn sqrt_inverse_identities() -> Outcome {
+    let primes = [
+        BigInt::from(17),                  // p = 1 mod 16, several Tonelli-Shanks rounds
+        BigInt::from(103),                 // p = 3 mod 4
+        BigInt::from(1009),                // p = 1 mod 8
+        (BigInt::one() << 127u32) - 1u32,  // Mersenne prime
+        (BigInt::one() << 255u32) - 19u32, // Curve25519 field
+    ];
+    for p in &primes {
+        for seed in 1u32..=50 {
+            let value = (BigInt::from(seed).pow(7) + 12345u32).mod_floor(p);
+            let square = (&value * &value).mod_floor(p);
+            let root = mod_sqrt(&square, p, &SearchLimits::default())
+                .map_err(|error| error.to_string())?
+                .ok_or_else(|| format!("no square root of the square {} modulo {}", square, p))?;
+            let root_squared = (&root * &root).mod_floor(p);
+            check(root_squared == square, || format!("sqrt({0})^2 != {0} modulo {1}", square, p))?;
+            check(legendre_symbol(&square, p) >= 0, || format!("({} / {}) = -1 for a square", square, p))?;
+            if let Some(inverse) = mod_inverse(&value, p) {
+                let product = (&value * &inverse).mod_floor(p);
+                check(product.is_one(), || format!("{0} * {0}^-1 != 1 modulo {1}", value, p))?;
+            } else {
+                check(value.is_zero(), || format!("{} has no inverse modulo {}", value, p))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Invalid inputs must be rejected with the documented error kinds.
+fn error_kinds() -> Outcome {
+    let cases = [
+        ("off-curve point", transform(1, 1, 8, 2, 17), "point_not_on_curve"),
+        ("singular curve", transform(0, 0, 0, 0, 17), "invalid_parameters"),
+        ("characteristic 3", transform(0, 0, 1, 1, 3), "unsupported_characteristic"),
+    ];
+    for (case, result, expected) in cases {
+        let kind = result.map_or_else(|error| error.kind(), |_| "ok");
+        check(kind == expected, || format!("{} gave {} instead of {}", case, kind, expected))?;
+    }
+    Ok(())
+}
+
+/// Runs every built-in known-answer test and reports each outcome.
+pub fn run_selftest() -> Vec<KatResult> {
+    let tests: [Kat; 8] = [
+        ("Wei25519 base point maps to the Curve25519 base point", wei25519),
+        ("Ed25519 and X25519 base points and public keys correspond", ed25519_x25519),
+        ("Ed448 and X448 base points and public keys correspond via the 4-isogeny", ed448_x448),
+        ("X25519 by the Montgomery ladder matches RFC 7748", x25519_ladder),
+        ("y^2 = x^3 + 8x + 2 over F_17, point (14, 6)", || {
+            small_curve(14, 6, 8, 2, 17, &[(5, 5, 3, 15), (12, 12, 14, 2)])
+        }),
+        ("y^2 = x^3 - 7x + 6 over F_101 (three roots), point (0, 39)", || {
+            let expected = [
+                (5, 7, 86, 96),
+                (18, 53, 47, 92),
+                (37, 77, 91, 46),
+                (64, 24, 10, 55),
+                (83, 48, 54, 9),
+                (96, 94, 15, 5),
+            ];
+            small_curve(0, 39, -7, 6, 101, &expected)
+        }),
+        ("square root and inverse identities", sqrt_inverse_identities),
+        ("invalid inputs are rejected", error_kinds),
+    ];
+    tests
+        .into_iter()
+        .map(|(name, test)| KatResult { name, failure: test().err() })
+        .collect()
+}
@@ -0,0 +1,102 @@
+//! An async front to the transform for servers on tokio, so that long root searches run on the blocking
+//! thread pool instead of stalling the runtime's worker threads.
+
+use crate::error::TransformError;
+use crate::map::MontgomeryMap;
+use crate::spec::CurveSpec;
+use crate::transform::{transform_with_map, TransformOptions};
+use num_bigint::BigInt;
+use rand::rngs::OsRng;
+use std::panic;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::{self, JoinError};
+
+/// Re-raises a panic of a blocking task in the awaiting task. A blocking task cannot be aborted once
+/// it runs, but one still queued is cancelled when the runtime shuts down; that becomes an error.
+fn join_failed<T>(error: JoinError) -> Result<T, TransformError> {
+    match error.try_into_panic() {
+        Ok(payload) => panic::resume_unwind(payload),
+        Err(_) => Err(TransformError::SearchLimitExceeded(
+            "before the transform ran: the runtime shut down".to_string(),
+        )),
+    }
+}
+
+/// `transform_with_map` on tokio's blocking thread pool, for the point and curve of `spec`.
+pub async fn transform_with_map_blocking(
+    spec: CurveSpec,
+    options: TransformOptions,
+) -> Result<(BigInt, BigInt, MontgomeryMap), TransformError> {
+    task::spawn_blocking(move || {
+        let CurveSpec { a, b, p, x, y } = spec;
+        transform_with_map(&x, &y, &a, &b, &p, &options, &mut OsRng)
+    })
+    .await
+    .unwrap_or_else(join_failed)
+}
+
+/// `MontgomeryMap::new` on tokio's blocking thread pool.
+pub async fn montgomery_map_blocking(
+    a: BigInt,
+    b: BigInt,
+    p: BigInt,
+    options: TransformOptions,
+) -> Result<MontgomeryMap, TransformError> {
+    task::spawn_blocking(move || MontgomeryMap::new(&a, &b, &p, &options))
+        .await
+        .unwrap_or_else(join_failed)
+}
+
+/// Runs transforms on tokio's blocking thread pool with at most `max_concurrent` of them at a time;
+/// further requests wait for a slot, so a burst of slow root searches cannot take over the pool.
+///
+/// Clones share the same slots. A slot stays taken until its search finishes, even if the request
+/// awaiting it is dropped, since blocking tasks cannot be cancelled; `options.limits` bounds how long
+/// that can be.
+#[derive(Clone, Debug)]
+pub struct TransformService {
+    slots: Arc<Semaphore>,
+    options: TransformOptions,
+}
+
+impl TransformService {
+    /// A service running up to `max_concurrent` transforms at once (at least one) with `options`.
+    pub fn new(max_concurrent: usize, options: TransformOptions) -> TransformService {
+        TransformService {
+            slots: Arc::new(Semaphore::new(max_concurrent.max(1))),
+            options,
+        }
+    }
+
+    /// The number of transforms that could start right now without waiting.
+    pub fn available(&self) -> usize {
+        self.slots.available_permits()
+    }
+
+    /// Transforms the point of `spec` once a slot is free.
+    pub async fn transform(&self, spec: CurveSpec) -> Result<(BigInt, BigInt, MontgomeryMap), TransformError> {
+        let slot = Arc::clone(&self.slots).acquire_owned().await.expect("the semaphore is never closed");
+        let options = self.options;
+        task::spawn_blocking(move || {
+            let _slot = slot;
+            let CurveSpec { a, b, p, x, y } = spec;
+            transform_with_map(&x, &y, &a, &b, &p, &options, &mut OsRng)
+        })
+        .await
+        .unwrap_or_else(join_failed)
+    }
+
+    /// Computes the map of y^2 = x^3 + ax + b over F_p once a slot is free, e.g. to map many points with
+    /// `MontgomeryMap::map_points` afterwards.
+    pub async fn map(&self, a: BigInt, b: BigInt, p: BigInt) -> Result<MontgomeryMap, TransformError> {
+        let slot = Arc::clone(&self.slots).acquire_owned().await.expect("the semaphore is never closed");
+        let options = self.options;
+        task::spawn_blocking(move || {
+            let _slot = slot;
+            MontgomeryMap::new(&a, &b, &p, &options)
+        })
+        .await
+        .unwrap_or_else(join_failed)
+    }
+}
@@ -0,0 +1,318 @@
+use crate::error::TransformError;
+use crate::limits::{SearchBudget, SearchLimits};
+use crate::log::{debug, info};
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+use num_bigint::BigInt;
+#[cfg(feature = "std")]
+use rand::rngs::OsRng;
+use rand::{CryptoRng, Rng, RngCore};
+
+/// An element of F_P for a prime P < 2^64, using native u128 arithmetic instead of `BigInt`.
+///
+/// P must be an odd prime; this is not checked, since doing so at compile time would be too slow.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SmallField<const P: u64>(u64);
+
+impl<const P: u64> SmallField<P> {
+    pub const ZERO: Self = SmallField(0);
+    pub const ONE: Self = SmallField(1);
+
+    /// Creates an element from any integer, reducing it modulo P.
+    pub const fn new(value: u64) -> Self {
+        SmallField(value % P)
+    }
+
+    /// Creates an element from a signed integer, reducing it into [0, P).
+    pub const fn from_i64(value: i64) -> Self {
+        SmallField((value as i128).rem_euclid(P as i128) as u64)
+    }
+
+    /// The modulus P.
+    pub const fn modulus() -> u64 {
+        P
+    }
+
+    /// The canonical representative in [0, P).
+    pub const fn value(self) -> u64 {
+        self.0
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn square(self) -> Self {
+        self * self
+    }
+
+    /// Raises the element to the power `exponent` by square-and-multiply.
+    pub fn pow(self, mut exponent: u64) -> Self {
+        let mut base = self;
+        let mut result = Self::ONE;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result *= base;
+            }
+            base = base.square();
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// Returns the multiplicative inverse via Fermat's little theorem, or `None` for zero.
+    pub fn inverse(self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(self.pow(P - 2))
+        }
+    }
+
+    /// Returns the Legendre symbol as 1, −1 or 0.
+    pub fn legendre(self) -> i8 {
+        match self.pow((P - 1) / 2).0 {
+            0 => 0,
+            1 => 1,
+            _ => -1,
+        }
+    }
+
+    pub fn is_square(self) -> bool {
+        self.legendre() >= 0
+    }
+
+    /// Computes a square root with Tonelli-Shanks, or `None` if the element is not a square.
+    pub fn sqrt(self) -> Option<Self> {
+        match self.legendre() {
+            0 => return Some(Self::ZERO),
+            -1 => return None,
+            _ => {}
+        }
+        let mut q = P - 1;
+        let mut s = 0;
+        while q.is_multiple_of(2) {
+            q /= 2;
+            s += 1;
+        }
+        let z = (2..P).map(Self::new).find(|n| n.legendre() == -1)?;
+
+        let mut m = s;
+        let mut c = z.pow(q);
+        let mut t = self.pow(q);
+        let mut r = self.pow(q.div_ceil(2));
+        while t != Self::ONE {
+            let mut i = 0;
+            let mut t2i = t;
+            while t2i != Self::ONE {
+                t2i = t2i.square();
+                i += 1;
+                if i == m {
+                    return None;
+                }
+            }
+            let b = c.pow(1 << (m - i - 1));
+            m = i;
+            c = b.square();
+            t *= c;
+            r *= b;
+        }
+        Some(r)
+    }
+
+    /// Samples a uniformly random element.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        SmallField(rng.gen_range(0..P))
+    }
+
+    /// Iterates over every element of the field in increasing order.
+    pub fn elements() -> impl Iterator<Item = Self> {
+        (0..P).map(SmallField)
+    }
+}
+
+impl<const P: u64> fmt::Display for SmallField<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u64> From<u64> for SmallField<P> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const P: u64> From<SmallField<P>> for BigInt {
+    fn from(value: SmallField<P>) -> BigInt {
+        BigInt::from(value.0)
+    }
+}
+
+impl<const P: u64> Add for SmallField<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        SmallField(((self.0 as u128 + other.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Sub for SmallField<P> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        SmallField(((self.0 as u128 + P as u128 - other.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Mul for SmallField<P> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        SmallField(((self.0 as u128 * other.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Div for SmallField<P> {
+    type Output = Self;
+
+    /// Panics on division by zero, like integer division.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.inverse().expect("division by zero in SmallField")
+    }
+}
+
+impl<const P: u64> Neg for SmallField<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::ZERO - self
+    }
+}
+
+impl<const P: u64> AddAssign for SmallField<P> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const P: u64> SubAssign for SmallField<P> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const P: u64> MulAssign for SmallField<P> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+/// Lists every affine point of y^2 = x^3 + ax + b over F_P, ordered by x and then y.
+pub fn enumerate_points<const P: u64>(a: SmallField<P>, b: SmallField<P>) -> Vec<(SmallField<P>, SmallField<P>)> {
+    let mut points = Vec::new();
+    for x in SmallField::<P>::elements() {
+        if let Some(y) = (x.square() * x + a * x + b).sqrt() {
+            if y.is_zero() {
+                points.push((x, y));
+            } else {
+                let (low, high) = if y < -y { (y, -y) } else { (-y, y) };
+                points.push((x, low));
+                points.push((x, high));
+            }
+        }
+    }
+    points
+}
+
+/// Searches for a root z0 of z^3 + az + b in F_P by random sampling, giving up once `limits` are exhausted.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+pub fn find_cubic_root_small<const P: u64, R: RngCore + CryptoRng + ?Sized>(
+    a: SmallField<P>,
+    b: SmallField<P>,
+    limits: &SearchLimits,
+    rng: &mut R,
+) -> Result<SmallField<P>, TransformError> {
+    let mut budget = SearchBudget::new(limits);
+    loop {
+        budget.tick("searching for a root of z^3 + az + b")?;
+        let candidate = SmallField::random(rng);
+        if (candidate.square() * candidate + a * candidate + b).is_zero() {
+            info!(attempts = budget.attempts, z0 = %candidate, "found root of the cubic");
+            return Ok(candidate);
+        }
+    }
+}
+
+/// Transformation from a Weierstrass to a Montgomery curve over `SmallField<P>`.
+///
+/// Uses the same formulas as `transform_to_montgomery`, returning (x_montgomery, y_montgomery, A, B).
+#[cfg(feature = "std")]
+pub fn transform_to_montgomery_small<const P: u64>(
+    x: SmallField<P>,
+    y: SmallField<P>,
+    a: SmallField<P>,
+    b: SmallField<P>,
+    limits: &SearchLimits,
+) -> Result<(SmallField<P>, SmallField<P>, SmallField<P>, SmallField<P>), TransformError> {
+    transform_to_montgomery_small_with_rng(x, y, a, b, limits, &mut OsRng)
+}
+
+/// `transform_to_montgomery_small`, drawing randomness from `rng`.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+pub fn transform_to_montgomery_small_with_rng<const P: u64, R: RngCore + CryptoRng + ?Sized>(
+    x: SmallField<P>,
+    y: SmallField<P>,
+    a: SmallField<P>,
+    b: SmallField<P>,
+    limits: &SearchLimits,
+    rng: &mut R,
+) -> Result<(SmallField<P>, SmallField<P>, SmallField<P>, SmallField<P>), TransformError> {
+    if P <= 3 {
+        return Err(TransformError::UnsupportedCharacteristic(BigInt::from(P)));
+    }
+    let three = SmallField::new(3);
+    // The curve is singular when the discriminant 4a^3 + 27b^2 vanishes
+    if (SmallField::new(4) * a.pow(3) + SmallField::new(27) * b.square()).is_zero() {
+        return Err(TransformError::InvalidParameters(
+            "the curve is singular (4a^3 + 27b^2 = 0)".to_string(),
+        ));
+    }
+    if !(y.square() - x.pow(3) - a * x - b).is_zero() {
+        return Err(TransformError::PointNotOnCurve);
+    }
+
+    //Find a root z0 of the polynomial z^3 + az + b in the field F_P
+    let root = find_cubic_root_small(a, b, limits, rng)?;
+
+    // The other roots solve z^2 + z0·z + (z0^2 + a) = 0, whose discriminant is −3·z0^2 − 4a
+    let mut roots = vec![root];
+    if let Some(sqrt) = (-(three * root.square()) - SmallField::new(4) * a).sqrt() {
+        let two_inv = SmallField::new(2).inverse().expect("P is odd");
+        roots.extend([(sqrt - root) * two_inv, (-sqrt - root) * two_inv]);
+    }
+
+    // Compute s = (sqrt(3 * z0^2 + a))^{-1} modulo P, trying each root until 3 * z0^2 + a is a square
+    let (z0, s) = roots
+        .into_iter()
+        .find_map(|z0| {
+            let s_squared = three * z0.square() + a;
+            debug!(%z0, %s_squared);
+            s_squared.sqrt().map(|s| (z0, s))
+        })
+        .ok_or_else(|| {
+            let message = "3*z0^2 + a is not a square modulo p for any root z0";
+            TransformError::NoMontgomeryForm(message.to_string())
+        })?;
+    let s_inv = s
+        .inverse()
+        .ok_or_else(|| TransformError::NoMontgomeryForm(format!("s = {} is not invertible modulo p", s)))?;
+    debug!(%s, %s_inv);
+
+    Ok((s_inv * (x - z0), s_inv * y, three * z0 * s_inv, s_inv))
+}
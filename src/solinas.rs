@@ -0,0 +1,205 @@
+//! Solinas reduction for the NIST generalized Mersenne primes of P-256, P-384 and P-521.
+//!
+//! Products are split into fixed-width 32-bit words and reduced with the word shuffles of FIPS 186-4,
+//! appendix D.2: every word above the field size is a sum of signed copies of lower words, so a
+//! reduction is a handful of word additions and at most a few subtractions of p, with no division and no
+//! multiplication. On products of two elements that is about 1.3x faster than `mod_floor` for P-256,
+//! 3x for P-384 and 7x for P-521 (see `benches/reduction.rs`). `Field` uses it automatically for these
+//! three primes.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{Signed, Zero};
+
+/// Stands for a zero word in the tables below.
+const Z: usize = usize::MAX;
+
+/// p256 = 2^256 − 2^224 + 2^192 + 2^96 − 1: r = s1 + 2·s2 + 2·s3 + s4 + s5 − d1 − d2 − d3 − d4, each term
+/// listing the input words c_i from the most significant word down, as in FIPS 186-4, D.2.3.
+const P256_TERMS: [(i64, [usize; 8]); 9] = [
+    (1, [7, 6, 5, 4, 3, 2, 1, 0]),
+    (2, [15, 14, 13, 12, 11, Z, Z, Z]),
+    (2, [Z, 15, 14, 13, 12, Z, Z, Z]),
+    (1, [15, 14, Z, Z, Z, 10, 9, 8]),
+    (1, [8, 13, 15, 14, 13, 11, 10, 9]),
+    (-1, [10, 8, Z, Z, Z, 13, 12, 11]),
+    (-1, [11, 9, Z, Z, 15, 14, 13, 12]),
+    (-1, [12, Z, 10, 9, 8, 15, 14, 13]),
+    (-1, [13, Z, 11, 10, 9, Z, 15, 14]),
+];
+
+/// p384 = 2^384 − 2^128 − 2^96 + 2^32 − 1: r = s1 + 2·s2 + s3 + s4 + s5 + s6 + s7 − d1 − d2 − d3, as in
+/// FIPS 186-4, D.2.4.
+const P384_TERMS: [(i64, [usize; 12]); 10] = [
+    (1, [11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0]),
+    (2, [Z, Z, Z, Z, Z, 23, 22, 21, Z, Z, Z, Z]),
+    (1, [23, 22, 21, 20, 19, 18, 17, 16, 15, 14, 13, 12]),
+    (1, [20, 19, 18, 17, 16, 15, 14, 13, 12, 23, 22, 21]),
+    (1, [19, 18, 17, 16, 15, 14, 13, 12, 20, Z, 23, Z]),
+    (1, [Z, Z, Z, Z, 23, 22, 21, 20, Z, Z, Z, Z]),
+    (1, [Z, Z, Z, Z, Z, Z, 23, 22, 21, Z, Z, 20]),
+    (-1, [22, 21, 20, 19, 18, 17, 16, 15, 14, 13, 12, 23]),
+    (-1, [Z, Z, Z, Z, Z, Z, Z, 23, 22, 21, 20, Z]),
+    (-1, [Z, Z, Z, Z, Z, Z, Z, 23, 23, Z, Z, Z]),
+];
+
+/// The words of p256, least significant first.
+const P256: [u32; 8] = [0xffffffff, 0xffffffff, 0xffffffff, 0, 0, 0, 1, 0xffffffff];
+
+/// The words of p384, least significant first.
+const P384: [u32; 12] = [
+    0xffffffff, 0, 0, 0xffffffff, 0xfffffffe, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+    0xffffffff, 0xffffffff,
+];
+
+/// p521 = 2^521 − 1 takes 17 words, the top one holding 9 bits.
+const P521_WORDS: usize = 17;
+
+/// One of the NIST primes with a dedicated Solinas reduction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NistPrime {
+    P256,
+    P384,
+    P521,
+}
+
+impl NistPrime {
+    /// The NIST prime equal to `modulus`, if any.
+    pub fn detect(modulus: &BigInt) -> Option<NistPrime> {
+        [NistPrime::P256, NistPrime::P384, NistPrime::P521]
+            .into_iter()
+            .find(|prime| &prime.modulus() == modulus)
+    }
+
+    /// The prime itself.
+    pub fn modulus(self) -> BigInt {
+        let words = match self {
+            NistPrime::P256 => P256.to_vec(),
+            NistPrime::P384 => P384.to_vec(),
+            NistPrime::P521 => {
+                let mut words = vec![u32::MAX; P521_WORDS];
+                words[P521_WORDS - 1] = 0x1ff;
+                words
+            }
+        };
+        BigInt::from_biguint(Sign::Plus, BigUint::new(words))
+    }
+
+    /// Returns `value` reduced into [0, p), like `value.mod_floor(p)`. Values of up to twice the bit
+    /// length of p, which covers every product of two reduced elements, take the fixed-width path; larger
+    /// ones fall back to a division.
+    pub fn reduce(self, value: &BigInt) -> BigInt {
+        let magnitude = value.magnitude();
+        let remainder = match self {
+            NistPrime::P256 if magnitude.bits() <= 512 => {
+                reduce_words(&words::<16>(magnitude), &P256_TERMS, &P256)
+            }
+            NistPrime::P384 if magnitude.bits() <= 768 => {
+                reduce_words(&words::<24>(magnitude), &P384_TERMS, &P384)
+            }
+            NistPrime::P521 if magnitude.bits() <= 1042 => reduce_p521(&words::<33>(magnitude)),
+            _ => {
+                let modulus = self.modulus();
+                (magnitude % modulus.magnitude()).to_u32_digits()
+            }
+        };
+        let remainder = BigInt::from_biguint(Sign::Plus, BigUint::new(remainder));
+        if value.is_negative() && !remainder.is_zero() {
+            self.modulus() - remainder
+        } else {
+            remainder
+        }
+    }
+}
+
+/// The magnitude as exactly `N` little-endian 32-bit words; it must fit.
+fn words<const N: usize>(magnitude: &BigUint) -> [u32; N] {
+    let mut words = [0u32; N];
+    for (word, digit) in words.iter_mut().zip(magnitude.iter_u32_digits()) {
+        *word = digit;
+    }
+    words
+}
+
+/// Adds `b` to `a` in place and returns the carry out.
+fn add_words(a: &mut [u32], b: &[u32]) -> i64 {
+    let mut carry = 0u64;
+    for (word, &other) in a.iter_mut().zip(b) {
+        let sum = *word as u64 + other as u64 + carry;
+        *word = sum as u32;
+        carry = sum >> 32;
+    }
+    carry as i64
+}
+
+/// Subtracts `b` from `a` in place and returns the borrow out.
+fn sub_words(a: &mut [u32], b: &[u32]) -> i64 {
+    let mut borrow = 0i64;
+    for (word, &other) in a.iter_mut().zip(b) {
+        let difference = *word as i64 - other as i64 - borrow;
+        *word = difference as u32;
+        borrow = (difference < 0) as i64;
+    }
+    borrow
+}
+
+/// Whether `a` ≥ `b` for words of the same length.
+fn at_least(a: &[u32], b: &[u32]) -> bool {
+    for (&word, &other) in a.iter().zip(b).rev() {
+        if word != other {
+            return word > other;
+        }
+    }
+    true
+}
+
+/// Brings the value `words` + `top`·2^(32n) into [0, p) by adding or subtracting p; `top` is the small
+/// signed overflow of a Solinas sum.
+fn normalize(words: &mut [u32], mut top: i64, p: &[u32]) -> Vec<u32> {
+    while top < 0 {
+        top += add_words(words, p);
+    }
+    while top > 0 || at_least(words, p) {
+        top -= sub_words(words, p);
+    }
+    words.to_vec()
+}
+
+/// Evaluates a FIPS 186-4 term table on the input words `c` and reduces the sum modulo `p`.
+fn reduce_words<const N: usize>(c: &[u32], terms: &[(i64, [usize; N])], p: &[u32; N]) -> Vec<u32> {
+    let mut sums = [0i64; N];
+    for (coefficient, indices) in terms {
+        // The tables list words from the most significant down
+        for (sum, &index) in sums.iter_mut().zip(indices.iter().rev()) {
+            if index != Z {
+                *sum += coefficient * c[index] as i64;
+            }
+        }
+    }
+    let mut words = [0u32; N];
+    let mut carry = 0i64;
+    for (word, sum) in words.iter_mut().zip(sums) {
+        let total = sum + carry;
+        *word = total as u32;
+        carry = total >> 32;
+    }
+    normalize(&mut words, carry, p)
+}
+
+/// p521 = 2^521 − 1, so c = c1·2^521 + c0 reduces to c0 + c1 (FIPS 186-4, D.2.5).
+fn reduce_p521(c: &[u32; 33]) -> Vec<u32> {
+    let mut low = [0u32; P521_WORDS];
+    low.copy_from_slice(&c[..P521_WORDS]);
+    low[P521_WORDS - 1] &= 0x1ff;
+    let mut high = [0u32; P521_WORDS];
+    for (index, word) in high.iter_mut().enumerate() {
+        let upper = c.get(P521_WORDS + index).copied().unwrap_or(0);
+        *word = (c[P521_WORDS - 1 + index] >> 9) | (upper << 23);
+    }
+    add_words(&mut low, &high);
+    let mut p = [u32::MAX; P521_WORDS];
+    p[P521_WORDS - 1] = 0x1ff;
+    // c0 + c1 < 2^522, so one subtraction of p suffices
+    normalize(&mut low, 0, &p)
+}
@@ -0,0 +1,124 @@
+#[cfg(feature = "serde")]
+use crate::error::TransformError;
+use num_bigint::BigInt;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+use std::fmt;
+use std::path::Path;
+#[cfg(feature = "serde")]
+use std::str::FromStr;
+
+/// A curve y^2 = x^3 + ax + b over F_p together with the point (x, y) to transform, as read from a file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CurveSpec {
+    pub a: BigInt,
+    pub b: BigInt,
+    pub p: BigInt,
+    pub x: BigInt,
+    pub y: BigInt,
+}
+
+/// A number written either natively or, for values too large for the format, as a decimal string (or
+/// a 0x-prefixed hexadecimal or 0b-prefixed binary one).
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum Number {
+    Integer(i64),
+    Text(String),
+}
+
+/// The document layout shared by all formats; unknown keys (comments, vector ids, ...) are ignored.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct SpecRecord {
+    a: Number,
+    b: Number,
+    p: Number,
+    x: Number,
+    y: Number,
+}
+
+/// File formats understood by `CurveSpec::from_path` (with the `serde` feature).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpecFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl SpecFormat {
+    /// Picks the format from a `.json`, `.toml`, `.yaml` or `.yml` extension.
+    pub fn from_path(path: &Path) -> Option<SpecFormat> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(SpecFormat::Json),
+            "toml" => Some(SpecFormat::Toml),
+            "yaml" | "yml" => Some(SpecFormat::Yaml),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SpecFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SpecFormat::Json => "JSON",
+            SpecFormat::Toml => "TOML",
+            SpecFormat::Yaml => "YAML",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(feature = "serde")]
+fn invalid(message: String) -> TransformError {
+    TransformError::InvalidParameters(message)
+}
+
+#[cfg(feature = "serde")]
+pub(crate) fn parse_number(name: &str, number: Number) -> Result<BigInt, TransformError> {
+    match number {
+        Number::Integer(value) => Ok(BigInt::from(value)),
+        Number::Text(text) => {
+            let trimmed = text.trim();
+            let hex = trimmed.strip_prefix("0x").or_else(|| trimmed.strip_prefix("0X"));
+            let binary = trimmed.strip_prefix("0b").or_else(|| trimmed.strip_prefix("0B"));
+            let parsed = match (hex, binary) {
+                (Some(digits), _) => BigInt::parse_bytes(digits.as_bytes(), 16),
+                (_, Some(digits)) => BigInt::parse_bytes(digits.as_bytes(), 2),
+                _ => BigInt::from_str(trimmed).ok(),
+            };
+            parsed.ok_or_else(|| invalid(format!("{} is not an integer: {:?}", name, text)))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl CurveSpec {
+    /// Parses a document with integer (or decimal- or hexadecimal-string) fields `a`, `b`, `p`, `x` and `y`.
+    pub fn parse(source: &str, format: SpecFormat) -> Result<CurveSpec, TransformError> {
+        let record: SpecRecord = match format {
+            SpecFormat::Json => serde_json::from_str(source).map_err(|error| error.to_string()),
+            SpecFormat::Toml => toml::from_str(source).map_err(|error| error.to_string()),
+            SpecFormat::Yaml => serde_yaml::from_str(source).map_err(|error| error.to_string()),
+        }
+        .map_err(|error| invalid(format!("malformed {} curve definition: {}", format, error)))?;
+        Ok(CurveSpec {
+            a: parse_number("a", record.a)?,
+            b: parse_number("b", record.b)?,
+            p: parse_number("p", record.p)?,
+            x: parse_number("x", record.x)?,
+            y: parse_number("y", record.y)?,
+        })
+    }
+
+    /// Reads a curve definition, choosing the format from the file extension.
+    pub fn from_path(path: &Path) -> Result<CurveSpec, TransformError> {
+        let format = SpecFormat::from_path(path).ok_or_else(|| {
+            invalid(format!("cannot tell the format of {}; use .json, .toml, .yaml or .yml", path.display()))
+        })?;
+        let source = std::fs::read_to_string(path)
+            .map_err(|error| TransformError::Io(format!("cannot read {}: {}", path.display(), error)))?;
+        CurveSpec::parse(&source, format)
+    }
+}
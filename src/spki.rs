@@ -0,0 +1,179 @@
+use crate::curve::Point;
+use crate::der::{
+    decode_oid, decode_pem, expect_element, read_element, TAG_BIT_STRING, TAG_INTEGER, TAG_OCTET_STRING,
+    TAG_OID, TAG_SEQUENCE,
+};
+use crate::error::TransformError;
+use crate::registry::{lookup_oid, CurveModel, NamedCurve};
+use crate::scalar_mul::{scalar_mul, GlvEndomorphism};
+use crate::weierstrass::WeierstrassCurve;
+use num_bigint::{BigInt, Sign};
+
+/// The algorithm OID id-ecPublicKey of RFC 5480, used by both public and private EC keys.
+pub const OID_EC_PUBLIC_KEY: &str = "1.2.840.10045.2.1";
+
+/// The field type OID prime-field of X9.62, the only one explicit parameters may use here.
+const OID_PRIME_FIELD: &str = "1.2.840.10045.1.1";
+
+/// Context-specific tags [0] and [1] of ECPrivateKey (RFC 5915).
+const TAG_PARAMETERS: u8 = 0xa0;
+const TAG_PUBLIC_KEY: u8 = 0xa1;
+
+/// An EC public key read from a key file, with its curve in short Weierstrass form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EcPublicKey {
+    pub curve: WeierstrassCurve,
+    pub point: Point,
+    /// The registry entry when the key names its curve by OID; `None` for explicit parameters.
+    pub named: Option<&'static NamedCurve>,
+}
+
+fn invalid(message: &str) -> TransformError {
+    TransformError::InvalidParameters(message.to_string())
+}
+
+fn unsigned(contents: &[u8]) -> BigInt {
+    BigInt::from_bytes_be(Sign::Plus, contents)
+}
+
+/// The curve, base point and base point order of ECParameters.
+struct EcParameters {
+    curve: WeierstrassCurve,
+    base: Point,
+    order: BigInt,
+    named: Option<&'static NamedCurve>,
+}
+
+/// The curve and base point of ECParameters: a namedCurve OID resolved with the registry, or
+/// explicit SpecifiedECDomain parameters (X9.62) over a prime field.
+fn ec_parameters(der: &[u8]) -> Result<EcParameters, TransformError> {
+    let (tag, contents, _) = read_element(der)?;
+    if tag == TAG_OID {
+        let oid = decode_oid(contents)?;
+        let named = lookup_oid(&oid)
+            .filter(|named| named.model == CurveModel::Weierstrass)
+            .ok_or_else(|| TransformError::InvalidParameters(format!("unknown EC curve OID {}", oid)))?;
+        let (curve, base) = named.to_weierstrass()?;
+        return Ok(EcParameters { curve, base, order: named.order(), named: Some(named) });
+    }
+    if tag != TAG_SEQUENCE {
+        return Err(invalid("ECParameters must name a curve or give it explicitly"));
+    }
+    let (_, rest) = expect_element(contents, TAG_INTEGER, "the ECParameters version")?;
+    let (field, rest) = expect_element(rest, TAG_SEQUENCE, "the FieldID")?;
+    let (field_type, field) = expect_element(field, TAG_OID, "the field type")?;
+    if decode_oid(field_type)? != OID_PRIME_FIELD {
+        return Err(invalid("only prime fields are supported"));
+    }
+    let (p, _) = expect_element(field, TAG_INTEGER, "the prime")?;
+    let (coefficients, rest) = expect_element(rest, TAG_SEQUENCE, "the curve coefficients")?;
+    let (a, coefficients) = expect_element(coefficients, TAG_OCTET_STRING, "the coefficient a")?;
+    let (b, _) = expect_element(coefficients, TAG_OCTET_STRING, "the coefficient b")?;
+    let (base, rest) = expect_element(rest, TAG_OCTET_STRING, "the base point")?;
+    let (order, _) = expect_element(rest, TAG_INTEGER, "the base point order")?;
+    let curve = WeierstrassCurve::new(&unsigned(a), &unsigned(b), &unsigned(p))?;
+    let base = curve.from_sec1(base)?;
+    Ok(EcParameters { curve, base, order: unsigned(order), named: None })
+}
+
+/// The SEC1 point inside a BIT STRING, which must have no unused bits.
+fn point_bits(contents: &[u8]) -> Result<&[u8], TransformError> {
+    match contents.split_first() {
+        Some((0, point)) => Ok(point),
+        _ => Err(invalid("the public key BIT STRING has unused bits")),
+    }
+}
+
+/// SubjectPublicKeyInfo (RFC 5280, RFC 5480): the algorithm with its ECParameters, then the point.
+fn subject_public_key_info(contents: &[u8]) -> Result<EcPublicKey, TransformError> {
+    let (algorithm, rest) = expect_element(contents, TAG_SEQUENCE, "the AlgorithmIdentifier")?;
+    let parameters = ec_algorithm(algorithm)?;
+    let (bits, _) = expect_element(rest, TAG_BIT_STRING, "the subjectPublicKey")?;
+    let EcParameters { curve, named, .. } = ec_parameters(parameters)?;
+    let point = curve.from_sec1(point_bits(bits)?)?;
+    Ok(EcPublicKey { curve, point, named })
+}
+
+/// Checks that an AlgorithmIdentifier is id-ecPublicKey and returns its parameters.
+fn ec_algorithm(algorithm: &[u8]) -> Result<&[u8], TransformError> {
+    let (oid, parameters) = expect_element(algorithm, TAG_OID, "the algorithm OID")?;
+    let oid = decode_oid(oid)?;
+    if oid != OID_EC_PUBLIC_KEY {
+        return Err(TransformError::InvalidParameters(format!(
+            "the key algorithm {} is not id-ecPublicKey ({})",
+            oid, OID_EC_PUBLIC_KEY
+        )));
+    }
+    Ok(parameters)
+}
+
+/// ECPrivateKey (RFC 5915): the public key is taken from its optional [1] field, or computed as d·G when
+/// the file leaves it out; `outer` holds the ECParameters of an enclosing PKCS#8 structure.
+fn ec_private_key(contents: &[u8], outer: Option<&[u8]>) -> Result<EcPublicKey, TransformError> {
+    let (_, rest) = expect_element(contents, TAG_INTEGER, "the ECPrivateKey version")?;
+    let (scalar, mut rest) = expect_element(rest, TAG_OCTET_STRING, "the private key")?;
+    let (mut parameters, mut public_key) = (outer, None);
+    while !rest.is_empty() {
+        let (tag, field, next) = read_element(rest)?;
+        match tag {
+            TAG_PARAMETERS => parameters = Some(field),
+            TAG_PUBLIC_KEY => public_key = Some(expect_element(field, TAG_BIT_STRING, "the public key")?.0),
+            _ => {}
+        }
+        rest = next;
+    }
+    let parameters = parameters.ok_or_else(|| invalid("the private key does not name its curve"))?;
+    let EcParameters { curve, base, order, named } = ec_parameters(parameters)?;
+    let point = match public_key {
+        Some(bits) => curve.from_sec1(point_bits(bits)?)?,
+        // j = 0 curves of prime order, such as secp256k1, take the GLV split of the scalar
+        None => match GlvEndomorphism::detect(&curve, &base, &order) {
+            Some(glv) => glv.mul(&curve, &base, &unsigned(scalar)),
+            None => scalar_mul(&curve, &base, &unsigned(scalar)),
+        },
+    };
+    Ok(EcPublicKey { curve, point, named })
+}
+
+/// PrivateKeyInfo (PKCS#8, RFC 5208): a version, the algorithm with its ECParameters, then an
+/// ECPrivateKey in an OCTET STRING.
+fn private_key_info(contents: &[u8]) -> Result<EcPublicKey, TransformError> {
+    let (_, rest) = expect_element(contents, TAG_INTEGER, "the PrivateKeyInfo version")?;
+    let (algorithm, rest) = expect_element(rest, TAG_SEQUENCE, "the AlgorithmIdentifier")?;
+    let parameters = ec_algorithm(algorithm)?;
+    let (key, _) = expect_element(rest, TAG_OCTET_STRING, "the private key")?;
+    let (key, _) = expect_element(key, TAG_SEQUENCE, "the ECPrivateKey")?;
+    ec_private_key(key, Some(parameters))
+}
+
+/// Reads the EC public key of a key file in PEM or DER form: a SubjectPublicKeyInfo (`PUBLIC KEY`), a
+/// PKCS#8 private key (`PRIVATE KEY`) or a SEC1 private key (`EC PRIVATE KEY`), as written by
+/// `openssl pkey`, `openssl genpkey` and `openssl ecparam -genkey`.
+///
+/// The curve may be named by OID, resolved with the registry, or given by explicit parameters; the
+/// point is checked to lie on it. Encrypted private keys are not supported.
+pub fn parse_public_key(bytes: &[u8]) -> Result<EcPublicKey, TransformError> {
+    let pem = std::str::from_utf8(bytes).ok().filter(|text| text.trim_start().starts_with("-----BEGIN "));
+    let (label, der) = match pem {
+        Some(text) => {
+            let (label, der) = decode_pem(text)?;
+            (Some(label), der)
+        }
+        None => (None, bytes.to_vec()),
+    };
+    let (contents, _) = expect_element(&der, TAG_SEQUENCE, "a key SEQUENCE")?;
+    let (first, _, _) = read_element(contents)?;
+    match (label.as_deref(), first) {
+        (Some("PUBLIC KEY") | None, TAG_SEQUENCE) => subject_public_key_info(contents),
+        (Some("PRIVATE KEY") | None, TAG_INTEGER) if is_pkcs8(contents) => private_key_info(contents),
+        (Some("EC PRIVATE KEY") | None, TAG_INTEGER) => ec_private_key(contents, None),
+        (Some(label), _) => Err(TransformError::InvalidParameters(format!("unsupported PEM block {:?}", label))),
+        (None, _) => Err(invalid("the DER is neither a public nor a private EC key")),
+    }
+}
+
+/// PKCS#8 follows its version with an AlgorithmIdentifier, SEC1 with an OCTET STRING.
+fn is_pkcs8(contents: &[u8]) -> bool {
+    let second = read_element(contents).and_then(|(_, _, rest)| read_element(rest));
+    matches!(second, Ok((TAG_SEQUENCE, _, _)))
+}
@@ -0,0 +1,161 @@
+//! Import of the JSON curve files of the std-curves database (<https://neuromancer.sk/std/>), so that
+//! published curve definitions such as secp256k1 can be named like registry curves.
+//!
+//! A std-curves file is a category, `{"name": ..., "curves": [...]}`, or a single curve; each curve has
+//! a `name`, optional `oid` and `aliases` (`category/name`), a `field` with `type` and `p`, a `form`
+//! (`Weierstrass`, `Montgomery`, `Edwards` or `TwistedEdwards`) with its `params`, each `{"raw": ...}`,
+//! a `generator` with `x` and `y`, an `order` and a `cofactor`, all as hexadecimal or decimal strings.
+//! Only curves over prime fields are taken. LMFDB lists curves over number fields, with no base field
+//! prime, so it has no format to import here.
+
+use crate::curve_db::{CatalogCurve, CurveDatabase};
+use crate::error::TransformError;
+use crate::numtheory::mod_inverse;
+use crate::registry::{lookup, CurveModel};
+use crate::spec::{parse_number, Number};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::One;
+use serde_json::Value;
+
+/// The curves taken from a std-curves file, and those left out with the reason.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Import {
+    pub database: CurveDatabase,
+    /// (name, reason) of each curve left out: a binary or extension field, an unknown form, a curve
+    /// already in the registry or imported under another name, or an invalid definition.
+    pub skipped: Vec<(String, String)>,
+}
+
+fn invalid(message: String) -> TransformError {
+    TransformError::InvalidParameters(message)
+}
+
+/// The curve objects of a document: a category's `curves`, a single curve, or an array of either.
+fn curve_objects(document: &Value) -> Vec<&Value> {
+    match document {
+        Value::Array(items) => items.iter().flat_map(curve_objects).collect(),
+        Value::Object(object) => match object.get("curves") {
+            Some(Value::Array(curves)) => curves.iter().collect(),
+            _ => vec![document],
+        },
+        _ => Vec::new(),
+    }
+}
+
+/// Whether `json` looks like a std-curves file: its curves have a `field` object.
+pub fn is_std_curves(json: &str) -> bool {
+    serde_json::from_str::<Value>(json).is_ok_and(|document| {
+        let curves = curve_objects(&document);
+        !curves.is_empty() && curves.iter().all(|curve| curve.get("field").is_some_and(Value::is_object))
+    })
+}
+
+/// A number given as a string, an integer or `{"raw": ...}`.
+fn number(curve: &str, key: &str, value: Option<&Value>) -> Result<BigInt, TransformError> {
+    let name = format!("{}.{}", curve, key);
+    match value {
+        Some(Value::Object(object)) => number(curve, key, object.get("raw")),
+        Some(Value::String(text)) => parse_number(&name, Number::Text(text.clone())),
+        Some(Value::Number(value)) => value
+            .as_i64()
+            .map(BigInt::from)
+            .ok_or_else(|| invalid(format!("{} is not an integer: {}", name, value))),
+        _ => Err(invalid(format!("curve {} has no {}", curve, key))),
+    }
+}
+
+/// Converts one curve object, or gives the reason it cannot be taken.
+fn convert(value: &Value, name: &str) -> Result<Result<CatalogCurve, String>, TransformError> {
+    let field = value.get("field");
+    match field.and_then(|field| field.get("type")).and_then(Value::as_str) {
+        Some("Prime") => {}
+        Some(other) => return Ok(Err(format!("{} fields are not supported", other.to_ascii_lowercase()))),
+        None => return Err(invalid(format!("curve {} has no field type", name))),
+    }
+    let p = number(name, "p", field.and_then(|field| field.get("p")))?;
+    let params = value.get("params");
+    let param = |key: &str| number(name, key, params.and_then(|params| params.get(key)));
+    let generator = value.get("generator");
+    let coordinate = |key: &str| number(name, key, generator.and_then(|generator| generator.get(key)));
+    let (mut x, mut y) = (coordinate("x")?, coordinate("y")?);
+    let (model, coefficients) = match value.get("form").and_then(Value::as_str) {
+        Some("Weierstrass") => (CurveModel::Weierstrass, (param("a")?, param("b")?)),
+        Some("Montgomery") => (CurveModel::Montgomery, (param("a")?, param("b")?)),
+        Some("TwistedEdwards") => (CurveModel::TwistedEdwards, (param("a")?, param("d")?)),
+        Some("Edwards") => {
+            // x^2 + y^2 = c^2·(1 + d·x^2·y^2) is the Edwards curve with d·c^4 under (x, y) -> (x/c, y/c)
+            let c = param("c")?;
+            let Some(c_inv) = mod_inverse(&c, &p) else {
+                return Ok(Err("c is zero".to_string()));
+            };
+            (x, y) = ((&x * &c_inv).mod_floor(&p), (&y * &c_inv).mod_floor(&p));
+            (CurveModel::TwistedEdwards, (BigInt::one(), (param("d")? * c.pow(4)).mod_floor(&p)))
+        }
+        Some(other) => return Ok(Err(format!("the {} form is not supported", other))),
+        None => return Err(invalid(format!("curve {} has no form", name))),
+    };
+    let cofactor = match value.get("cofactor") {
+        Some(cofactor) => u32::try_from(number(name, "cofactor", Some(cofactor))?).ok(),
+        None => None,
+    };
+    let aliases = value.get("aliases").and_then(Value::as_array).into_iter().flatten();
+    let aliases = aliases
+        .filter_map(Value::as_str)
+        .map(|alias| alias.rsplit('/').next().unwrap_or(alias).to_string())
+        .filter(|alias| !alias.eq_ignore_ascii_case(name))
+        .collect();
+    let order = match value.get("order") {
+        Some(order) => Some(number(name, "order", Some(order))?),
+        None => None,
+    };
+    Ok(Ok(CatalogCurve {
+        name: name.to_string(),
+        aliases,
+        oid: value.get("oid").and_then(Value::as_str).map(str::to_string),
+        model,
+        p,
+        coefficients,
+        base_point: (x, y),
+        order,
+        cofactor,
+    }))
+}
+
+/// Imports the curves of a std-curves JSON document.
+///
+/// Curves that cannot be taken are listed in `skipped` rather than failing the import; a registry
+/// curve is left out under its own name (std-curves calls P-256 both P-256 and secp256r1), and aliases
+/// taken by the registry or by an earlier curve are dropped. Malformed JSON or a curve without its
+/// field, form, parameters or generator is an error.
+pub fn import(json: &str) -> Result<Import, TransformError> {
+    let document: Value =
+        serde_json::from_str(json).map_err(|error| invalid(format!("malformed std-curves file: {}", error)))?;
+    let mut import = Import::default();
+    for value in curve_objects(&document) {
+        let name = value
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid("a std-curves curve has no name".to_string()))?;
+        let mut curve = match convert(value, name)? {
+            Ok(curve) => curve,
+            Err(reason) => {
+                import.skipped.push((name.to_string(), reason));
+                continue;
+            }
+        };
+        let database = &import.database;
+        let taken = |candidate: &str| lookup(candidate).is_some() || database.lookup(candidate).is_some();
+        if taken(&curve.name) || curve.oid.as_deref().is_some_and(taken) {
+            import.skipped.push((curve.name, "already known under this name or OID".to_string()));
+            continue;
+        }
+        curve.aliases.retain(|alias| !taken(alias));
+        if let Err(error) = curve.check() {
+            import.skipped.push((curve.name, error.to_string()));
+            continue;
+        }
+        import.database.insert(curve)?;
+    }
+    Ok(import)
+}
@@ -0,0 +1,534 @@
+use crate::admissibility::Obstruction;
+use crate::cubic::{cubic_roots, cubic_roots_in};
+use crate::error::TransformError;
+use crate::field::Field;
+use crate::fp2::{Fp2, Fp2Polynomial};
+#[cfg(feature = "parallel")]
+use crate::limits::SearchBudget;
+use crate::limits::SearchLimits;
+use crate::log::{debug, info};
+use crate::map::MontgomeryMap;
+use crate::numtheory::{is_probable_prime, MILLER_RABIN_ROUNDS};
+use crate::ops;
+#[cfg(feature = "parallel")]
+use crate::rng::DefaultRng;
+use num_bigint::BigInt;
+#[cfg(feature = "parallel")]
+use num_bigint::RandBigInt;
+use num_integer::Integer;
+use num_traits::Zero;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "parallel")]
+use rand::SeedableRng;
+#[cfg(feature = "parallel")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "parallel")]
+use std::thread;
+
+/// Number of root-search attempts between progress reports.
+#[cfg(feature = "parallel")]
+const PROGRESS_INTERVAL: u64 = 10_000;
+
+/// Settings that control how a transformation treats its inputs and searches.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TransformOptions {
+    /// Bounds on the randomized searches.
+    pub limits: SearchLimits,
+    /// Reject non-canonical inputs rather than reducing them modulo p.
+    pub strict: bool,
+}
+
+/// Brings `value` into the canonical range [0, p), or rejects it in strict mode.
+fn canonicalize(name: &str, value: &BigInt, p: &BigInt, strict: bool) -> Result<BigInt, TransformError> {
+    let reduced = value.mod_floor(p);
+    if strict && &reduced != value {
+        return Err(TransformError::InvalidParameters(format!(
+            "{} = {} is not in the range [0, p)",
+            name, value
+        )));
+    }
+    Ok(reduced)
+}
+
+/// Finds a root z0 of z^3 + az + b in F_p, the smallest of `cubic_roots`, with O(log p) field
+/// operations whatever the size of p; `limits` bound the random splitting steps.
+///
+/// A cubic without roots in F_p is `TransformError::NoMontgomeryForm`.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+pub fn find_cubic_root<R: RngCore + CryptoRng + ?Sized>(
+    a: &BigInt,
+    b: &BigInt,
+    p: &BigInt,
+    limits: &SearchLimits,
+    rng: &mut R,
+) -> Result<BigInt, TransformError> {
+    let Some(root) = cubic_roots(a, b, p, limits, rng)?.into_iter().next() else {
+        return Err(TransformError::NoMontgomeryForm(Obstruction::IrreducibleCubic.to_string()));
+    };
+    info!(z0 = %root, "found root of the cubic");
+    Ok(root)
+}
+
+/// Searches for a root by random sampling, giving up once `limits` are exhausted or, returning `None`,
+/// once `stop` is set by another thread.
+#[cfg(feature = "parallel")]
+fn search_cubic_root<R: RngCore + CryptoRng + ?Sized>(
+    a: &BigInt,
+    b: &BigInt,
+    field: &Field,
+    limits: &SearchLimits,
+    rng: &mut R,
+    stop: &AtomicBool,
+) -> Result<Option<BigInt>, TransformError> {
+    let p = field.modulus();
+    let mut budget = SearchBudget::new(limits);
+    while !stop.load(Ordering::Relaxed) {
+        budget.tick("searching for a root of z^3 + az + b")?;
+        let candidate = rng.gen_bigint_range(&BigInt::zero(), p);
+        // Evaluated as (z^2 + a) * z + b so that every reduction stays below p^2
+        let square_plus_a = field.reduce(&(&candidate * &candidate + a));
+        if field.reduce(&(square_plus_a * &candidate + b)).is_zero() {
+            info!(attempts = budget.attempts, z0 = %candidate, "found root of the cubic");
+            return Ok(Some(candidate));
+        }
+        if budget.attempts.is_multiple_of(PROGRESS_INTERVAL) {
+            debug!(attempts = budget.attempts, "still searching for a root of the cubic");
+        }
+    }
+    Ok(None)
+}
+
+/// A random search for a root z0 of z^3 + az + b in F_p spread over `threads` threads (all available
+/// cores when 0), each sampling from its own generator seeded from `rng`; the first root found stops the
+/// others.
+///
+/// `limits.max_attempts` bounds the attempts of all threads together. This is a fallback for fields
+/// where `cubic_roots` does not apply; for F_p it only helps when roots are a noticeable fraction of p.
+#[cfg(feature = "parallel")]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+pub fn find_cubic_root_parallel<R: RngCore + CryptoRng + ?Sized>(
+    a: &BigInt,
+    b: &BigInt,
+    p: &BigInt,
+    limits: &SearchLimits,
+    threads: usize,
+    rng: &mut R,
+) -> Result<BigInt, TransformError> {
+    let threads = match threads {
+        0 => thread::available_parallelism().map_or(1, |count| count.get()),
+        threads => threads,
+    };
+    let per_thread = SearchLimits {
+        max_attempts: limits.max_attempts.div_ceil(threads as u64),
+        ..*limits
+    };
+    let field = Field::new(p);
+    let stop = AtomicBool::new(false);
+    let mut generators: Vec<DefaultRng> = Vec::with_capacity(threads);
+    for _ in 0..threads {
+        generators.push(DefaultRng::from_rng(&mut *rng).expect("seeding from a CryptoRng does not fail"));
+    }
+    debug!(threads, "searching for a root of the cubic in parallel");
+    let results: Vec<Result<Option<BigInt>, TransformError>> = thread::scope(|scope| {
+        let workers: Vec<_> = generators
+            .into_iter()
+            .map(|mut generator| {
+                let (field, stop) = (&field, &stop);
+                scope.spawn(move || {
+                    let result = search_cubic_root(a, b, field, &per_thread, &mut generator, stop);
+                    if let Ok(Some(_)) = result {
+                        stop.store(true, Ordering::Relaxed);
+                    }
+                    result
+                })
+            })
+            .collect();
+        workers.into_iter().map(|worker| worker.join().expect("search threads do not panic")).collect()
+    });
+    let mut failure = None;
+    for result in results {
+        match result {
+            Ok(Some(root)) => return Ok(root),
+            Ok(None) => {}
+            Err(TransformError::SearchLimitExceeded(message)) => {
+                let message = format!("{} on each of {} threads", message, threads);
+                failure = Some(TransformError::SearchLimitExceeded(message));
+            }
+            Err(error) => failure = Some(error),
+        }
+    }
+    Err(failure.expect("a search without a root ran out of its limits"))
+}
+
+/// Rejects moduli that are not primes greater than 3.
+fn check_modulus(p: &BigInt) -> Result<(), TransformError> {
+    if p == &BigInt::from(2) || p == &BigInt::from(3) {
+        return Err(TransformError::UnsupportedCharacteristic(p.clone()));
+    }
+    if !is_probable_prime(p, MILLER_RABIN_ROUNDS) {
+        return Err(TransformError::InvalidParameters(format!("modulus {} is not a prime", p)));
+    }
+    Ok(())
+}
+
+/// Rejects singular curves, whose discriminant 4a^3 + 27b^2 vanishes.
+fn check_nonsingular(a: &BigInt, b: &BigInt, field: &Field) -> Result<(), TransformError> {
+    ops::record(1, 2);
+    let a_cubed = field.reduce(&(field.reduce(&(a * a)) * a));
+    if field.reduce(&(BigInt::from(4) * a_cubed + BigInt::from(27) * b * b)).is_zero() {
+        return Err(TransformError::InvalidParameters(
+            "the curve is singular (4a^3 + 27b^2 = 0)".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Finds every root z0 for canonical, non-singular coefficients and assembles the map from the first one
+/// for which 3·z0^2 + a is a square.
+fn compute_map<R: RngCore + CryptoRng + ?Sized>(
+    a: &BigInt,
+    b: &BigInt,
+    field: &Field,
+    limits: &SearchLimits,
+    rng: &mut R,
+) -> Result<MontgomeryMap, TransformError> {
+    //Find the roots z0 of the polynomial z^3 + az + b in the field F_p
+    let roots = cubic_roots_in(a, b, field, limits, rng)?;
+    if roots.is_empty() {
+        return Err(TransformError::NoMontgomeryForm(Obstruction::IrreducibleCubic.to_string()));
+    }
+    // Any root whose 3*z0^2 + a is a square will do, so a non-residue only rules out that root
+    for z0 in &roots {
+        ops::record(0, 1);
+        let s_squared = field.reduce(&(BigInt::from(3) * z0 * z0 + a));
+        if field.legendre(&s_squared) == 1 {
+            return map_from_root(a, b, z0.clone(), field, limits);
+        }
+        debug!(%z0, %s_squared, "3*z0^2 + a is not a square, trying the next root");
+    }
+    let obstruction = Obstruction::for_root_count(roots.len());
+    let roots: Vec<String> = roots.iter().map(BigInt::to_string).collect();
+    Err(TransformError::NoMontgomeryForm(format!("{} (z0 = {})", obstruction, roots.join(", "))))
+}
+
+/// Computes s and s_inv for a root z0 of the cubic and assembles the map.
+fn map_from_root(
+    a: &BigInt,
+    b: &BigInt,
+    z0: BigInt,
+    field: &Field,
+    limits: &SearchLimits,
+) -> Result<MontgomeryMap, TransformError> {
+    // Compute s = (sqrt(3 * z0^2 + a))^{-1} modulo p
+    ops::record(0, 1);
+    let s_squared = field.reduce(&(BigInt::from(3) * &z0 * &z0 + a));
+    debug!(%s_squared);
+
+    let s = field.sqrt(&s_squared, limits)?.ok_or_else(|| {
+        TransformError::NoMontgomeryForm(format!("3*z0^2 + a = {} is not a square modulo p", s_squared))
+    })?;
+    debug!(%s);
+    map_from_scale(a, b, z0, s, field)
+}
+
+/// Assembles the map for a root z0 of the cubic and a square root s of 3·z0^2 + a.
+fn map_from_scale(
+    a: &BigInt,
+    b: &BigInt,
+    z0: BigInt,
+    s: BigInt,
+    field: &Field,
+) -> Result<MontgomeryMap, TransformError> {
+    let p = field.modulus();
+
+    let s_inv = field
+        .inverse(&s)
+        .ok_or_else(|| TransformError::NoMontgomeryForm(format!("s = {} is not invertible modulo p", s)))?;
+    debug!(%s_inv);
+
+    // Compute the new parameters a and b
+    ops::record(1, 0);
+    let a_montgomery = field.reduce(&(BigInt::from(3) * &z0 * &s_inv));
+    let b_montgomery = field.reduce(&s_inv);
+    Ok(MontgomeryMap {
+        p: p.clone(),
+        a: a.clone(),
+        b: b.clone(),
+        z0,
+        s,
+        s_inv,
+        a_montgomery,
+        b_montgomery,
+    })
+}
+
+/// Validates the curve and computes its `MontgomeryMap`; backs `MontgomeryMap::new_with_rng`.
+pub(crate) fn derive_map<R: RngCore + CryptoRng + ?Sized>(
+    a: &BigInt,
+    b: &BigInt,
+    p: &BigInt,
+    options: &TransformOptions,
+    rng: &mut R,
+) -> Result<MontgomeryMap, TransformError> {
+    check_modulus(p)?;
+    let field = Field::new(p);
+    let a = &canonicalize("a", a, p, options.strict)?;
+    let b = &canonicalize("b", b, p, options.strict)?;
+    check_nonsingular(a, b, &field)?;
+    compute_map(a, b, &field, &options.limits, rng)
+}
+
+/// Validates the curve and the given root z0 of z^3 + az + b, then computes the map from it without
+/// searching; backs `MontgomeryMap::from_root`, and `MontgomeryMap::from_root_and_scale` when `s` is
+/// given too.
+pub(crate) fn derive_map_from_root(
+    a: &BigInt,
+    b: &BigInt,
+    p: &BigInt,
+    z0: &BigInt,
+    s: Option<&BigInt>,
+    options: &TransformOptions,
+) -> Result<MontgomeryMap, TransformError> {
+    check_modulus(p)?;
+    let field = Field::new(p);
+    let a = &canonicalize("a", a, p, options.strict)?;
+    let b = &canonicalize("b", b, p, options.strict)?;
+    let z0 = canonicalize("z0", z0, p, options.strict)?;
+    check_nonsingular(a, b, &field)?;
+    ops::record(1, 1);
+    let square_plus_a = field.reduce(&(&z0 * &z0 + a));
+    if !field.reduce(&(square_plus_a * &z0 + b)).is_zero() {
+        return Err(TransformError::InvalidParameters(format!(
+            "z0 = {} is not a root of z^3 + az + b",
+            z0
+        )));
+    }
+    let Some(s) = s else {
+        return map_from_root(a, b, z0, &field, &options.limits);
+    };
+    let s = canonicalize("s", s, p, options.strict)?;
+    ops::record(0, 2);
+    let s_squared = field.reduce(&(BigInt::from(3) * &z0 * &z0 + a));
+    if field.reduce(&(&s * &s)) != s_squared {
+        return Err(TransformError::InvalidParameters(format!(
+            "s = {} does not square to 3*z0^2 + a = {}",
+            s, s_squared
+        )));
+    }
+    map_from_scale(a, b, z0, s, &field)
+}
+
+/// Transformation function from Weierstrass to Montgomery curve.
+pub fn transform_to_montgomery(
+    x: &BigInt,
+    y: &BigInt,
+    a: &BigInt,
+    b: &BigInt,
+    p: &BigInt,
+    options: &TransformOptions,
+) -> Result<(BigInt, BigInt, BigInt, BigInt), TransformError> {
+    transform_to_montgomery_with_rng(x, y, a, b, p, options, &mut OsRng)
+}
+
+/// Transformation function from Weierstrass to Montgomery curve, drawing randomness from `rng`.
+pub fn transform_to_montgomery_with_rng<R: RngCore + CryptoRng + ?Sized>(
+    x: &BigInt,
+    y: &BigInt,
+    a: &BigInt,
+    b: &BigInt,
+    p: &BigInt,
+    options: &TransformOptions,
+    rng: &mut R,
+) -> Result<(BigInt, BigInt, BigInt, BigInt), TransformError> {
+    let (x_montgomery, y_montgomery, map) = transform_with_map(x, y, a, b, p, options, rng)?;
+    Ok((x_montgomery, y_montgomery, map.a_montgomery, map.b_montgomery))
+}
+
+/// Transforms (x, y) like `transform_to_montgomery_with_rng`, but returns the whole `MontgomeryMap`
+/// alongside the mapped point, so that z0, s and s_inv can be inspected or reused.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+pub fn transform_with_map<R: RngCore + CryptoRng + ?Sized>(
+    x: &BigInt,
+    y: &BigInt,
+    a: &BigInt,
+    b: &BigInt,
+    p: &BigInt,
+    options: &TransformOptions,
+    rng: &mut R,
+) -> Result<(BigInt, BigInt, MontgomeryMap), TransformError> {
+    check_modulus(p)?;
+    let field = Field::new(p);
+    let a = &canonicalize("a", a, p, options.strict)?;
+    let b = &canonicalize("b", b, p, options.strict)?;
+    check_nonsingular(a, b, &field)?;
+    let (x, y) = &canonical_point(x, y, a, b, &field, options.strict)?;
+
+    let map = compute_map(a, b, &field, &options.limits, rng)?;
+    // Map (x, y) to (x_montgomery, y_montgomery) on the Montgomery curve
+    let (x_montgomery, y_montgomery) = map.map_point(x, y);
+    Ok((x_montgomery, y_montgomery, map))
+}
+
+/// Transforms (x, y) like `transform_with_map`, but with the map of a known root z0 of z^3 + az + b
+/// instead of searching for one, as `MontgomeryMap::from_root` does. z0 is checked to be a root, and
+/// one whose 3·z0^2 + a is a non-residue fails with `NoMontgomeryForm` rather than trying another.
+pub fn transform_with_root(
+    x: &BigInt,
+    y: &BigInt,
+    a: &BigInt,
+    b: &BigInt,
+    p: &BigInt,
+    z0: &BigInt,
+    options: &TransformOptions,
+) -> Result<(BigInt, BigInt, MontgomeryMap), TransformError> {
+    let map = derive_map_from_root(a, b, p, z0, None, options)?;
+    map_known_point(x, y, map, options)
+}
+
+/// Transforms (x, y) like `transform_with_root`, with the scale s of the map given as well, for a
+/// standard that fixes it: s must square to 3·z0^2 + a, and the result is (s⁻¹·(x − z0), s⁻¹·y) on the
+/// curve with A = 3·z0·s⁻¹ and B = s⁻¹, where the derived map could have taken −s instead.
+#[allow(clippy::too_many_arguments)]
+pub fn transform_with_scale(
+    x: &BigInt,
+    y: &BigInt,
+    a: &BigInt,
+    b: &BigInt,
+    p: &BigInt,
+    z0: &BigInt,
+    s: &BigInt,
+    options: &TransformOptions,
+) -> Result<(BigInt, BigInt, MontgomeryMap), TransformError> {
+    let map = derive_map_from_root(a, b, p, z0, Some(s), options)?;
+    map_known_point(x, y, map, options)
+}
+
+/// Checks (x, y) against the curve of `map` and maps it.
+fn map_known_point(
+    x: &BigInt,
+    y: &BigInt,
+    map: MontgomeryMap,
+    options: &TransformOptions,
+) -> Result<(BigInt, BigInt, MontgomeryMap), TransformError> {
+    let (x, y) = &canonical_point(x, y, &map.a, &map.b, &Field::new(&map.p), options.strict)?;
+    let (x_montgomery, y_montgomery) = map.map_point(x, y);
+    Ok((x_montgomery, y_montgomery, map))
+}
+
+/// Reduces (x, y) like `canonicalize` and checks that it lies on y^2 = x^3 + ax + b.
+fn canonical_point(
+    x: &BigInt,
+    y: &BigInt,
+    a: &BigInt,
+    b: &BigInt,
+    field: &Field,
+    strict: bool,
+) -> Result<(BigInt, BigInt), TransformError> {
+    let x = canonicalize("x", x, field.modulus(), strict)?;
+    let y = canonicalize("y", y, field.modulus(), strict)?;
+    let x_cubed = field.reduce(&(field.reduce(&(&x * &x)) * &x));
+    if !field.reduce(&(&y * &y - x_cubed - a * &x - b)).is_zero() {
+        return Err(TransformError::PointNotOnCurve);
+    }
+    Ok((x, y))
+}
+
+/// Finds a root z0 of z^3 + az + b in F_p² like `find_cubic_root` does in F_p, with gcd(z^(p²) − z, f)
+/// and Cantor-Zassenhaus splitting; `limits` bound the random splitting steps.
+///
+/// A cubic without roots in F_p² is `TransformError::NoMontgomeryForm`.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+pub fn find_cubic_root_fp2<R: RngCore + CryptoRng + ?Sized>(
+    a: &Fp2,
+    b: &Fp2,
+    limits: &SearchLimits,
+    rng: &mut R,
+) -> Result<Fp2, TransformError> {
+    let cubic = Fp2Polynomial::new(vec![b.clone(), a.clone(), a.zero(), a.one()], a);
+    let Some(root) = cubic.roots(limits, rng)?.into_iter().next() else {
+        return Err(TransformError::NoMontgomeryForm("z^3 + az + b has no root in F_p^2".to_string()));
+    };
+    info!(z0 = %root, "found root of the cubic");
+    Ok(root)
+}
+
+/// Transformation from a Weierstrass to a Montgomery curve whose coefficients and coordinates lie in F_p².
+///
+/// Uses the same formulas as `transform_to_montgomery`; all four inputs must belong to the same extension.
+pub fn transform_to_montgomery_fp2(
+    x: &Fp2,
+    y: &Fp2,
+    a: &Fp2,
+    b: &Fp2,
+    options: &TransformOptions,
+) -> Result<(Fp2, Fp2, Fp2, Fp2), TransformError> {
+    transform_to_montgomery_fp2_with_rng(x, y, a, b, options, &mut OsRng)
+}
+
+/// `transform_to_montgomery_fp2`, drawing randomness from `rng`.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+pub fn transform_to_montgomery_fp2_with_rng<R: RngCore + CryptoRng + ?Sized>(
+    x: &Fp2,
+    y: &Fp2,
+    a: &Fp2,
+    b: &Fp2,
+    options: &TransformOptions,
+    rng: &mut R,
+) -> Result<(Fp2, Fp2, Fp2, Fp2), TransformError> {
+    if ![x, y, b].iter().all(|element| element.same_field(a)) {
+        return Err(TransformError::InvalidParameters(
+            "coefficients and coordinates belong to different extensions of F_p".to_string(),
+        ));
+    }
+    if a.p == BigInt::from(3) {
+        return Err(TransformError::UnsupportedCharacteristic(a.p.clone()));
+    }
+    let three = a.from_base(&BigInt::from(3));
+    let four_a_cubed = &a.from_base(&BigInt::from(4)) * &(&a.square() * a);
+    let twenty_seven_b_squared = &a.from_base(&BigInt::from(27)) * &b.square();
+    // The curve is singular when the discriminant 4a^3 + 27b^2 vanishes
+    if (&four_a_cubed + &twenty_seven_b_squared).is_zero() {
+        return Err(TransformError::InvalidParameters(
+            "the curve is singular (4a^3 + 27b^2 = 0)".to_string(),
+        ));
+    }
+    if !(&(&y.square() - &(&x.square() * x)) - &(&(a * x) + b)).is_zero() {
+        return Err(TransformError::PointNotOnCurve);
+    }
+
+    //Find a root z0 of the polynomial z^3 + az + b in the field F_p^2
+    let root = find_cubic_root_fp2(a, b, &options.limits, rng)?;
+
+    // The other roots solve z^2 + z0·z + (z0^2 + a) = 0, whose discriminant is −3·z0^2 − 4a
+    let mut roots = vec![root.clone()];
+    if let Some(sqrt) = (-&(&(&three * &root.square()) + &(&a.from_base(&BigInt::from(4)) * a))).sqrt() {
+        let two_inv = a.from_base(&BigInt::from(2)).inverse().expect("p is odd");
+        roots.push(&(&sqrt - &root) * &two_inv);
+        roots.push(&(&-&sqrt - &root) * &two_inv);
+    }
+
+    // Compute s = (sqrt(3 * z0^2 + a))^{-1} in F_p^2, trying each root until 3 * z0^2 + a is a square
+    let (z0, s) = roots
+        .into_iter()
+        .find_map(|z0| {
+            let s_squared = &(&three * &z0.square()) + a;
+            debug!(%z0, %s_squared);
+            s_squared.sqrt().map(|s| (z0, s))
+        })
+        .ok_or_else(|| {
+            let message = "3*z0^2 + a is not a square in F_p^2 for any root z0";
+            TransformError::NoMontgomeryForm(message.to_string())
+        })?;
+    debug!(%s);
+
+    let s_inv = s
+        .inverse()
+        .ok_or_else(|| TransformError::NoMontgomeryForm(format!("s = {} is not invertible in F_p^2", s)))?;
+    debug!(%s_inv);
+
+    let a_montgomery = &(&three * &z0) * &s_inv;
+    let x_montgomery = &s_inv * &(x - &z0);
+    let y_montgomery = &s_inv * y;
+    Ok((x_montgomery, y_montgomery, a_montgomery, s_inv))
+}
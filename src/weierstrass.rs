@@ -0,0 +1,227 @@
+use crate::curve::{CurveGroup, Point};
+use crate::encoding::{decode_point, encode_point};
+use crate::error::TransformError;
+use crate::limits::SearchLimits;
+use crate::numtheory::{mod_inverse, mod_sqrt};
+use crate::ops;
+use crate::order::{count_points, trace_of_frobenius};
+use crate::scalar_mul;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::Zero;
+
+/// The short Weierstrass curve y^2 = x^3 + ax + b over F_p.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WeierstrassCurve {
+    pub a: BigInt,
+    pub b: BigInt,
+    pub p: BigInt,
+}
+
+/// An isomorphism (x, y) -> (u^2·x, u^3·y) onto the curve with (u^4·a, u^6·b), from `scale` or
+/// `normalize_a_minus_3`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scaling {
+    /// The image curve.
+    pub curve: WeierstrassCurve,
+    pub u: BigInt,
+}
+
+impl Scaling {
+    /// Maps a point of the original curve to the image curve.
+    pub fn map_point(&self, point: &Point) -> Point {
+        let p = &self.curve.p;
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(x, y) => {
+                let u_squared = (&self.u * &self.u).mod_floor(p);
+                let x = (&u_squared * x).mod_floor(p);
+                let y = (u_squared * &self.u * y).mod_floor(p);
+                Point::Affine(x, y)
+            }
+        }
+    }
+}
+
+impl WeierstrassCurve {
+    /// Creates the curve, reducing the coefficients and rejecting singular curves.
+    pub fn new(a: &BigInt, b: &BigInt, p: &BigInt) -> Result<WeierstrassCurve, TransformError> {
+        if p <= &BigInt::from(3) {
+            return Err(TransformError::UnsupportedCharacteristic(p.clone()));
+        }
+        let curve = WeierstrassCurve {
+            a: a.mod_floor(p),
+            b: b.mod_floor(p),
+            p: p.clone(),
+        };
+        if curve.discriminant().is_zero() {
+            return Err(TransformError::InvalidParameters(
+                "the curve is singular (4a^3 + 27b^2 = 0)".to_string(),
+            ));
+        }
+        Ok(curve)
+    }
+
+    /// Returns 4a^3 + 27b^2 modulo p, which vanishes exactly for singular curves.
+    pub fn discriminant(&self) -> BigInt {
+        (BigInt::from(4) * self.a.pow(3) + BigInt::from(27) * &self.b * &self.b).mod_floor(&self.p)
+    }
+
+    /// Rewrites the curve with (u^4·a, u^6·b), an isomorphism that maps (x, y) to (u^2·x, u^3·y).
+    pub fn scale(&self, u: &BigInt) -> Result<Scaling, TransformError> {
+        let p = &self.p;
+        let u = u.mod_floor(p);
+        if u.is_zero() {
+            let message = "the scale u must be non-zero modulo p".to_string();
+            return Err(TransformError::InvalidParameters(message));
+        }
+        let u_squared = (&u * &u).mod_floor(p);
+        let u_fourth = (&u_squared * &u_squared).mod_floor(p);
+        let curve = WeierstrassCurve {
+            a: (&u_fourth * &self.a).mod_floor(p),
+            b: (u_fourth * u_squared * &self.b).mod_floor(p),
+            p: p.clone(),
+        };
+        Ok(Scaling { curve, u })
+    }
+
+    /// Finds u with a·u^4 = −3 and rewrites the curve into the a = −3 form favoured by hardware and
+    /// by fast doubling formulas; `None` when −3/a is not a fourth power (or a = 0).
+    pub fn normalize_a_minus_3(&self) -> Result<Option<Scaling>, TransformError> {
+        let p = &self.p;
+        let limits = SearchLimits::default();
+        let a_inv = match mod_inverse(&self.a, p) {
+            Some(a_inv) => a_inv,
+            None => return Ok(None),
+        };
+        let target = (BigInt::from(-3) * a_inv).mod_floor(p);
+        // u^4 = target: take a square root w of the target, then a square root of w or of −w
+        let w = match mod_sqrt(&target, p, &limits)? {
+            Some(w) => w,
+            None => return Ok(None),
+        };
+        for w in [w.clone(), (-w).mod_floor(p)] {
+            if let Some(u) = mod_sqrt(&w, p, &limits)? {
+                return self.scale(&u).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the j-invariant 1728·4a^3 / (4a^3 + 27b^2), which classifies curves up to isomorphism
+    /// over the algebraic closure.
+    pub fn j_invariant(&self) -> BigInt {
+        let p = &self.p;
+        let four_a_cubed = BigInt::from(4) * self.a.pow(3);
+        let discriminant_inv = mod_inverse(&self.discriminant(), p).expect("the curve is non-singular");
+        (BigInt::from(1728) * four_a_cubed * discriminant_inv).mod_floor(p)
+    }
+
+    /// The trace of Frobenius p + 1 − #E, counting the points with `count_points` (so p ≤ 2^24).
+    pub fn trace(&self) -> Result<BigInt, TransformError> {
+        trace_of_frobenius(&count_points(&self.a, &self.b, &self.p)?, &self.p)
+    }
+
+    /// Evaluates the right-hand side x^3 + ax + b.
+    pub fn rhs(&self, x: &BigInt) -> BigInt {
+        (x.pow(3) + &self.a * x + &self.b).mod_floor(&self.p)
+    }
+
+    /// Creates the affine point (x, y), checking that it lies on the curve.
+    pub fn point(&self, x: &BigInt, y: &BigInt) -> Result<Point, TransformError> {
+        let point = Point::Affine(x.mod_floor(&self.p), y.mod_floor(&self.p));
+        if self.contains(&point) {
+            Ok(point)
+        } else {
+            Err(TransformError::PointNotOnCurve)
+        }
+    }
+
+    /// The points with abscissa x: none when x^3 + ax + b is a non-residue, one when it is zero and
+    /// two, (x, y) and (x, p − y) with the smaller y first, otherwise.
+    pub fn lift_x(&self, x: &BigInt) -> Result<Vec<Point>, TransformError> {
+        lift(x, &self.rhs(x), &self.p)
+    }
+
+    /// Multiplies a point of the curve by the `cofactor` h, forcing it into the subgroup of order #E / h;
+    /// see `scalar_mul::clear_cofactor`.
+    pub fn clear_cofactor(&self, point: &Point, cofactor: &BigInt) -> Result<Point, TransformError> {
+        scalar_mul::clear_cofactor(self, point, cofactor)
+    }
+
+    /// Encodes a point of this curve in SEC1 form: 0x00 for infinity, 0x02/0x03 || x when compressed,
+    /// 0x04 || x || y otherwise.
+    pub fn to_sec1(&self, point: &Point, compressed: bool) -> Vec<u8> {
+        encode_point(point, &self.p, compressed)
+    }
+
+    /// Decodes a SEC1 point, recovering y from x and its parity bit for compressed encodings.
+    pub fn from_sec1(&self, bytes: &[u8]) -> Result<Point, TransformError> {
+        let point = decode_point(bytes, &self.p, |x| self.rhs(x))?;
+        if self.contains(&point) {
+            Ok(point)
+        } else {
+            Err(TransformError::PointNotOnCurve)
+        }
+    }
+}
+
+/// The affine points (x, y) with y^2 = `square`, an element of F_p, in increasing order of y.
+pub(crate) fn lift(x: &BigInt, square: &BigInt, p: &BigInt) -> Result<Vec<Point>, TransformError> {
+    let x = x.mod_floor(p);
+    let Some(y) = mod_sqrt(square, p, &SearchLimits::default())? else {
+        return Ok(Vec::new());
+    };
+    if y.is_zero() {
+        return Ok(vec![Point::Affine(x, y)]);
+    }
+    let negated = p - &y;
+    let (low, high) = if y < negated { (y, negated) } else { (negated, y) };
+    Ok(vec![Point::Affine(x.clone(), low), Point::Affine(x, high)])
+}
+
+impl CurveGroup for WeierstrassCurve {
+    fn modulus(&self) -> &BigInt {
+        &self.p
+    }
+
+    fn contains(&self, point: &Point) -> bool {
+        match point {
+            Point::Infinity => true,
+            Point::Affine(x, y) => (y * y).mod_floor(&self.p) == self.rhs(x),
+        }
+    }
+
+    fn negate(&self, point: &Point) -> Point {
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(x, y) => Point::Affine(x.clone(), (-y).mod_floor(&self.p)),
+        }
+    }
+
+    fn add(&self, first: &Point, second: &Point) -> Point {
+        let p = &self.p;
+        let (x1, y1, x2, y2) = match (first, second) {
+            (Point::Infinity, _) => return second.clone(),
+            (_, Point::Infinity) => return first.clone(),
+            (Point::Affine(x1, y1), Point::Affine(x2, y2)) => (x1, y1, x2, y2),
+        };
+        let lambda = if x1 == x2 {
+            if (y1 + y2).mod_floor(p).is_zero() {
+                return Point::Infinity;
+            }
+            // Tangent slope (3x^2 + a) / 2y
+            ops::record(2, 2);
+            let denominator = mod_inverse(&(BigInt::from(2) * y1), p).expect("2y is invertible");
+            (BigInt::from(3) * x1 * x1 + &self.a) * denominator
+        } else {
+            ops::record(2, 1);
+            let denominator = mod_inverse(&(x2 - x1).mod_floor(p), p).expect("x2 - x1 is invertible");
+            (y2 - y1) * denominator
+        }
+        .mod_floor(p);
+        let x3 = (&lambda * &lambda - x1 - x2).mod_floor(p);
+        let y3 = (lambda * (x1 - &x3) - y1).mod_floor(p);
+        Point::Affine(x3, y3)
+    }
+}
@@ -0,0 +1,63 @@
+use num_bigint::{BigInt, RandBigInt};
+use rand::SeedableRng;
+use weierstrass_to_montgomery::chain::AdditionChain;
+use weierstrass_to_montgomery::field::modpow;
+use weierstrass_to_montgomery::solinas::NistPrime;
+use weierstrass_to_montgomery::{named_curves, DefaultRng, Field, InversionAlgorithm};
+
+/// The primes of the three reductions: 2^255 − 19 folds, P-256 and P-384 take the Solinas routines, and
+/// the Brainpool primes and small primes use Barrett reduction.
+fn primes() -> Vec<BigInt> {
+    let mut primes: Vec<BigInt> = named_curves().iter().map(|curve| curve.p()).collect();
+    primes.extend([17u32, 1009, 65_537].map(BigInt::from));
+    primes.sort();
+    primes.dedup();
+    primes
+}
+
+#[test]
+fn pow_agrees_with_modpow() {
+    let mut rng = DefaultRng::seed_from_u64(165);
+    let p = NistPrime::P256.modulus();
+    let field = Field::new(&p);
+    let one = BigInt::from(1);
+    // Single bits, runs of ones, alternating bits, trailing zeros and random exponents
+    let small = [1u32, 2, 3, 4, 5, 6, 7, 8, 255, 256, 0xaaaa, 0x5555];
+    let mut exponents: Vec<BigInt> = small.map(BigInt::from).to_vec();
+    exponents.extend([(&one << 300) - 1u32, &one << 300, ((&one << 200) - 1u32) << 17, &p - 2u32]);
+    exponents.extend((0..20).map(|bits| rng.gen_biguint(bits * 13 + 1).into()).filter(|e: &BigInt| e > &one));
+    for exponent in exponents {
+        let chain = AdditionChain::new(&exponent);
+        let random = rng.gen_bigint_range(&one, &p);
+        for base in [BigInt::from(0), BigInt::from(1), BigInt::from(2), &p - 1u32, random] {
+            assert_eq!(chain.pow(&field, &base), modpow(&base, &exponent, &p), "{}^{}", base, exponent);
+        }
+        // At most one squaring per bit for the runs of ones and one per bit for joining them
+        assert!(chain.squarings() < 2 * exponent.bits(), "{}", exponent);
+        assert!(chain.multiplications() as u64 <= exponent.bits(), "{}", exponent);
+    }
+}
+
+#[test]
+fn a_single_long_run_takes_one_squaring_per_bit() {
+    // 2^255 − 21 is 250 ones, then 01011: the table builds a^(2^250 − 1) and joining adds five squarings
+    let exponent = (BigInt::from(1) << 255) - 21u32;
+    let chain = AdditionChain::new(&exponent);
+    assert_eq!(chain.squarings(), 254);
+    assert!(chain.multiplications() < 16);
+}
+
+#[test]
+fn fermat_inversion_over_the_chain_inverts() {
+    let mut rng = DefaultRng::seed_from_u64(165);
+    for p in primes() {
+        let field = Field::new(&p);
+        let one = BigInt::from(1);
+        for value in [one.clone(), BigInt::from(2), &p - 1u32, &p + 3u32, rng.gen_bigint_range(&one, &p)] {
+            let inverse = field.inverse_with(&value, InversionAlgorithm::Fermat).expect("non-zero");
+            assert_eq!(Some(inverse.clone()), field.inverse_with(&value, InversionAlgorithm::ExtendedGcd));
+            assert_eq!(field.reduce(&(&value * &inverse)), one, "{}^-1 mod {}", value, p);
+        }
+        assert_eq!(field.inverse_with(&p, InversionAlgorithm::Fermat), None);
+    }
+}
@@ -0,0 +1,131 @@
+//! `CborValue::encode` against the examples of RFC 8949, Appendix A, that its items can express, and the
+//! deterministic encoding rules of section 4.2.1: shortest heads and map keys sorted by their bytes.
+
+use num_bigint::BigInt;
+use num_traits::One;
+use weierstrass_to_montgomery::cbor::CborValue;
+
+fn hex(digits: &str) -> Vec<u8> {
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).expect("hex digits"))
+        .collect()
+}
+
+fn check(value: CborValue, expected: &str) {
+    assert_eq!(value.encode(), hex(expected), "{:?}", value);
+}
+
+fn text(value: &str) -> CborValue {
+    CborValue::Text(value.to_string())
+}
+
+#[test]
+fn integers_match_appendix_a() {
+    for (value, expected) in [
+        (0, "00"),
+        (1, "01"),
+        (10, "0a"),
+        (23, "17"),
+        (24, "1818"),
+        (25, "1819"),
+        (100, "1864"),
+        (1000, "1903e8"),
+        (1_000_000, "1a000f4240"),
+        (1_000_000_000_000, "1b000000e8d4a51000"),
+        (-1, "20"),
+        (-10, "29"),
+        (-100, "3863"),
+        (-1000, "3903e7"),
+    ] {
+        check(CborValue::integer(value), expected);
+    }
+    check(CborValue::Unsigned(u64::MAX), "1bffffffffffffffff");
+    // −18446744073709551616 = −1 − (2^64 − 1)
+    check(CborValue::Negative(u64::MAX), "3bffffffffffffffff");
+    check(CborValue::integer(i64::MIN), "3b7fffffffffffffff");
+}
+
+#[test]
+fn heads_take_the_shortest_argument() {
+    // Either side of each boundary between the 0-, 1-, 2-, 4- and 8-byte arguments
+    for (argument, expected) in [
+        (23, "17"),
+        (24, "1818"),
+        (255, "18ff"),
+        (256, "190100"),
+        (65535, "19ffff"),
+        (65536, "1a00010000"),
+        (u32::MAX as u64, "1affffffff"),
+        (1 << 32, "1b0000000100000000"),
+    ] {
+        check(CborValue::Unsigned(argument), expected);
+        // The head is the same for every major type; a tag has type 6
+        let tagged = CborValue::Tag(argument, Box::new(CborValue::Unsigned(0)));
+        let mut head = hex(expected);
+        head[0] |= 6 << 5;
+        head.push(0);
+        assert_eq!(tagged.encode(), head, "tag {}", argument);
+    }
+    // Lengths use the same heads
+    let long = "x".repeat(24);
+    assert_eq!(text(&long).encode(), [&[0x78, 24][..], long.as_bytes()].concat());
+    let bytes = vec![0xab; 256];
+    assert_eq!(CborValue::Bytes(bytes.clone()).encode(), [&[0x59, 1, 0][..], &bytes].concat());
+    let items = (1..=25).map(CborValue::Unsigned).collect();
+    check(CborValue::Array(items), "98190102030405060708090a0b0c0d0e0f101112131415161718181819");
+}
+
+#[test]
+fn bignums_are_tags_2_and_3() {
+    let two_64 = BigInt::one() << 64u32;
+    check(CborValue::bignum(&two_64), "c249010000000000000000");
+    // −18446744073709551617 = −1 − 2^64
+    check(CborValue::bignum(&(-&two_64 - 1u32)), "c349010000000000000000");
+    // The content has no leading zeros, so zero and −1 have empty byte strings
+    check(CborValue::bignum(&BigInt::from(0)), "c240");
+    check(CborValue::bignum(&BigInt::from(-1)), "c340");
+    check(CborValue::bignum(&BigInt::from(255)), "c241ff");
+    check(CborValue::bignum(&BigInt::from(-256)), "c341ff");
+    check(CborValue::Tag(1, Box::new(CborValue::Unsigned(1_363_896_240))), "c11a514b67b0");
+}
+
+#[test]
+fn strings_arrays_and_simple_values_match_appendix_a() {
+    check(CborValue::Bool(false), "f4");
+    check(CborValue::Bool(true), "f5");
+    check(CborValue::Bytes(Vec::new()), "40");
+    check(CborValue::Bytes(vec![1, 2, 3, 4]), "4401020304");
+    check(text(""), "60");
+    check(text("a"), "6161");
+    check(text("IETF"), "6449455446");
+    check(text("\"\\"), "62225c");
+    check(text("\u{fc}"), "62c3bc");
+    check(text("\u{6c34}"), "63e6b0b4");
+    check(CborValue::Array(Vec::new()), "80");
+    check(CborValue::Array([1, 2, 3].map(CborValue::Unsigned).to_vec()), "83010203");
+    let nested = |items: &[u64]| CborValue::Array(items.iter().copied().map(CborValue::Unsigned).collect());
+    let array = CborValue::Array(vec![CborValue::Unsigned(1), nested(&[2, 3]), nested(&[4, 5])]);
+    check(array, "8301820203820405");
+}
+
+#[test]
+fn maps_match_appendix_a_and_sort_their_keys() {
+    check(CborValue::Map(Vec::new()), "a0");
+    let two_three = CborValue::Array(vec![CborValue::Unsigned(2), CborValue::Unsigned(3)]);
+    let map = CborValue::Map(vec![("a".to_string(), CborValue::Unsigned(1)), ("b".to_string(), two_three)]);
+    check(map, "a26161016162820203");
+    let inner = CborValue::Map(vec![("b".to_string(), text("c"))]);
+    check(CborValue::Array(vec![text("a"), inner]), "826161a161626163");
+    // Given out of order, the entries come out sorted
+    let letters = ["e", "c", "a", "d", "b"];
+    let entries = letters.iter().map(|key| (key.to_string(), text(&key.to_uppercase()))).collect();
+    check(CborValue::Map(entries), "a56161614161626142616361436164614461656145");
+    // Keys sort by their encoded bytes, so a shorter key, with a smaller head, comes first
+    let entries = ["aa", "b", "a"].iter().map(|key| (key.to_string(), CborValue::Bool(true))).collect();
+    check(CborValue::Map(entries), "a36161f56162f5626161f5");
+    let (long, short) = ("a".repeat(24), "z".repeat(23));
+    let entries = vec![(long.clone(), CborValue::Unsigned(0)), (short.clone(), CborValue::Unsigned(1))];
+    let expected = [&[0xa2, 0x77][..], short.as_bytes(), &[1, 0x78, 24], long.as_bytes(), &[0]];
+    assert_eq!(CborValue::Map(entries).encode(), expected.concat());
+}
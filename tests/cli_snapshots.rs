@@ -0,0 +1,214 @@
+//! Snapshots of the command-line output, text and JSON, for representative inputs, so that a change of
+//! format shows up as a snapshot diff instead of breaking the scripts that parse it. Review changes with
+//! `cargo insta review`, or rerun with `INSTA_UPDATE=always` to accept them.
+
+use std::process::Command;
+
+/// The default curve y^2 = x^3 + 8x + 2 over F_17 and Wei25519 have a single root of the cubic, so the
+/// randomized root search always lands on the same map and the output is stable.
+const WEI25519: [&str; 2] = ["--curve", "Wei25519"];
+
+/// The command-line tool, built by cargo for the tests.
+const BINARY: &str = env!("CARGO_BIN_EXE_Weierstrass_Curve-points_Transformation_to_Montgomery_Curve_points");
+
+/// Runs the binary with `args` and returns its stdout and exit code; stderr, where logs go, is ignored.
+fn run(args: &[&str]) -> (String, i32) {
+    let output = Command::new(BINARY)
+        .args(["--no-progress"])
+        .args(args)
+        .output()
+        .expect("the binary runs");
+    let stdout = String::from_utf8(output.stdout).expect("the output is UTF-8");
+    (stdout, output.status.code().expect("the binary exits normally"))
+}
+
+/// Runs the binary with `args` and returns its stdout, failing the test unless it succeeds.
+fn stdout(args: &[&str]) -> String {
+    let (stdout, code) = run(args);
+    assert_eq!(code, 0, "{:?} failed", args);
+    stdout
+}
+
+#[test]
+fn transform() {
+    insta::assert_snapshot!("transform_text", stdout(&[]));
+    insta::assert_snapshot!("transform_json", stdout(&["--format", "json"]));
+    insta::assert_snapshot!("transform_intermediates_text", stdout(&["--intermediates"]));
+    assert_eq!(stdout(&["transform", "--intermediates"]), stdout(&["--intermediates"]));
+    insta::assert_snapshot!("transform_intermediates_json", stdout(&["--intermediates", "--format", "json"]));
+    let wei25519 = [&WEI25519[..], &["--format", "json"]].concat();
+    insta::assert_snapshot!("transform_wei25519_json", stdout(&wei25519));
+    // the point of order 16 on the default curve is both small and of composite order
+    insta::assert_snapshot!("transform_warnings_text", stdout(&["--check-order"]));
+    let (json, code) = run(&["--format", "json", "--check-order", "--deny-warnings"]);
+    assert_eq!(code, 6);
+    insta::assert_snapshot!("transform_warnings_json", json);
+    let points = ["--point", "14,6", "--point", "0,6", "--point", "2,3"];
+    insta::assert_snapshot!("transform_points_text", stdout(&points));
+    insta::assert_snapshot!("transform_points_json", stdout(&[&["--format", "json"], &points[..]].concat()));
+    let hex = [&WEI25519[..], &["--radix", "16", "--uppercase"]].concat();
+    insta::assert_snapshot!("transform_wei25519_hex_text", stdout(&hex));
+    let fingerprint = [&WEI25519[..], &["--fingerprint"]].concat();
+    insta::assert_snapshot!("transform_wei25519_fingerprint_text", stdout(&fingerprint));
+    insta::assert_snapshot!("transform_via_edwards_text", stdout(&["--via", "edwards"]));
+    let group = ["-a", "1", "-b", "0", "-p", "997", "-x", "0", "-y", "0", "--check-group", "--format", "json"];
+    insta::assert_snapshot!("transform_check_group_json", stdout(&group));
+    // (8, 0) goes to the point (0, 0) of order 2, which comes back; (0, 6) goes to u = −1, which does not
+    let origin = ["-x", "8", "-y", "0", "--via", "edwards", "--format", "json"];
+    insta::assert_snapshot!("transform_via_edwards_origin_json", stdout(&origin));
+    let (json, code) = run(&["-x", "0", "-y", "6", "--via", "edwards", "--format", "json"]);
+    assert_eq!(code, 1);
+    insta::assert_snapshot!("transform_via_edwards_exceptional_json", json);
+}
+
+/// The counts depend on the inversion algorithm, and `ct` switches `Field::inverse` to Fermat's.
+#[cfg(not(feature = "ct"))]
+#[test]
+fn op_count() {
+    insta::assert_snapshot!("transform_op_count_json", stdout(&["--op-count", "--format", "json"]));
+    let op_count = [&WEI25519[..], &["--op-count"]].concat();
+    insta::assert_snapshot!("transform_wei25519_op_count_text", stdout(&op_count));
+}
+
+#[test]
+fn inverse_and_verify() {
+    insta::assert_snapshot!("inverse_text", stdout(&["inverse", "5", "5"]));
+    insta::assert_snapshot!("inverse_json", stdout(&["--format", "json", "inverse", "5", "5"]));
+    insta::assert_snapshot!("verify_text", stdout(&["verify", "12", "12"]));
+    let (json, code) = run(&["--format", "json", "verify", "4", "4"]);
+    assert_eq!(code, 1);
+    insta::assert_snapshot!("verify_rejected_json", json);
+}
+
+#[test]
+fn info() {
+    insta::assert_snapshot!("info_text", stdout(&["info"]));
+    insta::assert_snapshot!("info_json", stdout(&["--format", "json", "info"]));
+}
+
+#[test]
+fn analyze() {
+    insta::assert_snapshot!("analyze_text", stdout(&["analyze"]));
+    insta::assert_snapshot!("analyze_json", stdout(&["--format", "json", "analyze"]));
+    // The findings are in the report, so nothing is logged at the default verbosity; the curve over F_43
+    // is anomalous and the default one has embedding degree 1
+    for args in [&["analyze"][..], &["-p", "43", "-a", "1", "-b", "14", "analyze"]] {
+        let output = Command::new(BINARY).arg("--no-progress").args(args).output();
+        let output = output.expect("the binary runs");
+        assert_eq!(String::from_utf8_lossy(&output.stderr), "", "{:?}", args);
+        assert!(String::from_utf8_lossy(&output.stdout).contains("\nwarning: "), "{:?}", args);
+    }
+}
+
+#[test]
+fn can_transform() {
+    insta::assert_snapshot!("can_transform_text", stdout(&["can-transform"]));
+    insta::assert_snapshot!("can_transform_json", stdout(&["--format", "json", "can-transform"]));
+    // y^2 = x^3 + 1 over F_7: 3·z0^2 is a non-residue for each of the roots 3, 5 and 6
+    let (text, code) = run(&["-a", "0", "-b", "1", "-p", "7", "-x", "0", "-y", "1", "can-transform"]);
+    assert_eq!(code, 3);
+    insta::assert_snapshot!("can_transform_rejected_text", text);
+}
+
+#[test]
+fn list_forms() {
+    let curve = ["-a", "1", "-b", "0", "-p", "13"];
+    insta::assert_snapshot!("list_forms_text", stdout(&[&curve[..], &["list-forms"]].concat()));
+    let json = [&curve[..], &["--format", "json", "list-forms"]].concat();
+    insta::assert_snapshot!("list_forms_json", stdout(&json));
+}
+
+#[test]
+fn generate() {
+    insta::assert_snapshot!("generate_curve_text", stdout(&["generate-curve", "-p", "1009", "--seed", "7"]));
+    let vectors = ["generate-vectors", "--count", "2", "--seed", "1"];
+    insta::assert_snapshot!("generate_vectors_text", stdout(&vectors));
+    insta::assert_snapshot!("generate_vectors_json", stdout(&[&["--format", "json"], &vectors[..]].concat()));
+    let fixtures = ["fixtures", "--count", "1", "--points", "2", "--seed", "1"];
+    insta::assert_snapshot!("fixtures_json", stdout(&fixtures));
+}
+
+#[test]
+fn errors() {
+    let output = Command::new(BINARY)
+        .args(["--error-format", "json", "-x", "1", "-y", "1"])
+        .output()
+        .expect("the binary runs");
+    let stderr = String::from_utf8(output.stderr).expect("the output is UTF-8");
+    insta::assert_snapshot!("error_json", stderr);
+    // y^2 = x^3 + x + 3 over F_7 has order 6, so no point of order 4
+    let output = Command::new(BINARY)
+        .args(["--error-format", "json", "-a", "1", "-b", "3", "-p", "7", "-x", "4", "-y", "1"])
+        .output()
+        .expect("the binary runs");
+    let stderr = String::from_utf8(output.stderr).expect("the output is UTF-8");
+    insta::assert_snapshot!("error_obstruction_json", stderr);
+    // Usage errors keep clap's exit code 2, which no domain error shares, and follow --error-format too
+    let output = Command::new(BINARY)
+        .args(["--error-format", "json", "--no-such-flag"])
+        .output()
+        .expect("the binary runs");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).expect("the output is UTF-8");
+    insta::assert_snapshot!("error_usage_json", stderr);
+    let (_, code) = run(&["-p", "15"]);
+    assert_eq!(code, 8);
+    let (_, code) = run(&["batch", "no/such/points.txt"]);
+    assert_eq!(code, 9);
+}
+
+#[test]
+fn completions_offer_curve_names() {
+    // Both --curve options complete registry names and aliases; the completion script is not snapshotted
+    // as it changes with every flag
+    let bash = stdout(&["completions", "bash"]);
+    let lines: Vec<&str> = bash.lines().collect();
+    let completed: Vec<&str> =
+        lines.windows(2).filter(|pair| pair[0].trim() == "--curve)").map(|pair| pair[1]).collect();
+    assert_eq!(completed.len(), 2, "{}", bash);
+    for line in completed {
+        for name in ["P-256", "secp256r1", "Wei25519", "brainpoolP512t1"] {
+            assert!(line.contains(name), "{} is not completed: {}", name, line);
+        }
+    }
+}
+
+#[test]
+fn batch() {
+    // Bad lines get an error row in place and the rest is still mapped; the output flags may follow the
+    // command
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("batch_points.txt");
+    std::fs::write(&path, "14 6\nnot a point\n0,6\n1 1\n\n# comment\n2 3 extra\n8,0\n").expect("writable");
+    let path = path.to_str().expect("UTF-8 path");
+    let (text, code) = run(&["batch", path]);
+    assert_eq!(code, 1);
+    insta::assert_snapshot!("batch_text", text);
+    let (json, code) = run(&["batch", path, "--format", "json", "--radix", "16"]);
+    assert_eq!(code, 1);
+    insta::assert_snapshot!("batch_json", json);
+}
+
+#[test]
+fn allow_composite_agrees_with_each_factor() {
+    // y^2 = x^3 + 3x + 2 has a single root of the cubic modulo 13, 17 and 19, so every run takes the same
+    // map, and the result modulo each factor is what the transform over that prime gives
+    let curve = |p: &str, y: &str| -> Vec<String> {
+        ["--format", "json", "-a", "3", "-b", "2", "-p", p, "-x", "2", "-y", y].map(String::from).to_vec()
+    };
+    let mut args = curve("4199", "251");
+    args.extend(["transform", "--allow-composite", "--factors", "13,17,19"].map(String::from));
+    let parse = |args: &[String]| -> serde_json::Value {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        serde_json::from_str(&stdout(&args)).expect("JSON output")
+    };
+    let composite = parse(&args);
+    for q in [13u32, 17, 19] {
+        let single = parse(&curve(&q.to_string(), &(251 % q).to_string()));
+        for field in ["x_montgomery", "y_montgomery", "a_montgomery", "b_montgomery"] {
+            let number = |value: &serde_json::Value| -> u32 {
+                value[field].as_str().expect("a decimal string").parse().expect("a number")
+            };
+            assert_eq!(number(&composite) % q, number(&single), "{} mod {}", field, q);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! The Chinese remainder theorem behind the composite transform, and the transform over Z/nZ against the
+//! transforms modulo each prime factor of n.
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use rand::SeedableRng;
+use weierstrass_to_montgomery::{
+    crt, transform_to_montgomery_composite_with_rng, transform_with_map, DefaultRng, TransformError,
+    TransformOptions,
+};
+
+fn congruences(pairs: &[(i64, u32)]) -> Vec<(BigInt, BigInt)> {
+    pairs.iter().map(|&(residue, modulus)| (BigInt::from(residue), BigInt::from(modulus))).collect()
+}
+
+#[test]
+fn crt_solves_coprime_moduli() {
+    assert_eq!(crt(&congruences(&[(2, 3), (3, 5), (2, 7)])), Some(BigInt::from(23)));
+    // No congruences leave the whole ring Z/1Z, and one is its own solution, reduced
+    assert_eq!(crt(&[]), Some(BigInt::from(0)));
+    assert_eq!(crt(&congruences(&[(-1, 10)])), Some(BigInt::from(9)));
+    // Every triple of residues modulo the coprime prime powers 4, 9 and 25
+    let triples = (0..4).flat_map(|r4| (0..9).flat_map(move |r9| (0..25).map(move |r25| (r4, r9, r25))));
+    for (r4, r9, r25) in triples {
+        let x = crt(&congruences(&[(r4, 4), (r9, 9), (r25, 25)])).expect("coprime moduli");
+        assert!(x >= BigInt::from(0) && x < BigInt::from(900));
+        let residues = [4, 9, 25].map(|modulus| x.mod_floor(&BigInt::from(modulus)));
+        assert_eq!(residues, [r4, r9, r25].map(BigInt::from));
+    }
+    // Unreduced and negative residues are reduced first
+    assert_eq!(crt(&congruences(&[(5, 3), (-2, 5)])), crt(&congruences(&[(2, 3), (3, 5)])));
+}
+
+#[test]
+fn crt_refuses_moduli_with_a_common_factor() {
+    // Consistent or not, congruences whose moduli share a factor have no answer here
+    assert_eq!(crt(&congruences(&[(1, 6), (1, 4)])), None);
+    assert_eq!(crt(&congruences(&[(0, 9), (3, 15)])), None);
+    assert_eq!(crt(&congruences(&[(1, 5), (1, 5)])), None);
+    assert_eq!(crt(&congruences(&[(1, 3), (2, 5), (4, 35)])), None);
+}
+
+#[test]
+fn composite_transform_matches_each_factor() {
+    // y^2 = x^3 + x has three roots of the cubic modulo 13 and 17, so the root search draws from the
+    // generator; replaying the same seed factor by factor must give the same maps
+    let (a, b) = (BigInt::from(1), BigInt::from(0));
+    let factors = [13, 17].map(BigInt::from);
+    let (x, y) = (BigInt::from(3), BigInt::from(76));
+    let options = TransformOptions::default();
+    for seed in 0..8 {
+        let mut rng = DefaultRng::seed_from_u64(seed);
+        let (u, v, a_montgomery, b_montgomery) =
+            transform_to_montgomery_composite_with_rng(&x, &y, &a, &b, &factors, &options, &mut rng)
+                .expect("both factors have a Montgomery form");
+        let mut rng = DefaultRng::seed_from_u64(seed);
+        for q in &factors {
+            let reduce = |value: &BigInt| value.mod_floor(q);
+            let (u_q, v_q, map) =
+                transform_with_map(&reduce(&x), &reduce(&y), &a, &b, q, &options, &mut rng).expect("a form");
+            let expected = [u_q, v_q, map.a_montgomery, map.b_montgomery];
+            let found = [&u, &v, &a_montgomery, &b_montgomery].map(reduce);
+            assert_eq!(found, expected, "seed {}, q = {}", seed, q);
+        }
+        // The image satisfies B·v^2 = u^3 + A·u^2 + u modulo n itself
+        let n = BigInt::from(221);
+        let rhs = &u * &u * &u + &a_montgomery * &u * &u + &u;
+        assert_eq!((&b_montgomery * &v * &v - rhs).mod_floor(&n), BigInt::from(0));
+    }
+}
+
+#[test]
+fn composite_transform_rejects_bad_factors() {
+    let (x, y, a, b) = (BigInt::from(3), BigInt::from(76), BigInt::from(1), BigInt::from(0));
+    let options = TransformOptions::default();
+    let mut rng = DefaultRng::seed_from_u64(0);
+    let mut transform = |factors: &[u32]| {
+        let factors: Vec<BigInt> = factors.iter().copied().map(BigInt::from).collect();
+        transform_to_montgomery_composite_with_rng(&x, &y, &a, &b, &factors, &options, &mut rng)
+    };
+    let message = |result| match result {
+        Err(TransformError::InvalidParameters(message)) => message,
+        other => panic!("expected InvalidParameters, got {:?}", other),
+    };
+    assert!(message(transform(&[13, 13])).contains("more than once"));
+    assert!(message(transform(&[13, 15])).contains("not a prime"));
+    assert!(matches!(transform(&[3, 13]), Err(TransformError::UnsupportedCharacteristic(_))));
+    assert!(matches!(transform(&[]), Err(TransformError::InvalidParameters(_))));
+}
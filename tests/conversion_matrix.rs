@@ -0,0 +1,138 @@
+use num_bigint::BigInt;
+use num_traits::Zero;
+use weierstrass_to_montgomery::{
+    cubic_roots, default_rng, legendre_symbol, lookup, CurveGroup, Isomorphism, MontgomeryCurve,
+    MontgomeryMap, MontgomeryToEdwards, Point, SearchLimits, TransformError, TransformOptions,
+    WeierstrassCurve,
+};
+
+/// The small primes whose every Weierstrass curve with a Montgomery form is run through the matrix.
+const PRIMES: [i64; 3] = [13, 17, 19];
+
+/// Which converter branch the Montgomery image of a point takes on the way to the Edwards curve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Path {
+    /// The neutral element, which every map keeps.
+    Infinity,
+    /// (0, 0), which goes to (0, −1) and back by the x = 0 branch of `point_to_montgomery`.
+    OriginOfOrderTwo,
+    /// v = 0 with u ≠ 0, of order 2, which has no affine image and goes to the neutral element.
+    OtherOrderTwo,
+    /// u = −1, of order 4, which has no affine image either.
+    MinusOne,
+    /// Everything else, by the generic formulas.
+    Generic,
+}
+
+fn path(point: &Point, p: &BigInt) -> Path {
+    match point {
+        Point::Infinity => Path::Infinity,
+        Point::Affine(u, v) if u.is_zero() && v.is_zero() => Path::OriginOfOrderTwo,
+        Point::Affine(_, v) if v.is_zero() => Path::OtherOrderTwo,
+        Point::Affine(u, _) if u == &(p - 1u32) => Path::MinusOne,
+        Point::Affine(..) => Path::Generic,
+    }
+}
+
+/// The maps of y^2 = x^3 + ax + b, one per root of the cubic that gives a Montgomery form.
+fn maps(a: &BigInt, b: &BigInt, p: &BigInt) -> Vec<MontgomeryMap> {
+    let options = TransformOptions::default();
+    let roots = cubic_roots(a, b, p, &SearchLimits::default(), &mut default_rng()).expect("p is prime");
+    let mut maps = Vec::new();
+    for z0 in roots {
+        match MontgomeryMap::from_root(a, b, p, &z0, &options) {
+            Ok(map) => maps.push(map),
+            Err(TransformError::NoMontgomeryForm(_)) => {}
+            Err(error) => panic!("z0 = {}: {}", z0, error),
+        }
+    }
+    maps
+}
+
+/// Runs `point` Weierstrass -> Montgomery -> Edwards -> Montgomery -> Weierstrass under `map`, checking
+/// each image against its curve, and returns the branch it took and the point it came back as.
+fn round_trip(map: &MontgomeryMap, point: &Point) -> (Path, Point) {
+    let p = &map.p;
+    let weierstrass = WeierstrassCurve::new(&map.a, &map.b, p).expect("non-singular");
+    let montgomery = MontgomeryCurve::new(&map.a_montgomery, &map.b_montgomery, p).expect("B ≠ 0");
+    let edwards = montgomery.to_twisted_edwards();
+    let to_montgomery = map.forward();
+    let to_edwards = MontgomeryToEdwards { curve: montgomery.clone() };
+
+    let image = Isomorphism::apply(&to_montgomery, point).expect("total");
+    assert!(montgomery.contains(&image), "{} -> {}", point, image);
+    let on_edwards = to_edwards.apply(&image).expect("total");
+    assert!(edwards.contains(&on_edwards), "{} -> {}", image, on_edwards);
+    let back_on_montgomery = to_edwards.invert().apply(&on_edwards).expect("total");
+    assert!(montgomery.contains(&back_on_montgomery));
+    let back = Isomorphism::apply(&to_montgomery.invert(), &back_on_montgomery).expect("total");
+    assert!(weierstrass.contains(&back));
+
+    // The same route as one chain, and its inverse, land on the same points
+    let chain = to_montgomery.compose(to_edwards);
+    assert_eq!(chain.apply(point), Some(on_edwards.clone()));
+    assert_eq!(chain.invert().apply(&on_edwards), Some(back.clone()));
+    (path(&image, p), back)
+}
+
+/// Whether the Edwards curve of `map` is complete, a a square and d not, so without exceptional points.
+fn is_complete(map: &MontgomeryMap) -> bool {
+    let curve = MontgomeryCurve::new(&map.a_montgomery, &map.b_montgomery, &map.p).expect("B ≠ 0");
+    let edwards = curve.to_twisted_edwards();
+    legendre_symbol(&edwards.a, &map.p) == 1 && legendre_symbol(&edwards.d, &map.p) == -1
+}
+
+#[test]
+fn every_point_of_every_small_curve_comes_back_bit_exact_or_is_exceptional() {
+    let mut seen = Vec::new();
+    for p in PRIMES {
+        let field = || (0..p).map(BigInt::from);
+        let p = BigInt::from(p);
+        for (a, b) in field().flat_map(|a| field().map(move |b| (a.clone(), b))) {
+            let Ok(curve) = WeierstrassCurve::new(&a, &b, &p) else {
+                continue;
+            };
+            let mut points = vec![Point::Infinity];
+            for (x, y) in field().flat_map(|x| field().map(move |y| (x.clone(), y))) {
+                points.extend(curve.point(&x, &y).ok());
+            }
+            for map in maps(&a, &b, &p) {
+                let complete = is_complete(&map);
+                // Batched, with one inversion for all points, the route lands where it does point by point
+                let montgomery =
+                    MontgomeryCurve::new(&map.a_montgomery, &map.b_montgomery, &p).expect("B ≠ 0");
+                let chain = map.forward().compose(MontgomeryToEdwards { curve: montgomery });
+                let one_by_one: Vec<Option<Point>> = points.iter().map(|point| chain.apply(point)).collect();
+                assert_eq!(chain.apply_all(&points), one_by_one, "y^2 = x^3 + {}x + {} mod {}", a, b, p);
+                for point in &points {
+                    let (path, back) = round_trip(&map, point);
+                    seen.push((path, complete));
+                    if let Path::OtherOrderTwo | Path::MinusOne = path {
+                        assert!(!complete, "{} is exceptional on a complete curve", point);
+                        assert_eq!(back, Point::Infinity, "{} came back as {}", point, back);
+                    } else {
+                        assert_eq!(&back, point, "y^2 = x^3 + {}x + {} mod {}", a, b, p);
+                    }
+                }
+            }
+        }
+    }
+    seen.sort_unstable();
+    seen.dedup();
+    // every branch is exercised, and the exact ones on both complete and incomplete Edwards curves
+    for path in [Path::Infinity, Path::OriginOfOrderTwo, Path::Generic] {
+        assert!(seen.contains(&(path, true)) && seen.contains(&(path, false)), "{:?}", path);
+    }
+    assert!(seen.contains(&(Path::OtherOrderTwo, false)) && seen.contains(&(Path::MinusOne, false)));
+}
+
+#[test]
+fn wei25519_base_point_comes_back_bit_exact() {
+    let curve = lookup("Wei25519").expect("in the registry");
+    let (a, b) = curve.coefficients();
+    let p = curve.p();
+    let base_point = curve.base_point();
+    let maps = maps(&a, &b, &p);
+    assert_eq!(maps.len(), 1);
+    assert_eq!(round_trip(&maps[0], &base_point), (Path::Generic, base_point));
+}
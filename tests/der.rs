@@ -0,0 +1,161 @@
+//! The DER, base64 and PEM readers behind the key and parameter parsers: lengths DER allows and the ones
+//! it does not, object identifiers of named curves, and PEM blocks as `openssl` writes them.
+
+use weierstrass_to_montgomery::der::{
+    decode_base64, decode_oid, decode_pem, encode_base64, named_curve_from_der, named_curve_from_parameters,
+    read_element,
+};
+use weierstrass_to_montgomery::TransformError;
+
+fn hex(digits: &str) -> Vec<u8> {
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).expect("hex digits"))
+        .collect()
+}
+
+/// The message of the `InvalidParameters` error of `result`.
+fn rejected<T: std::fmt::Debug>(result: Result<T, TransformError>) -> String {
+    match result {
+        Err(TransformError::InvalidParameters(message)) => message,
+        other => panic!("expected InvalidParameters, got {:?}", other),
+    }
+}
+
+/// An OCTET STRING with `length` bytes of contents, its length in the shortest form.
+fn octet_string(length: usize) -> Vec<u8> {
+    let mut bytes = vec![0x04];
+    match length {
+        0..=0x7f => bytes.push(length as u8),
+        0x80..=0xff => bytes.extend([0x81, length as u8]),
+        _ => bytes.extend([0x82, (length >> 8) as u8, length as u8]),
+    }
+    bytes.extend(vec![0xab; length]);
+    bytes
+}
+
+#[test]
+fn read_element_splits_definite_lengths() {
+    let bytes = hex("0402aabbcc");
+    assert_eq!(read_element(&bytes).expect("valid"), (0x04, &bytes[2..4], &bytes[4..]));
+    // Either side of the switch to the long form, and a two-byte length
+    for length in [0, 1, 0x7f, 0x80, 0xff, 0x100, 0x1234] {
+        let mut bytes = octet_string(length);
+        let header = bytes.len() - length;
+        bytes.push(0x05);
+        let (tag, contents, rest) = read_element(&bytes).expect("valid");
+        assert_eq!((tag, contents.len(), rest), (0x04, length, &[0x05][..]), "length {}", length);
+        assert_eq!(contents, &bytes[header..header + length]);
+    }
+}
+
+#[test]
+fn read_element_rejects_lengths_der_does_not_allow() {
+    assert!(rejected(read_element(&[])).contains("missing tag"));
+    assert!(rejected(read_element(&[0x04])).contains("missing length"));
+    assert!(rejected(read_element(&hex("040501020304"))).contains("shorter than the length"));
+    assert!(rejected(read_element(&hex("30800201010000"))).contains("indefinite"));
+    // The length bytes themselves cut short, or more of them than a length can take
+    assert!(rejected(read_element(&hex("048201"))).contains("truncated or oversized"));
+    assert!(rejected(read_element(&hex("0489010000000000000000"))).contains("truncated or oversized"));
+    assert!(rejected(read_element(&hex("04887fffffffffffffff00"))).contains("shorter than the length"));
+    // Long forms of lengths below 128, or with a leading zero byte
+    let mut padded = hex("048105");
+    padded.extend([0; 5]);
+    assert!(rejected(read_element(&padded)).contains("non-minimal"));
+    let mut padded = hex("04820080");
+    padded.extend([0; 0x80]);
+    assert!(rejected(read_element(&padded)).contains("non-minimal"));
+    assert!(rejected(read_element(&hex("048100"))).contains("non-minimal"));
+    // Tag number 31 and above spill into further tag bytes
+    assert!(rejected(read_element(&hex("1f810100"))).contains("multi-byte"));
+    assert!(rejected(read_element(&hex("bf2200"))).contains("multi-byte"));
+}
+
+#[test]
+fn decode_oid_reads_named_curve_identifiers() {
+    for (contents, dotted) in [
+        ("2a8648ce3d030107", "1.2.840.10045.3.1.7"),
+        ("2b81040022", "1.3.132.0.34"),
+        ("2b2403030208010107", "1.3.36.3.3.2.8.1.1.7"),
+        ("2b240303020801010e", "1.3.36.3.3.2.8.1.1.14"),
+        ("2b6570", "1.3.101.112"),
+        // Under the joint arc 2 the second arc may exceed 39
+        ("883703", "2.999.3"),
+        ("00", "0.0"),
+    ] {
+        assert_eq!(decode_oid(&hex(contents)).expect("valid"), dotted);
+    }
+}
+
+#[test]
+fn decode_oid_rejects_malformed_arcs() {
+    assert!(rejected(decode_oid(&[])).contains("truncated"));
+    assert!(rejected(decode_oid(&hex("2a86"))).contains("truncated"));
+    // 0x80 pads an arc with a leading zero digit, at the start or after the first arc
+    assert!(rejected(decode_oid(&hex("802a"))).contains("leading 0x80"));
+    assert!(rejected(decode_oid(&hex("2a808648"))).contains("leading 0x80"));
+    assert!(rejected(decode_oid(&hex("2a8648ce3d0380800107"))).contains("leading 0x80"));
+    // A continuation byte of 0x80 inside an arc is an ordinary zero digit
+    assert_eq!(decode_oid(&hex("2a818000")).expect("valid"), "1.2.16384");
+    assert!(rejected(decode_oid(&hex("2a8fffffffffffffffff7f"))).contains("64 bits"));
+}
+
+#[test]
+fn base64_round_trips_in_both_alphabets() {
+    for length in 0usize..20 {
+        let bytes: Vec<u8> = (0..length).map(|i| (i * 73 + 250) as u8).collect();
+        for url in [false, true] {
+            let text = encode_base64(&bytes, url);
+            assert_eq!(text.len().is_multiple_of(4), !url || length.is_multiple_of(3), "{}", text);
+            assert_eq!(decode_base64(&text, url).expect("valid"), bytes, "{}", text);
+        }
+    }
+    assert_eq!(decode_base64("aGVsbG8=", false).expect("padded"), b"hello");
+    assert_eq!(decode_base64("aGVs\n bG8", false).expect("unpadded"), b"hello");
+    assert_eq!(decode_base64("-_8", true).expect("URL-safe"), [0xfb, 0xff]);
+    assert_eq!(decode_base64("+/8", false).expect("standard"), [0xfb, 0xff]);
+    assert!(rejected(decode_base64("-_8", false)).contains("invalid base64 character"));
+    assert!(rejected(decode_base64("+/8", true)).contains("invalid base64 character"));
+    assert!(rejected(decode_base64("aGVsb", false)).contains("truncated"));
+}
+
+#[test]
+fn decode_pem_joins_wrapped_lines() {
+    // A P-256 SubjectPublicKeyInfo as `openssl ec -pubout` writes it, 64 characters to a line
+    let body = "MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEaxfR8uEsQkf4vOblY6RA8ncDfYEt\n\
+                6zOg9KE5RdiYwpZP40Li/hp/m47n60p8D54WK84zV2sxXs7LtkBoN79R9Q==";
+    let der = decode_base64(body, false).expect("base64");
+    for newline in ["\n", "\r\n"] {
+        let text = format!(
+            "Public key of the test\n-----BEGIN PUBLIC KEY-----{0}{1}{0}-----END PUBLIC KEY-----{0}",
+            newline,
+            body.replace('\n', newline)
+        );
+        let (label, decoded) = decode_pem(&text).expect("a PEM block");
+        assert_eq!((label.as_str(), &decoded), ("PUBLIC KEY", &der));
+    }
+    assert_eq!(der.len(), 91);
+    assert_eq!(read_element(&der).expect("a SEQUENCE").0, 0x30);
+
+    assert!(rejected(decode_pem("no block here")).contains("no PEM block"));
+    assert!(rejected(decode_pem("-----BEGIN PUBLIC KEY")).contains("unterminated"));
+    let mismatched = "-----BEGIN PUBLIC KEY-----\nAAAA\n-----END PRIVATE KEY-----\n";
+    assert!(rejected(decode_pem(mismatched)).contains("missing PEM footer"));
+}
+
+#[test]
+fn named_curves_resolve_from_their_parameters() {
+    assert_eq!(named_curve_from_der(&hex("06082a8648ce3d030107")).expect("P-256").name, "P-256");
+    let brainpool = named_curve_from_der(&hex("06092b2403030208010107")).expect("brainpoolP256r1");
+    assert_eq!(brainpool.name, "brainpoolP256r1");
+    // `openssl ecparam -name prime256v1` in PEM form
+    let pem = "-----BEGIN EC PARAMETERS-----\nBggqhkjOPQMBBw==\n-----END EC PARAMETERS-----\n";
+    assert_eq!(named_curve_from_parameters(pem.as_bytes()).expect("P-256").name, "P-256");
+
+    assert!(rejected(named_curve_from_der(&hex("06032a0304"))).contains("unknown curve OID 1.2.3.4"));
+    assert!(rejected(named_curve_from_der(&hex("3000"))).contains("specifiedCurve"));
+    assert!(rejected(named_curve_from_der(&hex("0500"))).contains("implicitCurve"));
+    assert!(rejected(named_curve_from_der(&hex("0400"))).contains("must be an OBJECT IDENTIFIER"));
+    assert!(rejected(named_curve_from_der(&hex("0608802a8648ce3d0301"))).contains("leading 0x80"));
+}
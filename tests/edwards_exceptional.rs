@@ -0,0 +1,93 @@
+use num_bigint::BigInt;
+use num_traits::Zero;
+use weierstrass_to_montgomery::{CurveGroup, MontgomeryCurve, Point};
+
+const P: i64 = 17;
+
+/// v^2 = u^3 + 6u^2 + u over F_17 has 16 points, all of its 2-torsion and the points (16, ±2) of order 4
+/// with u = −1; its Edwards curve 8x^2 + y^2 = 1 + 4x^2y^2 is not complete, as d = 4 is a square.
+fn incomplete() -> MontgomeryCurve {
+    MontgomeryCurve::new(&BigInt::from(6), &BigInt::from(1), &BigInt::from(P)).expect("A^2 ≠ 4")
+}
+
+/// v^2 = u^3 + 7u^2 + u over F_17, whose Edwards curve 9x^2 + y^2 = 1 + 5x^2y^2 is complete.
+fn complete() -> MontgomeryCurve {
+    MontgomeryCurve::new(&BigInt::from(7), &BigInt::from(1), &BigInt::from(P)).expect("A^2 ≠ 4")
+}
+
+fn points(curve: &MontgomeryCurve) -> Vec<Point> {
+    let mut points = vec![Point::Infinity];
+    for u in 0..P {
+        for v in 0..P {
+            if let Ok(point) = curve.point(&BigInt::from(u), &BigInt::from(v)) {
+                points.push(point);
+            }
+        }
+    }
+    points
+}
+
+/// The points the formula (u / v, (u − 1) / (u + 1)) is undefined at: v = 0 other than (0, 0), and u = −1.
+fn is_exceptional(point: &Point) -> bool {
+    match point {
+        Point::Infinity => false,
+        Point::Affine(u, v) => (!u.is_zero() && v.is_zero()) || u == &BigInt::from(P - 1),
+    }
+}
+
+#[test]
+fn exceptional_points_go_to_the_neutral_element() {
+    let curve = incomplete();
+    let exceptional: Vec<Point> = points(&curve).into_iter().filter(is_exceptional).collect();
+    let expected = [(2, 0), (9, 0), (16, 2), (16, 15)].map(|(u, v)| Point::Affine(u.into(), v.into()));
+    assert_eq!(exceptional, expected);
+    for point in &exceptional {
+        assert_eq!(curve.point_to_edwards(point), Point::Infinity, "{}", point);
+    }
+}
+
+#[test]
+fn the_point_of_order_two_at_the_origin_goes_to_zero_minus_one() {
+    for curve in [incomplete(), complete()] {
+        let origin = Point::Affine(BigInt::zero(), BigInt::zero());
+        let image = curve.point_to_edwards(&origin);
+        assert_eq!(image, Point::Affine(BigInt::zero(), BigInt::from(P - 1)));
+        assert_eq!(curve.to_twisted_edwards().point_to_montgomery(&image), origin);
+        assert_eq!(curve.point_to_edwards(&Point::Infinity), Point::Infinity);
+    }
+}
+
+#[test]
+fn other_points_map_onto_the_edwards_curve_and_back() {
+    for curve in [incomplete(), complete()] {
+        let edwards = curve.to_twisted_edwards();
+        for point in points(&curve).iter().filter(|point| !is_exceptional(point)) {
+            let image = curve.point_to_edwards(point);
+            assert!(edwards.contains(&image), "{} -> {}", point, image);
+            assert_eq!(&edwards.point_to_montgomery(&image), point);
+        }
+    }
+}
+
+#[test]
+fn a_complete_curve_has_no_exceptional_points() {
+    assert!(!points(&complete()).iter().any(is_exceptional));
+}
+
+#[test]
+fn the_map_keeps_the_group_law_away_from_exceptional_points() {
+    for curve in [incomplete(), complete()] {
+        let edwards = curve.to_twisted_edwards();
+        let points = points(&curve);
+        for first in &points {
+            for second in &points {
+                let sum = curve.add(first, second);
+                if [first, second, &sum].into_iter().any(is_exceptional) {
+                    continue;
+                }
+                let images = (curve.point_to_edwards(first), curve.point_to_edwards(second));
+                assert_eq!(edwards.add(&images.0, &images.1), curve.point_to_edwards(&sum));
+            }
+        }
+    }
+}
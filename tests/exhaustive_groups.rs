@@ -0,0 +1,78 @@
+use num_bigint::BigInt;
+use weierstrass_to_montgomery::{
+    check_map_exhaustively, count_points, cubic_roots, default_rng, MontgomeryMap, SearchLimits,
+    TransformError, TransformOptions, WeierstrassCurve,
+};
+
+/// The maps of y^2 = x^3 + ax + b, one per root of the cubic that gives a Montgomery form.
+fn maps(a: i64, b: i64, p: i64) -> Vec<MontgomeryMap> {
+    let [a, b, p] = [a, b, p].map(BigInt::from);
+    let options = TransformOptions::default();
+    let roots = cubic_roots(&a, &b, &p, &SearchLimits::default(), &mut default_rng()).expect("p is prime");
+    let mut maps = Vec::new();
+    for z0 in roots {
+        match MontgomeryMap::from_root(&a, &b, &p, &z0, &options) {
+            Ok(map) => maps.push(map),
+            Err(TransformError::NoMontgomeryForm(_)) => {}
+            Err(error) => panic!("z0 = {}: {}", z0, error),
+        }
+    }
+    maps
+}
+
+#[test]
+fn every_map_of_every_curve_over_small_primes_preserves_the_group() {
+    let (mut cyclic, mut non_cyclic) = (0, 0);
+    for p in [13, 17, 19, 23] {
+        for a in 0..p {
+            for b in 0..p {
+                let [a_big, b_big, p_big] = [a, b, p].map(BigInt::from);
+                if WeierstrassCurve::new(&a_big, &b_big, &p_big).is_err() {
+                    continue;
+                }
+                let order = count_points(&a_big, &b_big, &p_big).expect("p is small");
+                for map in maps(a, b, p) {
+                    let structure = check_map_exhaustively(&map)
+                        .unwrap_or_else(|error| panic!("y^2 = x^3 + {}x + {} mod {}: {}", a, b, p, error));
+                    assert_eq!(BigInt::from(structure.order()), order);
+                    assert_eq!(structure.generators.len(), structure.invariants.len());
+                    if let [n1, n2] = structure.invariants[..] {
+                        assert_eq!(n1 % n2, 0, "{}", structure);
+                        non_cyclic += 1;
+                    } else {
+                        cyclic += 1;
+                    }
+                }
+            }
+        }
+    }
+    // a Montgomery curve has a point of order 2; those with all three are Z/2n x Z/2, not cyclic
+    assert!(cyclic > 0 && non_cyclic > 0);
+}
+
+#[test]
+fn curves_just_below_the_bound() {
+    // y^2 = x^3 + x over F_997 has 1060 points, Z/530 x Z/2; y^2 = x^3 + x + 1 over F_991 is cyclic
+    for (a, b, p, structure) in [(1, 0, 997, "Z/530 x Z/2"), (1, 1, 991, "Z/952")] {
+        let maps = maps(a, b, p);
+        assert!(!maps.is_empty());
+        for map in maps {
+            let found = check_map_exhaustively(&map).expect("the map is an isomorphism");
+            assert_eq!(found.to_string(), structure);
+        }
+    }
+}
+
+#[test]
+fn a_wrong_image_curve_is_caught() {
+    let map = maps(8, 2, 17).remove(0);
+    let wrong = MontgomeryMap { b_montgomery: &map.b_montgomery * 3u32 % 17u32, ..map.clone() };
+    let error = check_map_exhaustively(&wrong).expect_err("the images are on another curve");
+    assert!(error.to_string().contains("not the points of the Montgomery curve"), "{}", error);
+}
+
+#[test]
+fn large_moduli_are_rejected() {
+    let map = maps(1, 0, 1009).remove(0);
+    assert!(matches!(check_map_exhaustively(&map), Err(TransformError::InvalidParameters(_))));
+}
@@ -0,0 +1,142 @@
+//! `scalar_mul_checked` against `scalar_mul` on valid input, and against faults injected into the
+//! double-and-add loop through a curve whose addition misbehaves once: a corrupted intermediate, an
+//! off-curve copy of the base, and a skipped addition.
+
+use num_bigint::{BigInt, RandBigInt};
+use rand::SeedableRng;
+use std::cell::Cell;
+use weierstrass_to_montgomery::{
+    lookup, scalar_mul, scalar_mul_checked, CurveGroup, DefaultRng, Point, TransformError, WeierstrassCurve,
+};
+
+#[derive(Clone, Copy, Debug)]
+enum Fault {
+    /// Flips the low bit of the x-coordinate of the sum.
+    CorruptSum,
+    /// Adds a copy of the base whose y-coordinate is off by one, so it is not on the curve.
+    OffCurveBase,
+    /// Returns the first operand, leaving the base out.
+    SkipAddition,
+}
+
+/// `curve`, except that the `at`-th call to `add` that is not a doubling suffers `fault`.
+struct Faulty<'a> {
+    curve: &'a WeierstrassCurve,
+    fault: Fault,
+    at: usize,
+    calls: Cell<usize>,
+}
+
+impl<'a> Faulty<'a> {
+    fn new(curve: &'a WeierstrassCurve, fault: Fault, at: usize) -> Faulty<'a> {
+        Faulty { curve, fault, at, calls: Cell::new(0) }
+    }
+}
+
+impl CurveGroup for Faulty<'_> {
+    fn modulus(&self) -> &BigInt {
+        self.curve.modulus()
+    }
+
+    fn contains(&self, point: &Point) -> bool {
+        self.curve.contains(point)
+    }
+
+    fn negate(&self, point: &Point) -> Point {
+        self.curve.negate(point)
+    }
+
+    fn add(&self, first: &Point, second: &Point) -> Point {
+        if first == second {
+            return self.curve.add(first, second);
+        }
+        let call = self.calls.get();
+        self.calls.set(call + 1);
+        match (call == self.at, self.fault, second) {
+            (true, Fault::CorruptSum, _) => match self.curve.add(first, second) {
+                Point::Affine(x, y) => Point::Affine(x ^ BigInt::from(1), y),
+                Point::Infinity => Point::Infinity,
+            },
+            (true, Fault::OffCurveBase, Point::Affine(x, y)) => {
+                self.curve.add(first, &Point::Affine(x.clone(), y + 1u32))
+            }
+            (true, Fault::SkipAddition, _) => first.clone(),
+            _ => self.curve.add(first, second),
+        }
+    }
+}
+
+fn wei25519() -> (WeierstrassCurve, Point, BigInt) {
+    let named = lookup("Wei25519").expect("in the registry");
+    let (curve, base) = named.to_weierstrass().expect("a Weierstrass curve");
+    (curve, base, named.order())
+}
+
+fn fault(result: Result<Point, TransformError>) -> String {
+    match result {
+        Err(error @ TransformError::FaultDetected(_)) => {
+            assert_eq!((error.exit_code(), error.kind()), (7, "fault_detected"));
+            error.to_string()
+        }
+        other => panic!("expected FaultDetected, got {:?}", other),
+    }
+}
+
+#[test]
+fn checked_agrees_with_scalar_mul() {
+    let (curve, base, order) = wei25519();
+    let mut rng = DefaultRng::seed_from_u64(190);
+    let mut scalars: Vec<BigInt> = [0, 1, 2, -1, -7].map(BigInt::from).to_vec();
+    scalars.extend([&order - 1u32, order.clone(), &order + 1u32]);
+    scalars.extend((0..8).map(|_| rng.gen_bigint_range(&-&order, &order)));
+    for scalar in &scalars {
+        let expected = scalar_mul(&curve, &base, scalar);
+        for recompute in [None, Some(&order)] {
+            let found = scalar_mul_checked(&curve, &base, scalar, recompute, &mut rng);
+            assert_eq!(found.expect("no fault"), expected, "k = {}, recompute = {:?}", scalar, recompute);
+        }
+    }
+    // The point at infinity is on every curve
+    let found = scalar_mul_checked(&curve, &Point::Infinity, &order, Some(&order), &mut rng);
+    assert_eq!(found.expect("no fault"), Point::Infinity);
+}
+
+#[test]
+fn faults_that_leave_the_curve_are_detected() {
+    let (curve, base, order) = wei25519();
+    let mut rng = DefaultRng::seed_from_u64(191);
+    let scalar = rng.gen_bigint_range(&(&order >> 1u32), &order);
+    for fault_kind in [Fault::CorruptSum, Fault::OffCurveBase] {
+        // At the first addition, and at the 41st and 101st of about 128
+        for at in [0, 40, 100] {
+            let faulty = Faulty::new(&curve, fault_kind, at);
+            let message = fault(scalar_mul_checked(&faulty, &base, &scalar, None, &mut rng));
+            assert!(message.contains("not on the curve"), "{:?} at {}: {}", fault_kind, at, message);
+        }
+    }
+}
+
+#[test]
+fn faults_that_stay_on_the_curve_need_the_recomputation() {
+    let (curve, base, order) = wei25519();
+    let mut rng = DefaultRng::seed_from_u64(192);
+    let scalar = rng.gen_bigint_range(&(&order >> 1u32), &order);
+    let expected = scalar_mul(&curve, &base, &scalar);
+    // A skipped addition gives a wrong point of the curve, which only the blinded second run reveals
+    let faulty = Faulty::new(&curve, Fault::SkipAddition, 40);
+    let unchecked = scalar_mul_checked(&faulty, &base, &scalar, None, &mut rng).expect("on the curve");
+    assert!(curve.contains(&unchecked) && unchecked != expected);
+    let faulty = Faulty::new(&curve, Fault::SkipAddition, 40);
+    let message = fault(scalar_mul_checked(&faulty, &base, &scalar, Some(&order), &mut rng));
+    assert!(message.contains("blinded scalar"), "{}", message);
+}
+
+#[test]
+fn an_off_curve_input_is_refused_before_multiplying() {
+    let (curve, base, order) = wei25519();
+    let mut rng = DefaultRng::seed_from_u64(193);
+    let Point::Affine(x, y) = base else { unreachable!("the base point is affine") };
+    let off_curve = Point::Affine(x, y + 1u32);
+    let result = scalar_mul_checked(&curve, &off_curve, &BigInt::from(5), Some(&order), &mut rng);
+    assert!(matches!(result, Err(TransformError::PointNotOnCurve)));
+}
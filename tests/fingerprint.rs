@@ -0,0 +1,54 @@
+//! `fingerprint::sha256` against the FIPS 180-4 example messages and around the block and padding
+//! boundaries, and `fingerprint` as the digest of the uncompressed encoding.
+
+use num_bigint::BigInt;
+use weierstrass_to_montgomery::fingerprint::sha256;
+use weierstrass_to_montgomery::{fingerprint, short_fingerprint, Point};
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[test]
+fn sha256_matches_the_fips_180_4_examples() {
+    let one_block = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    assert_eq!(hex(&sha256(b"abc")), one_block);
+    let empty = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+    assert_eq!(hex(&sha256(b"")), empty);
+    // 56 bytes leave no room for the length in the first block, so the padding takes a second one
+    let message = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+    assert_eq!(message.len(), 56);
+    let two_blocks = "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1";
+    assert_eq!(hex(&sha256(message)), two_blocks);
+    let million = "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0";
+    assert_eq!(hex(&sha256(&vec![b'a'; 1_000_000])), million);
+}
+
+#[test]
+fn sha256_pads_messages_around_the_block_boundary() {
+    // 55 bytes are the most that fit in one block with the padding, 64 fill it exactly
+    for (length, expected) in [
+        (55, "9f4390f8d30c2dd92ec9f095b65e2b9ae9b0a925a5258e241c9f1e910f734318"),
+        (56, "b35439a4ac6f0948b6d6f9e3c6af0f5f590ce20f1bde7090ef7970686ec6738a"),
+        (63, "7d3e74a05d7db15bce4ad9ec0658ea98e3f06eeecf16b4c6fff2da457ddc2f34"),
+        (64, "ffe054fe7ae0cb6dc65c3af9b61d5209f439851db43d0ba5997337df154668eb"),
+        (65, "635361c48bb9eab14198e76ea8ab7f1a41685d6ad62aa9146d301d4f17eb0ae0"),
+        (119, "31eba51c313a5c08226adf18d4a359cfdfd8d2e816b13f4af952f7ea6584dcfb"),
+    ] {
+        assert_eq!(hex(&sha256(&vec![b'a'; length])), expected, "{} bytes", length);
+    }
+}
+
+#[test]
+fn fingerprints_hash_the_reduced_uncompressed_encoding() {
+    let p = BigInt::from(257);
+    assert_eq!(fingerprint(&Point::Infinity, &p), sha256(&[0]));
+    let point = Point::Affine(BigInt::from(3), BigInt::from(256));
+    assert_eq!(fingerprint(&point, &p), sha256(&[4, 0, 3, 1, 0]));
+    // Coordinates are reduced first, so every representative has the same fingerprint
+    let unreduced = Point::Affine(BigInt::from(3 + 257), BigInt::from(-1));
+    assert_eq!(fingerprint(&unreduced, &p), fingerprint(&point, &p));
+    let short = short_fingerprint(&point, &p);
+    assert_eq!(short.replace(':', ""), hex(&fingerprint(&point, &p)[..8]));
+    assert_eq!(short.len(), 19);
+}
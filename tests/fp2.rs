@@ -0,0 +1,118 @@
+//! The field axioms of `Fp2` on every pair of elements of small extensions, the inverse and square-root
+//! identities, and the Frobenius x -> x^p as `conjugate`, on small fields and on F_p² for p = 2^255 − 19.
+
+use num_bigint::BigInt;
+use num_traits::One;
+use weierstrass_to_montgomery::{default_rng, Fp2, SearchLimits};
+
+/// The extension of F_p by the square root of its smallest non-residue.
+fn extension(p: &BigInt) -> Fp2 {
+    let non_residue = Fp2::find_non_residue(p, &SearchLimits::default()).expect("p is an odd prime");
+    Fp2::new(&BigInt::from(0), &BigInt::from(0), p, &non_residue).expect("a non-residue")
+}
+
+/// c0 + c1·i in the extension of `zero`.
+fn element(zero: &Fp2, c0: u32, c1: u32) -> Fp2 {
+    Fp2::new(&BigInt::from(c0), &BigInt::from(c1), &zero.p, &zero.non_residue).expect("the same extension")
+}
+
+/// Every element of F_p², for small p.
+fn elements(p: u32) -> Vec<Fp2> {
+    let zero = extension(&BigInt::from(p));
+    (0..p).flat_map(|c0| (0..p).map(move |c1| (c0, c1))).map(|(c0, c1)| element(&zero, c0, c1)).collect()
+}
+
+/// The inverse, square-root and Frobenius identities of a single element.
+fn check_element(x: &Fp2) {
+    let (zero, one) = (x.zero(), x.one());
+    match x.inverse() {
+        Some(inverse) => assert_eq!(x * &inverse, one, "{}", x),
+        None => assert!(x.is_zero(), "{} has no inverse", x),
+    }
+    if let Some(root) = x.sqrt() {
+        assert_eq!(root.square(), *x, "{}", x);
+    }
+    assert_eq!(x.square(), x * x, "{}", x);
+    assert_eq!(x + &-x, zero, "{}", x);
+    // The Frobenius is x -> x^p, fixes exactly F_p and is an involution; x times it is the norm
+    let p = x.p.clone();
+    assert_eq!(x.conjugate(), x.pow(&p), "{}", x);
+    assert_eq!(x.conjugate() == *x, x.is_base(), "{}", x);
+    assert_eq!(x.conjugate().conjugate(), *x, "{}", x);
+    assert_eq!(x * &x.conjugate(), x.from_base(&x.norm()), "{}", x);
+    // Every element is a root of z^(p^2) − z
+    assert_eq!(x.pow(&(&p * &p)), *x, "{}", x);
+}
+
+/// The axioms and the Frobenius on a pair of elements.
+fn check_pair(x: &Fp2, y: &Fp2) {
+    let context = format!("x = {}, y = {}", x, y);
+    assert_eq!(x * y, y * x, "{}", context);
+    assert_eq!(x + y, y + x, "{}", context);
+    assert_eq!(&(x + y) - y, *x, "{}", context);
+    assert_eq!(&(x + y) * x, &x.square() + &(x * y), "{}", context);
+    assert_eq!((x * y).conjugate(), &x.conjugate() * &y.conjugate(), "{}", context);
+    assert_eq!((x + y).conjugate(), &x.conjugate() + &y.conjugate(), "{}", context);
+    if let Some(inverse) = y.inverse() {
+        assert_eq!(&(x * y) * &inverse, *x, "{}", context);
+    }
+}
+
+#[test]
+fn every_pair_of_small_extensions_obeys_the_field_axioms() {
+    for p in [3, 7, 13] {
+        let elements = elements(p);
+        for x in &elements {
+            check_element(x);
+            for y in &elements {
+                check_pair(x, y);
+            }
+        }
+    }
+}
+
+#[test]
+fn exactly_half_the_non_zero_elements_are_squares() {
+    for p in [3, 7, 13, 17] {
+        let elements = elements(p);
+        let squares: std::collections::HashSet<Fp2> = elements.iter().map(Fp2::square).collect();
+        for x in &elements {
+            assert_eq!(x.is_square(), squares.contains(x), "{} mod {}", x, p);
+            assert_eq!(x.sqrt().is_some(), squares.contains(x), "{} mod {}", x, p);
+        }
+        // 0 and the (p^2 − 1) / 2 non-zero squares; F_p itself is all squares in F_p²
+        assert_eq!(squares.len() as u32, (p * p - 1) / 2 + 1);
+        assert!(elements.iter().filter(|x| x.is_base()).all(Fp2::is_square));
+    }
+}
+
+#[test]
+fn large_extension_obeys_the_identities() {
+    let p = (BigInt::one() << 255u32) - 19u32;
+    let zero = extension(&p);
+    let mut rng = default_rng();
+    let mut elements = vec![zero.clone(), zero.one(), -zero.one(), element(&zero, 0, 1)];
+    elements.extend((0..8).map(|_| zero.random(&mut rng)));
+    elements.extend((0..4).map(|_| zero.random(&mut rng).square()));
+    elements.push(zero.from_base(&BigInt::from(2)));
+    for x in &elements {
+        check_element(x);
+        for y in &elements {
+            check_pair(x, y);
+        }
+    }
+    // The non-residue has no root in F_p, but i is one in F_p²
+    let non_residue = zero.from_base(&zero.non_residue);
+    assert_eq!(element(&zero, 0, 1).square(), non_residue);
+    assert_eq!(non_residue.sqrt().map(|root| root.square()), Some(non_residue));
+}
+
+#[test]
+fn new_rejects_residues_and_even_moduli() {
+    let (zero, seven) = (BigInt::from(0), BigInt::from(7));
+    // 2 = 3^2 is a square modulo 7, 3 is not
+    assert!(Fp2::new(&zero, &zero, &seven, &BigInt::from(2)).is_err());
+    assert!(Fp2::new(&zero, &zero, &seven, &BigInt::from(3)).is_ok());
+    assert!(Fp2::new(&zero, &zero, &BigInt::from(8), &BigInt::from(3)).is_err());
+    assert!(Fp2::new(&zero, &zero, &BigInt::from(2), &BigInt::from(1)).is_err());
+}
@@ -0,0 +1,90 @@
+use num_bigint::BigInt;
+use num_traits::Num;
+use weierstrass_to_montgomery::{scalar_mul, GlvEndomorphism, Point, WeierstrassCurve};
+
+fn hex(value: &str) -> BigInt {
+    BigInt::from_str_radix(value, 16).expect("a hexadecimal integer")
+}
+
+/// secp256k1 (SEC 2), y^2 = x^3 + 7 over F_p with p = 2^256 − 2^32 − 977, with its base point and order.
+fn secp256k1() -> (WeierstrassCurve, Point, BigInt) {
+    let p = (BigInt::from(1) << 256) - (BigInt::from(1) << 32) - 977u32;
+    let curve = WeierstrassCurve::new(&BigInt::from(0), &BigInt::from(7), &p).expect("non-singular");
+    let x = hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+    let y = hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8");
+    let base = curve.point(&x, &y).expect("on the curve");
+    let order = hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+    (curve, base, order)
+}
+
+/// y^2 = x^3 + b over F_p.
+fn j0_curve(b: u32, p: u32) -> WeierstrassCurve {
+    WeierstrassCurve::new(&BigInt::from(0), &BigInt::from(b), &BigInt::from(p)).expect("non-singular")
+}
+
+/// The first point of `curve` by increasing x.
+fn first_point(curve: &WeierstrassCurve) -> Point {
+    (0u32..)
+        .find_map(|x| curve.lift_x(&BigInt::from(x)).expect("x is in the field").pop())
+        .expect("the curve has affine points")
+}
+
+fn scalars(order: &BigInt) -> Vec<BigInt> {
+    let mut scalars: Vec<BigInt> = [0, 1, 2, 3, 7, -1, -12345].into_iter().map(BigInt::from).collect();
+    scalars.extend([order - 1u32, order.clone(), order + 1u32, order * 3u32 + 5u32, order / 3u32]);
+    scalars
+}
+
+#[test]
+fn agrees_with_double_and_add_on_secp256k1() {
+    let (curve, base, order) = secp256k1();
+    let glv = GlvEndomorphism::detect(&curve, &base, &order).expect("secp256k1 has the endomorphism");
+    let mut points = vec![base.clone(), scalar_mul(&curve, &base, &BigInt::from(0xdead_beefu32))];
+    points.push(glv.apply(&curve, &points[1]));
+    for point in &points {
+        for scalar in scalars(&order) {
+            assert_eq!(glv.mul(&curve, point, &scalar), scalar_mul(&curve, point, &scalar), "{}", scalar);
+        }
+    }
+    // The halves of a full-length scalar are about half as long
+    let (k1, k2) = glv.decompose(&(&order - 1u32));
+    assert!(k1.bits() <= 129 && k2.bits() <= 129, "{} {}", k1, k2);
+}
+
+#[test]
+fn agrees_with_double_and_add_on_every_point_of_a_small_j0_curve() {
+    // y^2 = x^3 + 5 over F_1033 has the prime order 1087
+    let curve = j0_curve(5, 1033);
+    let order = BigInt::from(1087);
+    let glv = GlvEndomorphism::detect(&curve, &first_point(&curve), &order).expect("p and n are 1 mod 3");
+    for x in 0u32..1033 {
+        for point in curve.lift_x(&BigInt::from(x)).expect("x is in the field") {
+            for scalar in scalars(&order) {
+                let expected = scalar_mul(&curve, &point, &scalar);
+                assert_eq!(glv.mul(&curve, &point, &scalar), expected, "{}·{}", scalar, point);
+            }
+        }
+    }
+}
+
+#[test]
+fn is_refused_when_the_curve_has_a_cofactor() {
+    // y^2 = x^3 + 2 over F_1021 has 1036 = 28·37 points; a point of order 37 has the endomorphism on its
+    // subgroup, but GLV would be wrong on the other points
+    let curve = j0_curve(2, 1021);
+    let order = BigInt::from(37);
+    let point = scalar_mul(&curve, &first_point(&curve), &BigInt::from(28));
+    assert!(!point.is_infinity());
+    assert!(scalar_mul(&curve, &point, &order).is_infinity());
+    assert_eq!(GlvEndomorphism::detect(&curve, &point, &order), None);
+}
+
+#[test]
+fn is_refused_for_a_wrong_order_or_a_nonzero_a() {
+    let (curve, base, order) = secp256k1();
+    assert_eq!(GlvEndomorphism::detect(&curve, &base, &(&order + 6u32)), None);
+    assert_eq!(GlvEndomorphism::detect(&curve, &Point::Infinity, &order), None);
+    let (a, b, p) = (BigInt::from(1), BigInt::from(5), BigInt::from(1033));
+    let curve = WeierstrassCurve::new(&a, &b, &p).expect("non-singular");
+    assert_eq!(GlvEndomorphism::detect(&curve, &first_point(&curve), &BigInt::from(1087)), None);
+}
@@ -0,0 +1,91 @@
+//! `HessianMap` over every point of small curves with a rational point of order 3: each point goes to the
+//! Hessian curve and back unchanged, and the map respects addition.
+
+use num_bigint::BigInt;
+use weierstrass_to_montgomery::{
+    scalar_mul, CurveGroup, HessianCurve, HessianMap, Point, TransformError, WeierstrassCurve,
+};
+
+/// Every point of `curve`, the point at infinity first, by lifting each x.
+fn points(curve: &WeierstrassCurve) -> Vec<Point> {
+    let mut points = vec![Point::Infinity];
+    let mut x = BigInt::from(0);
+    while x < curve.p {
+        points.extend(curve.lift_x(&x).unwrap_or_default());
+        x += 1u32;
+    }
+    points
+}
+
+/// The first affine point P of `points` with 3P = O.
+fn order_three(curve: &WeierstrassCurve, points: &[Point]) -> Point {
+    let three = BigInt::from(3);
+    let torsion = points.iter().skip(1).find(|point| scalar_mul(curve, point, &three).is_infinity());
+    torsion.expect("the curve order is a multiple of 3").clone()
+}
+
+#[test]
+fn every_point_round_trips() {
+    // Orders 105, 87, 102 and 90; every p is 2 (mod 3)
+    for (a, b, p) in [(1, 1, 101), (1, 3, 101), (2, 2, 101), (0, 1, 89)] {
+        let [a, b, p] = [a, b, p].map(BigInt::from);
+        let curve = WeierstrassCurve::new(&a, &b, &p).expect("non-singular");
+        let points = points(&curve);
+        assert!(points.len().is_multiple_of(3), "y^2 = x^3 + {}x + {} over F_{}", a, b, p);
+        let torsion = order_three(&curve, &points);
+        let map = HessianMap::new(&curve, &torsion).expect("a point of order 3");
+        assert_eq!(map.to_hessian(&torsion), Point::Affine(&p - 1u32, BigInt::from(0)));
+        let images: Vec<Point> = points.iter().map(|point| map.to_hessian(point)).collect();
+        for (point, image) in points.iter().zip(&images) {
+            assert!(map.hessian.contains(image), "{} maps off the Hessian curve", point);
+            assert_eq!(map.to_weierstrass(image), *point, "{} does not come back", point);
+        }
+        // The map is a bijection and a homomorphism
+        let mut distinct = images.clone();
+        distinct.sort_by_key(|image| format!("{}", image));
+        distinct.dedup();
+        assert_eq!(distinct.len(), points.len());
+        for (first, first_image) in points.iter().zip(&images).step_by(7) {
+            for (second, second_image) in points.iter().zip(&images) {
+                let sum = map.to_hessian(&curve.add(first, second));
+                assert_eq!(map.hessian.add(first_image, second_image), sum, "{} + {}", first, second);
+            }
+        }
+    }
+}
+
+#[test]
+fn the_curve_built_from_a_hessian_curve_maps_back_to_it() {
+    let p = BigInt::from(101);
+    for d in [0, 2, 5, 50] {
+        let hessian = HessianCurve::new(&BigInt::from(d), &p).expect("non-singular");
+        let map = hessian.to_weierstrass().expect("a Weierstrass curve");
+        let torsion = Point::Affine(map.x0.clone(), map.y0.clone());
+        let rebuilt = HessianMap::new(&map.weierstrass, &torsion).expect("a point of order 3");
+        assert_eq!(rebuilt.hessian, hessian, "d = {}", d);
+        for point in points(&map.weierstrass) {
+            assert_eq!(rebuilt.to_weierstrass(&map.to_hessian(&point)), point, "d = {}", d);
+        }
+    }
+}
+
+#[test]
+fn points_that_are_not_of_order_three_are_rejected() {
+    let [a, b, p] = [1, 1, 101].map(BigInt::from);
+    let curve = WeierstrassCurve::new(&a, &b, &p).expect("non-singular");
+    let rejected = |torsion: &Point| match HessianMap::new(&curve, torsion) {
+        Err(TransformError::InvalidParameters(message)) => message,
+        other => panic!("expected InvalidParameters, got {:?}", other),
+    };
+    assert!(rejected(&Point::Infinity).contains("not 1"));
+    let not_torsion = points(&curve).into_iter().find(|point| {
+        !point.is_infinity() && !scalar_mul(&curve, point, &BigInt::from(3)).is_infinity()
+    });
+    assert!(rejected(&not_torsion.expect("105 is not 3")).contains("does not have order 3"));
+    let off_curve = Point::Affine(BigInt::from(0), BigInt::from(0));
+    assert!(matches!(HessianMap::new(&curve, &off_curve), Err(TransformError::PointNotOnCurve)));
+    // p = 1 (mod 3) is refused before the point is looked at
+    let [a, b, p] = [1, 1, 103].map(BigInt::from);
+    let curve = WeierstrassCurve::new(&a, &b, &p).expect("non-singular");
+    assert!(HessianMap::new(&curve, &Point::Infinity).is_err());
+}
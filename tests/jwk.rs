@@ -0,0 +1,108 @@
+//! An EC JWK of Wei25519 and Wei448 through `ec_jwk_to_spec`, the transform and `okp_jwk` to the X25519
+//! and X448 keys of RFC 8037, and the keys and curves each side must refuse.
+
+use num_bigint::{BigInt, Sign};
+use weierstrass_to_montgomery::der::{decode_base64, encode_base64};
+use weierstrass_to_montgomery::jwk::{ec_jwk_to_spec, okp_jwk};
+use weierstrass_to_montgomery::{
+    field_byte_len, lookup, MontgomeryCurve, MontgomeryMap, Point, TransformError, TransformOptions,
+};
+
+/// A big-endian base64url coordinate as long as the field, as RFC 7518 requires.
+fn coordinate(value: &BigInt, len: usize) -> String {
+    let (_, digits) = value.to_bytes_be();
+    let mut bytes = vec![0u8; len - digits.len()];
+    bytes.extend(digits);
+    encode_base64(&bytes, true)
+}
+
+/// The EC JWK of the base point of the Weierstrass curve `name`.
+fn ec_jwk(name: &str) -> String {
+    let named = lookup(name).expect("in the registry");
+    let Point::Affine(x, y) = named.base_point() else { unreachable!("the base point is affine") };
+    let len = field_byte_len(&named.p());
+    let (x, y) = (coordinate(&x, len), coordinate(&y, len));
+    format!(r#"{{"kty": "EC", "crv": "{}", "x": "{}", "y": "{}"}}"#, name, x, y)
+}
+
+/// The `crv` and little-endian `x` of an OKP JWK.
+fn okp_members(jwk: &str) -> (String, BigInt) {
+    let value: serde_json::Value = serde_json::from_str(jwk).expect("JSON");
+    assert_eq!(value["kty"], "OKP");
+    let x = decode_base64(value["x"].as_str().expect("x is a string"), true).expect("base64url");
+    (value["crv"].as_str().expect("crv is a string").to_string(), BigInt::from_bytes_le(Sign::Plus, &x))
+}
+
+fn rejected(result: Result<impl std::fmt::Debug, TransformError>) -> String {
+    match result {
+        Err(TransformError::InvalidParameters(message)) => message,
+        other => panic!("expected InvalidParameters, got {:?}", other),
+    }
+}
+
+#[test]
+fn ec_jwks_become_okp_jwks() {
+    // The base points of Wei25519 and Wei448 map to those of Curve25519 and Curve448, u = 9 and u = 5
+    let pairs = [("Wei25519", "Curve25519", "X25519", 9), ("Wei448", "Curve448", "X448", 5)];
+    for (weierstrass, montgomery, crv, u) in pairs {
+        let spec = ec_jwk_to_spec(&ec_jwk(weierstrass)).expect("a valid EC JWK");
+        let named = lookup(weierstrass).expect("in the registry");
+        assert_eq!((spec.a.clone(), spec.b.clone()), named.coefficients());
+        assert_eq!(Point::Affine(spec.x.clone(), spec.y.clone()), named.base_point());
+
+        let map = MontgomeryMap::new(&spec.a, &spec.b, &spec.p, &TransformOptions::default()).expect("a map");
+        let (u_image, v_image) = map.map_point(&spec.x, &spec.y);
+        let image_curve = MontgomeryCurve::new(&map.a_montgomery, &map.b_montgomery, &spec.p);
+        let rescaling = image_curve.expect("non-singular").canonicalize().expect("A^2 − 4 is not zero");
+        let (a, b) = lookup(montgomery).expect("in the registry").coefficients();
+        assert_eq!((&rescaling.curve.a, &rescaling.curve.b), (&a, &b), "{}", montgomery);
+        let image = rescaling.map_point(&Point::Affine(u_image, v_image));
+
+        let jwk = okp_jwk(&rescaling.curve, &image).expect("Curve25519 and Curve448 have OKP names");
+        assert_eq!(okp_members(&jwk), (crv.to_string(), BigInt::from(u)));
+        let value: serde_json::Value = serde_json::from_str(&jwk).expect("JSON");
+        let x = value["x"].as_str().expect("x is a string");
+        assert_eq!(decode_base64(x, true).expect("base64url").len(), field_byte_len(&spec.p));
+    }
+}
+
+#[test]
+fn ec_jwk_to_spec_rejects_other_keys_and_curves() {
+    let jwk = ec_jwk("Wei25519");
+    assert!(rejected(ec_jwk_to_spec(&jwk.replace("\"EC\"", "\"OKP\""))).contains("expected an EC key"));
+    assert!(rejected(ec_jwk_to_spec(&jwk.replace("\"EC\"", "\"RSA\""))).contains("expected an EC key"));
+    // Montgomery and Edwards curves have no EC JWK, and unknown names none at all
+    for crv in ["X25519", "Curve25519", "Ed25519", "P-257"] {
+        let message = rejected(ec_jwk_to_spec(&jwk.replace("Wei25519", crv)));
+        assert!(message.contains("unknown or non-Weierstrass JWK curve"), "{}", crv);
+    }
+    // The coordinates of a 32-byte field are 32 bytes long, not 48
+    let p384 = ec_jwk("P-384");
+    let x384 = &p384[p384.find("\"x\"").expect("an x")..];
+    let mixed = format!(r#"{{"kty": "EC", "crv": "Wei25519", {}"#, x384);
+    assert!(rejected(ec_jwk_to_spec(&mixed)).contains("is 48 bytes long instead of 32"));
+
+    let value: serde_json::Value = serde_json::from_str(&jwk).expect("JSON");
+    let x = value["x"].as_str().expect("x is a string");
+    let no_y = format!(r#"{{"kty": "EC", "crv": "Wei25519", "x": "{}"}}"#, x);
+    assert!(rejected(ec_jwk_to_spec(&no_y)).contains("needs a y coordinate"));
+    let off_curve = format!(r#"{{"kty": "EC", "crv": "Wei25519", "x": "{}", "y": "{}"}}"#, x, x);
+    assert!(matches!(ec_jwk_to_spec(&off_curve), Err(TransformError::PointNotOnCurve)));
+    assert!(rejected(ec_jwk_to_spec(r#"{"kty": "EC"}"#)).contains("malformed JWK"));
+}
+
+#[test]
+fn okp_jwk_rejects_other_curves_and_points() {
+    let named = lookup("Curve25519").expect("in the registry");
+    let (a, b) = named.coefficients();
+    let curve25519 = MontgomeryCurve::new(&a, &b, &named.p()).expect("non-singular");
+    let base = named.base_point();
+    assert_eq!(okp_members(&okp_jwk(&curve25519, &base).expect("X25519")).1, BigInt::from(9));
+    // A rescaled Curve25519 is isomorphic, but not the curve X25519 names
+    let rescaled = curve25519.rescale(&BigInt::from(2)).expect("d is not zero");
+    assert!(rejected(okp_jwk(&rescaled.curve, &rescaled.map_point(&base))).contains("neither Curve25519"));
+    assert!(rejected(okp_jwk(&curve25519, &Point::Infinity)).contains("point at infinity"));
+    let Point::Affine(u, v) = base else { unreachable!("the base point is affine") };
+    let off_curve = Point::Affine(u, v + 1u32);
+    assert!(matches!(okp_jwk(&curve25519, &off_curve), Err(TransformError::PointNotOnCurve)));
+}
@@ -0,0 +1,85 @@
+//! The `WMAP` binary encoding of `MontgomeryMap`: round trips, and the inputs `from_bytes` must refuse
+//! so that every map has exactly one encoding and a decoded map is usable.
+
+use num_bigint::BigInt;
+use num_traits::Zero;
+use weierstrass_to_montgomery::{
+    lookup, MontgomeryMap, TransformError, TransformOptions, MAP_FORMAT_VERSION,
+};
+
+fn wei25519_map() -> MontgomeryMap {
+    let curve = lookup("Wei25519").expect("in the registry");
+    let (a, b) = curve.coefficients();
+    MontgomeryMap::new(&a, &b, &curve.p(), &TransformOptions::default()).expect("Wei25519 has a map")
+}
+
+/// The map of y^2 = x^3 + x over F_13, whose b is zero.
+fn small_map() -> MontgomeryMap {
+    let [a, b, p] = [1, 0, 13].map(BigInt::from);
+    MontgomeryMap::new(&a, &b, &p, &TransformOptions::default()).expect("y^2 = x^3 + x over F_13 has a map")
+}
+
+/// Encodes the eight values as `to_bytes` does, but with each magnitude given as is.
+fn encode(magnitudes: &[Vec<u8>]) -> Vec<u8> {
+    let mut bytes = b"WMAP".to_vec();
+    bytes.extend_from_slice(&MAP_FORMAT_VERSION.to_be_bytes());
+    for magnitude in magnitudes {
+        bytes.extend_from_slice(&(magnitude.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(magnitude);
+    }
+    bytes
+}
+
+/// The minimal big-endian magnitudes of the values of `map`, empty for zero.
+fn magnitudes(map: &MontgomeryMap) -> Vec<Vec<u8>> {
+    let values = [&map.p, &map.a, &map.b, &map.z0, &map.s, &map.s_inv, &map.a_montgomery, &map.b_montgomery];
+    values.iter().map(|value| if value.is_zero() { Vec::new() } else { value.to_bytes_be().1 }).collect()
+}
+
+fn rejected(bytes: &[u8]) -> String {
+    match MontgomeryMap::from_bytes(bytes) {
+        Err(TransformError::InvalidParameters(message)) => message,
+        other => panic!("expected InvalidParameters, got {:?}", other),
+    }
+}
+
+#[test]
+fn maps_round_trip() {
+    let map = wei25519_map();
+    let bytes = map.to_bytes();
+    assert_eq!(encode(&magnitudes(&map)), bytes);
+    assert_eq!(MontgomeryMap::from_bytes(&bytes).expect("decodes"), map);
+    // Zero encodes as the empty magnitude
+    let small = small_map();
+    assert_eq!(encode(&magnitudes(&small)), small.to_bytes());
+    assert_eq!(MontgomeryMap::from_bytes(&small.to_bytes()).expect("decodes"), small);
+}
+
+#[test]
+fn leading_zero_bytes_are_rejected() {
+    let map = wei25519_map();
+    for index in 0..8 {
+        let mut padded = magnitudes(&map);
+        padded[index].insert(0, 0);
+        assert!(rejected(&encode(&padded)).contains("leading zero"), "value {}", index);
+    }
+    // Zero itself must be empty, not a single zero byte
+    let mut padded = magnitudes(&small_map());
+    let zero = padded.iter().position(Vec::is_empty).expect("b = 0");
+    padded[zero] = vec![0];
+    assert!(rejected(&encode(&padded)).contains("leading zero"));
+}
+
+#[test]
+fn composite_moduli_truncation_and_trailing_bytes_are_rejected() {
+    let mut values = magnitudes(&wei25519_map());
+    // 2^255 − 19 + 2 is odd and composite (divisible by 3)
+    let composite = (BigInt::from(1) << 255u32) - 17u32;
+    values[0] = composite.to_bytes_be().1;
+    assert!(rejected(&encode(&values)).contains("is not a prime"));
+
+    let bytes = wei25519_map().to_bytes();
+    assert!(rejected(&bytes[..bytes.len() - 1]).contains("truncated"));
+    assert!(rejected(&[&bytes[..], &[0]].concat()).contains("trailing"));
+    assert!(rejected(b"WMAQ").contains("header"));
+}
@@ -0,0 +1,104 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use weierstrass_to_montgomery::{batch_inverse, extended_gcd, mod_inverse, FieldElement};
+
+/// Inputs are checked for every pair in [-BOUND, BOUND]^2 against brute force.
+const BOUND: i64 = 40;
+
+fn brute_gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The inverse in [0, |modulus|) found by trying every candidate.
+fn brute_inverse(value: i64, modulus: i64) -> Option<i64> {
+    let modulus = modulus.abs();
+    (0..modulus).find(|candidate| (value * candidate - 1).rem_euclid(modulus) == 0)
+}
+
+#[test]
+fn extended_gcd_returns_a_non_negative_gcd_and_bezout_coefficients() {
+    for a in -BOUND..=BOUND {
+        for b in -BOUND..=BOUND {
+            let (gcd, x, y) = extended_gcd(&BigInt::from(a), &BigInt::from(b));
+            assert_eq!(gcd, BigInt::from(brute_gcd(a, b)), "gcd({}, {})", a, b);
+            assert_eq!(BigInt::from(a) * &x + BigInt::from(b) * &y, gcd, "Bezout for ({}, {})", a, b);
+        }
+    }
+}
+
+#[test]
+fn extended_gcd_of_zeros() {
+    let zeros = extended_gcd(&BigInt::zero(), &BigInt::zero());
+    assert_eq!(zeros, (BigInt::zero(), BigInt::one(), BigInt::zero()));
+    let (gcd, x, _) = extended_gcd(&BigInt::from(-7), &BigInt::zero());
+    assert_eq!((gcd, x), (BigInt::from(7), BigInt::from(-1)));
+}
+
+#[test]
+fn mod_inverse_matches_brute_force() {
+    for modulus in -BOUND..=BOUND {
+        for value in -BOUND..=BOUND {
+            let expected = if modulus == 0 { None } else { brute_inverse(value, modulus) };
+            let inverse = mod_inverse(&BigInt::from(value), &BigInt::from(modulus));
+            assert_eq!(inverse, expected.map(BigInt::from), "{}^-1 mod {}", value, modulus);
+        }
+    }
+}
+
+#[test]
+fn mod_inverse_edge_cases() {
+    let [zero, one, minus_one] = [0, 1, -1].map(BigInt::from);
+    // modulo 1 every value, zero included, is its own inverse class 0
+    assert_eq!(mod_inverse(&zero, &one), Some(zero.clone()));
+    assert_eq!(mod_inverse(&BigInt::from(-5), &minus_one), Some(zero.clone()));
+    assert_eq!(mod_inverse(&one, &zero), None);
+    assert_eq!(mod_inverse(&zero, &BigInt::from(7)), None);
+    assert_eq!(mod_inverse(&minus_one, &BigInt::from(7)), Some(BigInt::from(6)));
+    assert_eq!(mod_inverse(&BigInt::from(3), &BigInt::from(-7)), Some(BigInt::from(5)));
+}
+
+#[test]
+fn mod_inverse_of_large_values() {
+    let p = (BigInt::one() << 255u32) - 19u32;
+    for value in [BigInt::from(2), -(BigInt::one() << 300u32) - 1u32, &p * 3u32 + 1u32] {
+        let inverse = mod_inverse(&value, &p).expect("p is prime");
+        assert!(inverse >= BigInt::zero() && inverse < p);
+        assert!((value * inverse).mod_floor(&p).is_one());
+    }
+    assert_eq!(mod_inverse(&(&p * 5u32), &p), None);
+}
+
+#[test]
+fn batch_inverse_matches_inverting_one_by_one() {
+    assert!(batch_inverse(&[]).is_empty());
+    for modulus in [2, 13, 97] {
+        let modulus = BigInt::from(modulus);
+        let element = |value: i64| FieldElement::new(&BigInt::from(value), &modulus);
+        // Zeros anywhere, alone or in a row, come back as zero and leave the other inverses alone
+        let batches: Vec<Vec<i64>> = vec![
+            vec![0],
+            vec![0, 0, 0],
+            vec![5],
+            (-BOUND..=BOUND).collect(),
+            vec![0, 3, 0, 0, 7, -1, 0],
+            vec![1, 1, 96, 96, 98, 0],
+        ];
+        for batch in batches {
+            let elements: Vec<FieldElement> = batch.into_iter().map(element).collect();
+            let expected: Vec<FieldElement> =
+                elements.iter().map(|element| element.inverse().unwrap_or_else(|| element.zero())).collect();
+            assert_eq!(batch_inverse(&elements), expected, "mod {}", modulus);
+        }
+    }
+    let p = (BigInt::one() << 255u32) - 19u32;
+    let elements: Vec<FieldElement> =
+        (0u32..50).map(|i| FieldElement::new(&((&p >> i) * (i % 7)), &p)).collect();
+    let expected: Vec<FieldElement> =
+        elements.iter().map(|element| element.inverse().unwrap_or_else(|| element.zero())).collect();
+    assert_eq!(batch_inverse(&elements), expected);
+}
@@ -0,0 +1,81 @@
+//! Every square-root algorithm against brute force on small primes, and Tonelli-Shanks on primes whose
+//! p − 1 has more factors of 2 than fit in a machine word shift; the `FieldElement` roots keep errors apart
+//! from non-squares.
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::One;
+use weierstrass_to_montgomery::{
+    mod_sqrt_with, FieldElement, SearchLimits, SqrtAlgorithm, TransformError,
+};
+
+const ALGORITHMS: [SqrtAlgorithm; 3] =
+    [SqrtAlgorithm::Auto, SqrtAlgorithm::TonelliShanks, SqrtAlgorithm::Cipolla];
+
+/// Checks that `algorithm` finds a root of `value` modulo `p` exactly when there is one.
+fn check(value: &BigInt, p: &BigInt, algorithm: SqrtAlgorithm, is_square: bool) {
+    let context = format!("{:?}, {} mod {}", algorithm, value, p);
+    match mod_sqrt_with(value, p, algorithm, &SearchLimits::default()).expect("p is prime") {
+        Some(root) => {
+            assert!(is_square, "{}: a root of a non-square", context);
+            assert_eq!((&root * &root).mod_floor(p), value.mod_floor(p), "{}", context);
+        }
+        None => assert!(!is_square, "{}: no root of a square", context),
+    }
+}
+
+#[test]
+fn every_algorithm_matches_brute_force_on_small_primes() {
+    // 97 and 193 have 2-adicity 5 and 6, 257 has 8
+    for p in [3u32, 5, 7, 13, 17, 97, 193, 257] {
+        let squares: Vec<bool> = (0..p).map(|value| (0..p).any(|root| root * root % p == value)).collect();
+        let big_p = BigInt::from(p);
+        for value in 0..p {
+            for algorithm in ALGORITHMS {
+                check(&BigInt::from(value), &big_p, algorithm, squares[value as usize]);
+            }
+        }
+    }
+}
+
+#[test]
+fn tonelli_shanks_handles_a_2_adicity_above_32() {
+    // p − 1 = 27·2^40 and 15·2^48: the exponent 2^(m − i − 1) of a Tonelli-Shanks step outgrows a u32
+    for p in [BigInt::from(27u64 << 40) + 1u32, BigInt::from(15u64 << 48) + 1u32] {
+        let three = BigInt::from(3);
+        let mut values = vec![three.modpow(&(BigInt::one() << 33u32), &p), BigInt::from(4), BigInt::one()];
+        values.extend((2u32..40).map(|k| three.modpow(&(BigInt::one() << k), &p)));
+        for value in &values {
+            for algorithm in ALGORITHMS {
+                check(value, &p, algorithm, true);
+            }
+        }
+        // A non-residue stays without a root whichever algorithm is forced
+        let half = (&p - 1u32) / 2u32;
+        let non_residue = (2u32..).map(BigInt::from).find(|z| !z.modpow(&half, &p).is_one());
+        let non_residue = non_residue.expect("a prime has non-residues");
+        for algorithm in ALGORITHMS {
+            check(&non_residue, &p, algorithm, false);
+        }
+    }
+}
+
+#[test]
+fn field_element_roots_tell_errors_from_non_squares() {
+    let p = BigInt::from(13);
+    let [square, non_square] = [10, 5].map(|value| FieldElement::new(&BigInt::from(value), &p));
+    let root = square.sqrt().expect("13 is prime").expect("10 = 6^2");
+    assert_eq!(root.square(), square);
+    let (low, high) = square.sqrt_both().expect("13 is prime").expect("10 = 6^2");
+    assert_eq!((low.value, high.value), (BigInt::from(6), BigInt::from(7)));
+    assert_eq!(square.sqrt_even().expect("13 is prime").map(|root| root.value), Some(BigInt::from(6)));
+    assert_eq!(square.sqrt_odd().expect("13 is prime").map(|root| root.value), Some(BigInt::from(7)));
+    assert_eq!(non_square.sqrt().expect("13 is prime"), None);
+    assert_eq!(non_square.sqrt_both().expect("13 is prime"), None);
+    // Modulo 15 there is no field to take roots in, which is an error and not "no root"
+    let composite = FieldElement::new(&BigInt::from(4), &BigInt::from(15));
+    assert!(matches!(composite.sqrt(), Err(TransformError::InvalidParameters(_))));
+    assert!(matches!(composite.sqrt_both(), Err(TransformError::InvalidParameters(_))));
+    assert!(matches!(composite.sqrt_even(), Err(TransformError::InvalidParameters(_))));
+    assert!(matches!(composite.sqrt_odd(), Err(TransformError::InvalidParameters(_))));
+}
@@ -0,0 +1,62 @@
+//! `MontgomeryCurve::compress` and `decompress` over every point of a small curve, including the points
+//! with v = 0 and the point at infinity, and the encodings `decompress` must refuse.
+
+use num_bigint::BigInt;
+use num_traits::Zero;
+use weierstrass_to_montgomery::{CurveGroup, MontgomeryCurve, Point, TransformError};
+
+const P: u32 = 101;
+
+/// 5·v^2 = u^3 + 3·u^2 + u over F_101; B ≠ 1, so decompression has to divide by it.
+fn curve() -> MontgomeryCurve {
+    MontgomeryCurve::new(&BigInt::from(3), &BigInt::from(5), &BigInt::from(P)).expect("non-singular")
+}
+
+/// The point at infinity and every affine point, by brute force over u and v.
+fn points(curve: &MontgomeryCurve) -> Vec<Point> {
+    let affine = (0..P).flat_map(|u| (0..P).map(move |v| Point::Affine(BigInt::from(u), BigInt::from(v))));
+    std::iter::once(Point::Infinity).chain(affine.filter(|point| curve.contains(point))).collect()
+}
+
+#[test]
+fn every_point_round_trips() {
+    let curve = curve();
+    let points = points(&curve);
+    for point in &points {
+        let bytes = curve.compress(point);
+        assert_eq!(curve.decompress(&bytes).expect("a compressed point"), *point, "{}", point);
+        // The uncompressed form decodes to the same point
+        if let Point::Affine(u, v) = point {
+            let uncompressed = [4, u.to_bytes_be().1[0], v.to_bytes_be().1[0]];
+            assert_eq!(curve.decompress(&uncompressed).expect("an uncompressed point"), *point);
+        }
+    }
+    assert_eq!(curve.compress(&Point::Infinity), [0]);
+    // (0, 0) and the other points of order two, where v = 0, compress with an even tag
+    let two_torsion: Vec<&Point> =
+        points.iter().filter(|point| matches!(point, Point::Affine(_, v) if v.is_zero())).collect();
+    assert!(two_torsion.contains(&&Point::Affine(BigInt::zero(), BigInt::zero())));
+    for point in two_torsion {
+        assert_eq!(curve.compress(point)[0], 2, "{}", point);
+    }
+}
+
+#[test]
+fn malformed_and_off_curve_encodings_are_rejected() {
+    let curve = curve();
+    let rhs = |u: u32| curve.rhs(&BigInt::from(u));
+    // A u whose (u^3 + A·u^2 + u) / B has no square root lifts to no point
+    let lifts = |u: u32| !curve.lift_u(&BigInt::from(u)).expect("u is in the field").is_empty();
+    let u = (0..P).find(|&u| !lifts(u)).expect("about half the u have no point");
+    assert!(!rhs(u).is_zero());
+    for tag in [2, 3] {
+        assert!(matches!(curve.decompress(&[tag, u as u8]), Err(TransformError::PointNotOnCurve)));
+    }
+    // v = 0 has no odd root
+    assert!(matches!(curve.decompress(&[3, 0]), Err(TransformError::InvalidParameters(_))));
+    // An uncompressed point off the curve, a u not below p, and wrong lengths or tags
+    assert!(matches!(curve.decompress(&[4, 0, 1]), Err(TransformError::PointNotOnCurve)));
+    for bytes in [&[2, P as u8][..], &[2], &[2, 1, 1], &[0, 0], &[5, 1], &[]] {
+        assert!(matches!(curve.decompress(bytes), Err(TransformError::InvalidParameters(_))), "{:?}", bytes);
+    }
+}
@@ -0,0 +1,83 @@
+//! The x-only Montgomery ladder against `scalar_mul` for every point of small Montgomery curves and every
+//! scalar up to the group order, and the constant-time select and swap with the choice clear and set.
+
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+use weierstrass_to_montgomery::{scalar_mul, CurveGroup, FieldElement, MontgomeryCurve, Point};
+
+/// Every affine point of `curve`, by brute force over u and v.
+fn points(curve: &MontgomeryCurve, p: u32) -> Vec<Point> {
+    (0..p)
+        .flat_map(|u| (0..p).map(move |v| Point::Affine(BigInt::from(u), BigInt::from(v))))
+        .filter(|point| curve.contains(point))
+        .collect()
+}
+
+/// The u-coordinate of a point, with 0 for the point at infinity as the ladder returns it.
+fn u_of(point: &Point) -> BigInt {
+    match point {
+        Point::Infinity => BigInt::zero(),
+        Point::Affine(u, _) => u.clone(),
+    }
+}
+
+#[test]
+fn ladder_agrees_with_scalar_mul_on_small_curves() {
+    // The ladder does not use B, so B ≠ 1 must not change the u-coordinates it finds
+    for (a, b, p) in [(3, 1, 101), (3, 5, 101), (6, 1, 103), (10, 7, 89)] {
+        let [big_a, big_b, big_p] = [a, b, p].map(BigInt::from);
+        let curve = MontgomeryCurve::new(&big_a, &big_b, &big_p).expect("non-singular");
+        let points = points(&curve, p);
+        let order = points.len() as u32 + 1;
+        for point in &points {
+            // Every scalar below the group order, the order itself, which gives 0, and one past it
+            for k in 0..=order + 1 {
+                let scalar = BigInt::from(k);
+                let expected = u_of(&scalar_mul(&curve, point, &scalar));
+                let context = format!("{}·{} on A = {}, B = {} mod {}", k, point, a, b, p);
+                assert_eq!(curve.ladder(&u_of(point), &scalar), expected, "{}", context);
+            }
+        }
+    }
+}
+
+#[test]
+fn ct_select_and_swap_follow_the_choice() {
+    // One word and several: values below 2^255 − 19 take eight 32-bit words
+    for p in [BigInt::from(101), (BigInt::one() << 255u32) - 19u32] {
+        let first = FieldElement::new(&BigInt::from(7), &p);
+        let second = FieldElement::new(&(&p - 2u32), &p);
+        for (x, y) in [(&first, &second), (&second, &first), (&first, &first)] {
+            assert_eq!(x.ct_select(y, false), *x);
+            assert_eq!(x.ct_select(y, true), *y);
+            let (mut kept, mut other) = (x.clone(), y.clone());
+            FieldElement::ct_swap(&mut kept, &mut other, false);
+            assert_eq!((&kept, &other), (x, y));
+            FieldElement::ct_swap(&mut kept, &mut other, true);
+            assert_eq!((&kept, &other), (y, x));
+        }
+        // Zero has no words at all, so it is padded like any short value
+        let zero = first.zero();
+        assert_eq!(zero.ct_select(&second, true), second);
+        assert_eq!(second.ct_select(&zero, true), zero);
+
+        let points = [Point::Infinity, Point::Affine(BigInt::from(3), &p - 1u32)];
+        for (x, y) in [(&points[0], &points[1]), (&points[1], &points[0])] {
+            assert_eq!(x.ct_select(y, false, &p), *x);
+            assert_eq!(x.ct_select(y, true, &p), *y);
+            let (mut kept, mut other) = (x.clone(), y.clone());
+            Point::ct_swap(&mut kept, &mut other, false, &p);
+            assert_eq!((&kept, &other), (x, y));
+            Point::ct_swap(&mut kept, &mut other, true, &p);
+            assert_eq!((&kept, &other), (y, x));
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "different moduli")]
+fn ct_swap_rejects_elements_of_different_fields() {
+    let mut first = FieldElement::new(&BigInt::from(1), &BigInt::from(101));
+    let mut second = FieldElement::new(&BigInt::from(1), &BigInt::from(103));
+    FieldElement::ct_swap(&mut first, &mut second, true);
+}
@@ -0,0 +1,52 @@
+//! `mod_nth_root` against brute force: for small primes whose p − 1 shares 2-, 3- and higher prime-power
+//! factors with n, every residue has a root exactly when it is an n-th power, and the root is one.
+
+use num_bigint::BigInt;
+use num_traits::Pow;
+use weierstrass_to_montgomery::{mod_nth_root, SearchLimits, TransformError};
+
+const EXPONENTS: [u64; 11] = [1, 2, 3, 4, 6, 8, 9, 12, 16, 27, 64];
+
+/// x^n mod p by repeated multiplication.
+fn power(x: u64, n: u64, p: u64) -> u64 {
+    (0..n).fold(1 % p, |product, _| product * x % p)
+}
+
+#[test]
+fn every_residue_matches_brute_force() {
+    // p − 1 = 2^2·3, 2^4, 2·3^2, 2^2·3^2, 2^3·3^2, 2^5·3, 2^2·3^3, 2·3^4, 2^6·3, 2^8, 2^4·3^3, and
+    // 2·11 and 2·5·13 with no factor 3 or 4
+    for p in [13u64, 17, 19, 37, 73, 97, 109, 163, 193, 257, 433, 23, 131] {
+        let big_p = BigInt::from(p);
+        for n in EXPONENTS {
+            let mut powers = vec![false; p as usize];
+            for x in 0..p {
+                powers[power(x, n, p) as usize] = true;
+            }
+            for value in 0..p {
+                let context = format!("{}-th root of {} mod {}", n, value, p);
+                let root = mod_nth_root(&BigInt::from(value), n, &big_p, &SearchLimits::default());
+                match root.expect("p is prime") {
+                    Some(root) => {
+                        assert!(powers[value as usize], "{}: a root of a non-power", context);
+                        let root = u64::try_from(&root).expect("a root below p");
+                        assert_eq!(power(root, n, p), value, "{}", context);
+                    }
+                    None => assert!(!powers[value as usize], "{}: no root of an n-th power", context),
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn unreduced_values_and_a_zero_exponent() {
+    let p = BigInt::from(97);
+    // 28 = 14^3 mod 97 has a cube root however it is represented
+    for value in [28, 28 + 97, 28 - 97 * 3] {
+        let root = mod_nth_root(&BigInt::from(value), 3, &p, &SearchLimits::default()).expect("p is prime");
+        assert_eq!(root.expect("28 is a cube").pow(3u32) % &p, BigInt::from(28));
+    }
+    let zero_th = mod_nth_root(&BigInt::from(2), 0, &p, &SearchLimits::default());
+    assert!(matches!(zero_th, Err(TransformError::InvalidParameters(_))));
+}
@@ -0,0 +1,49 @@
+use num_bigint::BigInt;
+use weierstrass_to_montgomery::{count_ops, lookup, CurveGroup, MontgomeryCurve, OpCounts, WeierstrassCurve};
+
+fn counts(multiplications: u64, squarings: u64, inversions: u64) -> OpCounts {
+    OpCounts { multiplications, squarings, inversions }
+}
+
+/// y^2 = x^3 + 8x + 2 over F_17, the default curve of the command-line tool.
+fn small_curve() -> WeierstrassCurve {
+    WeierstrassCurve::new(&BigInt::from(8), &BigInt::from(2), &BigInt::from(17)).expect("non-singular")
+}
+
+#[test]
+fn affine_weierstrass_formulas_cost_what_the_textbook_says() {
+    let curve = small_curve();
+    let point = curve.point(&BigInt::from(14), &BigInt::from(6)).expect("on the curve");
+    let other = curve.point(&BigInt::from(2), &BigInt::from(3)).expect("on the curve");
+    let (_, addition) = count_ops(|| curve.add(&point, &other));
+    assert_eq!(addition, counts(2, 1, 1));
+    let (_, doubling) = count_ops(|| curve.double(&point));
+    assert_eq!(doubling, counts(2, 2, 1));
+    assert_eq!(counts(2, 1, 1).to_string(), "2M + 1S + 1I");
+}
+
+#[test]
+fn nested_counts_add_up() {
+    let curve = small_curve();
+    let point = curve.point(&BigInt::from(14), &BigInt::from(6)).expect("on the curve");
+    let ((_, inner), outer) = count_ops(|| {
+        curve.double(&point);
+        count_ops(|| curve.double(&point))
+    });
+    assert_eq!(inner, counts(2, 2, 1));
+    assert_eq!(outer, inner + inner);
+}
+
+#[test]
+fn the_ladder_costs_the_same_for_every_scalar() {
+    // Curve25519, whose ladder runs p.bits() + 1 steps of 6M + 4S whatever the bits of the scalar
+    let curve = lookup("Curve25519").expect("in the registry");
+    let p = curve.p();
+    let (a, b) = curve.coefficients();
+    let montgomery = MontgomeryCurve::new(&a, &b, &p).expect("B ≠ 0");
+    let u = BigInt::from(9);
+    let (_, one) = count_ops(|| montgomery.ladder(&u, &BigInt::from(1)));
+    let (_, large) = count_ops(|| montgomery.ladder(&u, &(&p - 2u32)));
+    assert_eq!(one, large);
+    assert!(one.multiplications >= 6 * (p.bits() + 1) && one.squarings >= 4 * (p.bits() + 1));
+}
@@ -0,0 +1,167 @@
+//! Interoperability with OpenSSL: keys generated by the `openssl` binary are read with
+//! `spki::parse_public_key`, transformed and checked against the inverse map. Built only with
+//! `--features openssl-tests`, and then the binary must be on the PATH.
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use weierstrass_to_montgomery::spki::parse_public_key;
+use weierstrass_to_montgomery::{
+    default_rng, field_byte_len, lookup, named_curves, scalar_mul, transform_with_map, CurveGroup, CurveModel,
+    MontgomeryCurve, MontgomeryMap, NamedCurve, Point, TransformError, TransformOptions, WeierstrassCurve,
+};
+
+/// Random keys generated per curve.
+const KEYS: usize = 3;
+
+/// Runs `openssl` with `input` on stdin and returns its stdout, failing the test on a non-zero exit.
+fn openssl(args: &[&str], input: &[u8]) -> Vec<u8> {
+    let mut child = Command::new("openssl")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("the openssl binary is on the PATH");
+    child.stdin.take().expect("stdin is piped").write_all(input).expect("openssl reads its input");
+    let output = child.wait_with_output().expect("openssl runs");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "openssl {:?} failed: {}", args, stderr);
+    output.stdout
+}
+
+fn element(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![tag];
+    if contents.len() < 0x80 {
+        bytes.push(contents.len() as u8);
+    } else {
+        let length = contents.len().to_be_bytes();
+        let length = &length[length.iter().position(|&byte| byte != 0).expect("non-zero length")..];
+        bytes.push(0x80 | length.len() as u8);
+        bytes.extend_from_slice(length);
+    }
+    bytes.extend_from_slice(contents);
+    bytes
+}
+
+fn integer(value: &BigInt) -> Vec<u8> {
+    element(0x02, &value.to_signed_bytes_be())
+}
+
+fn field_element(value: &BigInt, len: usize) -> Vec<u8> {
+    let (_, digits) = value.to_bytes_be();
+    let mut bytes = vec![0u8; len - digits.len()];
+    bytes.extend_from_slice(&digits);
+    element(0x04, &bytes)
+}
+
+/// Explicit ECParameters (X9.62 SpecifiedECDomain) for a registry curve, which OpenSSL accepts for
+/// curves it has no name for.
+fn explicit_parameters(named: &NamedCurve) -> Vec<u8> {
+    let (curve, base) = named.to_weierstrass().expect("registry curves convert");
+    let len = field_byte_len(&curve.p);
+    let prime_field = [0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x01, 0x01];
+    let field = element(0x30, &[&prime_field[..], &integer(&curve.p)].concat());
+    let coefficients = element(0x30, &[field_element(&curve.a, len), field_element(&curve.b, len)].concat());
+    let base = element(0x04, &curve.to_sec1(&base, false));
+    let order = integer(&named.order());
+    let cofactor = integer(&BigInt::from(named.cofactor));
+    let version = integer(&BigInt::from(1));
+    element(0x30, &[version, field, coefficients, base, order, cofactor].concat())
+}
+
+/// The inverse of `MontgomeryMap::map_point`, (u, v) -> (s·u + z0, s·v).
+fn unmap(s: &BigInt, z0: &BigInt, p: &BigInt, u: &BigInt, v: &BigInt) -> Point {
+    Point::Affine((s * u + z0).mod_floor(p), (s * v).mod_floor(p))
+}
+
+fn transform(
+    x: &BigInt,
+    y: &BigInt,
+    a: &BigInt,
+    b: &BigInt,
+    p: &BigInt,
+) -> Result<(BigInt, BigInt, MontgomeryMap), TransformError> {
+    transform_with_map(x, y, a, b, p, &TransformOptions::default(), &mut default_rng())
+}
+
+#[test]
+fn named_curve_keys_are_imported_in_every_encoding() {
+    let curves = named_curves().iter().filter(|named| named.model == CurveModel::Weierstrass);
+    for named in curves.filter(|named| named.oid.is_some()) {
+        let curve_option = format!("ec_paramgen_curve:{}", named.name);
+        for _ in 0..KEYS {
+            let pkcs8 = openssl(&["genpkey", "-algorithm", "EC", "-pkeyopt", &curve_option], b"");
+            let spki = openssl(&["pkey", "-pubout"], &pkcs8);
+            let key = parse_public_key(&spki).expect("the SPKI key is read");
+            assert_eq!(key.named.map(|entry| entry.name), Some(named.name));
+            let encodings = [
+                openssl(&["pkey", "-pubout", "-outform", "DER"], &pkcs8),
+                openssl(&["ec", "-pubout", "-conv_form", "compressed"], &pkcs8),
+                openssl(&["ec", "-pubout", "-param_enc", "explicit"], &pkcs8),
+                pkcs8.clone(),
+                openssl(&["ec"], &pkcs8),
+                // Without its public key the file makes the importer compute d·G itself
+                openssl(&["ec", "-no_public"], &pkcs8),
+            ];
+            for encoding in &encodings {
+                let other = parse_public_key(encoding).expect("every encoding is read");
+                assert_eq!((&other.curve, &other.point), (&key.curve, &key.point), "{}", named.name);
+            }
+            let Point::Affine(x, y) = &key.point else { panic!("OpenSSL keys are affine") };
+            let WeierstrassCurve { a, b, p } = &key.curve;
+            let result = transform(x, y, a, b, p);
+            // Prime-order curves have no point of order 2
+            assert!(matches!(result, Err(TransformError::NoMontgomeryForm(_))), "{}", named.name);
+        }
+    }
+}
+
+#[test]
+fn explicit_parameter_keys_transform_and_map_back() {
+    for (name, montgomery) in [("Wei25519", "Curve25519"), ("Wei448", "Curve448")] {
+        let named = lookup(name).expect("registered");
+        let parameters = explicit_parameters(named);
+        openssl(&["ecparam", "-inform", "DER", "-check", "-noout"], &parameters);
+        for _ in 0..KEYS {
+            let private = openssl(&["ecparam", "-inform", "DER", "-genkey", "-noout"], &parameters);
+            let spki = openssl(&["ec", "-pubout", "-param_enc", "explicit"], &private);
+            let key = parse_public_key(&spki).expect("the SPKI key is read");
+            assert_eq!(key.named, None);
+            let without_public = openssl(&["ec", "-no_public", "-param_enc", "explicit"], &private);
+            let recomputed = parse_public_key(&without_public).expect("the private key is read");
+            assert_eq!(recomputed.point, key.point, "d·G differs from OpenSSL's public key on {}", name);
+
+            let Point::Affine(x, y) = &key.point else { panic!("OpenSSL keys are affine") };
+            let WeierstrassCurve { a, b, p } = &key.curve;
+            let (u, v, map) = transform(x, y, a, b, p).expect("the curve has a Montgomery form");
+            let curve = MontgomeryCurve::new(&map.a_montgomery, &map.b_montgomery, p).expect("non-singular");
+            let image = Point::Affine(u.clone(), v.clone());
+            assert!(curve.contains(&image));
+            assert_eq!(unmap(&map.s, &map.z0, p, &u, &v), key.point, "the inverse map disagrees on {}", name);
+
+            let rescaling = curve.canonicalize().expect("canonicalizes");
+            let expected = lookup(montgomery).expect("registered");
+            assert_eq!((rescaling.curve.a.clone(), rescaling.curve.b.clone()), expected.coefficients());
+            // The map is a group isomorphism, so the image has the key's order too
+            let killed = scalar_mul(&rescaling.curve, &rescaling.map_point(&image), &named.order());
+            assert!(killed.is_infinity());
+        }
+    }
+}
+
+#[test]
+fn secp256k1_private_keys_give_openssls_public_keys_through_glv() {
+    for _ in 0..KEYS {
+        let generate = ["ecparam", "-name", "secp256k1", "-genkey", "-noout", "-param_enc", "explicit"];
+        let private = openssl(&generate, &[]);
+        let spki = openssl(&["ec", "-pubout", "-param_enc", "explicit"], &private);
+        let key = parse_public_key(&spki).expect("the SPKI key is read");
+        assert!(key.curve.a == BigInt::from(0) && key.curve.b == BigInt::from(7));
+        // Without its public key the importer computes d·G with the endomorphism of the j = 0 curve
+        let without_public = openssl(&["ec", "-no_public", "-param_enc", "explicit"], &private);
+        let recomputed = parse_public_key(&without_public).expect("the private key is read");
+        assert_eq!(recomputed.point, key.point);
+    }
+}
@@ -0,0 +1,182 @@
+//! `Polynomial` arithmetic over F_5 and F_7 against results worked out by hand: sums, products, division
+//! with remainder, monic gcds, derivatives and powers modulo another polynomial; and `roots` against an
+//! exhaustive search over F_13 to F_31.
+
+use num_bigint::BigInt;
+use rand::{Rng, SeedableRng};
+use weierstrass_to_montgomery::{DefaultRng, Polynomial, SearchLimits, TransformError};
+
+/// The polynomial with `coefficients`, lowest degree first, over F_p.
+fn poly(coefficients: &[i64], p: u32) -> Polynomial {
+    let coefficients: Vec<BigInt> = coefficients.iter().copied().map(BigInt::from).collect();
+    Polynomial::new(&coefficients, &BigInt::from(p)).expect("p is prime")
+}
+
+#[test]
+fn coefficients_are_reduced_and_trimmed() {
+    let f = poly(&[-1, 8, 14], 7);
+    assert_eq!(f.coefficients(), [6, 1].map(BigInt::from));
+    assert_eq!((f.degree(), f.leading_coefficient()), (Some(1), Some(&BigInt::from(1))));
+    let zero = poly(&[7, 0, -14], 7);
+    assert!(zero.is_zero() && zero.degree().is_none() && zero.leading_coefficient().is_none());
+    assert_eq!(zero, f.zero());
+    assert_eq!((f.one(), f.x()), (poly(&[1], 7), poly(&[0, 1], 7)));
+    assert_eq!(poly(&[6, 1, 3], 7).to_string(), "3*x^2 + x + 6");
+    assert_eq!(poly(&[0, 2, 0, 1], 7).to_string(), "x^3 + 2*x");
+    assert_eq!(zero.to_string(), "0");
+    match Polynomial::new(&[BigInt::from(1)], &BigInt::from(9)) {
+        Err(TransformError::InvalidParameters(message)) => assert!(message.contains("prime modulus")),
+        other => panic!("expected InvalidParameters, got {:?}", other),
+    }
+}
+
+#[test]
+fn sums_differences_and_products() {
+    // (3 + 5x + 6x^2) + (4 + 2x + x^2) = 7 + 7x + 7x^2 = 0 over F_7
+    let (f, g) = (poly(&[3, 5, 6], 7), poly(&[4, 2, 1], 7));
+    assert!((&f + &g).is_zero());
+    assert_eq!(&f - &poly(&[4, 2], 7), poly(&[6, 3, 6], 7));
+    assert_eq!(-poly(&[1, 1], 7), poly(&[6, 6], 7));
+    assert_eq!(&f - &f, f.zero());
+    // (x + 1)(x + 4) = x^2 + 5x + 4 = x^2 + 4, and (2x + 3)(3x + 4) = 6x^2 + 17x + 12 = x^2 + 2x + 2
+    // over F_5
+    assert_eq!(poly(&[1, 1], 5) * poly(&[4, 1], 5), poly(&[4, 0, 1], 5));
+    assert_eq!(poly(&[3, 2], 5) * poly(&[4, 3], 5), poly(&[2, 2, 1], 5));
+    assert!((&poly(&[3, 2], 5) * &poly(&[], 5)).is_zero());
+}
+
+#[test]
+fn division_with_remainder() {
+    // x^3 + 2x + 5 = (x + 3)(x^2 + 4x + 4) over F_7, so −3 = 4 is a root
+    let f = poly(&[5, 2, 0, 1], 7);
+    let (quotient, remainder) = f.div_rem(&poly(&[3, 1], 7)).expect("a non-zero divisor");
+    assert_eq!((quotient, remainder), (poly(&[4, 4, 1], 7), poly(&[], 7)));
+    assert_eq!([4, 1].map(|x| f.evaluate(&BigInt::from(x))), [0, 1].map(BigInt::from));
+    // 3x^2 + x + 1 = (2x + 1)(5x + 5) + 3 over F_7, with a divisor that is not monic
+    let (quotient, remainder) = poly(&[1, 1, 3], 7).div_rem(&poly(&[1, 2], 7)).expect("non-zero");
+    assert_eq!((quotient, remainder), (poly(&[5, 5], 7), poly(&[3], 7)));
+    // A dividend of lower degree is its own remainder, and nothing divides by zero
+    let (quotient, remainder) = poly(&[1, 1], 7).div_rem(&poly(&[0, 0, 1], 7)).expect("non-zero");
+    assert_eq!((quotient, remainder), (poly(&[], 7), poly(&[1, 1], 7)));
+    assert_eq!(f.div_rem(&poly(&[], 7)), None);
+    assert_eq!(f.rem(&poly(&[], 7)), None);
+}
+
+#[test]
+fn gcds_are_monic() {
+    // (x + 1)(x + 2) and (x + 1)(x + 3) over F_7, scaled by 3 and 5
+    let f = poly(&[2, 3, 1], 7);
+    let g = poly(&[3, 4, 1], 7);
+    assert_eq!(f.gcd(&g), poly(&[1, 1], 7));
+    assert_eq!((&f * &poly(&[3], 7)).gcd(&(&g * &poly(&[5], 7))), poly(&[1, 1], 7));
+    assert_eq!(poly(&[0, 1], 7).gcd(&poly(&[1, 1], 7)), poly(&[1], 7));
+    assert_eq!(poly(&[4, 2], 7).gcd(&poly(&[], 7)), poly(&[2, 1], 7));
+    assert_eq!(poly(&[], 7).gcd(&f), f);
+    assert!(poly(&[], 7).gcd(&poly(&[], 7)).is_zero());
+    assert_eq!(poly(&[6, 1, 3], 7).monic(), poly(&[2, 5, 1], 7));
+}
+
+#[test]
+fn derivatives_vanish_on_p_th_powers() {
+    assert_eq!(poly(&[5, 2, 0, 1], 7).derivative(), poly(&[2, 0, 3], 7));
+    // d/dx (x^5 + x) = 5x^4 + 1 = 1 over F_5
+    assert_eq!(poly(&[0, 1, 0, 0, 0, 1], 5).derivative(), poly(&[1], 5));
+    assert!(poly(&[3], 5).derivative().is_zero());
+}
+
+#[test]
+fn powers_modulo_an_irreducible_quadratic() {
+    // x^2 + 2 is irreducible over F_5, as −2 = 3 is not a square, and x^2 = 3 modulo it
+    let modulus = poly(&[2, 0, 1], 5);
+    let x = modulus.x();
+    let power = |base: &Polynomial, exponent: u32| base.pow_mod(&BigInt::from(exponent), &modulus);
+    assert_eq!(power(&x, 0), poly(&[1], 5));
+    assert_eq!(power(&x, 2), poly(&[3], 5));
+    // x^5 = x·(x^2)^2 = 9x = 4x is the conjugate −x, and x^25 = x, as F_25 has the Frobenius of order 2
+    assert_eq!(power(&x, 5), poly(&[0, 4], 5));
+    assert_eq!(power(&x, 25), x);
+    // (x + 1)^6 = (x + 1)^(5 + 1) is the norm (1 + x)(1 − x) = 1 − x^2 = 3
+    assert_eq!(power(&poly(&[1, 1], 5), 6), poly(&[3], 5));
+    // An unreduced base, and a product reduced the same way
+    assert_eq!(power(&poly(&[1, 0, 0, 1], 5), 1), poly(&[1, 3], 5));
+    assert_eq!(poly(&[1, 1], 5).mul_mod(&poly(&[4, 1], 5), &modulus), poly(&[2], 5));
+    // Everything is zero modulo a constant
+    assert!(x.pow_mod(&BigInt::from(3), &poly(&[2], 5)).is_zero());
+}
+
+/// The roots of `f` in increasing order, by trying every element.
+fn exhaustive_roots(f: &Polynomial) -> Vec<BigInt> {
+    let p = u32::try_from(f.modulus()).expect("a small field");
+    (0..p).map(BigInt::from).filter(|x| f.evaluate(x) == BigInt::from(0)).collect()
+}
+
+/// A random monic polynomial of `degree` with no root, so irreducible for degree 2 or 3.
+fn rootless(degree: usize, p: u32, rng: &mut DefaultRng) -> Polynomial {
+    loop {
+        let mut coefficients: Vec<i64> = (0..degree).map(|_| rng.gen_range(0..p as i64)).collect();
+        coefficients.push(1);
+        let f = poly(&coefficients, p);
+        if exhaustive_roots(&f).is_empty() {
+            return f;
+        }
+    }
+}
+
+#[test]
+fn roots_match_an_exhaustive_search() {
+    let mut rng = DefaultRng::seed_from_u64(194);
+    let limits = SearchLimits::default();
+    for p in [13u32, 17, 19, 23, 29, 31] {
+        for trial in 0..40 {
+            // Up to four linear factors, drawn with repetition so that roots repeat, times up to two
+            // irreducible quadratics or cubics and a non-zero constant
+            let mut f = poly(&[rng.gen_range(1..p as i64)], p);
+            for _ in 0..rng.gen_range(0..=4) {
+                f = &f * &poly(&[-rng.gen_range(0..p as i64), 1], p);
+            }
+            for _ in 0..rng.gen_range(0..=2) {
+                f = &f * &rootless(rng.gen_range(2..=3), p, &mut rng);
+            }
+            let expected = exhaustive_roots(&f);
+            let found = f.roots(&limits, &mut rng).expect("a non-zero polynomial");
+            assert_eq!(found, expected, "F_{}, trial {}: {}", p, trial, f);
+            // And a polynomial with uniformly random coefficients
+            let degree = rng.gen_range(1..=8);
+            let coefficients: Vec<i64> = (0..=degree).map(|_| rng.gen_range(0..p as i64)).collect();
+            let g = poly(&coefficients, p);
+            if !g.is_zero() {
+                let found = g.roots(&limits, &mut rng).expect("a non-zero polynomial");
+                assert_eq!(found, exhaustive_roots(&g), "F_{}, trial {}: {}", p, trial, g);
+            }
+        }
+    }
+}
+
+#[test]
+fn roots_of_special_polynomials() {
+    let mut rng = DefaultRng::seed_from_u64(195);
+    let limits = SearchLimits::default();
+    // x^13 − x splits into every element of F_13, and x^4 + 1 = (x^2 + 5)(x^2 + 8) into none of them
+    let all = poly(&[0, -1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1], 13);
+    let every_element: Vec<BigInt> = (0..13).map(BigInt::from).collect();
+    assert_eq!(all.roots(&limits, &mut rng).expect("non-zero"), every_element);
+    assert!(poly(&[1, 0, 0, 0, 1], 13).roots(&limits, &mut rng).expect("non-zero").is_empty());
+    // A root of multiplicity 5 is reported once, and a constant has none
+    let repeated = poly(&[-3, 1], 17) * poly(&[-3, 1], 17) * poly(&[-3, 1], 17);
+    let repeated = &repeated * &(poly(&[-3, 1], 17) * poly(&[-3, 1], 17));
+    assert_eq!(repeated.roots(&limits, &mut rng).expect("non-zero"), [BigInt::from(3)]);
+    assert!(poly(&[5], 17).roots(&limits, &mut rng).expect("non-zero").is_empty());
+    // F_2 is searched directly
+    assert_eq!(poly(&[0, 1, 1], 2).roots(&limits, &mut rng).expect("non-zero"), [0, 1].map(BigInt::from));
+    assert!(poly(&[1, 1, 1], 2).roots(&limits, &mut rng).expect("non-zero").is_empty());
+    match poly(&[], 13).roots(&limits, &mut rng) {
+        Err(TransformError::InvalidParameters(message)) => assert!(message.contains("zero polynomial")),
+        other => panic!("expected InvalidParameters, got {:?}", other),
+    }
+}
+
+#[test]
+#[should_panic(expected = "different fields")]
+fn polynomials_over_different_fields_do_not_mix() {
+    let _ = poly(&[1, 1], 5) + poly(&[1, 1], 7);
+}
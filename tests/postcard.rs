@@ -0,0 +1,112 @@
+//! The postcard encodings of the `compact` module: maps and points come back equal, and truncated,
+//! padded or inconsistent input is refused rather than decoded into something else.
+
+use num_bigint::BigInt;
+use weierstrass_to_montgomery::compact::{point_from_postcard, point_to_postcard};
+use weierstrass_to_montgomery::{
+    lookup, MontgomeryCurve, MontgomeryMap, Point, SmallField, TransformError, TransformOptions,
+    WeierstrassCurve,
+};
+
+fn wei25519_map() -> MontgomeryMap {
+    let curve = lookup("Wei25519").expect("in the registry");
+    let (a, b) = curve.coefficients();
+    MontgomeryMap::new(&a, &b, &curve.p(), &TransformOptions::default()).expect("Wei25519 has a map")
+}
+
+/// The map of y^2 = x^3 + x over F_13, whose b is zero.
+fn small_map() -> MontgomeryMap {
+    let [a, b, p] = [1, 0, 13].map(BigInt::from);
+    MontgomeryMap::new(&a, &b, &p, &TransformOptions::default()).expect("y^2 = x^3 + x over F_13 has a map")
+}
+
+fn rejected<T: std::fmt::Debug>(result: Result<T, TransformError>) -> String {
+    match result {
+        Err(TransformError::InvalidParameters(message)) => message,
+        other => panic!("expected InvalidParameters, got {:?}", other),
+    }
+}
+
+#[test]
+fn maps_round_trip() {
+    for map in [wei25519_map(), small_map()] {
+        let bytes = map.to_postcard();
+        assert_eq!(MontgomeryMap::from_postcard(&bytes).expect("valid"), map);
+        // Every value has a one-byte length, where `to_bytes` spends four, and the version takes one
+        assert_eq!(bytes.len() + 31, map.to_bytes().len());
+    }
+}
+
+#[test]
+fn truncated_padded_and_inconsistent_maps_are_rejected() {
+    let bytes = wei25519_map().to_postcard();
+    for length in 0..bytes.len() {
+        let message = rejected(MontgomeryMap::from_postcard(&bytes[..length]));
+        assert!(message.contains("malformed map"), "{} bytes: {}", length, message);
+    }
+    let padded = [&bytes[..], &[0]].concat();
+    assert!(rejected(MontgomeryMap::from_postcard(&padded)).contains("1 trailing bytes after map"));
+    // The version is the leading varint
+    let mut other_version = bytes.clone();
+    other_version[0] += 1;
+    let message = rejected(MontgomeryMap::from_postcard(&other_version));
+    assert!(message.contains("unsupported map format version"));
+    // The last byte is the low byte of B
+    let mut inconsistent = bytes;
+    *inconsistent.last_mut().expect("not empty") ^= 1;
+    assert!(rejected(MontgomeryMap::from_postcard(&inconsistent)).contains("inconsistent map"));
+}
+
+#[test]
+fn points_round_trip() {
+    let map = wei25519_map();
+    let weierstrass = WeierstrassCurve::new(&map.a, &map.b, &map.p).expect("non-singular");
+    let montgomery = MontgomeryCurve::new(&map.a_montgomery, &map.b_montgomery, &map.p);
+    let montgomery = montgomery.expect("non-singular");
+    let base = lookup("Wei25519").expect("in the registry").base_point();
+    let Point::Affine(x, y) = &base else { unreachable!("the base point is affine") };
+    let (u, v) = map.map_point(x, y);
+    let image = Point::Affine(u, v);
+    assert_eq!(point_to_postcard(&Point::Infinity), [0]);
+    for point in [&base, &Point::Infinity] {
+        let decoded = point_from_postcard(&point_to_postcard(point), &weierstrass);
+        assert_eq!(decoded.expect("on the curve"), *point);
+    }
+    assert_eq!(point_from_postcard(&point_to_postcard(&image), &montgomery).expect("on the curve"), image);
+    // (0, 0) encodes both coordinates as empty byte strings
+    let origin = Point::Affine(BigInt::from(0), BigInt::from(0));
+    assert_eq!(point_to_postcard(&origin), [1, 0, 0]);
+    assert_eq!(point_from_postcard(&[1, 0, 0], &montgomery).expect("on every Montgomery curve"), origin);
+}
+
+#[test]
+fn truncated_and_off_curve_points_are_rejected() {
+    let map = wei25519_map();
+    let weierstrass = WeierstrassCurve::new(&map.a, &map.b, &map.p).expect("non-singular");
+    let bytes = point_to_postcard(&lookup("Wei25519").expect("in the registry").base_point());
+    for length in 0..bytes.len() {
+        assert!(rejected(point_from_postcard(&bytes[..length], &weierstrass)).contains("malformed point"));
+    }
+    let padded = [&bytes[..], &[0]].concat();
+    assert!(rejected(point_from_postcard(&padded, &weierstrass)).contains("trailing bytes after point"));
+    // Variant 2 does not exist
+    assert!(rejected(point_from_postcard(&[2], &weierstrass)).contains("malformed point"));
+    let mut off_curve = bytes;
+    *off_curve.last_mut().expect("not empty") ^= 1;
+    assert!(matches!(point_from_postcard(&off_curve, &weierstrass), Err(TransformError::PointNotOnCurve)));
+}
+
+#[test]
+fn small_field_elements_round_trip_through_fixed_buffers() {
+    type Fp = SmallField<65521>;
+    let mut buffer = [0u8; 3];
+    for value in [0, 1, 127, 128, 65520] {
+        let element = Fp::new(value);
+        // A varint below 2^21 fits in 3 bytes
+        let used = postcard::to_slice(&element, &mut buffer).expect("fits");
+        assert_eq!(postcard::from_bytes::<Fp>(used).expect("valid"), element);
+    }
+    // Values are not reduced on the way in
+    let unreduced = postcard::to_slice(&65521u64, &mut buffer).expect("fits");
+    assert!(postcard::from_bytes::<Fp>(unreduced).is_err());
+}
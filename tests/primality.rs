@@ -0,0 +1,47 @@
+use num_bigint::BigInt;
+use rand::SeedableRng;
+use std::str::FromStr;
+use weierstrass_to_montgomery::{is_probable_prime, is_probable_prime_with_rng, DefaultRng, MILLER_RABIN_ROUNDS};
+
+fn big(value: &str) -> BigInt {
+    BigInt::from_str(value).expect("a decimal integer")
+}
+
+#[test]
+fn agrees_with_trial_division_below_ten_thousand() {
+    for n in -5i64..10_000 {
+        let prime = n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| n % d != 0);
+        assert_eq!(is_probable_prime(&BigInt::from(n), 0), prime, "{}", n);
+    }
+}
+
+#[test]
+fn strong_pseudoprimes_to_small_bases_below_2_64_are_caught_without_rounds() {
+    // 3825123056546413051 passes the prime bases up to 31, 2^64 − 59 is prime and 2^64 − 1 is not
+    assert!(!is_probable_prime(&big("3825123056546413051"), 0));
+    assert!(is_probable_prime(&big("18446744073709551557"), 0));
+    assert!(!is_probable_prime(&big("18446744073709551615"), 0));
+    // the Carmichael number 561 and the square of a prime
+    assert!(!is_probable_prime(&BigInt::from(561), 0));
+    assert!(!is_probable_prime(&BigInt::from(4_294_967_291u64 * 4_294_967_291), 0));
+}
+
+#[test]
+fn random_rounds_catch_pseudoprimes_to_the_first_thirteen_primes() {
+    // 3317044064679887385961981 is a strong probable prime to every prime base up to 41, and to base 2
+    // alone it passes
+    let n = big("3317044064679887385961981");
+    let mut rng = DefaultRng::seed_from_u64(7);
+    assert!(is_probable_prime_with_rng(&n, 0, &mut rng));
+    assert!(!is_probable_prime_with_rng(&n, MILLER_RABIN_ROUNDS, &mut rng));
+}
+
+#[test]
+fn large_primes_pass_every_round() {
+    let p25519 = (BigInt::from(1) << 255) - 19;
+    let mersenne = (BigInt::from(1) << 521) - 1;
+    for p in [p25519, mersenne, big("340282366920938463463374607431768211297")] {
+        assert!(is_probable_prime(&p, MILLER_RABIN_ROUNDS), "{}", p);
+        assert!(!is_probable_prime(&(&p * 3u32), MILLER_RABIN_ROUNDS), "3·{}", p);
+    }
+}
@@ -0,0 +1,126 @@
+//! The Protocol Buffers messages of the `proto` module through `encode_to_vec` and `decode`: curves,
+//! points, maps and transform results come back equal, and the conversions refuse what is not valid.
+
+use num_bigint::BigInt;
+use prost::Message;
+use weierstrass_to_montgomery::proto;
+use weierstrass_to_montgomery::{
+    lookup, MontgomeryCurve, MontgomeryMap, Point, TransformError, TransformOptions, WeierstrassCurve,
+};
+
+/// Encodes `message` and decodes it again, checking that the wire bytes are stable.
+fn round_trip<M: Message + Default + PartialEq + std::fmt::Debug>(message: &M) -> M {
+    let bytes = message.encode_to_vec();
+    let decoded = M::decode(bytes.as_slice()).expect("a message just encoded");
+    assert_eq!(decoded, *message);
+    assert_eq!(decoded.encode_to_vec(), bytes);
+    decoded
+}
+
+fn rejected<T: std::fmt::Debug>(result: Result<T, TransformError>) -> String {
+    match result {
+        Err(TransformError::InvalidParameters(message)) => message,
+        other => panic!("expected InvalidParameters, got {:?}", other),
+    }
+}
+
+fn wei25519() -> (WeierstrassCurve, Point) {
+    lookup("Wei25519").expect("in the registry").to_weierstrass().expect("a Weierstrass curve")
+}
+
+fn wei25519_map() -> MontgomeryMap {
+    let (curve, _) = wei25519();
+    let options = TransformOptions::default();
+    MontgomeryMap::new(&curve.a, &curve.b, &curve.p, &options).expect("Wei25519 has a map")
+}
+
+#[test]
+fn curves_round_trip() {
+    // y^2 = x^3 + 7 over F_13 has a = 0, which encodes as the empty string
+    let [a, b, p] = [0, 7, 13].map(BigInt::from);
+    let small = WeierstrassCurve::new(&a, &b, &p).expect("non-singular");
+    for curve in [wei25519().0, small] {
+        let message = round_trip(&proto::WeierstrassCurve::from(&curve));
+        assert_eq!(WeierstrassCurve::try_from(&message).expect("valid"), curve);
+    }
+    let named = lookup("Curve25519").expect("in the registry");
+    let (a, b) = named.coefficients();
+    let curve25519 = MontgomeryCurve::new(&a, &b, &named.p()).expect("non-singular");
+    let message = round_trip(&proto::MontgomeryCurve::from(&curve25519));
+    assert_eq!(MontgomeryCurve::try_from(&message).expect("valid"), curve25519);
+
+    // Unreduced and singular coefficients
+    let unreduced = proto::WeierstrassCurve { a: vec![13], b: vec![7], p: vec![13] };
+    assert!(rejected(WeierstrassCurve::try_from(&unreduced)).contains("not in the range"));
+    let singular = proto::WeierstrassCurve { a: Vec::new(), b: Vec::new(), p: vec![13] };
+    assert!(WeierstrassCurve::try_from(&singular).is_err());
+    let singular = proto::MontgomeryCurve { a: vec![2], b: vec![1], p: vec![13] };
+    assert!(rejected(MontgomeryCurve::try_from(&singular)).contains("singular"));
+}
+
+#[test]
+fn points_round_trip() {
+    let (curve, base) = wei25519();
+    for point in [base, Point::Infinity] {
+        let message = round_trip(&proto::Point::from(&point));
+        assert_eq!(message.to_point(&curve).expect("on the curve"), point);
+    }
+    // (0, 0) lies on every Montgomery curve and has both coordinates empty on the wire
+    let [a, b, p] = [3, 1, 13].map(BigInt::from);
+    let montgomery = MontgomeryCurve::new(&a, &b, &p).expect("non-singular");
+    let origin = Point::Affine(BigInt::from(0), BigInt::from(0));
+    let message = round_trip(&proto::Point::from(&origin));
+    assert!(message.x.is_empty() && message.y.is_empty() && !message.infinity);
+    assert_eq!(message.to_point(&montgomery).expect("on the curve"), origin);
+
+    let off_curve = proto::Point { infinity: false, x: vec![1], y: vec![1] };
+    assert!(matches!(off_curve.to_point(&montgomery), Err(TransformError::PointNotOnCurve)));
+    let infinity_with_coordinates = proto::Point { infinity: true, x: vec![1], y: Vec::new() };
+    assert!(rejected(infinity_with_coordinates.to_point(&montgomery)).contains("has coordinates"));
+}
+
+#[test]
+fn maps_round_trip() {
+    let map = wei25519_map();
+    round_trip(&proto::MontgomeryMap::from(&map));
+    assert_eq!(MontgomeryMap::from_protobuf(&map.to_protobuf()).expect("valid"), map);
+
+    let mut message = proto::MontgomeryMap::from(&map);
+    message.version += 1;
+    let bytes = message.encode_to_vec();
+    assert!(rejected(MontgomeryMap::from_protobuf(&bytes)).contains("unsupported map format version"));
+    // A decoded map is validated, so a z0 that is no longer a root is refused
+    let mut message = proto::MontgomeryMap::from(&map);
+    *message.z0.last_mut().expect("z0 is not zero") ^= 1;
+    let bytes = message.encode_to_vec();
+    assert!(rejected(MontgomeryMap::from_protobuf(&bytes)).contains("inconsistent map"));
+    let bytes = map.to_protobuf();
+    let truncated = MontgomeryMap::from_protobuf(&bytes[..bytes.len() - 1]);
+    assert!(rejected(truncated).contains("malformed map message"));
+}
+
+#[test]
+fn transform_results_round_trip() {
+    let map = wei25519_map();
+    let (_, base) = wei25519();
+    let Point::Affine(x, y) = &base else { unreachable!("the base point is affine") };
+    let (u, v) = map.map_point(x, y);
+    let image = Point::Affine(u, v);
+    for (point, image) in [(base.clone(), image.clone()), (Point::Infinity, Point::Infinity)] {
+        let message = round_trip(&proto::TransformResult::new(&map, &point, &image));
+        assert_eq!(message.to_parts().expect("valid"), (map.clone(), point, image));
+    }
+
+    // An image that is on the Montgomery curve but not the image of the point
+    let message = proto::TransformResult::new(&map, &base, &Point::Infinity);
+    assert!(rejected(round_trip(&message).to_parts()).contains("not the mapped point"));
+    for field in ["map", "point", "image"] {
+        let mut message = proto::TransformResult::new(&map, &base, &image);
+        match field {
+            "map" => message.map = None,
+            "point" => message.point = None,
+            _ => message.image = None,
+        }
+        assert!(rejected(round_trip(&message).to_parts()).contains(&format!("has no {}", field)));
+    }
+}
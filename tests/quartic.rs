@@ -0,0 +1,102 @@
+//! `QuarticMap` over every point of small curves with a rational point of order 2, from each root of the
+//! cubic: each point goes to the extended Jacobi quartic and back unchanged, and the map respects
+//! addition wherever both sides are affine.
+
+use num_bigint::BigInt;
+use weierstrass_to_montgomery::{
+    CurveGroup, Point, QuarticMap, SearchLimits, TransformError, WeierstrassCurve,
+};
+
+/// Every point of `curve`, the point at infinity first, by lifting each x.
+fn points(curve: &WeierstrassCurve) -> Vec<Point> {
+    let mut points = vec![Point::Infinity];
+    let mut x = BigInt::from(0);
+    while x < curve.p {
+        points.extend(curve.lift_x(&x).unwrap_or_default());
+        x += 1u32;
+    }
+    points
+}
+
+/// The roots of x^3 + ax + b, by trying every x.
+fn roots(curve: &WeierstrassCurve) -> Vec<BigInt> {
+    let mut roots = Vec::new();
+    let mut x = BigInt::from(0);
+    while x < curve.p {
+        if curve.rhs(&x) == BigInt::from(0) {
+            roots.push(x.clone());
+        }
+        x += 1u32;
+    }
+    roots
+}
+
+#[test]
+fn every_point_round_trips_from_every_root() {
+    // Three roots, then one, over each field
+    for (a, b, p) in [(1, 0, 101), (1, 2, 101), (2, 3, 103), (1, 3, 103)] {
+        let [a, b, p] = [a, b, p].map(BigInt::from);
+        let curve = WeierstrassCurve::new(&a, &b, &p).expect("non-singular");
+        let points = points(&curve);
+        let roots = roots(&curve);
+        assert!(roots.len() == 1 || roots.len() == 3, "y^2 = x^3 + {}x + {} over F_{}", a, b, p);
+        for theta in &roots {
+            let context = format!("y^2 = x^3 + {}x + {} over F_{}, theta = {}", a, b, p, theta);
+            let map = QuarticMap::from_root(&curve, theta).expect("a root");
+            let images: Vec<Option<Point>> = points.iter().map(|point| map.to_quartic(point)).collect();
+            for (point, image) in points.iter().zip(&images) {
+                match image {
+                    Some(image) => {
+                        assert!(map.quartic.contains(image), "{}: {} maps off the quartic", context, point);
+                        let back = map.to_weierstrass(image);
+                        assert_eq!(back, *point, "{}: {} does not come back", context, point);
+                    }
+                    // Only the other points of order 2 go to the points at infinity of the quartic
+                    None => {
+                        let zero = BigInt::from(0);
+                        let order_two = matches!(point, Point::Affine(x, y) if x != theta && *y == zero);
+                        assert!(order_two, "{}: {} has no image", context, point);
+                    }
+                }
+            }
+            assert_eq!(images.iter().filter(|image| image.is_none()).count(), roots.len() - 1, "{}", context);
+            let image = map.to_quartic(&Point::Affine(theta.clone(), BigInt::from(0)));
+            assert_eq!(image, Some(Point::Affine(BigInt::from(0), &p - 1u32)), "{}", context);
+            let mut distinct: Vec<String> = images.iter().flatten().map(ToString::to_string).collect();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(distinct.len(), points.len() + 1 - roots.len(), "{}", context);
+            // The addition law of the quartic has exceptions where the sum or the difference is at
+            // infinity, which only happens when there are three roots
+            for (first, first_image) in points.iter().zip(&images).step_by(5) {
+                for (second, second_image) in points.iter().zip(&images) {
+                    let sum = map.to_quartic(&curve.add(first, second));
+                    let difference = map.to_quartic(&curve.add(first, &curve.negate(second)));
+                    if let (Some(first_image), Some(second_image), Some(sum), Some(_)) =
+                        (first_image, second_image, sum, difference)
+                    {
+                        let found = map.quartic.add(first_image, second_image);
+                        assert_eq!(found, sum, "{}: {} + {}", context, first, second);
+                    }
+                }
+            }
+        }
+        // The root search lands on one of the same roots
+        let map = QuarticMap::new(&curve, &SearchLimits::default()).expect("a root");
+        assert!(roots.contains(&map.theta));
+    }
+}
+
+#[test]
+fn curves_without_a_point_of_order_two_are_rejected() {
+    // x^3 + x + 1 has no root modulo 101
+    let [a, b, p] = [1, 1, 101].map(BigInt::from);
+    let curve = WeierstrassCurve::new(&a, &b, &p).expect("non-singular");
+    assert!(roots(&curve).is_empty());
+    let rejected = |result| match result {
+        Err(TransformError::InvalidParameters(message)) => message,
+        other => panic!("expected InvalidParameters, got {:?}", other),
+    };
+    assert!(rejected(QuarticMap::new(&curve, &SearchLimits::default())).contains("no point of order 2"));
+    assert!(rejected(QuarticMap::from_root(&curve, &BigInt::from(5))).contains("is not a root"));
+}
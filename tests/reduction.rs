@@ -0,0 +1,111 @@
+//! The fast reductions against `mod_floor`, on values near 0, p and p^2, random products of two field
+//! elements, values past the fixed-width paths, and negative values.
+
+use num_bigint::{BigInt, RandBigInt};
+use num_integer::Integer;
+use rand::SeedableRng;
+use weierstrass_to_montgomery::field::{Barrett, PseudoMersenne};
+use weierstrass_to_montgomery::solinas::NistPrime;
+use weierstrass_to_montgomery::DefaultRng;
+
+/// Values around 0, p, 2p, p^2 and 2^(2k) for the k-bit modulus p, random products of two reduced
+/// elements and random values up to (p − 1)^2, each also negated.
+fn inputs(p: &BigInt, rng: &mut DefaultRng) -> Vec<BigInt> {
+    let one = BigInt::from(1);
+    let square = (p - 1u32) * (p - 1u32);
+    let top = (BigInt::from(1) << (2 * p.bits())) - 1u32;
+    let mut values = Vec::new();
+    for center in [BigInt::from(0), p.clone(), p * 2u32, square.clone(), p * p, top.clone()] {
+        for offset in -3i32..=3 {
+            values.push(&center + offset);
+        }
+    }
+    values.extend([&one << p.bits(), &top + 1u32, &top * &top, &one << (4 * p.bits() + 7)]);
+    for _ in 0..500 {
+        let x = rng.gen_bigint_range(&BigInt::from(0), p);
+        let y = rng.gen_bigint_range(&BigInt::from(0), p);
+        values.push(x * y);
+        values.push(rng.gen_bigint_range(&BigInt::from(0), &(&square + 1u32)));
+    }
+    let negated: Vec<BigInt> = values.iter().map(|value| -value).collect();
+    values.extend(negated);
+    values
+}
+
+#[test]
+fn solinas_reduction_agrees_with_mod_floor() {
+    let mut rng = DefaultRng::seed_from_u64(164);
+    for prime in [NistPrime::P256, NistPrime::P384, NistPrime::P521] {
+        let p = prime.modulus();
+        assert_eq!(NistPrime::detect(&p), Some(prime));
+        for value in inputs(&p, &mut rng) {
+            assert_eq!(prime.reduce(&value), value.mod_floor(&p), "{:?}: {}", prime, value);
+        }
+    }
+    assert_eq!(NistPrime::detect(&((BigInt::from(1) << 255) - 19u32)), None);
+}
+
+#[test]
+fn barrett_reduction_agrees_with_mod_floor() {
+    let mut rng = DefaultRng::seed_from_u64(112);
+    let one = BigInt::from(1);
+    // Small and odd-sized moduli, composites and powers of two among them, the curve primes, and moduli
+    // just above a power of two, where the quotient estimate is furthest off
+    let mut moduli: Vec<BigInt> = [2u32, 3, 17, 255, 256, 257, 65_537, 1_000_003].map(BigInt::from).to_vec();
+    moduli.extend([
+        (&one << 255) - 19u32,
+        NistPrime::P256.modulus(),
+        (&one << 521) - 1u32,
+        (&one << 256) + 1u32,
+        (&one << 127) + 45u32,
+        rng.gen_biguint(300).into(),
+    ]);
+    for p in moduli {
+        let barrett = Barrett::new(&p);
+        assert_eq!(barrett.modulus(), p);
+        for value in inputs(&p, &mut rng) {
+            assert_eq!(barrett.reduce(&value), value.mod_floor(&p), "mod {}: {}", p, value);
+        }
+    }
+}
+
+#[test]
+fn pseudo_mersenne_reduction_agrees_with_mod_floor() {
+    let mut rng = DefaultRng::seed_from_u64(163);
+    let one = BigInt::from(1);
+    // 2^255 − 19, 2^521 − 1, 2^127 − 1, 2^61 − 1, 2^192 − 2^64 − 1 and 2^k − c with c just below 2^(k/2)
+    let moduli = [
+        (&one << 255) - 19u32,
+        (&one << 521) - 1u32,
+        (&one << 127) - 1u32,
+        (&one << 61) - 1u32,
+        (&one << 192) - (&one << 64) - 1u32,
+        (&one << 256) - (&one << 128) + 1u32,
+        BigInt::from(13),
+    ];
+    for p in moduli {
+        let reduction = PseudoMersenne::detect(&p).unwrap_or_else(|| panic!("{} has a small c", p));
+        assert_eq!(reduction.modulus(), p);
+        for value in inputs(&p, &mut rng) {
+            assert_eq!(reduction.reduce(&value), value.mod_floor(&p), "mod {}: {}", p, value);
+        }
+    }
+}
+
+#[test]
+fn pseudo_mersenne_detection_needs_c_below_2_to_half_the_bits() {
+    let one = BigInt::from(1);
+    // c = 2^224 − 2^192 − 2^96 + 1 for P-256 and 2^129 for 2^256 − 2^129 are too large (P-384, with
+    // c = 2^128 + 2^96 − 2^32 + 1, qualifies); 2^255 + 19 is not below 2^255, and 11 is 2^4 − 5
+    for modulus in [
+        NistPrime::P256.modulus(),
+        (&one << 255) + 19u32,
+        (&one << 256) - (&one << 129),
+        BigInt::from(11),
+        BigInt::from(1),
+        BigInt::from(0),
+        BigInt::from(-7),
+    ] {
+        assert_eq!(PseudoMersenne::detect(&modulus), None, "{}", modulus);
+    }
+}
@@ -0,0 +1,211 @@
+//! The RFC 7748 and RFC 8032 vectors behind the `selftest` subcommand, asserted in the test suite too.
+
+use num_bigint::{BigInt, Sign};
+use weierstrass_to_montgomery::ed25519::{
+    curve25519, curve25519_base_point, curve25519_to_ed25519, decode_ed25519, ed25519, ed25519_base_point,
+    ed25519_public_key_to_x25519, ed25519_to_curve25519, encode_ed25519, x25519_public_key_to_ed25519,
+    KEY_LEN,
+};
+use weierstrass_to_montgomery::ed448::{
+    curve448, curve448_base_point, curve448_to_ed448, decode_ed448, ed448, ed448_base_point,
+    ed448_public_key_to_x448, ed448_to_curve448, X448_KEY_LEN,
+};
+use weierstrass_to_montgomery::{scalar_mul, CurveGroup};
+use weierstrass_to_montgomery::selftest::{run_selftest, KatResult};
+
+fn hex(digits: &str) -> Vec<u8> {
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).expect("hex digits"))
+        .collect()
+}
+
+fn little_endian(bytes: &[u8]) -> BigInt {
+    BigInt::from_bytes_le(Sign::Plus, bytes)
+}
+
+/// X25519(k, u) by the Montgomery ladder, with the clamping and masking of RFC 7748, section 5.
+fn x25519(scalar: &str, u: &str) -> Vec<u8> {
+    let mut scalar = hex(scalar);
+    scalar[0] &= 248;
+    scalar[KEY_LEN - 1] = (scalar[KEY_LEN - 1] & 127) | 64;
+    let mut u = hex(u);
+    u[KEY_LEN - 1] &= 127;
+    let (_, mut result) = curve25519().ladder(&little_endian(&u), &little_endian(&scalar)).to_bytes_le();
+    result.resize(KEY_LEN, 0);
+    result
+}
+
+#[test]
+fn selftest_passes() {
+    let results = run_selftest();
+    let failures: Vec<&KatResult> = results.iter().filter(|result| !result.passed()).collect();
+    assert!(failures.is_empty(), "{:?}", failures);
+}
+
+#[test]
+fn x25519_matches_rfc_7748() {
+    // Section 5.2
+    let vectors = [
+        (
+            "a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4",
+            "e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c",
+            "c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552",
+        ),
+        (
+            "4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d",
+            "e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493",
+            "95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957",
+        ),
+    ];
+    for (scalar, u, expected) in vectors {
+        assert_eq!(x25519(scalar, u), hex(expected), "{}", scalar);
+    }
+
+    // Section 6.1: both public keys from the base point u = 9, and the shared secret from either side
+    let nine = "0900000000000000000000000000000000000000000000000000000000000000";
+    let alice = "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a";
+    let bob = "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb";
+    let alice_public = "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a";
+    let bob_public = "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f";
+    let shared = hex("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+    assert_eq!(x25519(alice, nine), hex(alice_public));
+    assert_eq!(x25519(bob, nine), hex(bob_public));
+    assert_eq!(x25519(alice, bob_public), shared);
+    assert_eq!(x25519(bob, alice_public), shared);
+}
+
+#[test]
+fn ed25519_keys_of_rfc_8032_convert_to_x25519_and_back() {
+    // The public keys of tests 1 to 3 of RFC 8032, section 7.1, with u = (1 + y) / (1 − y)
+    let vectors = [
+        (
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            "d85e07ec22b0ad881537c2f44d662d1a143cf830c57aca4305d85c7a90f6b62e",
+        ),
+        (
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            "25c704c594b88afc00a76b69d1ed2b984d7e22550f3ed0802d04fbcd07d38d47",
+        ),
+        (
+            "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+            "cbb22fc9f790bd3eba9b84680c157ca4950a9894362601701f89c3c4d9fda23a",
+        ),
+    ];
+    for (ed25519_key, x25519_key) in vectors {
+        let (ed25519_key, x25519_key) = (hex(ed25519_key), hex(x25519_key));
+        let converted = ed25519_public_key_to_x25519(&ed25519_key).expect("a valid key");
+        assert_eq!(converted[..], x25519_key[..]);
+        let sign = ed25519_key[KEY_LEN - 1] >> 7 == 1;
+        let restored = x25519_public_key_to_ed25519(&x25519_key, sign).expect("a valid key");
+        assert_eq!(restored[..], ed25519_key[..]);
+        let point = decode_ed25519(&ed25519_key).expect("on Ed25519");
+        assert_eq!(encode_ed25519(&point)[..], ed25519_key[..]);
+    }
+}
+
+#[test]
+fn the_birational_map_is_a_group_isomorphism() {
+    // The base points correspond (RFC 7748, section 4.1), and so do their multiples
+    let (edwards, montgomery) = (ed25519(), curve25519());
+    assert_eq!(ed25519_to_curve25519(&ed25519_base_point()), curve25519_base_point());
+    for k in [2u32, 3, 8, 12345] {
+        let k = BigInt::from(k);
+        let image = ed25519_to_curve25519(&scalar_mul(&edwards, &ed25519_base_point(), &k));
+        assert_eq!(image, scalar_mul(&montgomery, &curve25519_base_point(), &k), "{}", k);
+        assert_eq!(curve25519_to_ed25519(&image), Some(scalar_mul(&edwards, &ed25519_base_point(), &k)));
+    }
+}
+
+/// X448(k, u) by the Montgomery ladder, with the clamping of RFC 7748, section 5.
+fn x448(scalar: &str, u: &str) -> Vec<u8> {
+    let mut scalar = hex(scalar);
+    scalar[0] &= 252;
+    scalar[X448_KEY_LEN - 1] |= 128;
+    let (_, mut result) = curve448().ladder(&little_endian(&hex(u)), &little_endian(&scalar)).to_bytes_le();
+    result.resize(X448_KEY_LEN, 0);
+    result
+}
+
+#[test]
+fn x448_matches_rfc_7748() {
+    // Section 5.2
+    let vectors = [
+        (
+            "3d262fddf9ec8e88495266fea19a34d28882acef045104d0d1aae121\
+             700a779c984c24f8cdd78fbff44943eba368f54b29259a4f1c600ad3",
+            "06fce640fa3487bfda5f6cf2d5263f8aad88334cbd07437f020f08f9\
+             814dc031ddbdc38c19c6da2583fa5429db94ada18aa7a7fb4ef8a086",
+            "ce3e4ff95a60dc6697da1db1d85e6afbdf79b50a2412d7546d5f239f\
+             e14fbaadeb445fc66a01b0779d98223961111e21766282f73dd96b6f",
+        ),
+        (
+            "203d494428b8399352665ddca42f9de8fef600908e0d461cb021f8c5\
+             38345dd77c3e4806e25f46d3315c44e0a5b4371282dd2c8d5be3095f",
+            "0fbcc2f993cd56d3305b0b7d9e55d4c1a8fb5dbb52f8e9a1e9b6201b\
+             165d015894e56c4d3570bee52fe205e28a78b91cdfbde71ce8d157db",
+            "884a02576239ff7a2f2f63b2db6a9ff37047ac13568e1e30fe63c4a7\
+             ad1b3ee3a5700df34321d62077e63633c575c1c954514e99da7c179d",
+        ),
+    ];
+    for (scalar, u, expected) in vectors {
+        assert_eq!(x448(scalar, u), hex(expected), "{}", scalar);
+    }
+
+    // Section 6.2, from the base point u = 5
+    let five = format!("05{}", "00".repeat(X448_KEY_LEN - 1));
+    let alice = "9a8f4925d1519f5775cf46b04b5800d4ee9ee8bae8bc5565d498c28d\
+                 d9c9baf574a9419744897391006382a6f127ab1d9ac2d8c0a598726b";
+    let bob = "1c306a7ac2a0e2e0990b294470cba339e6453772b075811d8fad0d1d\
+               6927c120bb5ee8972b0d3e21374c9c921b09d1b0366f10b65173992d";
+    let alice_public = "9b08f7cc31b7e3e67d22d5aea121074a273bd2b83de09c63faa73d2c\
+                        22c5d9bbc836647241d953d40c5b12da88120d53177f80e532c41fa0";
+    let bob_public = "3eb7a829b0cd20f5bcfc0b599b6feccf6da4627107bdb0d4f345b430\
+                      27d8b972fc3e34fb4232a13ca706dcb57aec3dae07bdc1c67bf33609";
+    let shared = hex("07fff4181ac6cc95ec1c16a94a0f74d12da232ce40a77552281d282b\
+                      b60c0b56fd2464c335543936521c24403085d59a449a5037514a879d");
+    assert_eq!(x448(alice, &five), hex(alice_public));
+    assert_eq!(x448(bob, &five), hex(bob_public));
+    assert_eq!(x448(alice, bob_public), shared);
+    assert_eq!(x448(bob, alice_public), shared);
+}
+
+#[test]
+fn ed448_keys_of_rfc_8032_go_through_the_isogeny() {
+    // The blank and 1-octet keys of RFC 8032, section 7.4, with the X448 keys of their scalars
+    let vectors = [
+        (
+            "5fd7449b59b461fd2ce787ec616ad46a1da1342485a70e1f8a0ea75d\
+             80e96778edf124769b46c7061bd6783df1e50f6cd1fa1abeafe8256180",
+            "3bd436b72a1d011cd3845717fcc6887852a2007fd595ac970bef67c7\
+             f24a5329ffd1dfd0b05f90adc9c6e70805e5817a1f09ca229bef8619",
+        ),
+        (
+            "43ba28f430cdff456ae531545f7ecd0ac834a55d9358c0372bfa0c6c\
+             6798c0866aea01eb00742802b8438ea4cb82169c235160627b4c3a9480",
+            "f89c6220b16c1e219b03c55fbf791204cfa00aefcb9ff28cd8248cfe\
+             9ebbf699bcf1a2ad4f013d538c6648689e846891cca5016fe4f326ec",
+        ),
+    ];
+    for (ed448_key, x448_key) in vectors {
+        let converted = ed448_public_key_to_x448(&hex(ed448_key)).expect("a valid key");
+        assert_eq!(converted[..], hex(x448_key)[..], "{}", ed448_key);
+        assert!(decode_ed448(&hex(ed448_key)).is_ok_and(|point| ed448().contains(&point)));
+    }
+}
+
+#[test]
+fn the_ed448_isogeny_and_its_dual_multiply_by_four() {
+    // The isogeny takes base point to base point (RFC 7748, section 4.2), commutes with multiplication, and
+    // the dual brings the image back to four times the point
+    let (edwards, montgomery) = (ed448(), curve448());
+    assert_eq!(ed448_to_curve448(&ed448_base_point()), curve448_base_point());
+    let four = BigInt::from(4);
+    for k in [1u32, 2, 3, 12345] {
+        let k = BigInt::from(k);
+        let point = scalar_mul(&edwards, &ed448_base_point(), &k);
+        let image = ed448_to_curve448(&point);
+        assert_eq!(image, scalar_mul(&montgomery, &curve448_base_point(), &k), "{}", k);
+        assert_eq!(curve448_to_ed448(&image), scalar_mul(&edwards, &point, &four), "{}", k);
+    }
+}
@@ -0,0 +1,101 @@
+use num_bigint::BigInt;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use std::str::FromStr;
+use weierstrass_to_montgomery::{
+    find_cubic_root_fp2, transform_to_montgomery_small_with_rng, transform_with_map, Fp2, SearchLimits,
+    SmallField, TransformError, TransformOptions,
+};
+
+/// y^2 = x^3 + x + 10 over F_17: the cubic has roots 9, 10 and 15, and only 15 has 3·z0^2 + a square.
+const A: i64 = 1;
+const B: i64 = 10;
+const P: i64 = 17;
+
+/// (u, v, A, B) for the point (5, 2), from z0 = 15 with s = 8 or s = 9.
+const EXPECTED: [(i64, i64, i64, i64); 2] = [(3, 13, 12, 15), (14, 4, 5, 2)];
+
+fn expected() -> Vec<[BigInt; 4]> {
+    EXPECTED.iter().map(|&(u, v, a, b)| [u, v, a, b].map(BigInt::from)).collect()
+}
+
+#[test]
+fn skips_roots_whose_s_squared_is_a_non_residue() {
+    let [x, y, a, b, p] = [5, 2, A, B, P].map(BigInt::from);
+    for seed in 0..20 {
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        let (u, v, map) = transform_with_map(&x, &y, &a, &b, &p, &TransformOptions::default(), &mut rng)
+            .expect("the root z0 = 15 admits a Montgomery form");
+        assert_eq!(map.z0, BigInt::from(15));
+        assert!(expected().contains(&[u, v, map.a_montgomery, map.b_montgomery]));
+    }
+}
+
+#[test]
+fn small_field_tries_the_other_roots() {
+    let field = |value: i64| SmallField::<17>::from_i64(value);
+    for seed in 0..50 {
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        let limits = SearchLimits::default();
+        let (u, v, a_m, b_m) =
+            transform_to_montgomery_small_with_rng(field(5), field(2), field(A), field(B), &limits, &mut rng)
+                .expect("the root z0 = 15 admits a Montgomery form");
+        let result = [u, v, a_m, b_m].map(BigInt::from);
+        assert!(expected().contains(&result), "seed {} gave {:?}", seed, result);
+    }
+}
+
+#[test]
+fn fails_when_no_root_gives_a_square() {
+    // y^2 = x^3 + 5x + 7 over F_1009 has the single root 780, with 3·780^2 + 5 = 933 a non-residue
+    let [x, y, a, b, p] = [780, 0, 5, 7, 1009].map(BigInt::from);
+    let mut rng = ChaCha20Rng::seed_from_u64(1);
+    let result = transform_with_map(&x, &y, &a, &b, &p, &TransformOptions::default(), &mut rng);
+    assert!(matches!(result, Err(TransformError::NoMontgomeryForm(_))));
+}
+
+#[test]
+fn a_cubic_without_roots_fails_without_exhausting_the_search() {
+    // secp256k1 has odd order, so z^3 + 7 has no root; this used to run into the search limit
+    let p = BigInt::from_str("115792089237316195423570985008687907853269984665640564039457584007908834671663")
+        .expect("decimal constant");
+    let x = BigInt::from_str("55066263022277343669578718895168534326250603453777594175500187360389116729240")
+        .expect("decimal constant");
+    let y = BigInt::from_str("32670510020758816978083085130507043184471273380659243275938904335757337482424")
+        .expect("decimal constant");
+    let options = TransformOptions {
+        limits: SearchLimits { max_attempts: 10, ..SearchLimits::default() },
+        ..TransformOptions::default()
+    };
+    let (a, b) = (BigInt::from(0), BigInt::from(7));
+    let result = transform_with_map(&x, &y, &a, &b, &p, &options, &mut ChaCha20Rng::seed_from_u64(1));
+    assert!(matches!(result, Err(TransformError::NoMontgomeryForm(_))));
+}
+
+#[test]
+fn cubic_roots_in_f_p2_are_found_without_sampling() {
+    // Over F_p² with p = 2^255 − 19, z^3 + az + b = (z − r1)(z − r2)(z − r3) with r3 = −r1 − r2: sampling
+    // would never hit one of three roots among p² elements
+    let p = (BigInt::from(1) << 255) - 19u32;
+    let non_residue = Fp2::find_non_residue(&p, &SearchLimits::default()).expect("p is an odd prime");
+    let element = |c0: u32, c1: u32| Fp2::new(&BigInt::from(c0), &BigInt::from(c1), &p, &non_residue);
+    let (r1, r2) = (element(3, 5).expect("in the field"), element(11, 2).expect("in the field"));
+    let r3 = -&(&r1 + &r2);
+    let a = &(&(&r1 * &r2) + &(&r1 * &r3)) + &(&r2 * &r3);
+    let b = -&(&(&r1 * &r2) * &r3);
+    let limits = SearchLimits { max_attempts: 10, ..SearchLimits::default() };
+    for seed in 0..5 {
+        let root = find_cubic_root_fp2(&a, &b, &limits, &mut ChaCha20Rng::seed_from_u64(seed))
+            .expect("the cubic splits over F_p^2");
+        assert!([&r1, &r2, &r3].contains(&&root), "{}", root);
+    }
+
+    // z^3 + 7 is irreducible over F_p for the secp256k1 prime, and stays so over F_p², as 3 is odd
+    let p = BigInt::from_str("115792089237316195423570985008687907853269984665640564039457584007908834671663")
+        .expect("decimal constant");
+    let non_residue = Fp2::find_non_residue(&p, &SearchLimits::default()).expect("p is an odd prime");
+    let zero = Fp2::new(&BigInt::from(0), &BigInt::from(0), &p, &non_residue).expect("in the field");
+    let seven = zero.from_base(&BigInt::from(7));
+    let result = find_cubic_root_fp2(&zero, &seven, &limits, &mut ChaCha20Rng::seed_from_u64(1));
+    assert!(matches!(result, Err(TransformError::NoMontgomeryForm(_))));
+}
@@ -0,0 +1,143 @@
+//! Multi-scalar multiplication against sums of `scalar_mul`, and comb tables against `scalar_mul`.
+
+use num_bigint::{BigInt, RandBigInt};
+use rand::SeedableRng;
+use weierstrass_to_montgomery::ed25519::{ed25519, ed25519_base_point};
+use weierstrass_to_montgomery::{
+    lookup, msm, scalar_mul, CurveGroup, DefaultRng, FixedBaseTable, Point, WeierstrassCurve,
+};
+
+/// y^2 = x^3 + 2x + 3 over F_1009 and its first `count` affine points by increasing x.
+fn small_curve(count: usize) -> (WeierstrassCurve, Vec<Point>) {
+    let (a, b, p) = (BigInt::from(2), BigInt::from(3), BigInt::from(1009));
+    let curve = WeierstrassCurve::new(&a, &b, &p).expect("non-singular");
+    let points = (0u32..1009)
+        .flat_map(|x| curve.lift_x(&BigInt::from(x)).expect("x is in the field"))
+        .take(count)
+        .collect();
+    (curve, points)
+}
+
+/// Σ scalars[i]·points[i], one `scalar_mul` at a time.
+fn naive<C: CurveGroup>(curve: &C, points: &[Point], scalars: &[BigInt]) -> Point {
+    points
+        .iter()
+        .zip(scalars)
+        .fold(Point::Infinity, |sum, (point, scalar)| curve.add(&sum, &scalar_mul(curve, point, scalar)))
+}
+
+#[test]
+fn msm_of_nothing_is_the_point_at_infinity() {
+    let (curve, _) = small_curve(0);
+    assert_eq!(msm(&curve, &[], &[]), Point::Infinity);
+}
+
+#[test]
+fn msm_agrees_with_a_sum_of_scalar_multiplications() {
+    let mut rng = DefaultRng::seed_from_u64(113);
+    // Across every window width: 1 to 3 points, 4 to 31, 32 to 255, and more
+    for count in [1, 2, 3, 4, 17, 31, 32, 100, 300] {
+        let (curve, points) = small_curve(count);
+        for bits in [1, 5, 10, 40] {
+            let bound = BigInt::from(1) << bits;
+            let scalars: Vec<BigInt> = (0..count).map(|_| rng.gen_bigint_range(&-&bound, &bound)).collect();
+            assert_eq!(msm(&curve, &points, &scalars), naive(&curve, &points, &scalars), "{} points", count);
+        }
+    }
+}
+
+#[test]
+fn msm_skips_zero_scalars_and_the_point_at_infinity() {
+    let (curve, mut points) = small_curve(6);
+    // The point at infinity, P and −P, which cancel, and a repeated point, which adds up
+    points[1] = Point::Infinity;
+    points[3] = curve.negate(&points[2]);
+    points[5] = points[4].clone();
+    let scalars = [7, 12345, 99, 99, 3, 4].map(BigInt::from);
+    let expected = curve.add(
+        &scalar_mul(&curve, &points[0], &BigInt::from(7)),
+        &scalar_mul(&curve, &points[4], &BigInt::from(7)),
+    );
+    assert_eq!(msm(&curve, &points, &scalars), expected);
+    assert_eq!(msm(&curve, &points, &[0, 0, 0, 0, 0, 0].map(BigInt::from)), Point::Infinity);
+    assert_eq!(msm(&curve, &[Point::Infinity], &[BigInt::from(5)]), Point::Infinity);
+}
+
+#[test]
+fn msm_agrees_on_full_size_scalars_in_other_models() {
+    let mut rng = DefaultRng::seed_from_u64(113);
+    let named = lookup("P-256").expect("registered");
+    let (p256, base) = named.to_weierstrass().expect("a Weierstrass curve");
+    let order = named.order();
+    let points: Vec<Point> = [1, 2, 3].map(|k| scalar_mul(&p256, &base, &BigInt::from(k))).to_vec();
+    let random = rng.gen_bigint_range(&BigInt::from(0), &order);
+    let scalars = vec![random, &order - 1u32, order.clone()];
+    assert_eq!(msm(&p256, &points, &scalars), naive(&p256, &points, &scalars));
+
+    let edwards = ed25519();
+    let base = ed25519_base_point();
+    let points = vec![base.clone(), edwards.double(&base), Point::Infinity, base.clone()];
+    let scalars: Vec<BigInt> = (0..4).map(|_| rng.gen_bigint(256)).collect();
+    assert_eq!(msm(&edwards, &points, &scalars), naive(&edwards, &points, &scalars));
+}
+
+#[test]
+#[should_panic(expected = "msm needs one scalar per point")]
+fn msm_needs_one_scalar_per_point() {
+    let (curve, points) = small_curve(2);
+    msm(&curve, &points, &[BigInt::from(1)]);
+}
+
+/// Scalars around 0 and the order n, up to twice n, and past the bit length of the table.
+fn comb_scalars(order: &BigInt, rng: &mut DefaultRng) -> Vec<BigInt> {
+    let mut scalars: Vec<BigInt> = [0, 1, 2, 3, -1, -2].map(BigInt::from).to_vec();
+    for offset in -2i32..=2 {
+        scalars.push(order + offset);
+        scalars.push(order * 2u32 + offset);
+    }
+    scalars.push(-order);
+    scalars.push(BigInt::from(1) << (order.bits() + 5));
+    scalars.extend((0..2).map(|_| rng.gen_bigint_range(&BigInt::from(0), order)));
+    scalars
+}
+
+#[test]
+fn comb_agrees_with_scalar_mul() {
+    let mut rng = DefaultRng::seed_from_u64(114);
+    for name in ["P-256", "Wei25519"] {
+        let named = lookup(name).expect("registered");
+        let (curve, base) = named.to_weierstrass().expect("a Weierstrass curve");
+        let order = named.order();
+        for teeth in [3, 8] {
+            let table = FixedBaseTable::new(&curve, &base, order.bits(), teeth);
+            assert!(table.max_bits() >= order.bits());
+            for scalar in comb_scalars(&order, &mut rng) {
+                let expected = scalar_mul(&curve, &base, &scalar);
+                assert_eq!(table.mul(&curve, &scalar), expected, "{}: {}", name, scalar);
+            }
+        }
+    }
+}
+
+#[test]
+fn comb_agrees_with_scalar_mul_on_edwards_and_small_curves() {
+    let mut rng = DefaultRng::seed_from_u64(114);
+    let edwards = ed25519();
+    let order = lookup("Ed25519").expect("registered").order();
+    let base = ed25519_base_point();
+    let table = FixedBaseTable::new(&edwards, &base, order.bits(), 5);
+    for scalar in comb_scalars(&order, &mut rng) {
+        assert_eq!(table.mul(&edwards, &scalar), scalar_mul(&edwards, &base, &scalar), "{}", scalar);
+    }
+
+    // Every scalar below 2^10 with tables of one to ten bits and up to sixteen teeth, including tables
+    // with more teeth than bits
+    let (curve, points) = small_curve(1);
+    for (max_bits, teeth) in [(1, 1), (4, 2), (10, 3), (10, 10), (3, 16)] {
+        let table = FixedBaseTable::new(&curve, &points[0], max_bits, teeth);
+        for scalar in -1024i32..1024 {
+            let scalar = BigInt::from(scalar);
+            assert_eq!(table.mul(&curve, &scalar), scalar_mul(&curve, &points[0], &scalar), "{}", scalar);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! `SmallField<P>` against `FieldElement` over the same prime: every operation on every pair of elements
+//! for small P, random pairs near 2^64, and the point counts of `enumerate_points` against `count_points`.
+
+use num_bigint::BigInt;
+use rand::{Rng, SeedableRng};
+use weierstrass_to_montgomery::{
+    count_points, enumerate_points, DefaultRng, FieldElement, SmallField, WeierstrassCurve,
+};
+
+/// Checks every operation on x and y against `FieldElement`.
+fn agree<const P: u64>(x: SmallField<P>, y: SmallField<P>) {
+    let p = BigInt::from(P);
+    let big = |value: SmallField<P>| FieldElement::new(&BigInt::from(value), &p);
+    let (bx, by) = (big(x), big(y));
+    let context = format!("x = {}, y = {} mod {}", x, y, P);
+    assert_eq!(big(x + y), &bx + &by, "{}", context);
+    assert_eq!(big(x - y), &bx - &by, "{}", context);
+    assert_eq!(big(x * y), &bx * &by, "{}", context);
+    assert_eq!(big(-x), -&bx, "{}", context);
+    assert_eq!(big(x.square()), bx.square(), "{}", context);
+    assert_eq!(big(x.pow(y.value())), bx.pow(&BigInt::from(y.value())), "{}", context);
+    assert_eq!(x.inverse().map(big), bx.inverse(), "{}", context);
+    if !y.is_zero() {
+        assert_eq!(big(x / y), &bx * &by.inverse().expect("non-zero"), "{}", context);
+    }
+    assert_eq!(x.is_square(), bx.is_square(), "{}", context);
+    // The two implementations may pick different roots, but not different squares
+    match (x.sqrt(), bx.sqrt().expect("P is prime")) {
+        (Some(root), Some(big_root)) => {
+            assert_eq!(big(root.square()), bx);
+            assert_eq!(big_root.square(), bx);
+        }
+        (None, None) => {}
+        (root, big_root) => panic!("{}: sqrt gave {:?} and {:?}", context, root, big_root),
+    }
+}
+
+fn every_pair<const P: u64>() {
+    for x in SmallField::<P>::elements() {
+        for y in SmallField::<P>::elements() {
+            agree(x, y);
+        }
+    }
+}
+
+fn random_pairs<const P: u64>(rng: &mut DefaultRng) {
+    let edges = [0, 1, 2, P - 2, P - 1].map(SmallField::<P>::new);
+    for (x, y) in edges.iter().flat_map(|x| edges.iter().map(move |y| (*x, *y))) {
+        agree(x, y);
+    }
+    for _ in 0..200 {
+        agree(SmallField::<P>::random(rng), SmallField::new(rng.gen()));
+    }
+}
+
+#[test]
+fn arithmetic_agrees_with_field_element_on_every_pair_of_small_fields() {
+    every_pair::<3>();
+    every_pair::<13>();
+    every_pair::<17>();
+    // 97 ≡ 1 mod 32, so square roots take several Tonelli-Shanks rounds
+    every_pair::<97>();
+}
+
+#[test]
+fn arithmetic_agrees_with_field_element_near_2_64() {
+    let mut rng = DefaultRng::seed_from_u64(109);
+    // 2^64 − 59, the largest prime below 2^64, whose products and sums overflow u64
+    random_pairs::<18_446_744_073_709_551_557>(&mut rng);
+    // 2^61 − 1 and a 63-bit prime ≡ 1 mod 2^32
+    random_pairs::<2_305_843_009_213_693_951>(&mut rng);
+    random_pairs::<9_223_372_006_790_004_737>(&mut rng);
+}
+
+#[test]
+fn enumerated_points_agree_with_count_points() {
+    fn check<const P: u64>() {
+        for a in SmallField::<P>::elements() {
+            for b in SmallField::<P>::elements() {
+                let [a_big, b_big, p] = [BigInt::from(a), BigInt::from(b), BigInt::from(P)];
+                if WeierstrassCurve::new(&a_big, &b_big, &p).is_err() {
+                    continue;
+                }
+                let order = count_points(&a_big, &b_big, &p).expect("p is small");
+                assert_eq!(BigInt::from(enumerate_points(a, b).len() + 1), order, "a = {}, b = {}", a, b);
+            }
+        }
+    }
+    check::<13>();
+    check::<17>();
+    check::<23>();
+}
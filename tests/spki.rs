@@ -0,0 +1,91 @@
+//! `parse_public_key` on checked-in key files written by `openssl`: SubjectPublicKeyInfo, PKCS#8 and
+//! SEC1 in DER and PEM, with named and explicit parameters, and private keys without the public point.
+//!
+//! The files under `tests/keys` are throwaway keys made with `openssl ecparam -genkey`, then converted
+//! with `openssl ec` (`-pubout`, `-param_enc explicit`, `-no_public`) and `openssl pkcs8 -topk8 -nocrypt`.
+
+use num_bigint::{BigInt, Sign};
+use weierstrass_to_montgomery::spki::{parse_public_key, EcPublicKey};
+use weierstrass_to_montgomery::{lookup, Point, TransformError};
+
+/// The uncompressed point `openssl ec -text` prints for the P-256 key.
+const P256_POINT: &str = "049f792eeeb518cdde12bbf4df6ceaa4bbdbfa27dca97391f6671ca7492567ca4a\
+                          8dd44d34e9bc675f672293b222e6a896f047080a19d110211579847286fdc690";
+/// The same for the brainpoolP256r1 key.
+const BRAINPOOL_POINT: &str = "0450a184afb2f0311410a4c71519365c21152bb5c231e00f05d6985d3733276541\
+                               a71ea1c0ac9369c8964a3d1ab17de81a2f8c62e5c66bac1057f3d97af8cd8373";
+/// The same for the secp256k1 key, whose file gives the curve explicitly and leaves the point out.
+const SECP256K1_POINT: &str = "04c759db8bc3a24588368b978cbde50b1aefd66fd66d90b20bda49894c54d28378\
+                               1f67f54884e14aac9ec7b4cfe91457f52d09939b96f517da3bee0a0b7049bcfb";
+
+fn point(uncompressed: &str) -> Point {
+    let bytes: Vec<u8> = (0..uncompressed.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&uncompressed[i..i + 2], 16).expect("hex digits"))
+        .collect();
+    assert_eq!(bytes[0], 4);
+    let (x, y) = bytes[1..].split_at(32);
+    Point::Affine(BigInt::from_bytes_be(Sign::Plus, x), BigInt::from_bytes_be(Sign::Plus, y))
+}
+
+/// Checks that `key` is `expected` on the registry curve `name`, named by OID when `named` is set.
+fn check(key: EcPublicKey, name: &str, named: bool, expected: &str) {
+    let registry = lookup(name).expect("in the registry");
+    let (curve, _) = registry.to_weierstrass().expect("a Weierstrass curve");
+    assert_eq!(key.curve, curve, "{}", name);
+    assert_eq!(key.point, point(expected), "{}", name);
+    assert_eq!(key.named.map(|named| named.name), named.then_some(registry.name), "{}", name);
+}
+
+#[test]
+fn p256_keys_decode_in_every_container() {
+    let files: [(&str, &[u8]); 7] = [
+        ("SPKI DER", include_bytes!("keys/p256-spki.der")),
+        ("SPKI PEM", include_bytes!("keys/p256-spki.pem")),
+        ("PKCS#8 DER", include_bytes!("keys/p256-pkcs8.der")),
+        ("PKCS#8 PEM", include_bytes!("keys/p256-pkcs8.pem")),
+        ("SEC1 DER", include_bytes!("keys/p256-sec1.der")),
+        ("SEC1 PEM", include_bytes!("keys/p256-sec1.pem")),
+        // Without the public key the point is recomputed as d·G
+        ("SEC1 PEM without the point", include_bytes!("keys/p256-sec1-no-public.pem")),
+    ];
+    for (what, bytes) in files {
+        let key = parse_public_key(bytes).unwrap_or_else(|error| panic!("{}: {}", what, error));
+        check(key, "P-256", true, P256_POINT);
+    }
+    // Explicit parameters give the same curve and point, but no registry entry
+    let explicit = parse_public_key(include_bytes!("keys/p256-spki-explicit.der")).expect("explicit SPKI");
+    check(explicit, "P-256", false, P256_POINT);
+}
+
+#[test]
+fn brainpool_and_explicit_secp256k1_keys_decode() {
+    let brainpool = parse_public_key(include_bytes!("keys/bp256-spki.der")).expect("brainpool SPKI");
+    check(brainpool, "brainpoolP256r1", true, BRAINPOOL_POINT);
+    let brainpool = parse_public_key(include_bytes!("keys/bp256-sec1.pem")).expect("brainpool SEC1");
+    check(brainpool, "brainpoolP256r1", true, BRAINPOOL_POINT);
+    // secp256k1 is not in the registry; d·G is recomputed from the explicit base point with the GLV
+    // endomorphism
+    let secp256k1 = parse_public_key(include_bytes!("keys/k256-sec1-explicit-no-public.der")).expect("SEC1");
+    assert_eq!((secp256k1.curve.a, secp256k1.curve.b), (BigInt::from(0), BigInt::from(7)));
+    let p = (BigInt::from(1) << 256u32) - (BigInt::from(1) << 32u32) - 977u32;
+    assert_eq!(secp256k1.curve.p, p);
+    assert_eq!((secp256k1.point, secp256k1.named), (point(SECP256K1_POINT), None));
+}
+
+#[test]
+fn damaged_and_mislabelled_keys_are_rejected() {
+    let spki = include_bytes!("keys/p256-spki.der");
+    // The last byte of y changed takes the point off the curve
+    let mut off_curve = spki.to_vec();
+    *off_curve.last_mut().expect("not empty") ^= 1;
+    assert!(matches!(parse_public_key(&off_curve), Err(TransformError::PointNotOnCurve)));
+    assert!(matches!(parse_public_key(&spki[..spki.len() - 1]), Err(TransformError::InvalidParameters(_))));
+    // A PEM label must match the structure inside
+    let pem = std::str::from_utf8(include_bytes!("keys/p256-spki.pem")).expect("ASCII");
+    let relabelled = pem.replace("PUBLIC KEY", "CERTIFICATE");
+    match parse_public_key(relabelled.as_bytes()) {
+        Err(TransformError::InvalidParameters(message)) => assert!(message.contains("unsupported PEM block")),
+        other => panic!("expected InvalidParameters, got {:?}", other),
+    }
+}